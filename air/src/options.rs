@@ -2,6 +2,9 @@ use super::{ExecutionOptionsError, HashFunction};
 use crate::trace::MIN_TRACE_LEN;
 use winter_air::{FieldExtension, ProofOptions as WinterProofOptions};
 
+/// Default maximum depth of nested procedure calls.
+const DEFAULT_MAX_CALL_DEPTH: usize = 1024;
+
 // PROVING OPTIONS
 // ================================================================================================
 
@@ -14,6 +17,13 @@ pub struct ProvingOptions {
 }
 
 impl ProvingOptions {
+    /// The smallest blowup factor supported by the VM.
+    ///
+    /// The constraints of the VM have degree up to 9, and thus, the constraint evaluation domain
+    /// must be at least 8 times larger than the execution trace; the low-degree extension domain
+    /// cannot be smaller than that.
+    pub const MIN_BLOWUP_FACTOR: usize = 8;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
 
@@ -132,10 +142,32 @@ impl From<ProvingOptions> for WinterProofOptions {
 ///
 /// - `max_cycles` specifies the maximum number of cycles a program is allowed to execute.
 /// - `expected_cycles` specifies the number of cycles a program is expected to execute.
+/// - `max_call_depth` specifies the maximum depth of nested procedure calls.
+/// - `strict_stack_depth` specifies whether reading more items from the operand stack than were
+///   placed onto it (either as inputs or by the program itself) should result in an error.
+/// - `strict_u32_overflow` specifies whether wrapping u32 additions, subtractions, and
+///   multiplications which do not fit into 32 bits should result in an error.
+/// - `strict_memory` specifies whether reading from a memory address which has never been written
+///   to should result in an error.
+/// - `deny_memory` specifies whether executing any instruction which accesses memory should
+///   result in an error.
+/// - `deny_advice` specifies whether executing any instruction which reads from the advice
+///   provider should result in an error.
+/// - `heartbeat` specifies whether the host should be notified periodically during execution so
+///   that it can abort it.
+/// - `step_hook` specifies whether the host should be notified after every executed clock cycle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ExecutionOptions {
     max_cycles: u32,
     expected_cycles: u32,
+    max_call_depth: usize,
+    strict_stack_depth: bool,
+    strict_u32_overflow: bool,
+    strict_memory: bool,
+    deny_memory: bool,
+    deny_advice: bool,
+    heartbeat: bool,
+    step_hook: bool,
 }
 
 impl Default for ExecutionOptions {
@@ -143,6 +175,14 @@ impl Default for ExecutionOptions {
         ExecutionOptions {
             max_cycles: u32::MAX,
             expected_cycles: MIN_TRACE_LEN as u32,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            strict_stack_depth: false,
+            strict_u32_overflow: false,
+            strict_memory: false,
+            deny_memory: false,
+            deny_advice: false,
+            heartbeat: false,
+            step_hook: false,
         }
     }
 }
@@ -173,9 +213,107 @@ impl ExecutionOptions {
         Ok(ExecutionOptions {
             max_cycles,
             expected_cycles,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            strict_stack_depth: false,
+            strict_u32_overflow: false,
+            strict_memory: false,
+            deny_memory: false,
+            deny_advice: false,
+            heartbeat: false,
+            step_hook: false,
         })
     }
 
+    /// Sets the maximum depth of nested procedure calls.
+    ///
+    /// Call depth is increased by every `call`, `syscall`, `dyncall`, and `dynexec` instruction
+    /// and is decreased when the invoked procedure returns. Execution fails if the depth exceeds
+    /// the specified value. Procedures invoked via `exec` are inlined and do not affect call depth.
+    pub fn with_max_call_depth(mut self, max_call_depth: usize) -> Self {
+        self.max_call_depth = max_call_depth;
+        self
+    }
+
+    /// Enables or disables detection of operand stack underflows.
+    ///
+    /// The operand stack is always at least 16 items deep, and the VM fills the slots which were
+    /// never written with ZEROs. When this option is enabled, execution fails if an operation
+    /// reads or overwrites more items of the stack than were placed onto it, instead of silently
+    /// consuming these ZEROs. A procedure invoked via `call` or `syscall` can access only the top
+    /// 16 items of the stack of its caller.
+    pub fn with_strict_stack_depth(mut self, enabled: bool) -> Self {
+        self.strict_stack_depth = enabled;
+        self
+    }
+
+    /// Enables or disables detection of u32 arithmetic overflows.
+    ///
+    /// By default, the `u32wrapping_add`, `u32wrapping_sub`, and `u32wrapping_mul` instructions
+    /// silently discard the carry, borrow, or high 32 bits of the result. When this option is
+    /// enabled, execution fails if any of these would be non-zero. Other u32 instructions (e.g.,
+    /// the overflowing variants or comparisons) are not affected, even though they are compiled
+    /// to the same operations. Note that this also applies to the wrapping instructions used
+    /// internally by the standard library, and thus this mode is suitable only for programs which
+    /// are expected to stay within u32 bounds.
+    ///
+    /// The instructions are identified via the assembly op decorators of the program, and thus,
+    /// this option has no effect unless the program was compiled in debug mode.
+    pub fn with_strict_u32_overflow(mut self, enabled: bool) -> Self {
+        self.strict_u32_overflow = enabled;
+        self
+    }
+
+    /// Enables or disables detection of reads from uninitialized memory.
+    ///
+    /// Memory is always initialized to ZEROs, and thus reading from an address which has never
+    /// been written to returns four ZERO elements. When this option is enabled, such reads result
+    /// in an error instead. Note that memory is tracked per execution context.
+    pub fn with_strict_memory(mut self, enabled: bool) -> Self {
+        self.strict_memory = enabled;
+        self
+    }
+
+    /// Enables or disables execution of instructions which access memory.
+    ///
+    /// When this option is enabled, execution fails on the first instruction which reads from or
+    /// writes to memory: `mem_load`, `mem_loadw`, `mem_store`, `mem_storew`, `mem_stream`, and
+    /// `adv_pipe`. Since procedure locals are stored in memory, this also applies to `loc_*`
+    /// instructions.
+    pub fn with_deny_memory(mut self, enabled: bool) -> Self {
+        self.deny_memory = enabled;
+        self
+    }
+
+    /// Enables or disables execution of instructions which read from the advice provider.
+    ///
+    /// When this option is enabled, execution fails on the first instruction which reads from the
+    /// advice stack (`adv_push`, `adv_loadw`, and `adv_pipe`) or from the advice Merkle store
+    /// (`mtree_get`, `mtree_set`, and other instructions based on `MPVERIFY` and `MRUPDATE`
+    /// operations). Advice injectors are not affected.
+    pub fn with_deny_advice(mut self, enabled: bool) -> Self {
+        self.deny_advice = enabled;
+        self
+    }
+
+    /// Enables or disables periodic notifications of the host during execution.
+    ///
+    /// When this option is enabled, the host is notified via `Host::on_heartbeat` roughly every
+    /// `HEARTBEAT_INTERVAL` cycles, which allows it to abort long-running executions.
+    pub fn with_heartbeat(mut self, enabled: bool) -> Self {
+        self.heartbeat = enabled;
+        self
+    }
+
+    /// Enables or disables notifications of the host after every executed clock cycle.
+    ///
+    /// When this option is enabled, the host is notified via `Host::on_step` with the operation
+    /// executed in every cycle, including the cycles of control flow operations. This slows down
+    /// the execution considerably, and is meant for tracing the execution of a program.
+    pub fn with_step_hook(mut self, enabled: bool) -> Self {
+        self.step_hook = enabled;
+        self
+    }
+
     /// Returns maximum number of cycles
     pub fn max_cycles(&self) -> u32 {
         self.max_cycles
@@ -185,4 +323,46 @@ impl ExecutionOptions {
     pub fn expected_cycles(&self) -> u32 {
         self.expected_cycles
     }
+
+    /// Returns maximum depth of nested procedure calls
+    pub fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Returns a flag indicating whether operand stack underflows should result in an error.
+    pub fn strict_stack_depth(&self) -> bool {
+        self.strict_stack_depth
+    }
+
+    /// Returns a flag indicating whether u32 arithmetic overflows should result in an error.
+    pub fn strict_u32_overflow(&self) -> bool {
+        self.strict_u32_overflow
+    }
+
+    /// Returns a flag indicating whether reads from uninitialized memory should result in an
+    /// error.
+    pub fn strict_memory(&self) -> bool {
+        self.strict_memory
+    }
+
+    /// Returns a flag indicating whether instructions which access memory are denied.
+    pub fn deny_memory(&self) -> bool {
+        self.deny_memory
+    }
+
+    /// Returns a flag indicating whether instructions which read from the advice provider are
+    /// denied.
+    pub fn deny_advice(&self) -> bool {
+        self.deny_advice
+    }
+
+    /// Returns a flag indicating whether the host is notified periodically during execution.
+    pub fn heartbeat(&self) -> bool {
+        self.heartbeat
+    }
+
+    /// Returns a flag indicating whether the host is notified after every executed clock cycle.
+    pub fn step_hook(&self) -> bool {
+        self.step_hook
+    }
 }
@@ -1,5 +1,5 @@
 use super::{
-    AssemblyError, BTreeMap, CallSet, CodeBlock, CodeBlockTable, Kernel, LibraryPath,
+    AssemblyError, BTreeMap, BTreeSet, CallSet, CodeBlock, CodeBlockTable, Kernel, LibraryPath,
     NamedProcedure, Procedure, ProcedureCache, ProcedureId, ProcedureName, RpoDigest, ToString,
     Vec,
 };
@@ -19,6 +19,7 @@ pub struct AssemblyContext {
     is_kernel: bool,
     kernel: Option<Kernel>,
     allow_phantom_calls: bool,
+    stats: CompileStats,
 }
 
 impl AssemblyContext {
@@ -34,6 +35,7 @@ impl AssemblyContext {
             is_kernel: is_kernel_module,
             kernel: None,
             allow_phantom_calls: false,
+            stats: CompileStats::default(),
         }
     }
 
@@ -49,6 +51,7 @@ impl AssemblyContext {
             is_kernel: false,
             kernel: None,
             allow_phantom_calls: false,
+            stats: CompileStats::default(),
         }
     }
 
@@ -78,6 +81,18 @@ impl AssemblyContext {
         self.current_proc_context().expect("no procedures").num_locals
     }
 
+    /// Returns statistics about the compilation performed in this context so far.
+    ///
+    /// Local procedures of the executable module which have not been invoked are reported as
+    /// eliminated, since they will not be included in the program.
+    pub fn stats(&self) -> CompileStats {
+        let mut stats = self.stats;
+        if let Some(module) = self.module_stack.first().filter(|module| module.is_executable()) {
+            stats.dead_procedures_eliminated += module.num_unused_procs();
+        }
+        stats
+    }
+
     /// Returns the name of the procedure by its ID from the procedure map.
     pub fn get_imported_procedure_name(&self, id: &ProcedureId) -> Option<ProcedureName> {
         if let Some(module) = self.module_stack.first() {
@@ -131,6 +146,7 @@ impl AssemblyContext {
     /// (both exported and internal) together with the combined callset of module's procedures.
     pub fn complete_module(&mut self) -> (Vec<NamedProcedure>, CallSet) {
         let module_ctx = self.module_stack.pop().expect("no modules");
+        self.stats.dead_procedures_eliminated += module_ctx.num_unused_procs();
         if self.is_kernel && self.module_stack.is_empty() {
             // if we are compiling a kernel and this is the last module on the module stack, then
             // it must be the Kernel module; thus, we build a Kernel struct from the procedures
@@ -175,6 +191,20 @@ impl AssemblyContext {
     /// of the current module's compiled procedures.
     pub fn complete_proc(&mut self, code: CodeBlock) {
         self.module_stack.last_mut().expect("no modules").complete_proc(code);
+        self.stats.procedure_count += 1;
+    }
+
+    // STATISTICS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the compilation of a single instruction.
+    pub(crate) fn record_instruction(&mut self) {
+        self.stats.instruction_count += 1;
+    }
+
+    /// Records the inlining of a procedure body via an `exec` instruction.
+    pub(crate) fn record_inline_expansion(&mut self) {
+        self.stats.inline_expansions += 1;
     }
 
     // CALL PROCESSORS
@@ -324,6 +354,31 @@ impl AssemblyContext {
     }
 }
 
+// COMPILE STATS
+// ================================================================================================
+
+/// Statistics about the compilation of a program.
+///
+/// The statistics describe the work done by the assembler during a single compilation. Modules
+/// which were compiled by an earlier invocation of the same assembler are taken from its procedure
+/// cache, and thus are not accounted for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompileStats {
+    /// Number of compiled instructions; instructions in the body of a `repeat` block are counted
+    /// once.
+    pub instruction_count: u64,
+    /// Number of compiled procedures, including the procedures of imported modules.
+    pub procedure_count: u32,
+    /// Number of procedure bodies inlined into the MAST by `exec` instructions.
+    pub inline_expansions: u32,
+    /// Number of compiled procedures which are neither exported nor invoked, and thus are not
+    /// included in the program.
+    pub dead_procedures_eliminated: u32,
+    /// Number of optimization passes applied while compiling the program; this is equal to the
+    /// optimization level of the assembler.
+    pub optimization_passes: u32,
+}
+
 // MODULE CONTEXT
 // ================================================================================================
 
@@ -342,6 +397,8 @@ struct ModuleContext {
     callset: CallSet,
     /// A map containing id and names of all imported procedures in the module.
     proc_map: BTreeMap<ProcedureId, ProcedureName>,
+    /// Indexes of the local procedures which have been invoked from within this module.
+    invoked_procs: BTreeSet<u16>,
 }
 
 impl ModuleContext {
@@ -361,6 +418,7 @@ impl ModuleContext {
             path: LibraryPath::exec_path(),
             callset: CallSet::default(),
             proc_map,
+            invoked_procs: BTreeSet::new(),
         }
     }
 
@@ -377,6 +435,7 @@ impl ModuleContext {
             path: module_path.clone(),
             callset: CallSet::default(),
             proc_map,
+            invoked_procs: BTreeSet::new(),
         }
     }
 
@@ -397,6 +456,15 @@ impl ModuleContext {
             .map(|proc| proc.inner())
     }
 
+    /// Returns the number of compiled local procedures which are neither exported nor invoked
+    /// from within this module.
+    pub fn num_unused_procs(&self) -> u32 {
+        let is_unused = |(idx, proc): &(usize, &NamedProcedure)| {
+            !proc.is_export() && !self.invoked_procs.contains(&(*idx as u16))
+        };
+        self.compiled_procs.iter().enumerate().filter(is_unused).count() as u32
+    }
+
     // PROCEDURE PROCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -460,6 +528,8 @@ impl ModuleContext {
             .get(proc_idx as usize)
             .ok_or_else(|| AssemblyError::local_proc_not_found(proc_idx, &self.path))?;
 
+        self.invoked_procs.insert(proc_idx);
+
         // get the context of the procedure currently being compiled
         let context = self.proc_stack.last_mut().expect("no proc context");
 
@@ -0,0 +1,195 @@
+use super::{Assembler, AssemblyError, LibraryError, Program, ToString, Vec};
+use crate::MaslLibrary;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+// HOT ASSEMBLER
+// ================================================================================================
+
+/// An [Assembler] which reloads its libraries when the underlying `.masl` files change.
+///
+/// A regular assembler cannot drop a library once it was added, and thus, picking up a modified
+/// library requires building a new assembler and re-reading every library file. A hot assembler
+/// keeps the libraries it loaded together with the modification times of their files, and
+/// [HotAssembler::check_reload()] rebuilds the inner assembler only when at least one of the files
+/// has changed. This makes repeated compilations against unchanged libraries (e.g., in a
+/// development server) almost free.
+///
+/// Libraries are added to the inner assembler in the order in which their paths were provided.
+pub struct HotAssembler {
+    base: Box<dyn Fn() -> Assembler>,
+    libraries: Vec<LoadedLibrary>,
+    assembler: Assembler,
+}
+
+impl HotAssembler {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [HotAssembler] with the libraries read from the specified files added to a
+    /// default assembler.
+    ///
+    /// # Errors
+    /// Returns an error if any of the library files cannot be read, or if the libraries cannot be
+    /// added to the assembler.
+    pub fn new<I, P>(library_paths: I) -> Result<Self, AssemblyError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        Self::with_base(Assembler::default, library_paths)
+    }
+
+    /// Returns a new [HotAssembler] with the libraries read from the specified files added to the
+    /// assembler built by `base`.
+    ///
+    /// `base` is invoked every time the inner assembler is rebuilt, and thus, it can be used to
+    /// configure the assembler (e.g., to put it into debug mode, or to add libraries which are not
+    /// read from files).
+    ///
+    /// # Errors
+    /// Returns an error if any of the library files cannot be read, or if the libraries cannot be
+    /// added to the assembler.
+    pub fn with_base<F, I, P>(base: F, library_paths: I) -> Result<Self, AssemblyError>
+    where
+        F: Fn() -> Assembler + 'static,
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let libraries = library_paths
+            .into_iter()
+            .map(|path| LoadedLibrary::read(path.as_ref().to_path_buf()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let assembler = build_assembler(&base, &libraries)?;
+
+        Ok(Self {
+            base: Box::new(base),
+            libraries,
+            assembler,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a reference to the inner assembler.
+    pub fn assembler(&self) -> &Assembler {
+        &self.assembler
+    }
+
+    /// Returns the paths of the library files loaded by this assembler.
+    pub fn library_paths(&self) -> impl Iterator<Item = &Path> {
+        self.libraries.iter().map(|library| library.path.as_path())
+    }
+
+    // RELOADING
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether any of the library files changed since they were loaded, and if so, reloads
+    /// the changed libraries and rebuilds the inner assembler.
+    ///
+    /// A file is considered changed when its modification time or its size differs from the one
+    /// recorded when the file was loaded. Returns true if the inner assembler was rebuilt.
+    ///
+    /// # Errors
+    /// Returns an error if any of the library files cannot be read, or if the libraries cannot be
+    /// added to the assembler. In this case, the previously loaded libraries are retained.
+    pub fn check_reload(&mut self) -> Result<bool, AssemblyError> {
+        let mut libraries = Vec::with_capacity(self.libraries.len());
+        let mut changed = false;
+        for library in self.libraries.iter() {
+            if library.stamp != FileStamp::read(&library.path)? {
+                libraries.push(LoadedLibrary::read(library.path.clone())?);
+                changed = true;
+            } else {
+                libraries.push(library.clone());
+            }
+        }
+
+        if changed {
+            self.assembler = build_assembler(&self.base, &libraries)?;
+            self.libraries = libraries;
+        }
+
+        Ok(changed)
+    }
+
+    // PROGRAM COMPILER
+    // --------------------------------------------------------------------------------------------
+
+    /// Reloads the changed library files, and compiles the provided source code into a [Program].
+    ///
+    /// # Errors
+    /// Returns an error if reloading of the libraries fails, or if parsing or compilation of the
+    /// specified program fails.
+    pub fn compile<S>(&mut self, source: S) -> Result<Program, AssemblyError>
+    where
+        S: AsRef<str>,
+    {
+        self.check_reload()?;
+        self.assembler.compile(source)
+    }
+}
+
+// LOADED LIBRARY
+// ================================================================================================
+
+/// A library read from a file, together with the stamp of the file at the time it was read.
+#[derive(Clone)]
+struct LoadedLibrary {
+    path: PathBuf,
+    stamp: FileStamp,
+    library: MaslLibrary,
+}
+
+impl LoadedLibrary {
+    /// Reads the library from the file at the specified path.
+    fn read(path: PathBuf) -> Result<Self, AssemblyError> {
+        // the stamp is taken before the file is read so that changes made while reading are
+        // picked up by the next reload
+        let stamp = FileStamp::read(&path)?;
+        let library = MaslLibrary::read_from_file(&path)?;
+        Ok(Self {
+            path,
+            stamp,
+            library,
+        })
+    }
+}
+
+/// Modification time and size of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Reads the stamp of the file at the specified path.
+    fn read(path: &Path) -> Result<Self, LibraryError> {
+        let file_error = |err: std::io::Error| {
+            LibraryError::file_error(&path.display().to_string(), &err.to_string())
+        };
+        let metadata = fs::metadata(path).map_err(file_error)?;
+        Ok(Self {
+            modified: metadata.modified().map_err(file_error)?,
+            len: metadata.len(),
+        })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds an assembler via `base` and adds the specified libraries to it.
+fn build_assembler<F>(base: &F, libraries: &[LoadedLibrary]) -> Result<Assembler, AssemblyError>
+where
+    F: Fn() -> Assembler + ?Sized,
+{
+    libraries
+        .iter()
+        .try_fold(base(), |assembler, loaded| assembler.with_library(&loaded.library))
+}
@@ -189,6 +189,55 @@ pub(super) fn mtree_verify(span: &mut SpanBuilder) -> Result<Option<CodeBlock>,
     span.add_op(MpVerify)
 }
 
+/// Checks whether the value `V` is a leaf of the Merkle tree with root `R`, and pushes 1 onto
+/// the stack if it is and 0 otherwise. The stack is expected to be arranged as follows (from the
+/// top):
+/// - leaf value `V`, 4 elements
+/// - root of the tree `R`, 4 elements
+///
+/// The position of the leaf (i.e., its depth `d` and index `i`) is read from the advice map, in
+/// which it is expected to be stored under `V` as `[d, i]`, and the node at that position is read
+/// from the advice provider. The node is then verified to open to `R` at depth `d` and index `i`
+/// via [Operation::MpVerify], as in `mtree_get`, and is compared with `V`. Thus, a result of 1
+/// proves that `V` is the node at depth `d` and index `i` of the tree. A result of 0 means that
+/// the node at the position supplied by the advice provider differs from `V`; as the position is
+/// not constrained, this is not a proof that `V` is not a leaf of the tree.
+///
+/// After the operations are executed, the stack will be arranged as follows:
+/// - 1 if `V` is a leaf of the tree and 0 otherwise, 1 element
+///
+/// This operation takes 31 VM cycles.
+pub(super) fn mtree_member(span: &mut SpanBuilder) -> Result<Option<CodeBlock>, AssemblyError> {
+    // stack: [V, R, ...]
+    span.push_advice_injector(AdviceInjector::MerkleLeafToStack);
+
+    #[rustfmt::skip]
+    let ops = [
+        // move the root above the value => [R, V, ...]
+        SwapW,
+
+        // pop the depth and the index of the leaf from the advice stack => [d, i, R, V, ...]
+        AdvPop, AdvPop, Swap,
+
+        // pop the node at that position from the advice stack => [N, d, i, R, V, ...]
+        AdvPop, AdvPop, AdvPop, AdvPop,
+
+        // verify the node N for root R with depth d and index i => [N, d, i, R, V, ...]
+        MpVerify,
+
+        // drop d, i, and R as they are no longer needed => [N, V, ...]
+        MovUp4, Drop, MovUp4, Drop,
+        SwapW, Drop, Drop, Drop, Drop,
+
+        // compare N and V element-wise => [b, ...]
+        MovUp4, Eq,
+        Swap, MovUp4, Eq, And,
+        Swap, MovUp3, Eq, And,
+        MovDn2, Eq, And,
+    ];
+    span.add_ops(ops)
+}
+
 // MERKLE TREES - HELPERS
 // ================================================================================================
 
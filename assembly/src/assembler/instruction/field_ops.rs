@@ -34,13 +34,20 @@ pub fn assertw(span: &mut SpanBuilder, err_code: Felt) -> Result<Option<CodeBloc
 
 /// Appends a sequence of operations to add an immediate value to the value at the top of the
 /// stack. Specifically, the sequences are:
-/// - if imm = 0: NOOP
+/// - if opt_level = 0: PUSH(imm) ADD
+/// - else if imm = 0: NOOP, or nothing if opt_level > 1
 /// - else if imm = 1: INCR
 /// - else if imm = 2: INCR INCR
 /// - otherwise: PUSH(imm) ADD
-pub fn add_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, AssemblyError> {
-    if imm == ZERO {
-        span.add_op(Noop)
+pub fn add_imm(
+    span: &mut SpanBuilder,
+    imm: Felt,
+    opt_level: u8,
+) -> Result<Option<CodeBlock>, AssemblyError> {
+    if opt_level == 0 {
+        span.add_ops([Push(imm), Add])
+    } else if imm == ZERO {
+        add_identity(span, opt_level)
     } else if imm == ONE {
         span.add_op(Incr)
     } else if imm == TWO {
@@ -52,11 +59,16 @@ pub fn add_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, A
 
 /// Appends a sequence of operations to subtract an immediate value from the value at the top of the
 /// stack. Specifically, the sequences are:
-/// - if imm = 0: NOOP
+/// - if opt_level = 0: PUSH(-imm) ADD
+/// - else if imm = 0: NOOP, or nothing if opt_level > 1
 /// - otherwise: PUSH(-imm) ADD
-pub fn sub_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, AssemblyError> {
-    if imm == ZERO {
-        span.add_op(Noop)
+pub fn sub_imm(
+    span: &mut SpanBuilder,
+    imm: Felt,
+    opt_level: u8,
+) -> Result<Option<CodeBlock>, AssemblyError> {
+    if opt_level > 0 && imm == ZERO {
+        add_identity(span, opt_level)
     } else {
         span.add_ops([Push(-imm), Add])
     }
@@ -64,14 +76,21 @@ pub fn sub_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, A
 
 /// Appends a sequence of operations to multiply the value at the top of the stack by an immediate
 /// value. Specifically, the sequences are:
-/// - if imm = 0: DROP PAD
-/// - else if imm = 1: NOOP
+/// - if opt_level = 0: PUSH(imm) MUL
+/// - else if imm = 0: DROP PAD
+/// - else if imm = 1: NOOP, or nothing if opt_level > 1
 /// - otherwise: PUSH(imm) MUL
-pub fn mul_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, AssemblyError> {
-    if imm == ZERO {
+pub fn mul_imm(
+    span: &mut SpanBuilder,
+    imm: Felt,
+    opt_level: u8,
+) -> Result<Option<CodeBlock>, AssemblyError> {
+    if opt_level == 0 {
+        span.add_ops([Push(imm), Mul])
+    } else if imm == ZERO {
         span.add_ops([Drop, Pad])
     } else if imm == ONE {
-        span.add_op(Noop)
+        add_identity(span, opt_level)
     } else {
         span.add_ops([Push(imm), Mul])
     }
@@ -80,21 +99,36 @@ pub fn mul_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, A
 /// Appends a sequence of operations to divide the value at the top of the stack by an immediate
 /// value. Specifically, the sequences are:
 /// - if imm = 0: Returns an error
-/// - else if imm = 1: NOOP
+/// - else if opt_level = 0: PUSH(1/imm) MUL
+/// - else if imm = 1: NOOP, or nothing if opt_level > 1
 /// - otherwise: PUSH(1/imm) MUL
 ///
 /// # Errors
 /// Returns an error if the immediate value is ZERO.
-pub fn div_imm(span: &mut SpanBuilder, imm: Felt) -> Result<Option<CodeBlock>, AssemblyError> {
+pub fn div_imm(
+    span: &mut SpanBuilder,
+    imm: Felt,
+    opt_level: u8,
+) -> Result<Option<CodeBlock>, AssemblyError> {
     if imm == ZERO {
         Err(AssemblyError::division_by_zero())
-    } else if imm == ONE {
-        span.add_op(Noop)
+    } else if opt_level > 0 && imm == ONE {
+        add_identity(span, opt_level)
     } else {
         span.add_ops([Push(imm.inv()), Mul])
     }
 }
 
+/// Appends a NOOP for an instruction which leaves the stack unchanged, unless the optimization
+/// level allows such instructions to be elided.
+fn add_identity(span: &mut SpanBuilder, opt_level: u8) -> Result<Option<CodeBlock>, AssemblyError> {
+    if opt_level > 1 {
+        Ok(None)
+    } else {
+        span.add_op(Noop)
+    }
+}
+
 // POWER OF TWO OPERATION
 // ================================================================================================
 
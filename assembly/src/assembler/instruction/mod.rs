@@ -1,10 +1,10 @@
 use super::{
     Assembler, AssemblyContext, AssemblyError, CodeBlock, Felt, Instruction, Operation,
-    ProcedureId, RpoDigest, SpanBuilder, ONE, ZERO,
+    ProcedureId, ProcedureName, RpoDigest, SpanBuilder, ONE, ZERO,
 };
 use crate::utils::bound_into_included_u64;
 use core::ops::RangeBounds;
-use vm_core::{Decorator, FieldElement, StarkField};
+use vm_core::{Decorator, FieldElement, SourceLocation, StarkField};
 
 mod adv_ops;
 mod crypto_ops;
@@ -24,16 +24,17 @@ impl Assembler {
     pub(super) fn compile_instruction(
         &self,
         instruction: &Instruction,
+        location: Option<SourceLocation>,
         span: &mut SpanBuilder,
         ctx: &mut AssemblyContext,
     ) -> Result<Option<CodeBlock>, AssemblyError> {
         use Operation::*;
 
         // if the assembler is in debug mode, start tracking the instruction about to be executed;
-        // this will allow us to map the instruction to the sequence of operations which were
-        // executed as a part of this instruction.
+        // this will allow us to map the instruction (and its location in the source code) to the
+        // sequence of operations which were executed as a part of this instruction.
         if self.in_debug_mode() {
-            span.track_instruction(instruction, ctx);
+            span.track_instruction(instruction, location, ctx);
         }
 
         let result = match instruction {
@@ -53,13 +54,13 @@ impl Assembler {
             }
 
             Instruction::Add => span.add_op(Add),
-            Instruction::AddImm(imm) => field_ops::add_imm(span, *imm),
+            Instruction::AddImm(imm) => field_ops::add_imm(span, *imm, self.opt_level),
             Instruction::Sub => span.add_ops([Neg, Add]),
-            Instruction::SubImm(imm) => field_ops::sub_imm(span, *imm),
+            Instruction::SubImm(imm) => field_ops::sub_imm(span, *imm, self.opt_level),
             Instruction::Mul => span.add_op(Mul),
-            Instruction::MulImm(imm) => field_ops::mul_imm(span, *imm),
+            Instruction::MulImm(imm) => field_ops::mul_imm(span, *imm, self.opt_level),
             Instruction::Div => span.add_ops([Inv, Mul]),
-            Instruction::DivImm(imm) => field_ops::div_imm(span, *imm),
+            Instruction::DivImm(imm) => field_ops::div_imm(span, *imm, self.opt_level),
             Instruction::Neg => span.add_op(Neg),
             Instruction::Inv => span.add_op(Inv),
             Instruction::Incr => span.add_op(Incr),
@@ -314,6 +315,7 @@ impl Assembler {
             Instruction::MTreeSet => crypto_ops::mtree_set(span),
             Instruction::MTreeMerge => crypto_ops::mtree_merge(span),
             Instruction::MTreeVerify => crypto_ops::mtree_verify(span),
+            Instruction::MTreeMember => crypto_ops::mtree_member(span),
 
             // ----- STARK proof verification -----------------------------------------------------
             Instruction::FriExt2Fold4 => span.add_op(FriE2F4),
@@ -332,7 +334,7 @@ impl Assembler {
             Instruction::Breakpoint => {
                 if self.in_debug_mode() {
                     span.add_op(Noop)?;
-                    span.track_instruction(instruction, ctx);
+                    span.track_instruction(instruction, location, ctx);
                 }
                 Ok(None)
             }
@@ -343,6 +345,11 @@ impl Assembler {
                 }
                 Ok(None)
             }
+
+            Instruction::Trace(trace_id) => {
+                span.push_decorator(Decorator::Trace(*trace_id));
+                Ok(None)
+            }
         };
 
         // compute and update the cycle count of the instruction which just finished executing
@@ -354,9 +361,121 @@ impl Assembler {
     }
 }
 
+// CYCLE COSTS
+// ================================================================================================
+
+/// Number of cycles consumed by a CALL, SYSCALL, or DYN block itself, i.e., by its opening
+/// operation and its END operation.
+const INVOCATION_BLOCK_CYCLES: u64 = 2;
+
+impl Assembler {
+    /// Returns the number of VM cycles it takes to execute the specified instruction when it is
+    /// compiled by this assembler in a procedure with the specified number of locals.
+    ///
+    /// Every VM operation is executed in a single cycle, and thus, the cost of an instruction is
+    /// the number of operations it is compiled into. The cost of `exec` is 0 as the body of the
+    /// invoked procedure is inlined, and the cost of `call`, `syscall`, `dynexec`, and `dyncall`
+    /// covers only the block which invokes the procedure; the cycles consumed by the invoked
+    /// procedure itself are not included. Operations which do not belong to any instruction
+    /// (e.g., the SPAN and END operations of basic blocks) are not included either.
+    ///
+    /// # Errors
+    /// Returns an error if the instruction cannot be compiled in isolation, e.g., if it accesses a
+    /// local with an index which is out of bounds.
+    pub fn instruction_cycle_cost(
+        &self,
+        instruction: &Instruction,
+        num_locals: u16,
+    ) -> Result<u64, AssemblyError> {
+        match instruction {
+            Instruction::ExecLocal(_) | Instruction::ExecImported(_) => return Ok(0),
+            Instruction::CallLocal(_)
+            | Instruction::CallMastRoot(_)
+            | Instruction::CallImported(_)
+            | Instruction::SysCall(_)
+            | Instruction::DynExec
+            | Instruction::DynCall => return Ok(INVOCATION_BLOCK_CYCLES),
+            // `caller` is compiled into a single CALLER operation, but it can be compiled only in
+            // the context of a kernel
+            Instruction::Caller => return Ok(1),
+            _ => (),
+        }
+
+        let mut ctx = AssemblyContext::for_program(None);
+        let name = ProcedureName::try_from("cycle_cost").expect("valid procedure name");
+        ctx.begin_proc(&name, false, num_locals)?;
+
+        let mut span = SpanBuilder::default();
+        self.compile_instruction(instruction, None, &mut span, &mut ctx)?;
+        Ok(span.num_ops() as u64)
+    }
+}
+
+// STACK EFFECTS
+// ================================================================================================
+
+impl Assembler {
+    /// Returns the net change of the stack depth caused by executing the specified instruction
+    /// when it is compiled by this assembler in a procedure with the specified number of locals.
+    ///
+    /// The change is computed statically from the operations the instruction is compiled into,
+    /// e.g., it is -1 for `add` and 4 for `padw`. `call` and `syscall` do not change the stack
+    /// depth, as the invoked procedure is executed in a new context which must end with the same
+    /// stack depth. Returns None for `exec`, `dynexec`, and `dyncall`, as their effect depends on
+    /// the invoked procedure.
+    ///
+    /// # Errors
+    /// Returns an error if the instruction cannot be compiled in isolation, e.g., if it accesses a
+    /// local with an index which is out of bounds.
+    pub fn instruction_stack_effect(
+        &self,
+        instruction: &Instruction,
+        num_locals: u16,
+    ) -> Result<Option<i32>, AssemblyError> {
+        match instruction {
+            Instruction::ExecLocal(_)
+            | Instruction::ExecImported(_)
+            | Instruction::DynExec
+            | Instruction::DynCall => return Ok(None),
+            Instruction::CallLocal(_)
+            | Instruction::CallMastRoot(_)
+            | Instruction::CallImported(_)
+            | Instruction::SysCall(_) => return Ok(Some(0)),
+            // `caller` overwrites the top word of the stack, but it can be compiled only in the
+            // context of a kernel
+            Instruction::Caller => return Ok(Some(0)),
+            _ => (),
+        }
+
+        let mut ctx = AssemblyContext::for_program(None);
+        let name = ProcedureName::try_from("stack_effect").expect("valid procedure name");
+        ctx.begin_proc(&name, false, num_locals)?;
+
+        let mut span = SpanBuilder::default();
+        self.compile_instruction(instruction, None, &mut span, &mut ctx)?;
+        Ok(Some(span.ops().iter().map(op_stack_effect).sum()))
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the net change of the stack depth caused by executing the specified operation.
+///
+/// Control flow operations are never part of the body of a basic block, and thus, they are
+/// treated as not changing the stack depth.
+fn op_stack_effect(op: &Operation) -> i32 {
+    use Operation::*;
+
+    match op {
+        SDepth | Clk | U32split | Pad | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7
+        | Dup9 | Dup11 | Dup13 | Dup15 | Push(_) | AdvPop => 1,
+        Assert(_) | FmpUpdate | Add | Mul | And | Or | Eq | U32add3 | U32madd | U32and | U32xor
+        | Drop | CSwap | CSwapW | MLoadW | MStoreW | MStore => -1,
+        _ => 0,
+    }
+}
+
 /// This is a helper function that appends a PUSH operation to the span block which puts the
 /// provided u32 value onto the stack.
 ///
@@ -9,6 +9,8 @@ impl Assembler {
         proc_idx: u16,
         context: &mut AssemblyContext,
     ) -> Result<Option<CodeBlock>, AssemblyError> {
+        context.record_inline_expansion();
+
         // register an "inlined" call to the procedure at the specified index in the module
         // currently being complied; this updates the callset of the procedure currently being
         // compiled
@@ -36,6 +38,7 @@ impl Assembler {
         // register an "inlined" call to the procedure; this updates the callset of the
         // procedure currently being compiled
         context.register_external_call(proc, true)?;
+        context.record_inline_expansion();
 
         // TODO: if the procedure consists of a single SPAN block, we could just append all
         // operations from that SPAN block to the span builder instead of returning a code block
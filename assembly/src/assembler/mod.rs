@@ -1,12 +1,12 @@
 use super::{
-    ast::{Instruction, ModuleAst, Node, ProcedureAst, ProgramAst},
+    ast::{CodeBody, Instruction, ModuleAst, Node, ProcedureAst, ProgramAst},
     btree_map,
     crypto::hash::RpoDigest,
-    AssemblyError, BTreeMap, CallSet, CodeBlock, CodeBlockTable, Felt, Kernel, Library,
-    LibraryError, LibraryPath, Module, NamedProcedure, Operation, Procedure, ProcedureId,
-    ProcedureName, Program, ToString, Vec, ONE, ZERO,
+    AssemblyError, BTreeMap, BTreeSet, CallSet, CodeBlock, CodeBlockTable, Felt, Kernel, Library,
+    LibraryError, LibraryLoadError, LibraryPath, Module, NamedProcedure, Operation, Procedure,
+    ProcedureId, ProcedureName, Program, ToString, Vec, ONE, ZERO,
 };
-use core::{borrow::Borrow, cell::RefCell};
+use core::{borrow::Borrow, cell::RefCell, iter};
 use vm_core::{utils::group_vector_elements, Decorator, DecoratorList};
 
 mod instruction;
@@ -18,11 +18,16 @@ mod span_builder;
 use span_builder::SpanBuilder;
 
 mod context;
-pub use context::AssemblyContext;
+pub use context::{AssemblyContext, CompileStats};
 
 mod procedure_cache;
 use procedure_cache::ProcedureCache;
 
+#[cfg(feature = "std")]
+mod hot_assembler;
+#[cfg(feature = "std")]
+pub use hot_assembler::HotAssembler;
+
 #[cfg(test)]
 mod tests;
 
@@ -34,15 +39,38 @@ mod tests;
 /// - If `with_kernel()` or `with_kernel_module()` methods are not used, the assembler will be
 ///   instantiated with a default empty kernel. Programs compiled using such assembler
 ///   cannot make calls to kernel procedures via `syscall` instruction.
-#[derive(Default)]
+/// - If `with_optimization_level()` method is not used, the assembler will be instantiated with
+///   optimization level 1.
 pub struct Assembler {
     kernel: Kernel,
     module_provider: ModuleProvider,
     proc_cache: RefCell<ProcedureCache>,
     in_debug_mode: bool,
+    opt_level: u8,
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        Self {
+            kernel: Kernel::default(),
+            module_provider: ModuleProvider::default(),
+            proc_cache: RefCell::default(),
+            in_debug_mode: false,
+            opt_level: Self::DEFAULT_OPT_LEVEL,
+        }
+    }
 }
 
 impl Assembler {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Optimization level used by the assembler unless a different one is specified.
+    pub const DEFAULT_OPT_LEVEL: u8 = 1;
+
+    /// Highest supported optimization level.
+    pub const MAX_OPT_LEVEL: u8 = 2;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
@@ -52,6 +80,30 @@ impl Assembler {
         self
     }
 
+    /// Sets the optimization level of the assembler. The levels are:
+    /// - 0: instructions are translated literally; e.g., `add.1` is compiled into `PUSH(1) ADD`.
+    /// - 1: instructions with immediate values are folded into cheaper sequences of operations
+    ///   where possible; e.g., `add.1` is compiled into `INCR`.
+    /// - 2: in addition to the above, instructions which leave the stack unchanged (e.g., `add.0`
+    ///   or `mul.1`) are elided instead of being compiled into a `NOOP`.
+    ///
+    /// Levels above 2 are treated as level 2. Since the levels produce different MASTs, the hash
+    /// of a program may differ depending on the level it was compiled with.
+    pub fn with_optimization_level(mut self, opt_level: u8) -> Self {
+        self.opt_level = opt_level.min(Self::MAX_OPT_LEVEL);
+        self
+    }
+
+    /// Allows modules from libraries added after this call to shadow previously added modules
+    /// with the same path.
+    ///
+    /// By default, adding a library which contains a module with the same path as a module of
+    /// a previously added library results in an error.
+    pub fn with_library_shadowing(mut self, allow_shadowing: bool) -> Self {
+        self.module_provider.set_allow_shadowing(allow_shadowing);
+        self
+    }
+
     /// Adds the library to provide modules for the compilation.
     pub fn with_library<L>(mut self, library: &L) -> Result<Self, AssemblyError>
     where
@@ -62,6 +114,10 @@ impl Assembler {
     }
 
     /// Adds a library bundle to provide modules for the compilation.
+    ///
+    /// # Errors
+    /// Returns an error if a module of one of the libraries has the same path as a module of
+    /// a previously added library, unless library shadowing is enabled.
     pub fn with_libraries<I, L>(self, mut libraries: I) -> Result<Self, AssemblyError>
     where
         L: Library,
@@ -70,6 +126,28 @@ impl Assembler {
         libraries.try_fold(self, |slf, library| slf.with_library(&library))
     }
 
+    /// Returns an assembler with the libraries declared in the `[dependencies]` table of the
+    /// specified `miden.toml` manifest.
+    ///
+    /// Dependency paths are resolved relative to the manifest's directory, and each library is
+    /// loaded with [MaslLibrary::read_from_file](crate::MaslLibrary::read_from_file).
+    ///
+    /// # Errors
+    /// Returns an error if the manifest is malformed, if any of the libraries cannot be read, or
+    /// if two libraries provide modules with the same path.
+    #[cfg(feature = "std")]
+    pub fn from_manifest<P>(manifest_path: P) -> Result<Self, AssemblyError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let manifest = crate::Manifest::read_from_file(manifest_path)?;
+        let assembler = manifest.library_paths().try_fold(Self::default(), |assembler, path| {
+            let library = crate::MaslLibrary::read_from_file(path)?;
+            assembler.with_library(&library)
+        })?;
+        Ok(assembler)
+    }
+
     /// Sets the kernel for the assembler to the kernel defined by the provided source.
     ///
     /// # Errors
@@ -107,6 +185,11 @@ impl Assembler {
         self.in_debug_mode
     }
 
+    /// Returns the optimization level of this assembler.
+    pub fn optimization_level(&self) -> u8 {
+        self.opt_level
+    }
+
     /// Returns a reference to the kernel for this assembler.
     ///
     /// If the assembler was instantiated without a kernel, the internal kernel will be empty.
@@ -140,15 +223,30 @@ impl Assembler {
     /// # Errors
     /// Returns an error if the compilation of the specified program fails.
     pub fn compile_ast(&self, program: &ProgramAst) -> Result<Program, AssemblyError> {
+        self.compile_ast_with_stats(program).map(|(program, _)| program)
+    }
+
+    /// Compiles the provided abstract syntax tree into a [Program] and returns it together with
+    /// statistics about the compilation.
+    ///
+    /// # Errors
+    /// Returns an error if the compilation of the specified program fails.
+    pub fn compile_ast_with_stats(
+        &self,
+        program: &ProgramAst,
+    ) -> Result<(Program, CompileStats), AssemblyError> {
         // compile the program
         let mut context = AssemblyContext::for_program(Some(program));
         let program_root = self.compile_in_context(program, &mut context)?;
+        let mut stats = context.stats();
+        stats.optimization_passes = self.opt_level as u32;
 
         // convert the context into a call block table for the program
         let cb_table = context.into_cb_table(&self.proc_cache.borrow())?;
 
         // build and return the program
-        Ok(Program::with_kernel(program_root, self.kernel.clone(), cb_table))
+        let program = Program::with_kernel(program_root, self.kernel.clone(), cb_table);
+        Ok((program, stats))
     }
 
     /// Compiles the provided [ProgramAst] into a program and returns the program root
@@ -179,7 +277,7 @@ impl Assembler {
         }
 
         // compile the program body
-        let program_root = self.compile_body(program.body().nodes().iter(), context, None)?;
+        let program_root = self.compile_body(program.body(), context, None)?;
 
         Ok(program_root)
     }
@@ -287,9 +385,9 @@ impl Assembler {
                 prologue: vec![Operation::Push(num_locals), Operation::FmpUpdate],
                 epilogue: vec![Operation::Push(-num_locals), Operation::FmpUpdate],
             };
-            self.compile_body(proc.body.nodes().iter(), context, Some(wrapper))?
+            self.compile_body(&proc.body, context, Some(wrapper))?
         } else {
-            self.compile_body(proc.body.nodes().iter(), context, None)?
+            self.compile_body(&proc.body, context, None)?
         };
 
         context.complete_proc(code);
@@ -300,24 +398,27 @@ impl Assembler {
     // CODE BODY COMPILER
     // --------------------------------------------------------------------------------------------
 
-    /// TODO: add comments
-    fn compile_body<A, N>(
+    /// Compiles the specified code body into a code block.
+    ///
+    /// If the body contains source locations, the locations of the instructions are attached to
+    /// the assembly op decorators emitted for them in debug mode.
+    fn compile_body(
         &self,
-        body: A,
+        body: &CodeBody,
         context: &mut AssemblyContext,
         wrapper: Option<BodyWrapper>,
-    ) -> Result<CodeBlock, AssemblyError>
-    where
-        A: Iterator<Item = N>,
-        N: Borrow<Node>,
-    {
+    ) -> Result<CodeBlock, AssemblyError> {
         let mut blocks: Vec<CodeBlock> = Vec::new();
         let mut span = SpanBuilder::new(wrapper);
 
-        for node in body {
-            match node.borrow() {
+        let locations = body.source_locations().iter().copied().map(Some).chain(iter::repeat(None));
+        for (node, location) in body.nodes().iter().zip(locations) {
+            match node {
                 Node::Instruction(inner) => {
-                    if let Some(block) = self.compile_instruction(inner, &mut span, context)? {
+                    context.record_instruction();
+                    if let Some(block) =
+                        self.compile_instruction(inner, location, &mut span, context)?
+                    {
                         span.extract_span_into(&mut blocks);
                         blocks.push(block);
                     }
@@ -329,12 +430,12 @@ impl Assembler {
                 } => {
                     span.extract_span_into(&mut blocks);
 
-                    let true_case = self.compile_body(true_case.nodes().iter(), context, None)?;
+                    let true_case = self.compile_body(true_case, context, None)?;
 
                     // else is an exception because it is optional; hence, will have to be replaced
                     // by noop span
                     let false_case = if !false_case.nodes().is_empty() {
-                        self.compile_body(false_case.nodes().iter(), context, None)?
+                        self.compile_body(false_case, context, None)?
                     } else {
                         CodeBlock::new_span(vec![Operation::Noop])
                     };
@@ -347,7 +448,7 @@ impl Assembler {
                 Node::Repeat { times, body } => {
                     span.extract_span_into(&mut blocks);
 
-                    let block = self.compile_body(body.nodes().iter(), context, None)?;
+                    let block = self.compile_body(body, context, None)?;
 
                     for _ in 0..*times {
                         blocks.push(block.clone());
@@ -357,7 +458,7 @@ impl Assembler {
                 Node::While { body } => {
                     span.extract_span_into(&mut blocks);
 
-                    let block = self.compile_body(body.nodes().iter(), context, None)?;
+                    let block = self.compile_body(body, context, None)?;
                     let block = CodeBlock::new_loop(block);
 
                     blocks.push(block);
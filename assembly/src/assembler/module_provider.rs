@@ -1,4 +1,4 @@
-use super::{BTreeMap, Library, LibraryError, Module, ProcedureId, Vec};
+use super::{BTreeMap, Library, LibraryError, LibraryLoadError, Module, ProcedureId, Vec};
 
 // MODULE PROVIDER
 // ================================================================================================
@@ -10,6 +10,9 @@ pub struct ModuleProvider {
     modules: Vec<Module>,
     /// Map from procedure id to the index of a module in which the procedure is defined.
     procedures: BTreeMap<ProcedureId, usize>,
+    /// When set, a module with the same path as a previously added module replaces it instead of
+    /// causing an error.
+    allow_shadowing: bool,
 }
 
 impl ModuleProvider {
@@ -25,16 +28,29 @@ impl ModuleProvider {
     // MODULE AND LIBRARY MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets whether modules added later are allowed to shadow previously added modules with the
+    /// same path.
+    pub fn set_allow_shadowing(&mut self, allow_shadowing: bool) {
+        self.allow_shadowing = allow_shadowing;
+    }
+
     /// Adds the provided module to this module provider.
     ///
+    /// If shadowing is allowed, a module with the same path as a previously added module replaces
+    /// that module.
+    ///
     /// # Errors
     ///
-    /// Will error if there is a duplicated module path.
+    /// Will error if there is a duplicated module path and shadowing is not allowed.
     fn add_module(&mut self, module: Module) -> Result<(), LibraryError> {
-        if self.modules.iter().any(|m| module.path == m.path) {
-            return Err(LibraryError::duplicate_module_path(&module.path));
-        }
-        let module_idx = self.modules.len();
+        let module_idx = match self.modules.iter().position(|m| module.path == m.path) {
+            Some(idx) if self.allow_shadowing => {
+                self.procedures.retain(|_, module_idx| *module_idx != idx);
+                idx
+            }
+            Some(_) => return Err(LibraryError::duplicate_module_path(&module.path)),
+            None => self.modules.len(),
+        };
         for proc in module.ast.reexported_procs().iter() {
             let proc_path = module.path.append(proc.name())?;
             let proc_id = ProcedureId::from(&proc_path);
@@ -45,7 +61,11 @@ impl ModuleProvider {
             let proc_id = ProcedureId::from(&proc_path);
             self.procedures.insert(proc_id, module_idx);
         }
-        self.modules.push(module);
+        if module_idx == self.modules.len() {
+            self.modules.push(module);
+        } else {
+            self.modules[module_idx] = module;
+        }
         Ok(())
     }
 
@@ -53,15 +73,18 @@ impl ModuleProvider {
     ///
     /// # Errors
     ///
-    /// Will error if there is a duplicated module path.
-    pub fn add_library<L>(&mut self, library: &L) -> Result<(), LibraryError>
+    /// Will error if there is a duplicated module path and shadowing is not allowed. The error
+    /// identifies the library and the path of the module which could not be added.
+    pub fn add_library<L>(&mut self, library: &L) -> Result<(), LibraryLoadError>
     where
         L: Library,
     {
         let namespace = library.root_ns();
         library.modules().try_for_each(|module| {
-            module.check_namespace(namespace)?;
-            self.add_module(module.clone())
+            module
+                .check_namespace(namespace)
+                .and_then(|_| self.add_module(module.clone()))
+                .map_err(|err| LibraryLoadError::new(namespace, &module.path, err))
         })
     }
 }
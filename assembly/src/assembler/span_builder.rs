@@ -2,7 +2,7 @@ use super::{
     AssemblyContext, AssemblyError, BodyWrapper, Borrow, CodeBlock, Decorator, DecoratorList,
     Instruction, Operation, ToString, Vec,
 };
-use vm_core::{AdviceInjector, AssemblyOp};
+use vm_core::{AdviceInjector, AssemblyOp, SourceLocation};
 
 // SPAN BUILDER
 // ================================================================================================
@@ -81,6 +81,16 @@ impl SpanBuilder {
         self.ops.resize(new_len, op);
     }
 
+    /// Returns the number of operations currently in this builder, excluding the epilogue.
+    pub fn num_ops(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns the operations currently in this builder, excluding the epilogue.
+    pub fn ops(&self) -> &[Operation] {
+        &self.ops
+    }
+
     // DECORATORS
     // --------------------------------------------------------------------------------------------
 
@@ -98,12 +108,22 @@ impl SpanBuilder {
     ///
     /// This indicates that the provided instruction should be tracked and the cycle count for
     /// this instruction will be computed when the call to set_instruction_cycle_count() is made.
-    pub fn track_instruction(&mut self, instruction: &Instruction, ctx: &AssemblyContext) {
+    /// If the location of the instruction in the source code is known, it is attached to the
+    /// decorator.
+    pub fn track_instruction(
+        &mut self,
+        instruction: &Instruction,
+        location: Option<SourceLocation>,
+        ctx: &AssemblyContext,
+    ) {
         let context_name = ctx.current_context_name().to_string();
         let num_cycles = 0;
         let op = instruction.to_string();
         let should_break = instruction.should_break();
-        let op = AssemblyOp::new(context_name, num_cycles, op, should_break);
+        let mut op = AssemblyOp::new(context_name, num_cycles, op, should_break);
+        if let Some(location) = location {
+            op = op.with_location(location);
+        }
         self.push_decorator(Decorator::AsmOp(op));
         self.last_asmop_pos = self.decorators.len() - 1;
     }
@@ -139,16 +159,17 @@ impl SpanBuilder {
     ///
     /// This consumes all operations and decorators in the builder, but does not touch the
     /// operations in the epilogue of the builder.
+    /// If the builder contains decorators but no operations (e.g., a block consisting only of
+    /// `trace` instructions), a NOOP is added so that the decorators are not lost.
     pub fn extract_span_into(&mut self, target: &mut Vec<CodeBlock>) {
+        if self.ops.is_empty() && !self.decorators.is_empty() {
+            self.ops.push(Operation::Noop);
+        }
+
         if !self.ops.is_empty() {
             let ops = self.ops.drain(..).collect();
             let decorators = self.decorators.drain(..).collect();
             target.push(CodeBlock::new_span_with_decorators(ops, decorators));
-        } else if !self.decorators.is_empty() {
-            // this is a bug in the assembler. we shouldn't have decorators added without their
-            // associated operations
-            // TODO: change this to an error or allow decorators in empty span blocks
-            unreachable!("decorators in an empty SPAN block")
         }
     }
 
@@ -1,5 +1,8 @@
-use super::{combine_blocks, Assembler, CodeBlock, Library, Module, Operation};
-use crate::{ast::ModuleAst, LibraryNamespace, LibraryPath, Version};
+use super::{combine_blocks, Assembler, CodeBlock, CompileStats, Library, Module, Operation};
+use crate::{
+    ast::{ModuleAst, ProgramAst},
+    LibraryNamespace, LibraryPath, Version,
+};
 use core::slice::Iter;
 
 // TESTS
@@ -139,3 +142,193 @@ fn nested_blocks() {
 
     assert_eq!(combined.hash(), program.hash());
 }
+
+#[test]
+fn compile_stats() {
+    let source = "
+        proc.foo push.1 drop end
+        proc.bar exec.foo end
+        proc.unused add end
+        begin
+            exec.bar
+            exec.bar
+            push.2
+        end";
+    let ast = ProgramAst::parse(source).unwrap();
+
+    let assembler = Assembler::default();
+    let (program, stats) = assembler.compile_ast_with_stats(&ast).unwrap();
+    let expected = CompileStats {
+        instruction_count: 7,
+        procedure_count: 3,
+        inline_expansions: 3,
+        dead_procedures_eliminated: 1,
+        optimization_passes: 1,
+    };
+    assert_eq!(stats, expected);
+    assert_eq!(program.hash(), assembler.compile_ast(&ast).unwrap().hash());
+}
+
+#[test]
+fn optimization_levels() {
+    let source = "begin add.2 mul.1 add.0 end";
+
+    let compile = |opt_level| {
+        Assembler::default().with_optimization_level(opt_level).compile(source).unwrap()
+    };
+
+    let expected = [
+        CodeBlock::new_span(vec![
+            Operation::Push(2_u64.into()),
+            Operation::Add,
+            Operation::Push(1_u64.into()),
+            Operation::Mul,
+            Operation::Push(0_u64.into()),
+            Operation::Add,
+        ]),
+        CodeBlock::new_span(vec![
+            Operation::Incr,
+            Operation::Incr,
+            Operation::Noop,
+            Operation::Noop,
+        ]),
+        CodeBlock::new_span(vec![Operation::Incr, Operation::Incr]),
+    ];
+    for (opt_level, expected) in expected.iter().enumerate() {
+        assert_eq!(compile(opt_level as u8).hash(), expected.hash());
+    }
+
+    // levels above the maximum are treated as the maximum level
+    assert_eq!(compile(Assembler::MAX_OPT_LEVEL + 1).hash(), expected[2].hash());
+}
+
+#[test]
+fn instruction_cycle_costs() {
+    use crate::ast::Instruction;
+
+    let assembler = Assembler::default();
+    let cost = |instruction| assembler.instruction_cycle_cost(&instruction, 2).unwrap();
+    assert_eq!(cost(Instruction::Add), 1);
+    assert_eq!(cost(Instruction::Sub), 2);
+    assert_eq!(cost(Instruction::AddImm(2_u64.into())), 2);
+    assert_eq!(cost(Instruction::LocLoad(1)), 3);
+    assert_eq!(cost(Instruction::ExecLocal(0)), 0);
+    assert_eq!(cost(Instruction::CallLocal(0)), 2);
+
+    // the cost depends on the optimization level of the assembler
+    let literal = Assembler::default().with_optimization_level(0);
+    let add_imm = Instruction::AddImm(2_u64.into());
+    assert_eq!(literal.instruction_cycle_cost(&add_imm, 0).unwrap(), 2);
+    let add_imm = Instruction::AddImm(5_u64.into());
+    assert_eq!(assembler.instruction_cycle_cost(&add_imm, 0).unwrap(), 2);
+
+    // locals out of bounds cannot be compiled
+    assert!(assembler.instruction_cycle_cost(&Instruction::LocLoad(2), 2).is_err());
+
+    let source = "\
+proc.foo.1
+    loc_store.0 push.1
+end
+begin
+    push.2 exec.foo
+    if.true
+        add
+    else
+        repeat.10 mul end
+    end
+end";
+    let ast = ProgramAst::parse(source).unwrap();
+    let costs = ast.instruction_costs(&assembler).unwrap();
+    let summary = costs
+        .iter()
+        .map(|cost| (cost.procedure.as_ref(), cost.instruction.to_string(), cost.cycles))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        [
+            ("foo", "loc_store.0".to_string(), 4),
+            ("foo", "push.1".to_string(), 2),
+            ("#main", "push.2".to_string(), 1),
+            ("#main", "exec.0".to_string(), 0),
+            ("#main", "add".to_string(), 1),
+            ("#main", "mul".to_string(), 1),
+        ]
+    );
+    assert_eq!(costs[0].location.map(|loc| (loc.line(), loc.column())), Some((2, 5)));
+    assert_eq!(costs[5].location.map(|loc| (loc.line(), loc.column())), Some((9, 19)));
+    assert_eq!(ast.estimated_cycle_count().unwrap(), 9);
+}
+
+#[test]
+fn procedure_stack_effects() {
+    use crate::ast::Instruction;
+
+    let assembler = Assembler::default();
+    let effect = |instruction| assembler.instruction_stack_effect(&instruction, 1).unwrap();
+    assert_eq!(effect(Instruction::Add), Some(-1));
+    assert_eq!(effect(Instruction::PadW), Some(4));
+    assert_eq!(effect(Instruction::MemStoreImm(0)), Some(-1));
+    assert_eq!(effect(Instruction::LocLoadW(0)), Some(0));
+    assert_eq!(effect(Instruction::U32CheckedAdd), Some(-1));
+    assert_eq!(effect(Instruction::CallLocal(0)), Some(0));
+    assert_eq!(effect(Instruction::ExecLocal(0)), None);
+
+    let source = "\
+proc.add3
+    add add
+end
+proc.push_or_keep
+    if.true push.1 else push.2 end
+end
+proc.unbalanced
+    if.true push.1 else swap end
+end
+proc.count_down
+    push.1 while.true sub.1 dup neq.0 end drop
+end
+begin
+    exec.add3 repeat.3 exec.push_or_keep end
+end";
+    let ast = ProgramAst::parse(source).unwrap();
+    let effects = ast.procedure_stack_effects(&assembler).unwrap();
+    assert_eq!(effects, [Some(-2), Some(0), None, Some(-1)]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hot_assembler_reloads_changed_libraries() {
+    use super::HotAssembler;
+    use crate::{LibraryNamespace, MaslLibrary};
+
+    let dir = std::env::temp_dir().join(format!("miden-hot-assembler-{}", std::process::id()));
+    let write_library = |source: &str| {
+        let path = LibraryPath::new("hot::math").unwrap();
+        let module = Module::new(path, ModuleAst::parse(source).unwrap());
+        let namespace = LibraryNamespace::new("hot").unwrap();
+        MaslLibrary::new(namespace, Version::MIN, false, vec![module], Vec::new())
+            .unwrap()
+            .write_to_dir(&dir)
+            .unwrap();
+    };
+    let program = "use.hot::math begin exec.math::foo end";
+
+    write_library("export.foo add end");
+    let mut assembler = HotAssembler::new([dir.join("hot.masl")]).unwrap();
+    let add_hash = assembler.compile(program).unwrap().hash();
+
+    // unchanged libraries do not trigger a rebuild
+    assert!(!assembler.check_reload().unwrap());
+
+    // a modified library is picked up by the next compilation
+    write_library("export.foo push.2 mul end");
+    let mul_hash = assembler.compile(program).unwrap().hash();
+    assert_ne!(add_hash, mul_hash);
+    assert!(!assembler.check_reload().unwrap());
+
+    // a failed reload keeps the previously loaded libraries
+    std::fs::remove_file(dir.join("hot.masl")).unwrap();
+    assert!(assembler.check_reload().is_err());
+    assert_eq!(assembler.assembler().compile(program).unwrap().hash(), mul_hash);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,97 @@
+use super::{CodeBody, Instruction, Node, ProcedureName, ProgramAst, SourceLocation, Vec};
+use crate::{Assembler, AssemblyError};
+
+// INSTRUCTION COST
+// ================================================================================================
+
+/// The number of VM cycles it takes to execute an instruction of a program.
+///
+/// See [Assembler::instruction_cycle_cost()] for details on how the cost is computed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionCost {
+    /// Name of the procedure the instruction belongs to (`#main` for the program body).
+    pub procedure: ProcedureName,
+    /// The instruction.
+    pub instruction: Instruction,
+    /// Location of the instruction in the source code, if the AST contains source locations.
+    pub location: Option<SourceLocation>,
+    /// Number of VM cycles it takes to execute the instruction.
+    pub cycles: u64,
+}
+
+// PROGRAM CYCLE COSTS
+// ================================================================================================
+
+impl ProgramAst {
+    /// Returns the cycle costs of all instructions of this program, as compiled by the specified
+    /// assembler.
+    ///
+    /// The instructions of the local procedures are listed first, followed by the instructions of
+    /// the program body. Every instruction is listed once: instructions in loops are not repeated,
+    /// and instructions in both branches of conditionals are listed.
+    ///
+    /// # Errors
+    /// Returns an error if any of the instructions cannot be compiled by the assembler.
+    pub fn instruction_costs(
+        &self,
+        assembler: &Assembler,
+    ) -> Result<Vec<InstructionCost>, AssemblyError> {
+        let mut costs = Vec::new();
+        for proc in self.procedures() {
+            collect_costs(assembler, &proc.name, proc.num_locals, &proc.body, &mut costs)?;
+        }
+        collect_costs(assembler, &ProcedureName::main(), 0, self.body(), &mut costs)?;
+        Ok(costs)
+    }
+
+    /// Returns an estimate of the number of VM cycles it takes to execute this program, computed
+    /// without executing it.
+    ///
+    /// The estimate is the sum of the cycle costs of all instructions of the program as compiled
+    /// by the default assembler, as returned by [ProgramAst::instruction_costs()]. Thus, every
+    /// loop is counted as if it was executed once, both branches of conditionals are counted,
+    /// and procedures are counted once regardless of how many times they are invoked.
+    ///
+    /// # Errors
+    /// Returns an error if any of the instructions cannot be compiled.
+    pub fn estimated_cycle_count(&self) -> Result<u64, AssemblyError> {
+        let costs = self.instruction_costs(&Assembler::default())?;
+        Ok(costs.iter().map(|cost| cost.cycles).sum())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Appends the cycle costs of the instructions in the specified body, and in the bodies nested in
+/// it, to `costs`.
+fn collect_costs(
+    assembler: &Assembler,
+    procedure: &ProcedureName,
+    num_locals: u16,
+    body: &CodeBody,
+    costs: &mut Vec<InstructionCost>,
+) -> Result<(), AssemblyError> {
+    let locations = body.source_locations();
+    for (idx, node) in body.nodes().iter().enumerate() {
+        match node {
+            Node::Instruction(instruction) => costs.push(InstructionCost {
+                procedure: procedure.clone(),
+                instruction: instruction.clone(),
+                location: locations.get(idx).copied(),
+                cycles: assembler.instruction_cycle_cost(instruction, num_locals)?,
+            }),
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => {
+                collect_costs(assembler, procedure, num_locals, true_case, costs)?;
+                collect_costs(assembler, procedure, num_locals, false_case, costs)?;
+            }
+            Node::Repeat { body, .. } | Node::While { body } => {
+                collect_costs(assembler, procedure, num_locals, body, costs)?;
+            }
+        }
+    }
+    Ok(())
+}
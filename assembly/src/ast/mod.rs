@@ -23,6 +23,9 @@ pub use nodes::{AdviceInjectorNode, Instruction, Node};
 mod code_body;
 pub use code_body::CodeBody;
 
+mod cycle_costs;
+pub use cycle_costs::InstructionCost;
+
 mod format;
 use format::*;
 
@@ -40,6 +43,8 @@ pub(crate) use parsers::{NAMESPACE_LABEL_PARSER, PROCEDURE_LABEL_PARSER};
 mod serde;
 pub use serde::AstSerdeOptions;
 
+mod stack_effects;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -154,6 +159,14 @@ impl ProgramAst {
         &self.body
     }
 
+    /// Returns a map of imported modules in this program.
+    pub fn import_paths(&self) -> Vec<&LibraryPath> {
+        match &self.import_info {
+            Some(info) => info.import_paths(),
+            None => Vec::<&LibraryPath>::new(),
+        }
+    }
+
     /// Returns a map containing IDs and names of imported procedures.
     pub fn get_imported_procedures_map(&self) -> BTreeMap<ProcedureId, ProcedureName> {
         if let Some(info) = &self.import_info {
@@ -27,6 +27,7 @@ pub enum AdviceInjectorNode {
     PushMapValImm { offset: u8 },
     PushMapValN,
     PushMapValNImm { offset: u8 },
+    IterMap,
     PushMtNode,
     InsertMem,
     InsertHdword,
@@ -60,6 +61,7 @@ impl From<&AdviceInjectorNode> for AdviceInjector {
                 include_len: true,
                 key_offset: (*offset) as usize,
             },
+            IterMap => Self::MapRangeToStack,
             PushMtNode => Self::MerkleNodeToStack,
             InsertMem => Self::MemToMap,
             InsertHdword => Self::HdwordToMap { domain: ZERO },
@@ -85,6 +87,7 @@ impl fmt::Display for AdviceInjectorNode {
             PushMapValImm { offset } => write!(f, "push_mapval.{offset}"),
             PushMapValN => write!(f, "push_mapvaln"),
             PushMapValNImm { offset } => write!(f, "push_mapvaln.{offset}"),
+            IterMap => write!(f, "iter_map"),
             PushMtNode => write!(f, "push_mtnode"),
             InsertMem => write!(f, "insert_mem"),
             InsertHdword => write!(f, "insert_hdword"),
@@ -113,6 +116,7 @@ const INSERT_HDWORD: u8 = 11;
 const INSERT_HDWORD_IMM: u8 = 12;
 const INSERT_HPERM: u8 = 13;
 const PUSH_SIG: u8 = 14;
+const ITER_MAP: u8 = 15;
 
 impl Serializable for AdviceInjectorNode {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
@@ -133,6 +137,7 @@ impl Serializable for AdviceInjectorNode {
                 target.write_u8(PUSH_MAPVALN_IMM);
                 target.write_u8(*offset);
             }
+            IterMap => target.write_u8(ITER_MAP),
             PushMtNode => target.write_u8(PUSH_MTNODE),
             InsertMem => target.write_u8(INSERT_MEM),
             InsertHdword => target.write_u8(INSERT_HDWORD),
@@ -173,6 +178,7 @@ impl Deserializable for AdviceInjectorNode {
                 }
                 Ok(AdviceInjectorNode::PushMapValNImm { offset })
             }
+            ITER_MAP => Ok(AdviceInjectorNode::IterMap),
             PUSH_MTNODE => Ok(AdviceInjectorNode::PushMtNode),
             INSERT_MEM => Ok(AdviceInjectorNode::InsertMem),
             INSERT_HDWORD => Ok(AdviceInjectorNode::InsertHdword),
@@ -105,16 +105,12 @@ impl fmt::Display for FormattableInstruction<'_> {
                 write!(f, "call.{proc_name}")?;
             }
             Instruction::ExecImported(proc_id) => {
-                let (_, path) = self.context.imported_proc(proc_id);
-                write!(f, "exec.{path}")?;
+                let (proc_name, path) = self.context.imported_proc(proc_id);
+                write!(f, "exec.{}::{proc_name}", path.last())?;
             }
             Instruction::CallImported(proc_id) => {
-                let (_, path) = self.context.imported_proc(proc_id);
-                write!(f, "call.{path}")?;
-            }
-            Instruction::SysCall(proc_id) => {
-                let (_, path) = self.context.imported_proc(proc_id);
-                write!(f, "syscall.{path}")?;
+                let (proc_name, path) = self.context.imported_proc(proc_id);
+                write!(f, "call.{}::{proc_name}", path.last())?;
             }
             Instruction::CallMastRoot(root) => {
                 write!(f, "call.")?;
@@ -296,6 +296,7 @@ pub enum Instruction {
     MTreeSet,
     MTreeMerge,
     MTreeVerify,
+    MTreeMember,
 
     // ----- STARK proof verification -------------------------------------------------------------
     FriExt2Fold4,
@@ -313,6 +314,7 @@ pub enum Instruction {
     // ----- debug decorators ---------------------------------------------------------------------
     Breakpoint,
     Debug(DebugOptions),
+    Trace(u32),
 }
 
 impl Instruction {
@@ -581,6 +583,7 @@ impl fmt::Display for Instruction {
             Self::MTreeSet => write!(f, "mtree_set"),
             Self::MTreeMerge => write!(f, "mtree_merge"),
             Self::MTreeVerify => write!(f, "mtree_verify"),
+            Self::MTreeMember => write!(f, "mtree_member"),
             Self::FriExt2Fold4 => write!(f, "fri_ext2fold4"),
 
             // ----- exec / call ------------------------------------------------------------------
@@ -599,6 +602,7 @@ impl fmt::Display for Instruction {
             // ----- debug decorators -------------------------------------------------------------
             Self::Breakpoint => write!(f, "breakpoint"),
             Self::Debug(options) => write!(f, "debug.{options}"),
+            Self::Trace(trace_id) => write!(f, "trace.{trace_id}"),
         }
     }
 }
@@ -350,6 +350,7 @@ impl Deserializable for Instruction {
             OpCode::MTreeSet => Ok(Instruction::MTreeSet),
             OpCode::MTreeMerge => Ok(Instruction::MTreeMerge),
             OpCode::MTreeVerify => Ok(Instruction::MTreeVerify),
+            OpCode::MTreeMember => Ok(Instruction::MTreeMember),
 
             // ----- STARK proof verification -----------------------------------------------------
             OpCode::FriExt2Fold4 => Ok(Instruction::FriExt2Fold4),
@@ -369,6 +370,7 @@ impl Deserializable for Instruction {
                 let options = debug::read_options_from(source)?;
                 Ok(Instruction::Debug(options))
             }
+            OpCode::Trace => Ok(Instruction::Trace(source.read_u32()?)),
 
             // ----- control flow -----------------------------------------------------------------
             // control flow instructions should be parsed as a part of Node::read_from() and we
@@ -269,6 +269,7 @@ pub enum OpCode {
     MTreeSet = 237,
     MTreeMerge = 238,
     MTreeVerify = 239,
+    MTreeMember = 251,
 
     // ----- STARK proof verification -------------------------------------------------------------
     FriExt2Fold4 = 240,
@@ -285,6 +286,7 @@ pub enum OpCode {
 
     // ----- debugging ----------------------------------------------------------------------------
     Debug = 249,
+    Trace = 250,
 
     // ----- control flow -------------------------------------------------------------------------
     IfElse = 253,
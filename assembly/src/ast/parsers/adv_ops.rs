@@ -66,6 +66,10 @@ pub fn parse_adv_inject(op: &Token) -> Result<Node, ParsingError> {
             }
             _ => return Err(ParsingError::extra_param(op)),
         },
+        "iter_map" => match op.num_parts() {
+            2 => AdvInject(IterMap),
+            _ => return Err(ParsingError::extra_param(op)),
+        },
         "push_mtnode" => match op.num_parts() {
             2 => AdvInject(PushMtNode),
             _ => return Err(ParsingError::extra_param(op)),
@@ -608,6 +608,7 @@ impl ParserContext<'_> {
             "mtree_set" => simple_instruction(op, MTreeSet),
             "mtree_merge" => simple_instruction(op, MTreeMerge),
             "mtree_verify" => simple_instruction(op, MTreeVerify),
+            "mtree_member" => simple_instruction(op, MTreeMember),
 
             "fri_ext2fold4" => simple_instruction(op, FriExt2Fold4),
 
@@ -624,6 +625,7 @@ impl ParserContext<'_> {
             // ----- debug decorators -------------------------------------------------------------
             "breakpoint" => simple_instruction(op, Breakpoint),
             "debug" => debug::parse_debug(op),
+            "trace" => debug::parse_trace(op),
 
             // ----- catch all --------------------------------------------------------------------
             _ => Err(ParsingError::invalid_op(op)),
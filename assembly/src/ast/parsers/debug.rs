@@ -1,5 +1,5 @@
 use super::{
-    parse_checked_param,
+    parse_checked_param, parse_param,
     Instruction::*,
     Node::{self, Instruction},
     ParsingError, Token,
@@ -34,3 +34,17 @@ pub fn parse_debug(op: &Token) -> Result<Node, ParsingError> {
 
     Ok(Instruction(Debug(options)))
 }
+
+/// Returns `Trace` instruction node.
+///
+/// # Errors
+/// Returns an error if the instruction token does not contain exactly one parameter, or if the
+/// parameter is not a valid u32 value.
+pub fn parse_trace(op: &Token) -> Result<Node, ParsingError> {
+    debug_assert_eq!(op.parts()[0], "trace");
+    match op.num_parts() {
+        0 | 1 => Err(ParsingError::missing_param(op, "trace.<trace_id>")),
+        2 => Ok(Instruction(Trace(parse_param(op, 1)?))),
+        _ => Err(ParsingError::extra_param(op)),
+    }
+}
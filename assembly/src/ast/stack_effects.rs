@@ -0,0 +1,117 @@
+use super::{CodeBody, Instruction, ModuleAst, Node, ProcedureAst, ProgramAst, Vec};
+use crate::{Assembler, AssemblyError};
+
+// STACK EFFECTS
+// ================================================================================================
+
+impl ProgramAst {
+    /// Returns the net change of the stack depth caused by executing each of the local procedures
+    /// of this program, as compiled by the specified assembler.
+    ///
+    /// The changes are computed statically, without executing the procedures:
+    /// - the change of an instruction is computed by [Assembler::instruction_stack_effect()], and
+    ///   the change of `exec` of a local procedure is the change of that procedure.
+    /// - `if.true` pops the condition, and its branches must change the stack depth by the same
+    ///   amount.
+    /// - `while.true` pops the condition, and its body must push exactly one element (the
+    ///   condition of the next iteration), so that every iteration leaves the stack depth
+    ///   unchanged.
+    /// - `repeat.n` changes the stack depth by n times the change of its body.
+    ///
+    /// The change of a procedure is None if it cannot be determined statically, e.g., if the
+    /// procedure invokes an imported procedure via `exec`, or if the branches of a conditional
+    /// change the stack depth by different amounts.
+    ///
+    /// # Errors
+    /// Returns an error if any of the instructions cannot be compiled by the assembler.
+    pub fn procedure_stack_effects(
+        &self,
+        assembler: &Assembler,
+    ) -> Result<Vec<Option<i32>>, AssemblyError> {
+        procedure_stack_effects(assembler, self.procedures())
+    }
+}
+
+impl ModuleAst {
+    /// Returns the net change of the stack depth caused by executing each of the procedures of
+    /// this module, as compiled by the specified assembler.
+    ///
+    /// See [ProgramAst::procedure_stack_effects()] for details on how the changes are computed.
+    ///
+    /// # Errors
+    /// Returns an error if any of the instructions cannot be compiled by the assembler.
+    pub fn procedure_stack_effects(
+        &self,
+        assembler: &Assembler,
+    ) -> Result<Vec<Option<i32>>, AssemblyError> {
+        procedure_stack_effects(assembler, self.procs())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the net change of the stack depth caused by executing each of the specified
+/// procedures, in the order of the procedures.
+fn procedure_stack_effects(
+    assembler: &Assembler,
+    procs: &[ProcedureAst],
+) -> Result<Vec<Option<i32>>, AssemblyError> {
+    // a procedure can invoke only the procedures declared before it, and thus, the effects of
+    // all procedures it invokes are known by the time it is processed
+    let mut effects = Vec::with_capacity(procs.len());
+    for proc in procs {
+        let effect = body_stack_effect(assembler, proc.num_locals, &proc.body, &effects)?;
+        effects.push(effect);
+    }
+    Ok(effects)
+}
+
+/// Returns the net change of the stack depth caused by executing the specified body, or None if
+/// it cannot be determined statically.
+fn body_stack_effect(
+    assembler: &Assembler,
+    num_locals: u16,
+    body: &CodeBody,
+    proc_effects: &[Option<i32>],
+) -> Result<Option<i32>, AssemblyError> {
+    let mut total = 0;
+    for node in body.nodes() {
+        let effect = match node {
+            Node::Instruction(Instruction::ExecLocal(idx)) => {
+                proc_effects.get(*idx as usize).copied().flatten()
+            }
+            Node::Instruction(instruction) => {
+                assembler.instruction_stack_effect(instruction, num_locals)?
+            }
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => {
+                let true_effect =
+                    body_stack_effect(assembler, num_locals, true_case, proc_effects)?;
+                let false_effect =
+                    body_stack_effect(assembler, num_locals, false_case, proc_effects)?;
+                match (true_effect, false_effect) {
+                    (Some(t), Some(f)) if t == f => Some(t - 1),
+                    _ => None,
+                }
+            }
+            Node::Repeat { times, body } => {
+                body_stack_effect(assembler, num_locals, body, proc_effects)?
+                    .and_then(|effect| effect.checked_mul(i32::try_from(*times).ok()?))
+            }
+            Node::While { body } => {
+                match body_stack_effect(assembler, num_locals, body, proc_effects)? {
+                    Some(1) => Some(-1),
+                    _ => None,
+                }
+            }
+        };
+        match effect {
+            Some(effect) => total += effect,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(total))
+}
@@ -241,6 +241,22 @@ fn test_ast_parsing_adv_injection() {
     assert_program_output(source, BTreeMap::new(), nodes);
 }
 
+#[test]
+fn test_ast_parsing_trace() {
+    let source = "begin trace.0 push.1 trace.4294967295 end";
+    let nodes: Vec<Node> = vec![
+        Node::Instruction(Instruction::Trace(0)),
+        Node::Instruction(Instruction::PushU8(1)),
+        Node::Instruction(Instruction::Trace(u32::MAX)),
+    ];
+
+    assert_program_output(source, BTreeMap::new(), nodes);
+
+    assert!(ProgramAst::parse("begin trace end").is_err());
+    assert!(ProgramAst::parse("begin trace.1.2 end").is_err());
+    assert!(ProgramAst::parse("begin trace.4294967296 end").is_err());
+}
+
 #[test]
 fn test_ast_parsing_use() {
     let source = "\
@@ -255,6 +271,28 @@ fn test_ast_parsing_use() {
     assert_program_output(source, procedures, nodes);
 }
 
+#[test]
+fn test_ast_program_format_invocations() {
+    let source = "\
+    use.std::math::u64
+    proc.foo
+        push.1
+    end
+    begin
+        exec.foo
+        exec.u64::wrapping_add
+        call.u64::checked_add
+        syscall.bar
+    end";
+    let formatted = ProgramAst::parse(source).unwrap().to_string();
+
+    // local and imported procedures are referred to by their names
+    assert!(formatted.contains("exec.foo\n"));
+    assert!(formatted.contains("exec.u64::wrapping_add\n"));
+    assert!(formatted.contains("call.u64::checked_add\n"));
+    assert!(formatted.contains("syscall."));
+}
+
 #[test]
 fn test_ast_parsing_module_nested_if() {
     let source = "\
@@ -764,6 +802,12 @@ fn test_ast_program_serde_simple() {
     assert_correct_program_serialization(source, true);
 }
 
+#[test]
+fn test_ast_program_serde_trace() {
+    let source = "begin push.1 trace.12 drop end";
+    assert_correct_program_serialization(source, true);
+}
+
 #[test]
 fn test_ast_program_serde_local_procs() {
     let source = "\
@@ -1,6 +1,6 @@
 use super::{
     ast::ProcReExport, crypto::hash::RpoDigest, tokens::SourceLocation, LibraryNamespace,
-    ProcedureId, ProcedureName, String, ToString, Token, Vec,
+    LibraryPath, ProcedureId, ProcedureName, String, ToString, Token, Vec,
 };
 use core::fmt;
 
@@ -32,6 +32,7 @@ pub enum AssemblyError {
     ProcedureNameError(String),
     SysCallInKernel(String),
     LibraryError(String),
+    LibraryLoadError(LibraryLoadError),
     Io(String),
 }
 
@@ -123,6 +124,12 @@ impl From<LibraryError> for AssemblyError {
     }
 }
 
+impl From<LibraryLoadError> for AssemblyError {
+    fn from(err: LibraryLoadError) -> Self {
+        Self::LibraryLoadError(err)
+    }
+}
+
 impl From<LabelError> for AssemblyError {
     fn from(err: LabelError) -> Self {
         Self::ProcedureNameError(format!("invalid procedure name: {err}"))
@@ -150,6 +157,7 @@ impl fmt::Display for AssemblyError {
             Io(description) => write!(f, "I/O error: {description}"),
             KernelProcNotFound(proc_id) => write!(f, "procedure {proc_id} not found in kernel"),
             LibraryError(err) | ParsingError(err) | ProcedureNameError(err) => write!(f, "{err}"),
+            LibraryLoadError(err) => write!(f, "{err}"),
             LocalProcNotFound(proc_idx, module_path) => write!(f, "procedure at index {proc_idx} not found in module {module_path}"),
             ParamOutOfBounds(value, min, max) => write!(f, "parameter value must be greater than or equal to {min} and less than or equal to {max}, but was {value}"),
             PhantomCallsNotAllowed(mast_root) => write!(f, "cannot call phantom procedure with MAST root {mast_root}: phantom calls not allowed"),
@@ -748,8 +756,9 @@ impl fmt::Display for LabelError {
 // LIBRARY ERROR
 // ================================================================================================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LibraryError {
+    ChecksumMismatch(String),
     DeserializationFailed(String, String),
     DuplicateModulePath(String),
     DuplicateNamespace(String),
@@ -758,6 +767,7 @@ pub enum LibraryError {
         expected: String,
         actual: String,
     },
+    InvalidManifest(String, String),
     InvalidNamespace(LabelError),
     InvalidPath(PathError),
     InvalidVersionNumber {
@@ -788,6 +798,10 @@ pub enum LibraryError {
 }
 
 impl LibraryError {
+    pub fn checksum_mismatch(path: &str) -> Self {
+        Self::ChecksumMismatch(path.into())
+    }
+
     pub fn deserialization_error(path: &str, message: &str) -> Self {
         Self::DeserializationFailed(path.into(), message.into())
     }
@@ -811,6 +825,10 @@ impl LibraryError {
         }
     }
 
+    pub fn invalid_manifest(path: &str, message: &str) -> Self {
+        Self::InvalidManifest(path.into(), message.into())
+    }
+
     pub fn invalid_namespace(err: LabelError) -> Self {
         Self::InvalidNamespace(err)
     }
@@ -868,6 +886,7 @@ impl fmt::Display for LibraryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use LibraryError::*;
         match self {
+            ChecksumMismatch(path) => write!(f, "Library checksum mismatch - '{path}'"),
             DeserializationFailed(path, message) => {
                 write!(f, "library deserialization failed - '{path}': {message}")
             }
@@ -879,6 +898,9 @@ impl fmt::Display for LibraryError {
             InconsistentNamespace { expected, actual } => {
                 write!(f, "inconsistent module namespace: expected '{expected}', but was {actual}")
             }
+            InvalidManifest(path, message) => {
+                write!(f, "invalid manifest - '{path}': {message}")
+            }
             InvalidNamespace(err) => {
                 write!(f, "invalid namespace: {err}")
             }
@@ -940,10 +962,59 @@ impl From<PathError> for LibraryError {
     }
 }
 
+// LIBRARY LOAD ERROR
+// ================================================================================================
+
+/// An error which can be generated while adding a library to an assembler.
+///
+/// Identifies the library which could not be added and the path of its module which caused the
+/// failure; if the module conflicts with a module of a previously added library, this is the path
+/// defined by both libraries.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LibraryLoadError {
+    /// Root namespace of the library which could not be added.
+    pub library_name: String,
+    /// Path of the module of the library which could not be added.
+    pub conflicting_path: LibraryPath,
+    /// The underlying error.
+    pub source: LibraryError,
+}
+
+impl LibraryLoadError {
+    pub fn new(
+        library_name: &LibraryNamespace,
+        module_path: &LibraryPath,
+        source: LibraryError,
+    ) -> Self {
+        Self {
+            library_name: library_name.to_string(),
+            conflicting_path: module_path.clone(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for LibraryLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to load library '{}': module '{}': {}",
+            self.library_name, self.conflicting_path, self.source
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LibraryLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
 // PATH ERROR
 // ================================================================================================
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum PathError {
     ComponentInvalidChar { component: String },
     ComponentInvalidFirstChar { component: String },
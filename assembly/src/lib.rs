@@ -16,6 +16,8 @@ use vm_core::{
 };
 
 mod library;
+#[cfg(feature = "std")]
+pub use library::Manifest;
 pub use library::{Library, LibraryNamespace, LibraryPath, MaslLibrary, Module, Version};
 
 mod procedures;
@@ -29,10 +31,14 @@ mod tokens;
 use tokens::{Token, TokenStream};
 
 mod errors;
-pub use errors::{AssemblyError, LabelError, LibraryError, ParsingError, PathError};
+pub use errors::{
+    AssemblyError, LabelError, LibraryError, LibraryLoadError, ParsingError, PathError,
+};
 
 mod assembler;
-pub use assembler::{Assembler, AssemblyContext};
+#[cfg(feature = "std")]
+pub use assembler::HotAssembler;
+pub use assembler::{Assembler, AssemblyContext, CompileStats};
 
 #[cfg(test)]
 mod tests;
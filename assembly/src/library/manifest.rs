@@ -0,0 +1,187 @@
+use super::{LibraryError, String, ToString, Vec};
+use core::fmt;
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+// PROJECT MANIFEST
+// ================================================================================================
+
+/// A project manifest describing the libraries a program depends on.
+///
+/// The manifest is read from a `miden.toml` file. Only the `[dependencies]` table is interpreted;
+/// every entry in it maps a dependency name to the path of a compiled `.masl` library, either
+/// directly or via a `path` key of an inline table:
+///
+/// ```toml
+/// [dependencies]
+/// std = "libs/std.masl"
+/// crypto = { path = "../crypto/crypto.masl" }
+/// ```
+///
+/// Relative paths are resolved against the directory containing the manifest. Other tables are
+/// ignored, so the manifest can carry additional project metadata.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Manifest {
+    #[serde(default, deserialize_with = "deserialize_dependencies")]
+    dependencies: Vec<(String, PathBuf)>,
+}
+
+/// A dependency declared in the `[dependencies]` table of a manifest.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Dependency {
+    Path(PathBuf),
+    Table { path: PathBuf },
+}
+
+impl From<Dependency> for PathBuf {
+    fn from(dependency: Dependency) -> Self {
+        match dependency {
+            Dependency::Path(path) | Dependency::Table { path } => path,
+        }
+    }
+}
+
+impl Manifest {
+    /// Default file name of a project manifest.
+    pub const FILE_NAME: &'static str = "miden.toml";
+
+    /// Reads and parses the manifest at the specified path.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, if it isn't a well-formed manifest, or if the
+    /// same dependency is declared more than once.
+    pub fn read_from_file<P>(path: P) -> Result<Self, LibraryError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let source = fs::read_to_string(path)
+            .map_err(|err| LibraryError::file_error(&display, &err.to_string()))?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+        Self::parse(&source, base_dir)
+            .map_err(|message| LibraryError::invalid_manifest(&display, &message))
+    }
+
+    /// Returns the declared dependencies as `(name, library path)` pairs, in declaration order.
+    pub fn dependencies(&self) -> &[(String, PathBuf)] {
+        &self.dependencies
+    }
+
+    /// Returns the library paths of all declared dependencies, in declaration order.
+    pub fn library_paths(&self) -> impl Iterator<Item = &Path> {
+        self.dependencies.iter().map(|(_, path)| path.as_path())
+    }
+
+    // PARSER
+    // --------------------------------------------------------------------------------------------
+
+    /// Parses the manifest source, resolving dependency paths relative to `base_dir`.
+    fn parse(source: &str, base_dir: &Path) -> Result<Self, String> {
+        let mut manifest: Self = toml::from_str(source).map_err(|err| err.message().to_string())?;
+        for (_, path) in manifest.dependencies.iter_mut() {
+            *path = base_dir.join(&path);
+        }
+        Ok(manifest)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Deserializes the `[dependencies]` table of a manifest into `(name, library path)` pairs,
+/// keeping the order in which the dependencies are declared.
+fn deserialize_dependencies<'de, D>(deserializer: D) -> Result<Vec<(String, PathBuf)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct DependenciesVisitor;
+
+    impl<'de> Visitor<'de> for DependenciesVisitor {
+        type Value = Vec<(String, PathBuf)>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a table of library paths or `{ path = \"...\" }` tables")
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut dependencies = Vec::new();
+            while let Some((name, dependency)) = map.next_entry::<String, Dependency>()? {
+                dependencies.push((name, dependency.into()));
+            }
+            Ok(dependencies)
+        }
+    }
+
+    deserializer.deserialize_map(DependenciesVisitor)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_dependencies() {
+        let source = r#"
+            # project metadata is ignored
+            [package]
+            name = "example"
+
+            [dependencies]
+            std = "libs/std.masl" # trailing comment
+            "my-lib" = { path = "../my#lib/lib.masl" }
+
+            [dev-dependencies]
+            test = "test.masl"
+        "#;
+
+        let manifest = Manifest::parse(source, Path::new("project")).unwrap();
+        let expected = vec![
+            ("std".to_string(), PathBuf::from("project/libs/std.masl")),
+            ("my-lib".to_string(), PathBuf::from("project/../my#lib/lib.masl")),
+        ];
+        assert_eq!(manifest.dependencies(), expected.as_slice());
+    }
+
+    #[test]
+    fn parse_manifest_errors() {
+        let base = Path::new("");
+        assert!(Manifest::parse("[dependencies\n", base).is_err());
+        assert!(Manifest::parse("[dependencies]\nstd\n", base).is_err());
+        assert!(Manifest::parse("[dependencies]\nstd = 1\n", base).is_err());
+        assert!(Manifest::parse("[dependencies]\nstd = { version = \"1\" }\n", base).is_err());
+
+        assert!(Manifest::parse("[dependencies]\na = \"a.masl\"\na = \"b.masl\"\n", base).is_err());
+    }
+
+    #[test]
+    fn parse_manifest_inline_tables() {
+        // separators inside of strings are a part of the path
+        let source = "[dependencies]\nlib = { path = \"a,b/lib.masl\", version = \"1\" }\n";
+        let manifest = Manifest::parse(source, Path::new("")).unwrap();
+        assert_eq!(manifest.dependencies(), [("lib".to_string(), PathBuf::from("a,b/lib.masl"))]);
+
+        // the table may also be written as a regular table
+        let source = "[dependencies.lib]\npath = \"lib.masl\"\n";
+        let manifest = Manifest::parse(source, Path::new("")).unwrap();
+        assert_eq!(manifest.dependencies(), [("lib".to_string(), PathBuf::from("lib.masl"))]);
+
+        // manifests without dependencies are valid
+        let manifest = Manifest::parse("[package]\nname = \"example\"\n", Path::new("")).unwrap();
+        assert!(manifest.dependencies().is_empty());
+    }
+}
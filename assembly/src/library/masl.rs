@@ -64,6 +64,12 @@ impl MaslLibrary {
     pub const MODULE_EXTENSION: &'static str = "masm";
     /// Name of the root module.
     pub const MOD: &'static str = "mod";
+    /// Length of the SHA-256 checksum of the serialized library, which is appended to `masl`
+    /// files.
+    pub const CHECKSUM_LEN: usize = 32;
+    /// Marker preceding the checksum at the end of `masl` files; the last byte is the version of
+    /// the trailer format. Files without the marker were written before checksums were added.
+    pub const CHECKSUM_MARKER: [u8; 8] = *b"MASLSUM\x01";
 
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
@@ -73,7 +79,7 @@ impl MaslLibrary {
     /// # Errors
     /// Returns an error if the provided `modules` vector is empty or contains more than
     /// [u16::MAX] elements.
-    pub(super) fn new(
+    pub fn new(
         namespace: LibraryNamespace,
         version: Version,
         has_source_locations: bool,
@@ -114,12 +120,38 @@ impl MaslLibrary {
     pub fn clear_locations(&mut self) {
         self.modules.iter_mut().for_each(|m| m.clear_locations())
     }
+
+    // CHECKSUM
+    // --------------------------------------------------------------------------------------------
+
+    /// Splits the contents of a `masl` file into the serialized library and the checksum of the
+    /// serialized library.
+    ///
+    /// The checksum is returned only if the contents end with [MaslLibrary::CHECKSUM_MARKER]
+    /// followed by the checksum; otherwise, the file is a legacy file without a checksum, and all
+    /// of its contents are returned as the serialized library.
+    pub fn split_checksum(contents: &[u8]) -> (&[u8], Option<&[u8]>) {
+        let trailer_len = Self::CHECKSUM_MARKER.len() + Self::CHECKSUM_LEN;
+        let trailer_start = match contents.len().checked_sub(trailer_len) {
+            Some(trailer_start) => trailer_start,
+            None => return (contents, None),
+        };
+
+        let (bytes, trailer) = contents.split_at(trailer_start);
+        let (marker, checksum) = trailer.split_at(Self::CHECKSUM_MARKER.len());
+        if marker == Self::CHECKSUM_MARKER {
+            (bytes, Some(checksum))
+        } else {
+            (contents, None)
+        }
+    }
 }
 
 #[cfg(feature = "std")]
 mod use_std {
     use super::*;
     use crate::{ast::ModuleAst, BTreeMap};
+    use sha2::{Digest, Sha256};
     use std::{fs, io, path::Path};
 
     impl MaslLibrary {
@@ -184,24 +216,33 @@ mod use_std {
         }
 
         /// Read a library from a file.
+        ///
+        /// # Errors
+        /// Returns an error if the SHA-256 checksum at the end of the file doesn't match the
+        /// checksum of the serialized library. Legacy files without a checksum are read as is.
         pub fn read_from_file<P>(path: P) -> Result<MaslLibrary, LibraryError>
         where
             P: AsRef<Path>,
         {
-            // convert path to str
-            let path_str = path.as_ref().to_str().unwrap_or("path contains invalid unicode");
-
-            // read bytes from file
-            let contents =
-                fs::read(&path).map_err(|e| LibraryError::file_error(path_str, &e.to_string()))?;
+            read_from_file_helper(path, true)
+        }
 
-            // read library from bytes
-            Self::read_from_bytes(&contents)
-                .map_err(|e| LibraryError::deserialization_error(path_str, &e.to_string()))
+        /// Read a library from a file without verifying its checksum.
+        ///
+        /// This should be used only for library files which were intentionally modified after
+        /// they were written.
+        pub fn read_from_file_unchecked<P>(path: P) -> Result<MaslLibrary, LibraryError>
+        where
+            P: AsRef<Path>,
+        {
+            read_from_file_helper(path, false)
         }
 
         /// Write the library to a target director, using its namespace as file name and the
         /// appropriate extension.
+        ///
+        /// [MaslLibrary::CHECKSUM_MARKER] followed by the SHA-256 checksum of the serialized
+        /// library is appended to the file.
         pub fn write_to_dir<P>(&self, dir_path: P) -> io::Result<()>
         where
             P: AsRef<Path>,
@@ -210,7 +251,10 @@ mod use_std {
             let mut path = dir_path.as_ref().join(self.namespace.as_str());
             path.set_extension(Self::LIBRARY_EXTENSION);
 
-            let bytes = self.to_bytes();
+            let mut bytes = self.to_bytes();
+            let checksum = Sha256::digest(&bytes);
+            bytes.extend_from_slice(&Self::CHECKSUM_MARKER);
+            bytes.extend_from_slice(&checksum);
             fs::write(path, bytes)
         }
     }
@@ -218,6 +262,32 @@ mod use_std {
     // HELPER FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
+    /// Read a library from a file, verifying the checksum at the end of the file if `verify` is
+    /// set.
+    fn read_from_file_helper<P>(path: P, verify: bool) -> Result<MaslLibrary, LibraryError>
+    where
+        P: AsRef<Path>,
+    {
+        // convert path to str
+        let path_str = path.as_ref().to_str().unwrap_or("path contains invalid unicode");
+
+        // read bytes from file
+        let contents =
+            fs::read(&path).map_err(|e| LibraryError::file_error(path_str, &e.to_string()))?;
+
+        // split the library bytes from the checksum; legacy files have no checksum to verify
+        let (bytes, checksum) = MaslLibrary::split_checksum(&contents);
+        if let Some(checksum) = checksum {
+            if verify && Sha256::digest(bytes).as_slice() != checksum {
+                return Err(LibraryError::checksum_mismatch(path_str));
+            }
+        }
+
+        // read library from bytes
+        MaslLibrary::read_from_bytes(bytes)
+            .map_err(|e| LibraryError::deserialization_error(path_str, &e.to_string()))
+    }
+
     /// Read a directory and recursively feed the state map with path->ast tuples.
     ///
     /// Helper for [`Self::read_from_dir`].
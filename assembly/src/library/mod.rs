@@ -5,6 +5,11 @@ use super::{
 };
 use core::{cmp::Ordering, fmt, ops::Deref, str::from_utf8};
 
+#[cfg(feature = "std")]
+mod manifest;
+#[cfg(feature = "std")]
+pub use manifest::Manifest;
+
 mod masl;
 pub use masl::MaslLibrary;
 
@@ -47,7 +52,8 @@ impl<T> Library for &T
 where
     T: Library,
 {
-    type ModuleIterator<'a> = T::ModuleIterator<'a>
+    type ModuleIterator<'a>
+        = T::ModuleIterator<'a>
     where
         Self: 'a;
 
@@ -1,4 +1,7 @@
-use super::{LibraryNamespace, LibraryPath, MaslLibrary, Module, ModuleAst, Version};
+use super::{
+    super::{Assembler, AssemblyError, LibraryLoadError},
+    LibraryError, LibraryNamespace, LibraryPath, MaslLibrary, Module, ModuleAst, ToString, Version,
+};
 use vm_core::utils::{Deserializable, Serializable, SliceReader};
 
 #[test]
@@ -56,3 +59,110 @@ fn masl_locations_serialization() {
     bundle.clear_locations();
     assert_eq!(bundle, deserialized);
 }
+
+#[test]
+fn masl_file_checksum() {
+    let path = LibraryPath::new("test::foo").unwrap();
+    let ast = ModuleAst::parse("export.foo add end").unwrap();
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let bundle =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![Module::new(path, ast)], Vec::new())
+            .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("miden-masl-checksum-{}", std::process::id()));
+    bundle.write_to_dir(&dir).unwrap();
+    let file = dir.join("test.masl");
+    assert_eq!(MaslLibrary::read_from_file(&file).unwrap(), bundle);
+
+    // corrupt one byte of the serialized library
+    let original = std::fs::read(&file).unwrap();
+    let mut bytes = original.clone();
+    bytes[original.len() / 2] ^= 1;
+    std::fs::write(&file, bytes).unwrap();
+    let err = MaslLibrary::read_from_file(&file).unwrap_err();
+    assert_eq!(err.to_string(), format!("Library checksum mismatch - '{}'", file.display()));
+
+    // corrupt one byte of the checksum; verification can be skipped if this is intentional
+    let mut bytes = original;
+    *bytes.last_mut().unwrap() ^= 1;
+    std::fs::write(&file, bytes).unwrap();
+    assert!(MaslLibrary::read_from_file(&file).is_err());
+    assert_eq!(MaslLibrary::read_from_file_unchecked(&file).unwrap(), bundle);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn masl_file_without_checksum() {
+    let path = LibraryPath::new("test::foo").unwrap();
+    let ast = ModuleAst::parse("export.foo add end").unwrap();
+    let namespace = LibraryNamespace::new("test").unwrap();
+    let bundle =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![Module::new(path, ast)], Vec::new())
+            .unwrap();
+
+    // legacy files consist of the serialized library only, and are read without verification
+    let dir = std::env::temp_dir().join(format!("miden-masl-legacy-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("test.masl");
+    std::fs::write(&file, bundle.to_bytes()).unwrap();
+    assert_eq!(MaslLibrary::read_from_file(&file).unwrap(), bundle);
+    assert_eq!(MaslLibrary::read_from_file_unchecked(&file).unwrap(), bundle);
+
+    // files too short to hold a checksum are reported as malformed rather than as mismatched
+    std::fs::write(&file, [1, 2, 3]).unwrap();
+    for result in [MaslLibrary::read_from_file(&file), MaslLibrary::read_from_file_unchecked(&file)]
+    {
+        let err = result.unwrap_err();
+        assert!(matches!(err, LibraryError::DeserializationFailed(..)), "{err}");
+    }
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn masl_dependencies_from_manifest() {
+    let path = LibraryPath::new("dep::math").unwrap();
+    let ast = ModuleAst::parse("export.double dup add end").unwrap();
+    let namespace = LibraryNamespace::new("dep").unwrap();
+    let library =
+        MaslLibrary::new(namespace, Version::MIN, false, vec![Module::new(path, ast)], Vec::new())
+            .unwrap();
+
+    let dir = std::env::temp_dir().join(format!("miden-manifest-{}", std::process::id()));
+    library.write_to_dir(dir.join("libs")).unwrap();
+    let manifest = dir.join("miden.toml");
+    std::fs::write(&manifest, "[dependencies]\ndep = { path = \"libs/dep.masl\" }\n").unwrap();
+
+    let source = "use.dep::math begin push.2 exec.math::double end";
+    let expected = Assembler::default().with_library(&library).unwrap().compile(source).unwrap();
+    let program = Assembler::from_manifest(&manifest).unwrap().compile(source).unwrap();
+    assert_eq!(expected.hash(), program.hash());
+
+    // a dependency which does not exist is reported as an error
+    std::fs::write(&manifest, "[dependencies]\nmissing = \"libs/missing.masl\"\n").unwrap();
+    assert!(Assembler::from_manifest(&manifest).is_err());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn library_load_error_context() {
+    let build_library = |source: &str| {
+        let path = LibraryPath::new("test::foo").unwrap();
+        let ast = ModuleAst::parse(source).unwrap();
+        let namespace = LibraryNamespace::new("test").unwrap();
+        MaslLibrary::new(namespace, Version::MIN, false, vec![Module::new(path, ast)], Vec::new())
+            .unwrap()
+    };
+
+    let assembler =
+        Assembler::default().with_library(&build_library("export.foo add end")).unwrap();
+    let err = assembler.with_library(&build_library("export.foo mul end")).err().unwrap();
+    let expected = LibraryLoadError {
+        library_name: "test".to_string(),
+        conflicting_path: LibraryPath::new("test::foo").unwrap(),
+        source: LibraryError::duplicate_module_path("test::foo"),
+    };
+    assert_eq!(err, AssemblyError::LibraryLoadError(expected));
+}
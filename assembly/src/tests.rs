@@ -1216,6 +1216,41 @@ fn program_with_reexported_proc_in_another_library() {
     assert!(assembler.compile(source).is_err());
 }
 
+#[test]
+fn library_module_shadowing() {
+    const NAMESPACE: &str = "dummy";
+    const MODULE: &str = "math::u64";
+
+    let namespace = LibraryNamespace::try_from(NAMESPACE.to_string()).unwrap();
+    let path = LibraryPath::try_from(MODULE.to_string()).unwrap().prepend(&namespace).unwrap();
+    let build_library = |body: &str| {
+        let ast = ModuleAst::parse(body).unwrap();
+        DummyLibrary::new(
+            namespace.clone(),
+            vec![Module {
+                path: path.clone(),
+                ast,
+            }],
+        )
+    };
+    let library_1 = build_library("export.foo push.1 end");
+    let library_2 = build_library("export.foo push.2 end");
+
+    // by default, two libraries defining the same module cannot be loaded together
+    assert!(Assembler::default()
+        .with_libraries([&library_1, &library_2].into_iter())
+        .is_err());
+
+    // when shadowing is allowed, the module from the library loaded last is used
+    let assembler = Assembler::default()
+        .with_library_shadowing(true)
+        .with_libraries([&library_1, &library_2].into_iter())
+        .unwrap();
+    let source = format!("use.{NAMESPACE}::{MODULE} begin exec.u64::foo end");
+    let program = assembler.compile(source).unwrap();
+    assert_eq!("begin span push(2) end end", format!("{program}"));
+}
+
 #[test]
 fn module_alias() {
     const NAMESPACE: &str = "dummy";
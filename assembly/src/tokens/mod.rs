@@ -1,14 +1,13 @@
 use super::{
-    ast::InvocationTarget, BTreeMap, ByteReader, ByteWriter, Deserializable, DeserializationError,
-    LibraryPath, ParsingError, ProcedureName, Serializable, String, ToString, Vec,
+    ast::InvocationTarget, BTreeMap, LibraryPath, ParsingError, ProcedureName, String, ToString,
+    Vec,
 };
 use core::fmt;
 
 mod lines;
 pub use lines::{LineInfo, LinesStream};
 
-mod location;
-pub use location::SourceLocation;
+pub use vm_core::SourceLocation;
 
 mod stream;
 pub use stream::TokenStream;
@@ -48,6 +48,26 @@ pub enum AdviceInjector {
     ///   Merkle store: {TREE_ROOT<-NODE}
     MerkleNodeToStack,
 
+    /// Pushes the position of a leaf of the Merkle tree specified by the values on the top of the
+    /// operand stack onto the advice stack, together with the node of the tree at that position.
+    ///
+    /// Inputs:
+    ///   Operand stack: [VALUE, TREE_ROOT, ...]
+    ///   Advice stack: [...]
+    ///   Advice map: {VALUE: [depth, index]}
+    ///   Merkle store: {TREE_ROOT<-NODE}
+    ///
+    /// Outputs:
+    ///   Operand stack: [VALUE, TREE_ROOT, ...]
+    ///   Advice stack: [depth, index, NODE, ...]
+    ///   Advice map: {VALUE: [depth, index]}
+    ///   Merkle store: {TREE_ROOT<-NODE}
+    ///
+    /// The position of the leaf is read from the advice map, in which it is stored under the value
+    /// of the leaf. If there is no such entry, the position of the left child of the root (i.e.,
+    /// depth 1 and index 0) is pushed instead.
+    MerkleLeafToStack,
+
     /// Updates the node of a Merkle tree specified by the values at the top of the operand stack.
     /// Returns the path from the updated node to the new root of the tree to the caller.
     ///
@@ -88,6 +108,30 @@ pub enum AdviceInjector {
         key_offset: usize,
     },
 
+    /// Pushes all entries of the advice map with keys in the range [START_KEY, END_KEY) onto the
+    /// advice stack, preceded by the number of such entries. The entries are pushed in the
+    /// ascending order of their keys, where keys are compared as 256-bit integers with the last
+    /// element of the key being the most significant.
+    ///
+    /// Inputs:
+    ///   Operand stack: [START_KEY, END_KEY, ...]
+    ///   Advice stack: [...]
+    ///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+    ///
+    /// Outputs:
+    ///   Operand stack: [START_KEY, END_KEY, ...]
+    ///   Advice stack: [n, KEY_1, len_1, values_1, ..., KEY_n, len_n, values_n, ...]
+    ///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+    ///
+    /// Where KEY_1, ..., KEY_n are the keys of the advice map in the range [START_KEY, END_KEY)
+    /// in ascending order, and len_i is the number of elements in values_i.
+    ///
+    /// This is an unconstrained advice injector: no constraints check that the pushed entries are
+    /// in the range, that they are ordered, that they match the values in the advice map, or that
+    /// no entries are omitted. Programs which rely on the entries must verify them, e.g., by
+    /// hashing them and comparing the result with a commitment to the map.
+    MapRangeToStack,
+
     /// Pushes the result of [u64] division (both the quotient and the remainder) onto the advice
     /// stack.
     ///
@@ -285,6 +329,7 @@ impl fmt::Display for AdviceInjector {
         match self {
             Self::MerkleNodeMerge => write!(f, "merkle_node_merge"),
             Self::MerkleNodeToStack => write!(f, "merkle_node_to_stack"),
+            Self::MerkleLeafToStack => write!(f, "merkle_leaf_to_stack"),
             Self::UpdateMerkleNode => {
                 write!(f, "update_merkle_node")
             }
@@ -298,6 +343,7 @@ impl fmt::Display for AdviceInjector {
                     write!(f, "map_value_to_stack.{key_offset}")
                 }
             }
+            Self::MapRangeToStack => write!(f, "map_range_to_stack"),
             Self::DivU64 => write!(f, "div_u64"),
             Self::Ext2Inv => write!(f, "ext2_inv"),
             Self::Ext2Intt => write!(f, "ext2_intt"),
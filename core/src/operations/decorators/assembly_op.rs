@@ -1,3 +1,4 @@
+use super::SourceLocation;
 use crate::utils::string::String;
 use core::fmt;
 
@@ -11,6 +12,7 @@ pub struct AssemblyOp {
     num_cycles: u8,
     op: String,
     should_break: bool,
+    location: Option<SourceLocation>,
 }
 
 impl AssemblyOp {
@@ -22,9 +24,17 @@ impl AssemblyOp {
             num_cycles,
             op,
             should_break,
+            location: None,
         }
     }
 
+    /// Returns this [AssemblyOp] with the location of the assembly instruction in the source
+    /// code set to the specified location.
+    pub fn with_location(mut self, location: SourceLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
     /// Returns the context name for this operation.
     pub fn context_name(&self) -> &str {
         &self.context_name
@@ -45,6 +55,12 @@ impl AssemblyOp {
         self.should_break
     }
 
+    /// Returns the location of the assembly instruction in the source code, if the program was
+    /// compiled from source with location information.
+    pub const fn location(&self) -> Option<&SourceLocation> {
+        self.location.as_ref()
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -10,6 +10,9 @@ pub use assembly_op::AssemblyOp;
 mod debug;
 pub use debug::DebugOptions;
 
+mod source_location;
+pub use source_location::SourceLocation;
+
 // DECORATORS
 // ================================================================================================
 
@@ -30,6 +33,9 @@ pub enum Decorator {
     /// Prints out information about the state of the VM based on the specified options. This
     /// decorator is executed only in debug mode.
     Debug(DebugOptions),
+    /// Emits a trace event with the specified ID to the host. Executing this decorator does not
+    /// affect the state of the VM.
+    Trace(u32),
 }
 
 impl fmt::Display for Decorator {
@@ -40,6 +46,7 @@ impl fmt::Display for Decorator {
                 write!(f, "asmOp({}, {})", assembly_op.op(), assembly_op.num_cycles())
             }
             Self::Debug(options) => write!(f, "debug({options})"),
+            Self::Trace(trace_id) => write!(f, "trace({trace_id})"),
         }
     }
 }
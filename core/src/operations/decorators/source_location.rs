@@ -1,4 +1,4 @@
-use super::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use core::fmt;
 
 // SOURCE LOCATION
@@ -20,7 +20,7 @@ impl Default for SourceLocation {
 
 impl SourceLocation {
     // CONSTRUCTORS
-    // -------------------------------------------------------------------------------------------------
+    // --------------------------------------------------------------------------------------------
 
     /// Creates a new instance of [SourceLocation].
     pub const fn new(line: u32, column: u32) -> Self {
@@ -28,15 +28,20 @@ impl SourceLocation {
     }
 
     // PUBLIC ACCESSORS
-    // -------------------------------------------------------------------------------------------------
+    // --------------------------------------------------------------------------------------------
 
     /// Returns the line of the location.
     pub const fn line(&self) -> u32 {
         self.line
     }
 
+    /// Returns the column of the location.
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+
     // STATE MUTATORS
-    // -------------------------------------------------------------------------------------------------
+    // --------------------------------------------------------------------------------------------
 
     /// Moves the column by the given offset.
     pub fn move_column(&mut self, offset: u32) {
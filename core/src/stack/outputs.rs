@@ -1,6 +1,6 @@
 use super::{
-    ByteWriter, Felt, OutputError, Serializable, StackTopState, StarkField, ToElements, Vec,
-    STACK_TOP_SIZE,
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Felt, OutputError, Serializable,
+    StackTopState, StarkField, ToElements, Vec, STACK_TOP_SIZE,
 };
 
 // STACK OUTPUTS
@@ -182,6 +182,20 @@ impl Serializable for StackOutputs {
     }
 }
 
+impl Deserializable for StackOutputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let stack_len = source.read_u32()?;
+        let stack = (0..stack_len).map(|_| source.read_u64()).collect::<Result<_, _>>()?;
+
+        let overflow_addrs_len = source.read_u32()?;
+        let overflow_addrs =
+            (0..overflow_addrs_len).map(|_| source.read_u64()).collect::<Result<_, _>>()?;
+
+        Self::new(stack, overflow_addrs)
+            .map_err(|err| DeserializationError::InvalidValue(format!("{err}")))
+    }
+}
+
 impl ToElements<Felt> for StackOutputs {
     fn to_elements(&self) -> Vec<Felt> {
         // infallible conversion from u64 to Felt is OK here because we check validity of u64
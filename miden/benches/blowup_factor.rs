@@ -0,0 +1,72 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use miden::{
+    prove, Assembler, DefaultHost, FieldExtension, HashFunction, ProvingOptions, StackInputs,
+};
+use std::time::Duration;
+use stdlib::StdLibrary;
+
+/// Security level targeted by all benchmarked options.
+const SECURITY_BITS: u32 = 96;
+
+/// Grinding factor of all benchmarked options.
+const GRINDING_FACTOR: u32 = 16;
+
+fn blowup_factor(c: &mut Criterion) {
+    let mut group = c.benchmark_group("blowup_factor");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let source = "
+        use.std::crypto::hashes::sha256
+
+        begin
+            exec.sha256::hash_2to1
+        end";
+    let assembler = Assembler::default()
+        .with_library(&StdLibrary::default())
+        .expect("failed to load stdlib");
+    let program = assembler.compile(source).expect("Failed to compile test source.");
+
+    // a larger blowup factor makes every query contribute more bits of security, and thus, fewer
+    // queries are needed for the same security level; blowup factors below the minimum of the VM
+    // cannot be used to prove programs
+    let mut blowup_factor = ProvingOptions::MIN_BLOWUP_FACTOR;
+    while blowup_factor <= 64 {
+        let num_queries = (SECURITY_BITS - GRINDING_FACTOR).div_ceil(blowup_factor.ilog2());
+        let options = ProvingOptions::new(
+            num_queries as usize,
+            blowup_factor,
+            GRINDING_FACTOR,
+            FieldExtension::Quadratic,
+            8,
+            255,
+            HashFunction::Blake3_192,
+        );
+
+        // the proof size does not change between runs, so it is reported once per blowup factor
+        let (_, proof) =
+            prove(&program, StackInputs::default(), DefaultHost::default(), options.clone())
+                .expect("failed to prove program");
+        let proof_size = proof.to_bytes().len();
+        println!(
+            "blowup factor {blowup_factor}: {num_queries} queries, proof size {proof_size} bytes"
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("sha256", format!("{blowup_factor}x, {proof_size} bytes")),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    prove(&program, StackInputs::default(), DefaultHost::default(), options.clone())
+                        .expect("failed to prove program")
+                });
+            },
+        );
+        blowup_factor *= 2;
+    }
+
+    group.finish();
+}
+
+criterion_group!(blowup_factor_group, blowup_factor);
+criterion_main!(blowup_factor_group);
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use miden::{prove, Assembler, DefaultHost, HashFunction, ProvingOptions, StackInputs};
+use std::time::Duration;
+use stdlib::StdLibrary;
+
+fn program_proving(c: &mut Criterion) {
+    let mut group = c.benchmark_group("program_proving");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let source = "
+        use.std::crypto::hashes::sha256
+
+        begin
+            exec.sha256::hash_2to1
+        end";
+    let assembler = Assembler::default()
+        .with_library(&StdLibrary::default())
+        .expect("failed to load stdlib");
+    let program = assembler.compile(source).expect("Failed to compile test source.");
+
+    // the Poseidon hash function is not supported by the prover, so only the supported hash
+    // functions are compared
+    let hash_fns = [HashFunction::Blake3_192, HashFunction::Blake3_256, HashFunction::Rpo256];
+    for hash_fn in hash_fns {
+        let mut options = ProvingOptions::with_96_bit_security(hash_fn == HashFunction::Rpo256);
+        options.hash_fn = hash_fn;
+        group.bench_with_input(
+            BenchmarkId::new("sha256", format!("{hash_fn:?}")),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    prove(&program, StackInputs::default(), DefaultHost::default(), options.clone())
+                        .expect("failed to prove program")
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(proving_group, program_proving);
+criterion_main!(proving_group);
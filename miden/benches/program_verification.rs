@@ -0,0 +1,71 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use miden::{
+    prove, verify, Assembler, DefaultHost, Kernel, ProgramHashCache, ProgramInfo, ProvingOptions,
+    StackInputs,
+};
+use std::time::Duration;
+use stdlib::StdLibrary;
+
+/// Number of proofs of the same program verified in each iteration.
+const NUM_VERIFICATIONS: usize = 100;
+
+fn program_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("program_verification");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let source = "
+        use.std::crypto::hashes::sha256
+
+        begin
+            exec.sha256::hash_2to1
+        end";
+    let compile = || {
+        Assembler::default()
+            .with_library(&StdLibrary::default())
+            .expect("failed to load stdlib")
+            .compile(source)
+            .expect("Failed to compile test source.")
+    };
+    let program = compile();
+    let (stack_outputs, proof) = prove(
+        &program,
+        StackInputs::default(),
+        DefaultHost::default(),
+        ProvingOptions::default(),
+    )
+    .expect("failed to prove program");
+
+    // without a cache, the program is compiled to compute its hash before every verification
+    group.bench_function("uncached", |bench| {
+        bench.iter(|| {
+            for _ in 0..NUM_VERIFICATIONS {
+                let program_info = ProgramInfo::from(compile());
+                verify(program_info, StackInputs::default(), stack_outputs.clone(), proof.clone())
+                    .expect("failed to verify proof");
+            }
+        });
+    });
+
+    // with a cache, the program is compiled on the first lookup of its key only, and the cached
+    // hash is used for all other verifications
+    group.bench_function("cached", |bench| {
+        bench.iter(|| {
+            let cache = ProgramHashCache::default();
+            for _ in 0..NUM_VERIFICATIONS {
+                let key = ProgramHashCache::key([source.as_bytes()]);
+                let program_hash = cache
+                    .get_or_insert_with::<_, String>(key, || Ok(compile().hash()))
+                    .expect("failed to compute program hash");
+                let program_info = ProgramInfo::new(program_hash, Kernel::default());
+                verify(program_info, StackInputs::default(), stack_outputs.clone(), proof.clone())
+                    .expect("failed to verify proof");
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(verification_group, program_verification);
+criterion_main!(verification_group);
@@ -0,0 +1,20 @@
+use super::data::InputFile;
+use clap::Parser;
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Derive an advice map key from a list of field elements")]
+pub struct AdviceKeyCmd {
+    /// Hex-encoded field elements (optionally prefixed with 0x) to derive the key from
+    #[clap(required = true)]
+    values: Vec<String>,
+}
+
+impl AdviceKeyCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let values = self.values.iter().map(String::as_str).collect::<Vec<_>>();
+
+        // only the key is printed so that the output can be used directly by scripts
+        println!("{}", InputFile::derive_advice_key(&values)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use super::data::InputFile;
+use super::digest::display_digest;
+use clap::Parser;
+use miden::{
+    crypto::{MerkleStore, RpoDigest},
+    utils::Serializable,
+};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Merge the Merkle stores of several input files into a single serialized store")]
+pub struct BuildStoreCmd {
+    /// Paths to the input files whose Merkle stores are merged
+    #[clap(required = true, value_parser)]
+    input_files: Vec<PathBuf>,
+
+    /// Path to the file the merged Merkle store is written to
+    #[clap(short = 'o', long = "output", value_parser)]
+    output_file: PathBuf,
+}
+
+impl BuildStoreCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Build Merkle store");
+        println!("============================================================");
+
+        let merged = self.merge_stores()?;
+        for collision in merged.collisions.iter() {
+            println!(
+                "Root {} of `{}` collides with a root of `{}`",
+                display_digest(collision.root.as_bytes()),
+                collision.path.display(),
+                collision.first_path.display()
+            );
+        }
+
+        fs::write(&self.output_file, merged.store.to_bytes()).map_err(|err| {
+            format!("Failed to write Merkle store to `{}` - {}", self.output_file.display(), err)
+        })?;
+
+        println!(
+            "Wrote Merkle store with {} roots and {} internal nodes to `{}`",
+            merged.roots.len(),
+            merged.store.num_internal_nodes(),
+            self.output_file.display()
+        );
+        Ok(())
+    }
+
+    /// Parses the Merkle store of every input file and merges them into a single store.
+    ///
+    /// A tree whose root was already added by a previous input file (or earlier in the same file)
+    /// is reported as a collision; its nodes are shared with the tree added first, and thus, the
+    /// merged store is not affected by it.
+    fn merge_stores(&self) -> Result<MergedStore, String> {
+        let mut merged = MergedStore::default();
+        let mut root_paths = BTreeMap::new();
+        for path in self.input_files.iter() {
+            let input_data = InputFile::read(&Some(path.clone()), path, false)?;
+            let Some((store, roots)) = input_data.parse_merkle_store_with_roots()? else {
+                continue;
+            };
+
+            for root in roots {
+                match root_paths.get(&root) {
+                    Some(first_path) => merged.collisions.push(RootCollision {
+                        root,
+                        path: path.clone(),
+                        first_path: PathBuf::clone(first_path),
+                    }),
+                    None => {
+                        root_paths.insert(root, path.clone());
+                        merged.roots.push(root);
+                    }
+                }
+            }
+            merged.store.extend(store.inner_nodes());
+        }
+        Ok(merged)
+    }
+}
+
+// MERGED STORE
+// ================================================================================================
+
+/// Merkle store merged from several input files.
+#[derive(Debug, Default)]
+struct MergedStore {
+    /// Store containing the nodes of all trees.
+    store: MerkleStore,
+    /// Distinct roots of the trees in the store, in the order in which they were added.
+    roots: Vec<RpoDigest>,
+    /// Roots which were added more than once.
+    collisions: Vec<RootCollision>,
+}
+
+/// Root of a tree which was already added to the store from another input file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RootCollision {
+    root: RpoDigest,
+    /// Input file of the tree which collides with the tree added first.
+    path: PathBuf,
+    /// Input file from which the tree with this root was added first.
+    first_path: PathBuf,
+}
@@ -0,0 +1,25 @@
+use super::data::InputFile;
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Check that the Merkle trees of an input file are internally consistent")]
+pub struct CheckMerkleCmd {
+    /// Path to input file
+    #[clap(value_parser)]
+    input_file: PathBuf,
+}
+
+impl CheckMerkleCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Check Merkle store");
+        println!("============================================================");
+
+        let input_data = InputFile::read(&Some(self.input_file.clone()), &self.input_file, false)?;
+        let num_trees = input_data.check_merkle_store()?;
+
+        println!("All {num_trees} Merkle trees are consistent");
+        Ok(())
+    }
+}
@@ -1,20 +1,71 @@
 use clap::Parser;
 
 use super::data::{Debug, Libraries, ProgramFile};
-use std::path::PathBuf;
+use super::digest::display_digest;
+use assembly::{
+    ast::{CodeBody, Instruction, Node},
+    Assembler, CompileStats, Manifest,
+};
+use miden::{code_blocks::CodeBlock, Digest, ModuleAst, Program, ProgramAst};
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+use stdlib::StdLibrary;
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Compile a miden program")]
 pub struct CompileCmd {
+    /// Allow modules of libraries loaded later to shadow modules with the same path from
+    /// libraries loaded earlier
+    #[clap(long = "allow-shadowing")]
+    allow_shadowing: bool,
     /// Path to .masm assembly file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
-    /// Paths to .masl library files
+    /// Paths to .masl library files; if omitted, the dependencies declared in a `miden.toml`
+    /// manifest in the program's directory are used
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    /// List procedures defined in the source file instead of compiling it
+    #[clap(long = "list-procedures")]
+    list_procedures: bool,
+    /// Maximum number of instructions the program may contain
+    #[clap(long = "max-instructions", default_value = "10000000")]
+    max_instructions: usize,
+    /// Maximum number of procedures the program may define
+    #[clap(long = "max-procedures", default_value = "65535")]
+    max_procedures: usize,
+    /// Optimization level: 0 compiles instructions literally, 1 folds instructions with immediate
+    /// values into cheaper operations, and 2 also elides instructions with no effect
+    #[clap(
+        long = "opt-level",
+        default_value_t = Assembler::DEFAULT_OPT_LEVEL,
+        value_parser = clap::value_parser!(u8).range(0..=Assembler::MAX_OPT_LEVEL as i64)
+    )]
+    opt_level: u8,
+    /// Maximum size of the compiled .masb file in bytes
+    #[clap(long = "max-size")]
+    max_size: Option<usize>,
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
+    /// Print the hash of the standard library the program is compiled against
+    #[clap(long = "print-stdlib-hash")]
+    print_stdlib_hash: bool,
+    /// Skip verification of the checksums of library files
+    #[clap(long = "skip-checksum")]
+    skip_checksum: bool,
+    /// Fail if the hash of the standard library differs from this hex-encoded hash
+    #[clap(long = "stdlib-hash")]
+    stdlib_hash: Option<String>,
+    /// Print static estimates of the resources used by the program
+    #[clap(long = "stats")]
+    stats: bool,
+    /// Print statistics about the compilation
+    #[clap(short = 'v', long = "verbose")]
+    verbose: bool,
 }
 
 impl CompileCmd {
@@ -23,20 +74,451 @@ impl CompileCmd {
         println!("Compile program");
         println!("============================================================");
 
-        // load the program from file and parse it
+        if self.list_procedures {
+            return self.print_procedures();
+        }
+
+        // load the program from file, parse it, and make sure it is not too large to compile
         let program = ProgramFile::read(&self.assembly_file)?;
+        program.check_size(self.max_instructions, self.max_procedures)?;
 
-        // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        // load libraries from files, falling back on the project manifest if none were given
+        let library_paths = if self.library_paths.is_empty() {
+            manifest_library_paths(&self.assembly_file)?
+        } else {
+            self.library_paths.clone()
+        };
+        let libraries = Libraries::new(library_paths, self.allow_shadowing, self.skip_checksum)?
+            .with_stdlib_digest(self.stdlib_hash.as_deref())?;
+        if self.print_stdlib_hash {
+            println!("stdlib hash is {}", hex::encode(StdLibrary::default().digest()));
+        }
 
         // compile the program
-        let compiled_program = program.compile(&Debug::Off, libraries.libraries)?;
+        let (compiled_program, stats) =
+            program.compile_with_stats(&Debug::Off, self.opt_level, libraries)?;
+        if self.verbose {
+            print_compile_stats(&stats);
+        }
+        if self.stats {
+            ProgramStats::new(program.ast(), &compiled_program).print();
+        }
 
         // report program hash to user
         let program_hash: [u8; 32] = compiled_program.hash().into();
-        println!("program hash is {}", hex::encode(program_hash));
+        println!("program hash is {}", display_digest(program_hash));
+
+        // make sure the compiled file fits within the size budget before writing it
+        if let Some(max_size) = self.max_size {
+            program.check_compiled_size(max_size)?;
+        }
 
         // write the compiled file
         program.write(self.output_file.clone())
     }
+
+    /// Prints name, export status, and number of locals of each procedure defined in the source
+    /// file. The file may contain either a program or a library module.
+    fn print_procedures(&self) -> Result<(), String> {
+        let path = &self.assembly_file;
+        println!("Reading source file `{}`", path.display());
+        let source = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to open source file `{}` - {}", path.display(), err))?;
+
+        let procedures = list_procedures(&source)
+            .map_err(|err| format!("Failed to parse source file `{}` - {}", path.display(), err))?;
+
+        println!("{:<32} {:<8} {:>6}", "procedure", "export", "locals");
+        for procedure in procedures.iter() {
+            let export = if procedure.is_export { "yes" } else { "no" };
+            println!("{:<32} {:<8} {:>6}", procedure.name, export, procedure.num_locals);
+        }
+
+        Ok(())
+    }
+}
+
+// COMPILE STATS
+// ================================================================================================
+
+/// Prints statistics about the compilation of a program.
+fn print_compile_stats(stats: &CompileStats) {
+    println!(
+        "Compilation statistics:
+├── Instructions: {}
+├── Procedures: {}
+├── Inline expansions: {}
+├── Dead procedures eliminated: {}
+└── Optimization passes: {}",
+        stats.instruction_count,
+        stats.procedure_count,
+        stats.inline_expansions,
+        stats.dead_procedures_eliminated,
+        stats.optimization_passes
+    );
+}
+
+// PROGRAM STATS
+// ================================================================================================
+
+/// Static estimates of the resources used by a program, computed without executing it.
+#[derive(Debug, PartialEq, Eq)]
+struct ProgramStats {
+    /// Number of local procedures defined by the program.
+    num_procedures: usize,
+    /// Number of operations in the span blocks of the compiled program; blocks of procedures
+    /// invoked via `call` are counted once, and loop bodies are counted once regardless of the
+    /// number of iterations.
+    num_operations: usize,
+    /// Maximum depth of nested procedure invocations; invocations of procedures which are not
+    /// defined by the program itself count as a single level.
+    max_call_depth: usize,
+    /// Whether the program imports any modules of the standard library.
+    uses_stdlib: bool,
+}
+
+impl ProgramStats {
+    /// Computes the stats from the AST of a program and the program compiled from it.
+    fn new(ast: &ProgramAst, program: &Program) -> Self {
+        // local procedures can invoke only procedures defined before them, and thus, the depths
+        // can be computed in the order in which the procedures are defined
+        let mut proc_depths = Vec::with_capacity(ast.procedures().len());
+        for proc in ast.procedures() {
+            let depth = invocation_depth(&proc.body, &proc_depths);
+            proc_depths.push(depth);
+        }
+
+        Self {
+            num_procedures: ast.procedures().len(),
+            num_operations: count_operations(program.root(), program, &mut BTreeSet::new()),
+            max_call_depth: invocation_depth(ast.body(), &proc_depths),
+            uses_stdlib: ast.import_paths().iter().any(|path| path.first() == "std"),
+        }
+    }
+
+    /// Prints the stats.
+    fn print(&self) {
+        println!(
+            "Program statistics:
+├── Procedures: {}
+├── Operations: {}
+├── Max call depth: {}
+└── Uses stdlib: {}",
+            self.num_procedures,
+            self.num_operations,
+            self.max_call_depth,
+            if self.uses_stdlib { "yes" } else { "no" }
+        );
+    }
+}
+
+/// Returns the maximum depth of nested procedure invocations in the specified code body, given
+/// the depths of the bodies of local procedures.
+fn invocation_depth(body: &CodeBody, proc_depths: &[usize]) -> usize {
+    body.nodes()
+        .iter()
+        .map(|node| match node {
+            Node::Instruction(Instruction::ExecLocal(idx) | Instruction::CallLocal(idx)) => {
+                1 + proc_depths.get(*idx as usize).copied().unwrap_or_default()
+            }
+            Node::Instruction(
+                Instruction::ExecImported(_)
+                | Instruction::CallImported(_)
+                | Instruction::CallMastRoot(_)
+                | Instruction::SysCall(_)
+                | Instruction::DynExec
+                | Instruction::DynCall,
+            ) => 1,
+            Node::Instruction(_) => 0,
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => invocation_depth(true_case, proc_depths)
+                .max(invocation_depth(false_case, proc_depths)),
+            Node::Repeat { body, .. } | Node::While { body } => invocation_depth(body, proc_depths),
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// Returns the number of operations in the span blocks reachable from the specified block,
+/// following calls to blocks of the program which have not been counted yet.
+fn count_operations(block: &CodeBlock, program: &Program, called: &mut BTreeSet<Digest>) -> usize {
+    match block {
+        CodeBlock::Span(span) => span.op_batches().iter().map(|batch| batch.ops().len()).sum(),
+        CodeBlock::Join(join) => {
+            count_operations(join.first(), program, called)
+                + count_operations(join.second(), program, called)
+        }
+        CodeBlock::Split(split) => {
+            count_operations(split.on_true(), program, called)
+                + count_operations(split.on_false(), program, called)
+        }
+        CodeBlock::Loop(block) => count_operations(block.body(), program, called),
+        CodeBlock::Call(call) => match program.cb_table().get(call.fn_hash()) {
+            Some(target) if called.insert(call.fn_hash()) => {
+                count_operations(target, program, called)
+            }
+            _ => 0,
+        },
+        CodeBlock::Dyn(_) | CodeBlock::Proxy(_) => 0,
+    }
+}
+
+// PROJECT MANIFEST
+// ================================================================================================
+
+/// Returns the library paths declared in the `miden.toml` manifest located in the directory of
+/// the specified program, or an empty list if there is no manifest.
+fn manifest_library_paths(program_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let manifest_path = program_path.with_file_name(Manifest::FILE_NAME);
+    if !manifest_path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    println!("Reading manifest file `{}`", manifest_path.display());
+    let manifest = Manifest::read_from_file(&manifest_path)
+        .map_err(|err| format!("Failed to read manifest: {err}"))?;
+    Ok(manifest.library_paths().map(PathBuf::from).collect())
+}
+
+// PROCEDURE INFO
+// ================================================================================================
+
+/// Summary of a procedure defined in a source file.
+#[derive(Debug, PartialEq, Eq)]
+struct ProcedureInfo {
+    name: String,
+    is_export: bool,
+    num_locals: u16,
+}
+
+/// Parses the source as a program or, if it does not contain a program body, as a module, and
+/// returns the procedures it defines in the order they are declared.
+fn list_procedures(source: &str) -> Result<Vec<ProcedureInfo>, String> {
+    let procedures = match ProgramAst::parse(source) {
+        Ok(program) => program.procedures().to_vec(),
+        Err(program_err) => match ModuleAst::parse(source) {
+            Ok(module) => module.procs().to_vec(),
+            Err(_) => return Err(program_err.to_string()),
+        },
+    };
+
+    Ok(procedures
+        .into_iter()
+        .map(|proc| ProcedureInfo {
+            name: proc.name.to_string(),
+            is_export: proc.is_export,
+            num_locals: proc.num_locals,
+        })
+        .collect())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        list_procedures, manifest_library_paths, CompileCmd, Debug, Libraries, ProcedureInfo,
+        ProgramFile, ProgramStats,
+    };
+    use miden::{Assembler, DefaultHost, StackInputs};
+    use std::{fs, path::PathBuf};
+
+    fn info(name: &str, is_export: bool, num_locals: u16) -> ProcedureInfo {
+        ProcedureInfo {
+            name: name.to_string(),
+            is_export,
+            num_locals,
+        }
+    }
+
+    #[test]
+    fn list_module_procedures() {
+        let source = "
+            proc.helper.2
+                loc_store.0
+            end
+
+            export.foo
+                exec.helper
+            end
+
+            export.bar.4
+                push.1
+            end";
+
+        let procedures = list_procedures(source).unwrap();
+        assert_eq!(
+            procedures,
+            vec![info("helper", false, 2), info("foo", true, 0), info("bar", true, 4)]
+        );
+    }
+
+    #[test]
+    fn list_program_procedures() {
+        let source = "
+            proc.foo.1
+                loc_store.0
+            end
+
+            proc.bar
+                exec.foo
+            end
+
+            begin
+                exec.bar
+            end";
+
+        let procedures = list_procedures(source).unwrap();
+        assert_eq!(procedures, vec![info("foo", false, 1), info("bar", false, 0)]);
+    }
+
+    #[test]
+    fn manifest_in_program_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("main.masm");
+
+        // without a manifest no libraries are loaded
+        assert_eq!(manifest_library_paths(&program).unwrap(), Vec::<PathBuf>::new());
+
+        fs::write(dir.path().join("miden.toml"), "[dependencies]\nfoo = \"libs/foo.masl\"\n")
+            .unwrap();
+        assert_eq!(
+            manifest_library_paths(&program).unwrap(),
+            vec![dir.path().join("libs/foo.masl")]
+        );
+
+        fs::write(dir.path().join("miden.toml"), "[dependencies]\nfoo = 1\n").unwrap();
+        assert!(manifest_library_paths(&program).is_err());
+    }
+
+    #[test]
+    fn compiled_size_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, "begin push.1 push.2 add repeat.4 dup end end").unwrap();
+        let output_file = dir.path().join("program.masb");
+
+        let compile = |max_size| {
+            CompileCmd {
+                allow_shadowing: false,
+                assembly_file: assembly_file.clone(),
+                library_paths: Vec::new(),
+                list_procedures: false,
+                max_instructions: 10_000_000,
+                max_procedures: 65535,
+                max_size,
+                opt_level: Assembler::DEFAULT_OPT_LEVEL,
+                output_file: Some(output_file.clone()),
+                print_stdlib_hash: false,
+                skip_checksum: false,
+                stats: false,
+                stdlib_hash: None,
+                verbose: false,
+            }
+            .execute()
+        };
+
+        // without a budget the program is compiled, which gives us its size
+        compile(None).unwrap();
+        let size = fs::metadata(&output_file).unwrap().len() as usize;
+        fs::remove_file(&output_file).unwrap();
+
+        // a program which fits the budget is written
+        compile(Some(size)).unwrap();
+        assert!(output_file.exists());
+        fs::remove_file(&output_file).unwrap();
+
+        // a program which exceeds the budget is rejected before it is written
+        let err = compile(Some(size - 1)).unwrap_err();
+        assert_eq!(
+            err,
+            format!(
+                "Compiled program `{}` is {size} bytes, but at most {} bytes are allowed",
+                assembly_file.display(),
+                size - 1
+            )
+        );
+        assert!(!output_file.exists());
+    }
+
+    #[test]
+    fn program_stats() {
+        let source = "
+            use.std::math::u64
+
+            proc.foo
+                add mul
+            end
+
+            proc.bar
+                exec.foo
+                swap drop
+            end
+
+            proc.baz
+                exec.bar
+                call.foo
+            end
+
+            begin
+                call.foo
+                if.true
+                    exec.baz
+                else
+                    exec.bar
+                end
+            end";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("program.masm");
+        fs::write(&path, source).unwrap();
+        let program_file = ProgramFile::read(&path).unwrap();
+        let program = Assembler::default()
+            .with_library(&stdlib::StdLibrary::default())
+            .unwrap()
+            .compile(source)
+            .unwrap();
+
+        let stats = ProgramStats::new(program_file.ast(), &program);
+        assert_eq!(stats.num_procedures, 3);
+        // baz -> bar -> foo is the deepest chain of invocations
+        assert_eq!(stats.max_call_depth, 3);
+        assert!(stats.uses_stdlib);
+        // the body of foo is counted once although it is called from two places
+        assert_eq!(stats.num_operations, 10);
+    }
+
+    #[test]
+    fn opt_levels_produce_equivalent_programs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("program.masm");
+        fs::write(&path, "begin push.5 add.2 mul.1 sub.0 div.1 end").unwrap();
+        let program_file = ProgramFile::read(&path).unwrap();
+
+        let compile = |opt_level| {
+            let libraries = Libraries::new(Vec::<PathBuf>::new(), false, false).unwrap();
+            program_file.compile_with_stats(&Debug::Off, opt_level, libraries).unwrap()
+        };
+        let (literal, literal_stats) = compile(0);
+        let (optimized, optimized_stats) = compile(Assembler::MAX_OPT_LEVEL);
+
+        // the levels produce different MASTs, but the programs compute the same result
+        assert_ne!(literal.hash(), optimized.hash());
+        assert_eq!(literal_stats.optimization_passes, 0);
+        assert_eq!(optimized_stats.optimization_passes, 2);
+        let run = |program| {
+            let trace = processor::execute(
+                program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                Default::default(),
+            )
+            .unwrap();
+            trace.stack_outputs().stack_truncated(1).to_vec()
+        };
+        assert_eq!(run(&literal), vec![7]);
+        assert_eq!(run(&optimized), vec![7]);
+    }
 }
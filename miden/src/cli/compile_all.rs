@@ -0,0 +1,458 @@
+use super::data::{Debug, Libraries, ProgramFile};
+use assembly::{LibraryNamespace, LibraryPath, MaslLibrary, Module, Version};
+use clap::Parser;
+use miden::{crypto::Blake3_256, utils::ByteWriter, ModuleAst, ProgramAst};
+use rayon::prelude::*;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Compile all miden programs in a directory")]
+pub struct CompileAllCmd {
+    /// Path to the directory with the .masm files, which is searched recursively; files without a
+    /// `begin ... end` block are modules which the programs can import
+    #[clap(long = "dir", value_parser)]
+    dir: PathBuf,
+    /// Paths to .masl library files
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    /// Namespace under which the modules in the directory are imported, e.g. `mylib`; defaults to
+    /// the name of the directory
+    #[clap(short = 'n', long = "namespace")]
+    namespace: Option<String>,
+    /// Path to the directory into which the compiled .masb files are written, mirroring the
+    /// structure of the source directory
+    #[clap(long = "out-dir", value_parser)]
+    out_dir: PathBuf,
+}
+
+impl CompileAllCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Compile programs");
+        println!("============================================================");
+
+        let namespace = match &self.namespace {
+            Some(namespace) => namespace.clone(),
+            None => self
+                .dir
+                .canonicalize()
+                .ok()
+                .and_then(|dir| dir.file_name().map(|name| name.to_string_lossy().into_owned()))
+                .ok_or_else(|| format!("Failed to infer namespace of `{}`", self.dir.display()))?,
+        };
+
+        // parse all files and order them so that every module precedes the files importing it
+        let files = SourceFile::read_dir(&self.dir, &namespace)?;
+        let order = dependency_order(&files)?;
+        let hashes = source_hashes(&files, &order, &self.library_paths)?;
+
+        // the modules in the directory are provided to the programs as a library
+        let libraries = Libraries::new(&self.library_paths, false, false)?;
+        let local_library = local_library(&files, &order, &namespace)?;
+
+        // programs whose sources and dependencies did not change since they were last compiled
+        // are skipped
+        let (programs, skipped): (Vec<usize>, Vec<usize>) = order
+            .iter()
+            .copied()
+            .filter(|&idx| files[idx].is_program())
+            .partition(|&idx| !self.is_up_to_date(&files[idx], &hashes[idx]));
+
+        // programs do not depend on each other, and thus, are compiled in parallel
+        let failures = programs
+            .par_iter()
+            .filter_map(|&idx| {
+                let libraries = Libraries {
+                    libraries: libraries
+                        .libraries
+                        .iter()
+                        .cloned()
+                        .chain(local_library.clone())
+                        .collect(),
+                    allow_shadowing: false,
+                    stdlib_digest: None,
+                };
+                self.compile(&files[idx], &hashes[idx], libraries)
+                    .err()
+                    .map(|err| format!("{}: {err}", files[idx].rel_path.display()))
+            })
+            .collect::<Vec<_>>();
+
+        println!(
+            "Compiled {} of {} programs ({} unchanged programs skipped, {} modules)",
+            programs.len() - failures.len(),
+            programs.len() + skipped.len(),
+            skipped.len(),
+            files.len() - programs.len() - skipped.len()
+        );
+        if !failures.is_empty() {
+            return Err(format!(
+                "Failed to compile {} program(s):\n  {}",
+                failures.len(),
+                failures.join("\n  ")
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path of the compiled file of the specified program in the output directory.
+    fn out_path(&self, file: &SourceFile) -> PathBuf {
+        self.out_dir.join(&file.rel_path).with_extension("masb")
+    }
+
+    /// Returns true if the compiled file of the specified program exists and was compiled from
+    /// sources with the specified hash.
+    fn is_up_to_date(&self, file: &SourceFile, hash: &[u8; 32]) -> bool {
+        let out_path = self.out_path(file);
+        out_path.exists()
+            && fs::read_to_string(out_path.with_extension("hash"))
+                .is_ok_and(|prev_hash| prev_hash.trim() == hex::encode(hash))
+    }
+
+    /// Compiles the specified program and writes the compiled file into the output directory,
+    /// together with a `.hash` file recording the hash of the sources it was compiled from.
+    fn compile(
+        &self,
+        file: &SourceFile,
+        hash: &[u8; 32],
+        libraries: Libraries,
+    ) -> Result<(), String> {
+        let program_file = ProgramFile::read(&self.dir.join(&file.rel_path))?;
+        program_file.compile(&Debug::Off, libraries)?;
+
+        let out_path = self.out_path(file);
+        if let Some(parent) = out_path.parent() {
+            fs::create_dir_all(parent).map_err(|err| {
+                format!("Failed to create output directory `{}` - {}", parent.display(), err)
+            })?;
+        }
+        program_file.write(Some(out_path.clone()))?;
+
+        let hash_path = out_path.with_extension("hash");
+        fs::write(&hash_path, hex::encode(hash))
+            .map_err(|err| format!("Failed to write file `{}` - {}", hash_path.display(), err))
+    }
+}
+
+// SOURCE FILE
+// ================================================================================================
+
+/// Parsed contents of a .masm file in the source directory.
+enum SourceAst {
+    Program(ProgramAst),
+    Module(LibraryPath, ModuleAst),
+}
+
+/// A .masm file in the source directory.
+struct SourceFile {
+    /// Path of the file relative to the source directory.
+    rel_path: PathBuf,
+    /// Contents of the file.
+    source: String,
+    ast: SourceAst,
+    /// Indexes of the modules in the source directory imported by this file.
+    deps: Vec<usize>,
+}
+
+impl SourceFile {
+    /// Reads and parses all .masm files in the specified directory and its subdirectories, sorted
+    /// by path, and resolves the modules each of them imports from the specified namespace.
+    ///
+    /// A file containing a `begin ... end` block is a program; any other file is a module whose
+    /// path is the namespace followed by the path of the file relative to the directory, e.g.,
+    /// `mylib::math::u64` for `math/u64.masm`.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be read or parsed, or if it imports a module from the
+    /// namespace which is not defined in the directory.
+    fn read_dir(dir: &Path, namespace: &str) -> Result<Vec<Self>, String> {
+        let mut files = Vec::new();
+        for path in find_masm_files(dir)? {
+            let source = fs::read_to_string(&path)
+                .map_err(|err| format!("Failed to read file `{}` - {}", path.display(), err))?;
+            let rel_path = path.strip_prefix(dir).expect("file is in the directory").to_path_buf();
+
+            let ast = match ProgramAst::parse(&source) {
+                Ok(ast) => SourceAst::Program(ast),
+                Err(program_err) => {
+                    let ast = ModuleAst::parse(&source).map_err(|_| {
+                        format!("Failed to parse file `{}` - {}", path.display(), program_err)
+                    })?;
+                    SourceAst::Module(module_path(&rel_path, namespace)?, ast)
+                }
+            };
+            files.push(Self {
+                rel_path,
+                source,
+                ast,
+                deps: Vec::new(),
+            });
+        }
+
+        let modules = files
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, file)| match &file.ast {
+                SourceAst::Module(path, _) => Some((path.to_string(), idx)),
+                SourceAst::Program(_) => None,
+            })
+            .collect::<HashMap<_, _>>();
+        for file in files.iter_mut() {
+            let mut deps = BTreeSet::new();
+            for import in file.import_paths().into_iter().filter(|path| path.first() == namespace) {
+                let dep = modules.get(import.as_str()).ok_or_else(|| {
+                    format!("`{}` imports unknown module `{import}`", file.rel_path.display())
+                })?;
+                deps.insert(*dep);
+            }
+            file.deps = deps.into_iter().collect();
+        }
+
+        Ok(files)
+    }
+
+    /// Returns true if this file contains a program.
+    fn is_program(&self) -> bool {
+        matches!(self.ast, SourceAst::Program(_))
+    }
+
+    /// Returns the paths of the modules imported by this file.
+    fn import_paths(&self) -> Vec<LibraryPath> {
+        match &self.ast {
+            SourceAst::Program(ast) => ast.import_paths().into_iter().cloned().collect(),
+            SourceAst::Module(_, ast) => ast.import_paths().into_iter().cloned().collect(),
+        }
+    }
+}
+
+/// Returns the paths of all .masm files in the specified directory and its subdirectories, sorted
+/// by path.
+fn find_masm_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|err| format!("Failed to read directory `{}` - {}", dir.display(), err))?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("Failed to read directory `{}` - {}", dir.display(), err))?
+            .path();
+        if path.is_dir() {
+            paths.extend(find_masm_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == MaslLibrary::MODULE_EXTENSION) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Returns the path of the module in the file at the specified path relative to the source
+/// directory.
+fn module_path(rel_path: &Path, namespace: &str) -> Result<LibraryPath, String> {
+    let mut path = LibraryPath::new(namespace)
+        .map_err(|err| format!("Invalid namespace `{namespace}` - {err}"))?;
+    for component in rel_path.with_extension("").iter() {
+        path = path.append(component.to_string_lossy()).map_err(|err| {
+            format!("Invalid module path for file `{}` - {}", rel_path.display(), err)
+        })?;
+    }
+    Ok(path)
+}
+
+// DEPENDENCY ORDER
+// ================================================================================================
+
+/// Returns the indexes of the specified files ordered so that every module precedes the files
+/// which import it; files which do not depend on each other are ordered by path.
+///
+/// # Errors
+/// Returns an error listing the files which form a cycle if the imports are cyclic.
+fn dependency_order(files: &[SourceFile]) -> Result<Vec<usize>, String> {
+    /// Visits the file at `idx` after all its dependencies; `stack` holds the files which are
+    /// being visited, i.e., the path of imports from the first visited file.
+    fn visit(
+        idx: usize,
+        files: &[SourceFile],
+        visited: &mut [bool],
+        stack: &mut Vec<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<(), String> {
+        if let Some(pos) = stack.iter().position(|&file| file == idx) {
+            let cycle = stack[pos..]
+                .iter()
+                .chain([&idx])
+                .map(|&file| files[file].rel_path.display().to_string())
+                .collect::<Vec<_>>();
+            return Err(format!("Dependency cycle detected: {}", cycle.join(" -> ")));
+        }
+        if visited[idx] {
+            return Ok(());
+        }
+
+        stack.push(idx);
+        for &dep in files[idx].deps.iter() {
+            visit(dep, files, visited, stack, order)?;
+        }
+        stack.pop();
+
+        visited[idx] = true;
+        order.push(idx);
+        Ok(())
+    }
+
+    let mut visited = vec![false; files.len()];
+    let mut order = Vec::with_capacity(files.len());
+    for idx in 0..files.len() {
+        visit(idx, files, &mut visited, &mut Vec::new(), &mut order)?;
+    }
+
+    Ok(order)
+}
+
+/// Returns the hash of the sources of each of the specified files, indexed like the files.
+///
+/// The hash of a file is the BLAKE3-256 hash of the version of the CLI, the contents of the
+/// library files, the contents of the file, and the hashes of the modules it imports. Thus, the
+/// hash of a program changes whenever any of the sources it is compiled from changes.
+fn source_hashes(
+    files: &[SourceFile],
+    order: &[usize],
+    library_paths: &[PathBuf],
+) -> Result<Vec<[u8; 32]>, String> {
+    let mut prefix = Vec::new();
+    prefix.write_bytes(env!("CARGO_PKG_VERSION").as_bytes());
+    for path in library_paths {
+        let contents = fs::read(path)
+            .map_err(|err| format!("Failed to read file `{}` - {}", path.display(), err))?;
+        prefix.write_u64(contents.len() as u64);
+        prefix.write_bytes(&contents);
+    }
+
+    // the hashes of the dependencies of a file are computed before the hash of the file itself
+    let mut hashes = vec![[0; 32]; files.len()];
+    for &idx in order {
+        let mut bytes = prefix.clone();
+        bytes.write_u64(files[idx].source.len() as u64);
+        bytes.write_bytes(files[idx].source.as_bytes());
+        for &dep in files[idx].deps.iter() {
+            bytes.write_bytes(&hashes[dep]);
+        }
+        hashes[idx] = Blake3_256::hash(&bytes).into();
+    }
+
+    Ok(hashes)
+}
+
+/// Returns a library with the modules among the specified files, or None if there are no modules.
+fn local_library(
+    files: &[SourceFile],
+    order: &[usize],
+    namespace: &str,
+) -> Result<Option<MaslLibrary>, String> {
+    let mut modules = Vec::new();
+    let mut dependencies = BTreeSet::new();
+    for &idx in order {
+        if let SourceAst::Module(path, ast) = &files[idx].ast {
+            for import in ast.import_paths().into_iter().filter(|path| path.first() != namespace) {
+                dependencies.insert(import.first().to_string());
+            }
+            modules.push(Module::new(path.clone(), ast.clone()));
+        }
+    }
+    if modules.is_empty() {
+        return Ok(None);
+    }
+
+    let namespace = LibraryNamespace::try_from(namespace.to_string())
+        .map_err(|err| format!("Invalid namespace `{namespace}` - {err}"))?;
+    let dependencies = dependencies
+        .into_iter()
+        .map(|dep| {
+            LibraryNamespace::try_from(dep.clone())
+                .map_err(|err| format!("Invalid namespace `{dep}` - {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    MaslLibrary::new(namespace, Version::default(), false, modules, dependencies)
+        .map(Some)
+        .map_err(|err| format!("Failed to build library from modules - {err}"))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::CompileAllCmd;
+    use crate::cli::data::ProgramFile;
+    use std::{fs, path::Path};
+
+    fn build_cmd(dir: &Path) -> CompileAllCmd {
+        CompileAllCmd {
+            dir: dir.join("src"),
+            library_paths: Vec::new(),
+            namespace: Some("mylib".to_string()),
+            out_dir: dir.join("out"),
+        }
+    }
+
+    #[test]
+    fn compiles_programs_in_dependency_order() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src/math")).unwrap();
+        fs::create_dir_all(dir.path().join("src/bin")).unwrap();
+        fs::write(dir.path().join("src/math/add.masm"), "export.add3 push.3 add end").unwrap();
+        fs::write(
+            dir.path().join("src/math/mul.masm"),
+            "use.mylib::math::add\nexport.add3_mul2 exec.add::add3 push.2 mul end",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("src/bin/main.masm"),
+            "use.mylib::math::mul\nbegin push.1 exec.mul::add3_mul2 end",
+        )
+        .unwrap();
+        fs::write(dir.path().join("src/other.masm"), "begin push.1 push.2 add end").unwrap();
+
+        // programs are compiled into the output directory, mirroring the source directory
+        let cmd = build_cmd(dir.path());
+        cmd.execute().unwrap();
+        let compiled = dir.path().join("out/bin/main.masb");
+        assert!(ProgramFile::read_compiled(&compiled).is_ok());
+        assert!(dir.path().join("out/other.masb").exists());
+        assert!(!dir.path().join("out/math/add.masb").exists());
+
+        // unchanged programs are skipped, but changes to imported modules are picked up
+        fs::write(&compiled, "stale").unwrap();
+        cmd.execute().unwrap();
+        assert_eq!(fs::read(&compiled).unwrap(), b"stale");
+        fs::write(dir.path().join("src/math/add.masm"), "export.add3 push.4 add end").unwrap();
+        cmd.execute().unwrap();
+        assert!(ProgramFile::read_compiled(&compiled).is_ok());
+    }
+
+    #[test]
+    fn dependency_cycles_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/a.masm"), "use.mylib::b\nexport.foo exec.b::bar end")
+            .unwrap();
+        fs::write(dir.path().join("src/b.masm"), "use.mylib::a\nexport.bar exec.a::foo end")
+            .unwrap();
+        fs::write(dir.path().join("src/main.masm"), "use.mylib::a\nbegin exec.a::foo end").unwrap();
+
+        let err = build_cmd(dir.path()).execute().unwrap_err();
+        assert_eq!(err, "Dependency cycle detected: a.masm -> b.masm -> a.masm");
+
+        // imports of modules which are not in the directory are reported as well
+        fs::write(dir.path().join("src/b.masm"), "use.mylib::c\nexport.bar exec.c::baz end")
+            .unwrap();
+        let err = build_cmd(dir.path()).execute().unwrap_err();
+        assert!(err.contains("imports unknown module `mylib::c`"));
+    }
+}
@@ -0,0 +1,228 @@
+use super::{
+    data::{Debug, Libraries, ProgramFile, ProofFile},
+    extract_public_inputs::PublicInputs,
+};
+use clap::Parser;
+use miden::{math::StarkField, ExecutionProof, Kernel, ProgramInfo};
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Number of bytes of the proof written per line of the generated source file.
+const BYTES_PER_LINE: usize = 16;
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Generate a standalone Rust verifier with an embedded proof of a program")]
+pub struct CompileToVerifierCmd {
+    /// Paths to .masl library files used to compile the program
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    /// Path to a local checkout of the miden crate which the generated verifier depends on,
+    /// instead of the published crate
+    #[clap(long = "miden-path", value_parser)]
+    miden_path: Option<PathBuf>,
+    /// Path to the generated verifier: a .rs file, or a directory in which a Cargo package with
+    /// the verifier is created
+    #[clap(long = "out", value_parser)]
+    out: PathBuf,
+    /// Path to the .masm program file
+    #[clap(long = "program", value_parser)]
+    program_file: PathBuf,
+    /// Path to the proof file; the public inputs are read from its header
+    #[clap(long = "proof", value_parser)]
+    proof_file: PathBuf,
+}
+
+impl CompileToVerifierCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Compile to verifier");
+        println!("============================================================");
+
+        // compile the program and make sure the proof was generated for it
+        let libraries = Libraries::new(&self.library_paths, false, false)?;
+        let program = ProgramFile::read(&self.program_file)?.compile(&Debug::Off, libraries)?;
+        let public_inputs = PublicInputs::read(&self.proof_file)?;
+        if public_inputs.program_hash != program.hash() {
+            return Err(format!(
+                "Proof file `{}` was generated for program {}, but `{}` has hash {}",
+                self.proof_file.display(),
+                hex::encode(public_inputs.program_hash.as_bytes()),
+                self.program_file.display(),
+                hex::encode(program.hash().as_bytes())
+            ));
+        }
+
+        // make sure the proof is valid, so that the generated verifier succeeds
+        let proof = read_proof(&self.proof_file)?;
+        miden::verify(
+            ProgramInfo::new(program.hash(), Kernel::default()),
+            public_inputs.stack_inputs.clone(),
+            public_inputs.stack_outputs.clone(),
+            proof.clone(),
+        )
+        .map_err(|err| format!("Program failed verification! - {}", err))?;
+
+        let source = verifier_source(&self.program_file, &public_inputs, &proof);
+        let source_path = if self.out.extension().is_some_and(|ext| ext == "rs") {
+            self.out.clone()
+        } else {
+            self.write_package()?
+        };
+        fs::write(&source_path, source).map_err(|err| {
+            format!("Failed to write verifier `{}` - {}", source_path.display(), err)
+        })?;
+
+        println!("Verifier written to {}", source_path.display());
+        Ok(())
+    }
+
+    /// Writes the manifest of the Cargo package of the verifier to the output directory and
+    /// returns the path of the source file of the verifier.
+    fn write_package(&self) -> Result<PathBuf, String> {
+        let src_dir = self.out.join("src");
+        fs::create_dir_all(&src_dir).map_err(|err| {
+            format!("Failed to create directory `{}` - {}", src_dir.display(), err)
+        })?;
+
+        let name = self
+            .out
+            .file_name()
+            .map(|name| name.to_string_lossy().replace(|c: char| !c.is_alphanumeric(), "_"))
+            .unwrap_or_else(|| "verifier".to_string());
+        let manifest_path = self.out.join("Cargo.toml");
+        fs::write(&manifest_path, package_manifest(&name, self.miden_path.as_deref())).map_err(
+            |err| format!("Failed to write manifest `{}` - {}", manifest_path.display(), err),
+        )?;
+
+        Ok(src_dir.join("main.rs"))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads the proof from the specified proof file.
+fn read_proof(proof_file: &Path) -> Result<ExecutionProof, String> {
+    let (proof, _) = ProofFile::read(&Some(proof_file.to_path_buf()), proof_file)?;
+    Ok(proof)
+}
+
+/// Returns the manifest of a Cargo package with the specified name which depends on the miden
+/// crate without any of its default features, as only the verifier is needed.
+///
+/// The package is its own workspace, so that it can be built even if it is created inside of
+/// another workspace.
+fn package_manifest(name: &str, miden_path: Option<&Path>) -> String {
+    let source = match miden_path {
+        Some(path) => format!("path = {:?}", path.display().to_string()),
+        None => format!("version = \"{}\"", env!("CARGO_PKG_VERSION")),
+    };
+    format!(
+        "[package]
+name = \"{name}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+[workspace]
+
+[dependencies]
+miden = {{ package = \"miden-vm\", {source}, default-features = false }}
+"
+    )
+}
+
+/// Returns the source of a Rust program which verifies the specified proof against the specified
+/// public inputs, and exits with a non-zero status if verification fails.
+fn verifier_source(
+    program_file: &Path,
+    public_inputs: &PublicInputs,
+    proof: &ExecutionProof,
+) -> String {
+    let program_name = program_file.file_name().unwrap_or_default().to_string_lossy();
+    // stack inputs are listed in the order in which they are pushed onto the stack
+    let stack_inputs = public_inputs
+        .stack_inputs
+        .values()
+        .iter()
+        .rev()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    let stack_outputs = &public_inputs.stack_outputs;
+
+    format!(
+        "//! Verifier of a proof of execution of the Miden program `{program_name}`.
+//!
+//! This file was generated by `miden compile-to-verifier`. The program hash, the public inputs,
+//! and the proof are embedded as constants, and the verifier exits with a non-zero status if the
+//! proof fails to verify.
+
+use miden::{{Digest, ExecutionProof, Kernel, ProgramInfo, StackInputs, StackOutputs}};
+use std::process::ExitCode;
+
+/// Hash of the program.
+const PROGRAM_HASH: [u8; 32] = {program_hash};
+
+/// Stack inputs, in the order in which they are pushed onto the stack.
+const STACK_INPUTS: [u64; {num_inputs}] = {stack_inputs};
+
+/// Stack outputs, starting with the top of the stack.
+const STACK_OUTPUTS: [u64; {num_outputs}] = {stack_outputs};
+
+/// Overflow addresses of the stack outputs.
+const OVERFLOW_ADDRS: [u64; {num_overflow_addrs}] = {overflow_addrs};
+
+/// Serialized proof.
+const PROOF: &[u8] = &{proof};
+
+fn main() -> ExitCode {{
+    match verify() {{
+        Ok(security_level) => {{
+            println!(\"Proof verified with {{security_level}}-bit security\");
+            ExitCode::SUCCESS
+        }}
+        Err(err) => {{
+            eprintln!(\"Program failed verification! - {{err}}\");
+            ExitCode::FAILURE
+        }}
+    }}
+}}
+
+fn verify() -> Result<u32, String> {{
+    let program_hash = Digest::try_from(PROGRAM_HASH).map_err(|err| err.to_string())?;
+    let program_info = ProgramInfo::new(program_hash, Kernel::default());
+    let stack_inputs = StackInputs::try_from_values(STACK_INPUTS).map_err(|err| err.to_string())?;
+    let stack_outputs = StackOutputs::new(STACK_OUTPUTS.to_vec(), OVERFLOW_ADDRS.to_vec())
+        .map_err(|err| err.to_string())?;
+    let proof = ExecutionProof::from_bytes(PROOF).map_err(|err| err.to_string())?;
+    miden::verify(program_info, stack_inputs, stack_outputs, proof).map_err(|err| err.to_string())
+}}
+",
+        program_hash = format_array(&public_inputs.program_hash.as_bytes(), 8),
+        num_inputs = stack_inputs.len(),
+        stack_inputs = format_array(&stack_inputs, 8),
+        num_outputs = stack_outputs.stack().len(),
+        stack_outputs = format_array(stack_outputs.stack(), 8),
+        num_overflow_addrs = stack_outputs.overflow_addrs().len(),
+        overflow_addrs = format_array(stack_outputs.overflow_addrs(), 8),
+        proof = format_array(&proof.to_bytes(), BYTES_PER_LINE),
+    )
+}
+
+/// Formats the specified values as a Rust array literal with the specified number of values per
+/// line.
+fn format_array<T: ToString>(values: &[T], per_line: usize) -> String {
+    if values.is_empty() {
+        return "[]".to_string();
+    }
+
+    let mut array = "[\n".to_string();
+    for line in values.chunks(per_line) {
+        let line = line.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+        writeln!(array, "    {},", line.join(", ")).expect("writing to a string cannot fail");
+    }
+    array.push(']');
+    array
+}
@@ -0,0 +1,472 @@
+use super::data::NumberFormat;
+use miden::{
+    crypto::{MerklePath, MerkleStore, NodeIndex, RpoDigest},
+    math::{Felt, StarkField},
+    utils::IntoBytes,
+    AdviceInputs, AdviceProvider, DefaultHost, ExecutionError, MemAdviceProvider, Operation,
+    Program, StackInputs, Word,
+};
+use processor::{AdviceSource, ProcessState, SignatureKind};
+use std::{cell::RefCell, collections::BTreeSet};
+
+// INPUT COVERAGE
+// ================================================================================================
+
+/// Coverage of the inputs of a program by one of its executions, i.e., which of the operand stack
+/// inputs, advice stack elements, and advice map entries were consumed by the program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCoverage {
+    /// Operand stack inputs in the order of the input file, i.e., with the top of the stack last.
+    operand_stack: Vec<Felt>,
+    /// Number of operand stack inputs, counting from the first one, which were never consumed.
+    unused_operands: usize,
+    /// Advice stack elements in the order of the input file, i.e., in the order they are popped.
+    advice_stack: Vec<Felt>,
+    /// Number of advice stack elements, counting from the first one, which were popped.
+    consumed_advice: usize,
+    /// Keys of the advice map entries, each with a flag set if the entry was read.
+    advice_map: Vec<([u8; 32], bool)>,
+}
+
+impl InputCoverage {
+    /// Executes the program against the specified inputs in debug mode, and returns the coverage
+    /// of the inputs by the execution.
+    ///
+    /// An operand stack input is consumed once an operation reads it, moves it, or removes it from
+    /// the stack; an advice stack element once it is popped; and an advice map entry once its
+    /// values are read (e.g., by `adv.push_mapval`).
+    ///
+    /// # Errors
+    /// Returns an error if the execution fails.
+    pub fn measure(
+        program: &Program,
+        stack_inputs: StackInputs,
+        advice_inputs: AdviceInputs,
+    ) -> Result<Self, ExecutionError> {
+        let provider = CoverageAdviceProvider::new(
+            MemAdviceProvider::from(advice_inputs.clone()),
+            advice_inputs.stack().len(),
+        );
+        let mut host = DefaultHost::new(provider);
+
+        let mut operand_stack = stack_inputs.values().to_vec();
+        let mut stack_coverage = StackCoverage::new(operand_stack.clone());
+        let mut prev_stack: Option<Vec<Felt>> = None;
+        for state in processor::execute_iter(program, stack_inputs, &mut host) {
+            let state = state?;
+            if let (Some(prev_stack), Some(op)) = (prev_stack.as_deref(), state.op) {
+                stack_coverage.update(prev_stack, &state.stack, op);
+            }
+            prev_stack = Some(state.stack);
+        }
+        operand_stack.reverse();
+
+        let provider = host.into_inner();
+        let read_keys = provider.read_keys.borrow();
+        Ok(Self {
+            operand_stack,
+            unused_operands: stack_coverage.untouched,
+            advice_stack: advice_inputs.stack().to_vec(),
+            consumed_advice: provider.consumed(),
+            advice_map: advice_inputs
+                .map_keys()
+                .map(|key| (*key, read_keys.contains(key)))
+                .collect(),
+        })
+    }
+
+    /// Returns the lines of a report of the coverage, listing the unused inputs with their values
+    /// formatted in the specified format.
+    pub fn report(&self, number_format: NumberFormat) -> Vec<String> {
+        let unused_operands = self.operand_stack[..self.unused_operands]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                format!("unused operand stack input {i}: {}", number_format.format(value.as_int()))
+            })
+            .collect();
+        let unused_advice = self.advice_stack[self.consumed_advice..]
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let i = self.consumed_advice + i;
+                format!("unused advice stack element {i}: {}", number_format.format(value.as_int()))
+            })
+            .collect();
+        let unused_keys = self
+            .advice_map
+            .iter()
+            .filter(|(_, read)| !read)
+            .map(|(key, _)| format!("unused advice map key {}", hex::encode(key)))
+            .collect();
+        let read_keys = self.advice_map.iter().filter(|(_, read)| *read).count();
+
+        let sections: [(String, Vec<String>); 3] = [
+            (
+                format!(
+                    "Operand stack: {} of {} inputs consumed",
+                    self.operand_stack.len() - self.unused_operands,
+                    self.operand_stack.len()
+                ),
+                unused_operands,
+            ),
+            (
+                format!(
+                    "Advice stack: {} of {} elements consumed",
+                    self.consumed_advice,
+                    self.advice_stack.len()
+                ),
+                unused_advice,
+            ),
+            (
+                format!("Advice map: {read_keys} of {} entries read", self.advice_map.len()),
+                unused_keys,
+            ),
+        ];
+
+        let mut lines = vec!["Input coverage:".to_string()];
+        for (i, (summary, unused)) in sections.iter().enumerate() {
+            let is_last = i == sections.len() - 1;
+            let (branch, indent) = if is_last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            lines.push(format!("{branch}{summary}"));
+            for (j, line) in unused.iter().enumerate() {
+                let sub_branch = if j == unused.len() - 1 {
+                    "└── "
+                } else {
+                    "├── "
+                };
+                lines.push(format!("{indent}{sub_branch}{line}"));
+            }
+        }
+        lines
+    }
+}
+
+// OPERAND STACK COVERAGE
+// ================================================================================================
+
+/// Number of elements at the top of the stack which operations can access.
+const STACK_TOP_SIZE: usize = 16;
+
+/// Tracks the operand stack inputs which have not been consumed during an execution.
+///
+/// Operations only ever read or change elements at the top of the stack, and thus, the inputs
+/// which have not been consumed always form a contiguous block at the bottom of the inputs. The
+/// block is located by comparing the top of the stack before and after every operation, with the
+/// elements below the ones the operation changed shifted by its change of the stack depth.
+///
+/// The stack depth resets when a procedure is invoked via `call`, and thus, inputs hidden from
+/// such a procedure may be reported as consumed even if they were not.
+struct StackCoverage {
+    /// Operand stack inputs with the top of the stack first.
+    inputs: Vec<Felt>,
+    /// Number of stack elements above the block of untouched inputs.
+    above: usize,
+    /// Number of inputs at the bottom of the inputs which have not been consumed.
+    untouched: usize,
+}
+
+impl StackCoverage {
+    fn new(inputs: Vec<Felt>) -> Self {
+        let untouched = inputs.len();
+        Self {
+            inputs,
+            above: 0,
+            untouched,
+        }
+    }
+
+    /// Updates the block of untouched inputs from the states of the stack (with the top of the
+    /// stack first) before and after the specified operation was executed.
+    fn update(&mut self, before: &[Felt], after: &[Felt], op: Operation) {
+        // elements which an operation reads without changing them are consumed as well
+        self.touch(stack_reads(&op).saturating_sub(self.above));
+        if self.untouched == 0 {
+            return;
+        }
+
+        // END pops the condition off the stack only when it exits a loop, in which case the top
+        // of the stack changes
+        let shift = stack_shift(&op).unwrap_or_else(|| {
+            let top = STACK_TOP_SIZE.min(before.len()).min(after.len());
+            if before[..top] == after[..top] {
+                0
+            } else {
+                -1
+            }
+        });
+        self.touch(self.untouched - self.count_matching(after, shift));
+        self.above = (self.above as isize + shift).max(0) as usize;
+    }
+
+    /// Moves the specified number of inputs at the top of the block of untouched inputs out of the
+    /// block.
+    fn touch(&mut self, num_inputs: usize) {
+        let num_inputs = num_inputs.min(self.untouched);
+        self.above += num_inputs;
+        self.untouched -= num_inputs;
+    }
+
+    /// Returns the number of inputs at the bottom of the block of untouched inputs which are still
+    /// in place in the specified stack after the block was shifted by `shift` elements.
+    ///
+    /// Only the top 16 elements of the stack are compared, as operations cannot access the
+    /// elements below them.
+    fn count_matching(&self, stack: &[Felt], shift: isize) -> usize {
+        let first_input = self.inputs.len() - self.untouched;
+        (0..self.untouched)
+            .rev()
+            .take_while(|&i| {
+                let pos = (self.above + i) as isize + shift;
+                pos >= STACK_TOP_SIZE as isize
+                    || (pos >= 0 && stack.get(pos as usize) == Some(&self.inputs[first_input + i]))
+            })
+            .count()
+    }
+}
+
+/// Returns the change of the stack depth caused by the specified operation, or None if it
+/// depends on the state of the VM.
+fn stack_shift(op: &Operation) -> Option<isize> {
+    use Operation::*;
+    match op {
+        Pad | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup9 | Dup11 | Dup13
+        | Dup15 | Push(_) | AdvPop | SDepth | Clk | U32split => Some(1),
+        Assert(_) | FmpUpdate | Add | Mul | And | Or | Eq | U32add3 | U32madd | U32and | U32xor
+        | Drop | CSwap | CSwapW | MLoadW | MStoreW | MStore | Split | Loop | Repeat => Some(-1),
+        End => None,
+        _ => Some(0),
+    }
+}
+
+/// Returns the number of elements at the top of the stack which the specified operation may read
+/// without changing them.
+fn stack_reads(op: &Operation) -> usize {
+    use Operation::*;
+    match op {
+        Dup0 => 1,
+        Dup1 | Swap | U32assert2(_) => 2,
+        Dup2 | MovUp2 | MovDn2 | CSwap => 3,
+        Dup3 | MovUp3 | MovDn3 | Dyn => 4,
+        Dup4 | MovUp4 | MovDn4 => 5,
+        Dup5 | MovUp5 | MovDn5 => 6,
+        Dup6 | MovUp6 | MovDn6 => 7,
+        Dup7 | MovUp7 | MovDn7 => 8,
+        SwapW => 8,
+        MovUp8 | MovDn8 | CSwapW => 9,
+        Dup9 | MpVerify => 10,
+        Dup11 | SwapW2 => 12,
+        Dup13 | MrUpdate => 14,
+        Dup15 | SwapW3 | SwapDW => 16,
+        _ => 0,
+    }
+}
+
+// COVERAGE ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] which records how many elements of the initial advice stack were popped
+/// and which advice map entries were read, and forwards all requests to the wrapped provider.
+struct CoverageAdviceProvider<A> {
+    provider: A,
+    /// Number of elements of the initial advice stack which have not been popped.
+    remaining: usize,
+    /// Number of elements of the initial advice stack.
+    initial: usize,
+    /// Number of elements pushed onto the advice stack during the execution which are still on
+    /// top of the initial elements.
+    pushed: usize,
+    /// Keys of the advice map entries which were read.
+    read_keys: RefCell<BTreeSet<[u8; 32]>>,
+}
+
+impl<A: AdviceProvider> CoverageAdviceProvider<A> {
+    /// Returns a new [CoverageAdviceProvider] wrapping the specified provider, whose advice stack
+    /// initially holds `stack_len` elements.
+    fn new(provider: A, stack_len: usize) -> Self {
+        Self {
+            provider,
+            remaining: stack_len,
+            initial: stack_len,
+            pushed: 0,
+            read_keys: RefCell::new(BTreeSet::new()),
+        }
+    }
+
+    /// Returns the number of elements of the initial advice stack which were popped.
+    fn consumed(&self) -> usize {
+        self.initial - self.remaining
+    }
+
+    /// Records that the specified number of elements were popped from the advice stack; the
+    /// elements pushed during the execution are popped before the initial ones.
+    fn record_pop(&mut self, num_elements: usize) {
+        let from_pushed = num_elements.min(self.pushed);
+        self.pushed -= from_pushed;
+        self.remaining = self.remaining.saturating_sub(num_elements - from_pushed);
+    }
+
+    /// Records that the advice map entry under the specified key was read.
+    fn record_read(&self, key: [u8; 32]) {
+        self.read_keys.borrow_mut().insert(key);
+    }
+}
+
+/// Pass-through implementations of [AdviceProvider] methods, with the advice stack pops and the
+/// advice map reads recorded.
+#[rustfmt::skip]
+impl<A: AdviceProvider> AdviceProvider for CoverageAdviceProvider<A> {
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        let value = self.provider.pop_stack(process)?;
+        self.record_pop(1);
+        Ok(value)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        let word = self.provider.pop_stack_word(process)?;
+        self.record_pop(4);
+        Ok(word)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(&mut self, process: &S) -> Result<[Word; 2], ExecutionError> {
+        let dword = self.provider.pop_stack_dword(process)?;
+        self.record_pop(8);
+        Ok(dword)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        let num_elements = match &source {
+            AdviceSource::Value(_) => 1,
+            AdviceSource::Word(_) => 4,
+            AdviceSource::Map { key, include_len } => {
+                let values = self.get_mapped_values(&key.into_bytes());
+                values.map_or(0, <[Felt]>::len) + usize::from(*include_len)
+            }
+        };
+        self.provider.push_stack(source)?;
+        self.pushed += num_elements;
+        Ok(())
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_signature(&self, kind: SignatureKind, pub_key: Word, msg: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.record_read(pub_key.into_bytes());
+        self.provider.get_signature(kind, pub_key, msg)
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        self.record_read(*key);
+        self.provider.get_mapped_values(key)
+    }
+
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        self.record_read(*key);
+        self.provider.read_mapped_values(key)
+    }
+
+    fn get_map_keys_in_range(&self, start_key: Word, end_key: Word) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_map_keys_in_range(start_key, end_key)
+    }
+
+    fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(&self, root: Word, depth: &Felt, index: &Felt) -> Result<MerklePath, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn get_leaf_depth(&self, root: Word, tree_depth: &Felt, index: &Felt) -> Result<u8, ExecutionError> {
+        self.provider.get_leaf_depth(root, tree_depth, index)
+    }
+
+    fn find_lone_leaf(&self, root: Word, root_index: NodeIndex, tree_depth: u8) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        self.provider.find_lone_leaf(root, root_index, tree_depth)
+    }
+
+    fn update_merkle_node(&mut self, root: Word, depth: &Felt, index: &Felt, value: Word) -> Result<(MerklePath, Word), ExecutionError> {
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+        where
+            I: Iterator<Item = R>,
+            R: core::borrow::Borrow<RpoDigest> {
+        self.provider.get_store_subset(roots)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{InputCoverage, NumberFormat};
+    use miden::{math::Felt, utils::IntoBytes, AdviceInputs, Assembler, StackInputs};
+
+    #[test]
+    fn report_unused_inputs() {
+        // the program reads the top two operand stack inputs, pops one element of the advice
+        // stack, and reads one of the two advice map entries
+        let source = "
+            begin
+                add
+                adv_push.1 add
+                push.1.2.3.4 adv.push_mapval dropw adv_push.2 add add
+            end";
+        let program = Assembler::default().compile(source).unwrap();
+
+        let stack_inputs = StackInputs::try_from_values([7, 1, 2]).unwrap();
+        let used_key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let unused_key = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+        let advice_inputs =
+            AdviceInputs::default().with_stack_values([10, 20, 30]).unwrap().with_map([
+                (used_key.into_bytes(), vec![Felt::new(100), Felt::new(200)]),
+                (unused_key.into_bytes(), vec![Felt::new(300)]),
+            ]);
+
+        let coverage = InputCoverage::measure(&program, stack_inputs, advice_inputs).unwrap();
+        assert_eq!(
+            coverage.report(NumberFormat::Dec),
+            [
+                "Input coverage:",
+                "├── Operand stack: 2 of 3 inputs consumed",
+                "│   └── unused operand stack input 0: 7",
+                "├── Advice stack: 1 of 3 elements consumed",
+                "│   ├── unused advice stack element 1: 20",
+                "│   └── unused advice stack element 2: 30",
+                "└── Advice map: 1 of 2 entries read",
+                "    └── unused advice map key \
+                0500000000000000060000000000000007000000000000000800000000000000",
+            ]
+        );
+    }
+
+    #[test]
+    fn report_inputs_read_in_place() {
+        // inputs which are only duplicated are consumed, even though they remain on the stack
+        let program = Assembler::default().compile("begin dup.2 drop swap end").unwrap();
+        let stack_inputs = StackInputs::try_from_values([1, 2, 3, 4]).unwrap();
+
+        let coverage =
+            InputCoverage::measure(&program, stack_inputs, AdviceInputs::default()).unwrap();
+        assert_eq!(
+            coverage.report(NumberFormat::Dec)[1..3],
+            [
+                "├── Operand stack: 3 of 4 inputs consumed",
+                "│   └── unused operand stack input 0: 1",
+            ]
+        );
+    }
+}
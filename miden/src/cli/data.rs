@@ -1,18 +1,31 @@
-use assembly::{Library, MaslLibrary};
+use super::library_policy::LibraryPolicy;
+use super::spans::{InputPhase, Phase};
+use assembly::{
+    ast::{AstSerdeOptions, CodeBody, Node},
+    CompileStats, Library, LibraryError, LibraryLoadError, MaslLibrary, Module,
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use clap::ValueEnum;
 use miden::{
-    crypto::{MerkleStore, MerkleTree, NodeIndex, PartialMerkleTree, RpoDigest, SimpleSmt},
-    math::Felt,
-    utils::{Deserializable, SliceReader},
-    AdviceInputs, Assembler, Digest, ExecutionProof, MemAdviceProvider, Program, ProgramAst,
-    StackInputs, StackOutputs, Word,
+    code_blocks::CodeBlock,
+    crypto::{
+        Blake3_256, MerkleStore, MerkleTree, NodeIndex, PartialMerkleTree, RpoDigest, SimpleSmt,
+    },
+    math::{Felt, StarkField},
+    utils::{ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader},
+    AdviceInputs, AdviceMapKey, Assembler, AssemblyError, Digest, ExecutionError, ExecutionProof,
+    FieldExtension, HashFunction, MemAdviceProvider, ParsingError, Program, ProgramAst,
+    ProvingOptions, StackInputs, StackOutputs, Word,
 };
+use processor::{Operation, ProcessState, StackError, TrapState};
 use serde_derive::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
-    fs,
-    io::Write,
+    collections::{BTreeMap, HashMap},
+    env, fmt, fs,
+    io::{self, IsTerminal, Read, Write},
     path::{Path, PathBuf},
-    time::Instant,
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use stdlib::StdLibrary;
 
@@ -32,6 +45,59 @@ impl Debug {
     }
 }
 
+/// Defines how advice map entries which are present in more than one input source, but are
+/// mapped to different values, are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Fail with an error listing all conflicting keys.
+    #[default]
+    Error,
+    /// Keep the values specified in the input file.
+    PreferFile,
+    /// Keep the values specified in the advice map file passed as an argument.
+    PreferArg,
+}
+
+impl ConflictPolicy {
+    /// Merges advice inputs read from the input file with the advice inputs passed as an
+    /// argument according to this policy.
+    pub fn merge(&self, file: AdviceInputs, arg: AdviceInputs) -> Result<AdviceInputs, String> {
+        match self {
+            Self::Error => {
+                file.merge(arg).map_err(|e| format!("failed to merge advice inputs: {e}"))
+            }
+            Self::PreferFile => Ok(file.merge_prefer_left(arg)),
+            Self::PreferArg => Ok(arg.merge_prefer_left(file)),
+        }
+    }
+}
+
+/// Writes a file at the specified path atomically.
+///
+/// The data is first written by `write` into a temporary file next to the target (the target path
+/// with `.tmp` appended), which is then renamed into place. If writing fails, the temporary file is
+/// removed and any existing file at the target path is left untouched.
+fn write_atomic<F>(path: &Path, write: F) -> Result<(), String>
+where
+    F: FnOnce(&mut fs::File) -> io::Result<()>,
+{
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let result = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            write(&mut file)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    result.map_err(|err| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write file `{}` - {}", path.display(), err)
+    })
+}
+
 // MERKLE DATA
 // ================================================================================================
 
@@ -58,38 +124,76 @@ pub enum MerkleData {
 // INPUT FILE
 // ================================================================================================
 
+/// Maximum depth of Merkle trees which can be specified in input files.
+const MAX_MERKLE_TREE_DEPTH: u8 = u64::BITS as u8;
+
+/// Depth of Sparse Merkle trees specified in input files.
+const SPARSE_MERKLE_TREE_DEPTH: u8 = u64::BITS as u8;
+
+/// Path of the input file which denotes that the inputs are read from stdin.
+const STDIN_PATH: &str = "-";
+
 // TODO consider using final types instead of string representations.
-/// Input file struct that is used to deserialize input data from file. It consists of four
+/// Input file struct that is used to deserialize input data from file. It consists of five
 /// components:
-/// - operand_stack
+/// - operand_stack (optionally complemented by operand_words)
 /// - advice_stack
 /// - advice_map
 /// - merkle_store
+/// - memory
 #[derive(Deserialize, Debug)]
 pub struct InputFile {
     /// String representation of the initial operand stack, composed of chained field elements.
+    #[serde(default)]
     pub operand_stack: Vec<String>,
+    /// Optional list of 32 byte hex strings representing words which are appended to the initial
+    /// operand stack after the elements of `operand_stack`; see [InputFile::parse_stack_inputs].
+    #[serde(default)]
+    pub operand_words: Vec<String>,
     /// Optional string representation of the initial advice stack, composed of chained field
     /// elements.
     pub advice_stack: Option<Vec<String>>,
     /// Optional map of 32 byte hex strings to vectors of u64s representing the initial advice map.
-    pub advice_map: Option<HashMap<String, Vec<u64>>>,
+    pub advice_map: Option<AdviceMapEntries>,
     /// Optional vector of merkle data which will be loaded into the initial merkle store. Merkle
     /// data is represented as 32 byte hex strings and node indexes are represented as u64s.
     pub merkle_store: Option<Vec<MerkleData>>,
+    /// Optional list of `[address, [v0, v1, v2, v3]]` entries representing words which are written
+    /// into memory before the program is executed; see [MemoryImage].
+    pub memory: Option<Vec<(u64, [u64; 4])>>,
+    /// Format in which the roots of Merkle trees are printed while the Merkle store is parsed.
+    #[serde(skip)]
+    pub number_format: NumberFormat,
+    /// Time spent in each phase of parsing the input file.
+    #[serde(skip)]
+    pub parse_timings: InputParseTimings,
 }
 
 /// Helper methods to interact with the input file
 impl InputFile {
-    pub fn read(inputs_path: &Option<PathBuf>, program_path: &Path) -> Result<Self, String> {
+    /// Reads the input file from the specified path or, if the path is not provided, from the
+    /// file with the same name as the program file, but with `.inputs` extension. If the path is
+    /// `-`, the inputs are read from stdin.
+    ///
+    /// If `template` is set, `${VAR}` and `${VAR:-default}` references to environment variables
+    /// in the file are substituted before the file is deserialized.
+    pub fn read(
+        inputs_path: &Option<PathBuf>,
+        program_path: &Path,
+        template: bool,
+    ) -> Result<Self, String> {
         // if file not specified explicitly and corresponding file with same name as program_path
         // with '.inputs' extension does't exist, set operand_stack to empty vector
         if !inputs_path.is_some() && !program_path.with_extension("inputs").exists() {
             return Ok(Self {
                 operand_stack: Vec::new(),
+                operand_words: Vec::new(),
                 advice_stack: Some(Vec::new()),
-                advice_map: Some(HashMap::new()),
+                advice_map: Some(AdviceMapEntries::default()),
                 merkle_store: None,
+                memory: None,
+                number_format: NumberFormat::default(),
+                parse_timings: InputParseTimings::default(),
             });
         }
 
@@ -100,43 +204,158 @@ impl InputFile {
             None => program_path.with_extension("inputs"),
         };
 
-        println!("Reading input file `{}`", path.display());
-
-        // read input file to string
-        let inputs_file = fs::read_to_string(&path)
-            .map_err(|err| format!("Failed to open input file `{}` - {}", path.display(), err))?;
+        // a path of `-` means that the inputs are read from stdin
+        let mut inputs_file = if path == Path::new(STDIN_PATH) {
+            if !template {
+                return Self::from_stdin();
+            }
+            let mut inputs_file = String::new();
+            Self::stdin_reader()
+                .read_to_string(&mut inputs_file)
+                .map_err(|err| format!("Failed to read input data from stdin - {}", err))?;
+            inputs_file
+        } else {
+            statusln!("Reading input file `{}`", path.display());
+
+            // read input file to string
+            fs::read_to_string(&path).map_err(|err| {
+                format!("Failed to open input file `{}` - {}", path.display(), err)
+            })?
+        };
+        if template {
+            inputs_file = substitute_env_vars(&inputs_file, |name| env::var(name).ok())?;
+        }
 
         // deserialize input data
-        let inputs: InputFile = serde_json::from_str(&inputs_file)
+        let timings = InputParseTimings::default();
+        let mut inputs: InputFile = timings
+            .time(InputPhase::Deserialize, || serde_json::from_str(&inputs_file))
             .map_err(|err| format!("Failed to deserialize input data - {}", err))?;
+        inputs.parse_timings = timings;
+
+        Ok(inputs)
+    }
+
+    /// Reads the input data as JSON from stdin.
+    ///
+    /// If stdin is an interactive terminal, a prompt is printed before the data is read.
+    pub fn from_stdin() -> Result<Self, String> {
+        let reader = Self::stdin_reader();
+        let timings = InputParseTimings::default();
+        let mut inputs: InputFile = timings
+            .time(InputPhase::Deserialize, || serde_json::from_reader(reader))
+            .map_err(|err| format!("Failed to deserialize input data from stdin - {}", err))?;
+        inputs.parse_timings = timings;
 
         Ok(inputs)
     }
 
+    /// Returns a handle to stdin, printing a prompt if stdin is an interactive terminal.
+    fn stdin_reader() -> io::StdinLock<'static> {
+        let stdin = io::stdin();
+        if stdin.is_terminal() {
+            statusln!("Reading input from stdin...");
+        }
+        stdin.lock()
+    }
+
+    /// Sets the format in which numeric values derived from the input file are printed.
+    pub fn with_number_format(mut self, number_format: NumberFormat) -> Self {
+        self.number_format = number_format;
+        self
+    }
+
+    /// Appends the stack values of the specified output file to the advice stack.
+    ///
+    /// The values are appended after the advice stack values specified in this file, starting
+    /// with the top of the output stack, and thus, the top of the output stack is the first of
+    /// them to be read from the advice stack.
+    pub fn with_advice_from_outputs(mut self, outputs: &OutputFile) -> Result<Self, String> {
+        let values = outputs
+            .stack
+            .iter()
+            .map(|value| parse_number(value).map(|value| value.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.advice_stack.get_or_insert_with(Vec::new).extend(values);
+        Ok(self)
+    }
+
+    /// Prepends the values of the specified memory image to the advice stack, so that a program
+    /// seeded with the image (see [MemoryImage::seed]) reads them before any other advice stack
+    /// value.
+    pub fn with_memory(mut self, memory: &MemoryImage) -> Self {
+        if !memory.0.is_empty() {
+            let advice_stack = self.advice_stack.get_or_insert_with(Vec::new);
+            let values = memory.advice_stack().into_iter().map(|value| value.to_string());
+            advice_stack.splice(0..0, values);
+        }
+        self
+    }
+
     /// Parse advice provider data from the input file.
     pub fn parse_advice_provider(&self) -> Result<MemAdviceProvider, String> {
+        Ok(MemAdviceProvider::from(self.parse_advice_inputs(false)?))
+    }
+
+    /// Parse advice provider data from the input file and merge it with the advice map read from
+    /// the specified advice map file (if any), resolving conflicting keys using `policy`.
+    ///
+    /// If `strict_advice` is set, advice maps which define the same key more than once are
+    /// rejected; otherwise, the value defined last is used.
+    pub fn parse_advice_provider_with_map(
+        &self,
+        advice_map_path: Option<&Path>,
+        policy: ConflictPolicy,
+        strict_advice: bool,
+    ) -> Result<MemAdviceProvider, String> {
+        let advice_inputs =
+            self.parse_advice_inputs_with_map(advice_map_path, policy, strict_advice)?;
+        Ok(MemAdviceProvider::from(advice_inputs))
+    }
+
+    /// Parse advice inputs from the input file and merge them with the advice map read from the
+    /// specified advice map file (if any), resolving conflicting keys using `policy`.
+    ///
+    /// See [InputFile::parse_advice_provider_with_map()] for details.
+    pub fn parse_advice_inputs_with_map(
+        &self,
+        advice_map_path: Option<&Path>,
+        policy: ConflictPolicy,
+        strict_advice: bool,
+    ) -> Result<AdviceInputs, String> {
+        let mut advice_inputs = self.parse_advice_inputs(strict_advice)?;
+        if let Some(path) = advice_map_path {
+            let arg_inputs = AdviceMapFile::read(path)?.parse_advice_inputs(strict_advice)?;
+            advice_inputs = policy.merge(advice_inputs, arg_inputs)?;
+        }
+        Ok(advice_inputs)
+    }
+
+    /// Parse advice inputs from the input file.
+    fn parse_advice_inputs(&self, strict_advice: bool) -> Result<AdviceInputs, String> {
         let mut advice_inputs = AdviceInputs::default();
 
-        let stack = self
-            .parse_advice_stack()
+        let timings = &self.parse_timings;
+        let stack = timings
+            .time(InputPhase::AdviceStack, || self.parse_advice_stack())
             .map_err(|e| format!("failed to parse advice provider: {e}"))?;
         advice_inputs = advice_inputs.with_stack_values(stack).map_err(|e| e.to_string())?;
 
-        if let Some(map) = self
-            .parse_advice_map()
+        if let Some(map) = timings
+            .time(InputPhase::AdviceMap, || self.parse_advice_map(strict_advice))
             .map_err(|e| format!("failed to parse advice provider: {e}"))?
         {
             advice_inputs = advice_inputs.with_map(map);
         }
 
-        if let Some(merkle_store) = self
-            .parse_merkle_store()
+        if let Some(merkle_store) = timings
+            .time(InputPhase::MerkleStore, || self.parse_merkle_store())
             .map_err(|e| format!("failed to parse advice provider: {e}"))?
         {
             advice_inputs = advice_inputs.with_merkle_store(merkle_store);
         }
 
-        Ok(MemAdviceProvider::from(advice_inputs))
+        Ok(advice_inputs)
     }
 
     /// Parse advice stack data from the input file.
@@ -154,44 +373,33 @@ impl InputFile {
     }
 
     /// Parse advice map data from the input file.
-    fn parse_advice_map(&self) -> Result<Option<HashMap<[u8; 32], Vec<Felt>>>, String> {
-        let advice_map = match &self.advice_map {
-            Some(advice_map) => advice_map,
-            None => return Ok(None),
-        };
-
-        let map = advice_map
-            .iter()
-            .map(|(k, v)| {
-                // decode hex key
-                let mut key = [0u8; 32];
-                hex::decode_to_slice(k, &mut key)
-                    .map_err(|e| format!("failed to decode advice map key `{k}` - {e}"))?;
-
-                // convert values to Felt
-                let values = v
-                    .iter()
-                    .map(|v| {
-                        Felt::try_from(*v).map_err(|e| {
-                            format!("failed to convert advice map value `{v}` to Felt - {e}")
-                        })
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                Ok((key, values))
-            })
-            .collect::<Result<HashMap<[u8; 32], Vec<Felt>>, String>>()?;
-
-        Ok(Some(map))
+    fn parse_advice_map(
+        &self,
+        strict_advice: bool,
+    ) -> Result<Option<HashMap<[u8; 32], Vec<Felt>>>, String> {
+        match &self.advice_map {
+            Some(advice_map) => parse_advice_map(advice_map, strict_advice).map(Some),
+            None => Ok(None),
+        }
     }
 
     /// Parse merkle store data from the input file.
     fn parse_merkle_store(&self) -> Result<Option<MerkleStore>, String> {
+        Ok(self.parse_merkle_store_with_roots()?.map(|(merkle_store, _)| merkle_store))
+    }
+
+    /// Parse merkle store data from the input file, and return the store together with the roots
+    /// of the trees added to it, in the order in which the trees are specified.
+    pub fn parse_merkle_store_with_roots(
+        &self,
+    ) -> Result<Option<(MerkleStore, Vec<RpoDigest>)>, String> {
         let merkle_data = match &self.merkle_store {
             Some(merkle_data) => merkle_data,
             None => return Ok(None),
         };
 
         let mut merkle_store = MerkleStore::default();
+        let mut roots = Vec::with_capacity(merkle_data.len());
         for data in merkle_data {
             match data {
                 MerkleData::MerkleTree(data) => {
@@ -199,32 +407,125 @@ impl InputFile {
                     let tree = MerkleTree::new(leaves)
                         .map_err(|e| format!("failed to parse a Merkle tree: {e}"))?;
                     merkle_store.extend(tree.inner_nodes());
-                    println!("Added Merkle tree with root {} to the Merkle store", tree.root());
+                    statusln!(
+                        "Added Merkle tree with root {} to the Merkle store",
+                        self.number_format.format_word(&tree.root())
+                    );
+                    roots.push(tree.root());
                 }
                 MerkleData::SparseMerkleTree(data) => {
-                    let entries = Self::parse_sparse_merkle_tree(data)?;
-                    let tree = SimpleSmt::with_leaves(u64::BITS as u8, entries)
+                    let entries = Self::parse_sparse_merkle_tree(data, SPARSE_MERKLE_TREE_DEPTH)?;
+                    let tree = SimpleSmt::with_leaves(SPARSE_MERKLE_TREE_DEPTH, entries)
                         .map_err(|e| format!("failed to parse a Sparse Merkle Tree: {e}"))?;
                     merkle_store.extend(tree.inner_nodes());
-                    println!(
+                    statusln!(
                         "Added Sparse Merkle tree with root {} to the Merkle store",
-                        tree.root()
+                        self.number_format.format_word(&tree.root())
                     );
+                    roots.push(tree.root());
                 }
                 MerkleData::PartialMerkleTree(data) => {
                     let entries = Self::parse_partial_merkle_tree(data)?;
                     let tree = PartialMerkleTree::with_leaves(entries)
                         .map_err(|e| format!("failed to parse a Partial Merkle Tree: {e}"))?;
                     merkle_store.extend(tree.inner_nodes());
-                    println!(
+                    statusln!(
                         "Added Partial Merkle tree with root {} to the Merkle store",
-                        tree.root()
+                        self.number_format.format_word(&tree.root())
                     );
+                    roots.push(tree.root());
+                }
+            }
+        }
+
+        Ok(Some((merkle_store, roots)))
+    }
+
+    /// Parse the memory entries of the input file into a [MemoryImage].
+    pub fn parse_memory(&self) -> Result<MemoryImage, String> {
+        let entries = self.memory.iter().flatten().copied();
+        MemoryImage::from_entries(entries).map_err(|e| format!("failed to parse memory: {e}"))
+    }
+
+    /// Checks that all Merkle trees in the input file are internally consistent, returning the
+    /// number of checked trees.
+    ///
+    /// Each tree is built in the same way as when the Merkle store is parsed, and then, for
+    /// every leaf declared in the input file, the path to the leaf is opened from the root of the
+    /// tree in a Merkle store containing the nodes of the tree, and the leaf and the path are
+    /// verified to hash to the root. This catches, for example, nodes of a partial Merkle tree
+    /// whose provided values do not match the values of their descendants.
+    pub fn check_merkle_store(&self) -> Result<usize, String> {
+        let merkle_data = self.merkle_store.as_deref().unwrap_or_default();
+
+        for (i, data) in merkle_data.iter().enumerate() {
+            let (kind, root, leaves, store) = match data {
+                MerkleData::MerkleTree(data) => {
+                    let leaves = Self::parse_merkle_tree(data)?;
+                    let tree = MerkleTree::new(leaves)
+                        .map_err(|e| format!("failed to parse a Merkle tree: {e}"))?;
+                    let leaves = Self::index_leaves(tree.depth(), tree.leaves())?;
+                    let store = tree.inner_nodes().collect::<MerkleStore>();
+                    ("Merkle tree", tree.root(), leaves, store)
+                }
+                MerkleData::SparseMerkleTree(data) => {
+                    let entries = Self::parse_sparse_merkle_tree(data, SPARSE_MERKLE_TREE_DEPTH)?;
+                    let tree = SimpleSmt::with_leaves(SPARSE_MERKLE_TREE_DEPTH, entries)
+                        .map_err(|e| format!("failed to parse a Sparse Merkle Tree: {e}"))?;
+                    let leaves = Self::index_leaves(tree.depth(), tree.leaves())?;
+                    let store = tree.inner_nodes().collect::<MerkleStore>();
+                    ("Sparse Merkle tree", tree.root(), leaves, store)
+                }
+                MerkleData::PartialMerkleTree(data) => {
+                    let leaves = Self::parse_partial_merkle_tree(data)?;
+                    let tree = PartialMerkleTree::with_leaves(leaves.clone())
+                        .map_err(|e| format!("failed to parse a Partial Merkle Tree: {e}"))?;
+                    let store = tree.inner_nodes().collect::<MerkleStore>();
+                    ("Partial Merkle tree", tree.root(), leaves, store)
+                }
+            };
+
+            for (index, leaf) in leaves {
+                let consistent = match store.get_path(root, index) {
+                    Ok(path) => path.value == leaf && path.path.verify(index.value(), leaf, &root),
+                    Err(_) => false,
+                };
+                if !consistent {
+                    return Err(format!(
+                        "{kind} #{i} is inconsistent: leaf {} at depth {} and index {} does not \
+                        hash to the root {}",
+                        self.number_format.format_word(&leaf),
+                        index.depth(),
+                        index.value(),
+                        self.number_format.format_word(&root)
+                    ));
                 }
             }
+
+            statusln!(
+                "{kind} #{i} with root {} is consistent",
+                self.number_format.format_word(&root)
+            );
         }
 
-        Ok(Some(merkle_store))
+        Ok(merkle_data.len())
+    }
+
+    /// Returns the node indexes of the provided leaves of a Merkle tree of the specified depth.
+    fn index_leaves<'a>(
+        depth: u8,
+        leaves: impl Iterator<Item = (u64, &'a Word)>,
+    ) -> Result<Vec<(NodeIndex, RpoDigest)>, String> {
+        leaves
+            .map(|(index, leaf)| {
+                let node_index = NodeIndex::new(depth, index).map_err(|e| {
+                    format!(
+                        "failed to create node index with depth {depth} and index {index} - {e}"
+                    )
+                })?;
+                Ok((node_index, RpoDigest::new(*leaf)))
+            })
+            .collect()
     }
 
     /// Parse and return merkle tree leaves.
@@ -237,10 +538,17 @@ impl InputFile {
             .collect()
     }
 
-    /// Parse and return Sparse Merkle Tree entries.
-    fn parse_sparse_merkle_tree(tree: &[(u64, String)]) -> Result<Vec<(u64, Word)>, String> {
+    /// Parse and return Sparse Merkle Tree entries for a tree of the specified depth.
+    ///
+    /// # Errors
+    /// Returns an error if an index of an entry does not fit into a tree of the specified depth.
+    fn parse_sparse_merkle_tree(
+        tree: &[(u64, String)],
+        depth: u8,
+    ) -> Result<Vec<(u64, Word)>, String> {
         tree.iter()
             .map(|(index, v)| {
+                Self::check_node_index(depth, *index)?;
                 let leaf = Self::parse_word(v)?;
                 Ok((*index, leaf))
             })
@@ -248,11 +556,16 @@ impl InputFile {
     }
 
     /// Parse and return Partial Merkle Tree entries.
+    ///
+    /// # Errors
+    /// Returns an error if a node depth exceeds the maximum depth of a Partial Merkle Tree or if
+    /// a node index does not fit into its depth.
     fn parse_partial_merkle_tree(
         tree: &[((u8, u64), String)],
     ) -> Result<Vec<(NodeIndex, RpoDigest)>, String> {
         tree.iter()
             .map(|((depth, index), v)| {
+                Self::check_node_index(*depth, *index)?;
                 let node_index = NodeIndex::new(*depth, *index).map_err(|e| {
                     format!(
                         "failed to create node index with depth {depth} and index {index} - {e}"
@@ -264,9 +577,51 @@ impl InputFile {
             .collect()
     }
 
+    /// Checks that a node with the specified index can exist at the specified depth of a Merkle
+    /// tree.
+    fn check_node_index(depth: u8, index: u64) -> Result<(), String> {
+        if depth > MAX_MERKLE_TREE_DEPTH {
+            return Err(format!(
+                "depth {depth} exceeds max depth {MAX_MERKLE_TREE_DEPTH} of a Merkle tree"
+            ));
+        }
+
+        let max = u64::MAX.checked_shr((MAX_MERKLE_TREE_DEPTH - depth) as u32).unwrap_or(0);
+        if index > max {
+            return Err(format!("index {index} exceeds max index {max} for depth {depth}"));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that all words in this input file (i.e., Merkle store nodes and advice map keys) are
+    /// hex strings prefixed with `0x`.
+    ///
+    /// This is used in strict hex mode to catch words which were mistakenly written as decimal
+    /// numbers, since these would otherwise be silently parsed as hex. Operand stack and advice
+    /// values are not affected.
+    pub fn check_strict_hex(&self) -> Result<(), String> {
+        let merkle_words = self.merkle_store.iter().flatten().flat_map(|data| match data {
+            MerkleData::MerkleTree(leaves) => leaves.iter().collect::<Vec<_>>(),
+            MerkleData::SparseMerkleTree(leaves) => leaves.iter().map(|(_, v)| v).collect(),
+            MerkleData::PartialMerkleTree(leaves) => leaves.iter().map(|(_, v)| v).collect(),
+        });
+        for word in merkle_words {
+            check_hex_prefix(word, "Merkle store node")?;
+        }
+
+        for (key, _) in self.advice_map.iter().flat_map(|entries| entries.0.iter()) {
+            check_hex_prefix(key, "advice map key")?;
+        }
+
+        Ok(())
+    }
+
     /// Parse a `Word` from a hex string.
+    ///
+    /// The string must encode exactly 32 bytes, and may optionally be prefixed with `0x`.
     pub fn parse_word(word_hex: &str) -> Result<Word, String> {
-        let word_value = &word_hex[2..];
+        let word_value = word_hex.strip_prefix("0x").unwrap_or(word_hex);
         let mut word_data = [0u8; 32];
         hex::decode_to_slice(word_value, &mut word_data)
             .map_err(|e| format!("failed to decode `Word` from hex {word_hex} - {e}"))?;
@@ -279,229 +634,1996 @@ impl InputFile {
         Ok(word)
     }
 
+    /// Derives an advice map key from the provided field elements.
+    ///
+    /// Each value must be a hex-encoded field element, optionally prefixed with `0x`. The key is
+    /// the RPO hash of the elements encoded as 32 bytes (see [AdviceMapKey]), and is returned as a
+    /// `0x`-prefixed hex string which can be used as a key in the `advice_map` of an input file.
+    pub fn derive_advice_key(hex_values: &[&str]) -> Result<String, String> {
+        let felts = hex_values
+            .iter()
+            .map(|value| {
+                let digits = value.strip_prefix("0x").unwrap_or(value);
+                let value = u64::from_str_radix(digits, 16)
+                    .map_err(|err| format!("Invalid hex value {value} - {err}"))?;
+                if value >= Felt::MODULUS {
+                    return Err(format!("Value {value} is not a valid field element"));
+                }
+                Ok(Felt::new(value))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(format!("0x{}", hex::encode(AdviceMapKey::from_felts(&felts))))
+    }
+
     /// Parse and return the stack inputs for the program.
+    ///
+    /// The elements of `operand_stack` are followed by the elements of the words in
+    /// `operand_words`, with the elements of each word listed in order. As the last listed element
+    /// ends up on top of the stack, the words are placed above the elements of `operand_stack`,
+    /// the last word is on top of the stack, and the first element of each word is the deepest.
     pub fn parse_stack_inputs(&self) -> Result<StackInputs, String> {
-        let stack_inputs = self
+        let mut stack_inputs = self
             .operand_stack
             .iter()
             .map(|v| v.parse::<u64>().map_err(|e| e.to_string()))
             .collect::<Result<Vec<_>, _>>()?;
+        for word in self.operand_words.iter() {
+            let word = Self::parse_word(word)?;
+            stack_inputs.extend(word.iter().map(|felt| felt.as_int()));
+        }
 
         StackInputs::try_from_values(stack_inputs).map_err(|e| e.to_string())
     }
 }
 
-// OUTPUT FILE
-// ================================================================================================
+/// Substitutes all `${VAR}` references in the specified text with the values returned by
+/// `lookup` for the variable names.
+///
+/// A default value can be provided as `${VAR:-default}`; it is used if the variable is not
+/// defined or is empty.
+///
+/// # Errors
+/// Returns an error if a variable without a default value is not defined, or if a reference is not
+/// terminated.
+fn substitute_env_vars<F>(text: &str, lookup: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let reference = &rest[start + 2..];
+        let end = reference
+            .find('}')
+            .ok_or_else(|| format!("Unterminated variable reference `{}`", &rest[start..]))?;
+
+        let (name, default) = match reference[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&reference[..end], None),
+        };
+        let value = match (lookup(name).filter(|value| !value.is_empty()), default) {
+            (Some(value), _) => value,
+            (None, Some(default)) => default.to_string(),
+            (None, None) => {
+                return Err(format!("Environment variable `{name}` used in input file is not set"))
+            }
+        };
+        result.push_str(&value);
+        rest = &reference[end + 1..];
+    }
+    result.push_str(rest);
 
-/// Output file struct
-#[derive(Deserialize, Serialize, Debug)]
-pub struct OutputFile {
-    pub stack: Vec<String>,
-    pub overflow_addrs: Vec<String>,
+    Ok(result)
 }
 
-/// Helper methods to interact with the output file
-impl OutputFile {
-    /// Returns a new [OutputFile] from the specified outputs vectors
-    pub fn new(stack_outputs: &StackOutputs) -> Self {
-        Self {
-            stack: stack_outputs.stack().iter().map(|&v| v.to_string()).collect::<Vec<String>>(),
-            overflow_addrs: stack_outputs
-                .overflow_addrs()
-                .iter()
-                .map(|&v| v.to_string())
-                .collect::<Vec<String>>(),
+/// Returns an error if the specified word is not prefixed with `0x`.
+fn check_hex_prefix(word: &str, kind: &str) -> Result<(), String> {
+    if word.starts_with("0x") {
+        Ok(())
+    } else {
+        Err(format!("Invalid {kind} `{word}` - strict hex mode requires a `0x` prefix"))
+    }
+}
+
+// INPUT PARSE TIMINGS
+// ================================================================================================
+
+/// Time spent in each phase of parsing an input file.
+///
+/// If the advice inputs of a file are parsed more than once, the time spent in all of the parses
+/// is accumulated.
+#[derive(Debug, Default)]
+pub struct InputParseTimings(Mutex<Vec<(InputPhase, Duration)>>);
+
+impl InputParseTimings {
+    /// Runs `f` in the span of the specified phase and records the time it took.
+    fn time<T>(&self, phase: InputPhase, f: impl FnOnce() -> T) -> T {
+        let _span = phase.enter();
+        let now = Instant::now();
+        let result = f();
+        self.lock().push((phase, now.elapsed()));
+        result
+    }
+
+    /// Returns the total time spent in the specified phase, or `None` if the phase was not
+    /// executed.
+    pub fn total(&self, phase: InputPhase) -> Option<Duration> {
+        let timings = self.lock();
+        let mut durations = timings.iter().filter(|(p, _)| *p == phase).map(|(_, d)| *d).peekable();
+        durations.peek()?;
+        Some(durations.sum())
+    }
+
+    /// Returns a table with the time spent in each phase in milliseconds, followed by the total
+    /// time spent in all phases; phases which were not executed are marked with `-`.
+    pub fn to_table(&self) -> String {
+        let mut table = format!("{:<20} {:>12}\n", "Phase", "Time (ms)");
+        let mut total = Duration::ZERO;
+        for phase in InputPhase::ALL {
+            let time = match self.total(phase) {
+                Some(duration) => {
+                    total += duration;
+                    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+                }
+                None => "-".to_string(),
+            };
+            table.push_str(&format!("{:<20} {:>12}\n", phase.name(), time));
         }
+        table.push_str(&format!("{:<20} {:>12.3}\n", "total", total.as_secs_f64() * 1000.0));
+        table
     }
 
-    /// Read the output file
-    pub fn read(outputs_path: &Option<PathBuf>, program_path: &Path) -> Result<Self, String> {
-        // If outputs_path has been provided then use this as path.  Alternatively we will
-        // replace the program_path extension with `.outputs` and use this as a default.
-        let path = match outputs_path {
-            Some(path) => path.clone(),
-            None => program_path.with_extension("outputs"),
-        };
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<(InputPhase, Duration)>> {
+        self.0.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
 
-        println!("Reading output file `{}`", path.display());
+// ADVICE MAP FILE
+// ================================================================================================
 
-        // read outputs file to string
-        let outputs_file = fs::read_to_string(&path)
-            .map_err(|err| format!("Failed to open outputs file `{}` - {}", path.display(), err))?;
+/// Entries of an advice map in the order in which they are defined in a file.
+///
+/// Unlike a map, this keeps all entries with duplicate keys, so that such keys can be reported.
+#[derive(Debug, Default)]
+pub struct AdviceMapEntries(Vec<(String, Vec<u64>)>);
 
-        // deserialize outputs data
-        let outputs: OutputFile = serde_json::from_str(&outputs_file)
-            .map_err(|err| format!("Failed to deserialize outputs data - {}", err))?;
+impl<'de> serde::Deserialize<'de> for AdviceMapEntries {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct EntriesVisitor;
 
-        Ok(outputs)
+        impl<'de> serde::de::Visitor<'de> for EntriesVisitor {
+            type Value = AdviceMapEntries;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of hex strings to arrays of u64 values")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(AdviceMapEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
     }
+}
 
-    /// Write the output file
-    pub fn write(stack_outputs: &StackOutputs, path: &PathBuf) -> Result<(), String> {
-        // if path provided, create output file
-        println!("Creating output file `{}`", path.display());
+/// Advice map file struct that is used to deserialize a standalone advice map from file. The
+/// advice map is represented as a map of 32 byte hex strings to vectors of u64s, in the same
+/// format as the `advice_map` component of the input file.
+#[derive(Deserialize, Debug)]
+pub struct AdviceMapFile(AdviceMapEntries);
 
-        let file = fs::File::create(&path).map_err(|err| {
-            format!("Failed to create output file `{}` - {}", path.display(), err)
-        })?;
+/// Helper methods to interact with the advice map file
+impl AdviceMapFile {
+    /// Reads the advice map file at the specified path.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        statusln!("Reading advice map file `{}`", path.display());
 
-        println!("Writing data to output file");
+        let advice_map_file = fs::read_to_string(path).map_err(|err| {
+            format!("Failed to open advice map file `{}` - {}", path.display(), err)
+        })?;
 
-        // write outputs to output file
-        serde_json::to_writer_pretty(file, &Self::new(stack_outputs))
-            .map_err(|err| format!("Failed to write output data - {}", err))
+        serde_json::from_str(&advice_map_file)
+            .map_err(|err| format!("Failed to deserialize advice map data - {}", err))
     }
 
-    /// Converts outputs vectors for stack and overflow addresses to [StackOutputs].
-    pub fn stack_outputs(&self) -> Result<StackOutputs, String> {
-        let stack = self.stack.iter().map(|v| v.parse::<u64>().unwrap()).collect::<Vec<u64>>();
+    /// Parse the advice map into [AdviceInputs] containing only the advice map.
+    pub fn parse_advice_inputs(&self, strict_advice: bool) -> Result<AdviceInputs, String> {
+        let map = parse_advice_map(&self.0, strict_advice)
+            .map_err(|e| format!("failed to parse advice map file: {e}"))?;
+        Ok(AdviceInputs::default().with_map(map))
+    }
+}
 
-        let overflow_addrs = self
-            .overflow_addrs
+/// Parses an advice map represented as a list of 32 byte hex strings mapped to vectors of u64s.
+///
+/// If a key is defined more than once, the value defined last is used, unless `strict` is set. In
+/// the strict mode, all redefined keys are reported as an error.
+fn parse_advice_map(
+    advice_map: &AdviceMapEntries,
+    strict: bool,
+) -> Result<HashMap<[u8; 32], Vec<Felt>>, String> {
+    let mut map = HashMap::new();
+    // map from a key to the position at which it was first defined
+    let mut positions = HashMap::new();
+    let mut duplicates = Vec::new();
+
+    for (position, (k, v)) in advice_map.0.iter().enumerate() {
+        // decode hex key
+        let mut key = [0u8; 32];
+        hex::decode_to_slice(k, &mut key)
+            .map_err(|e| format!("failed to decode advice map key `{k}` - {e}"))?;
+
+        // convert values to Felt
+        let values = v
             .iter()
-            .map(|v| v.parse::<u64>().unwrap())
-            .collect::<Vec<u64>>();
+            .map(|v| {
+                Felt::try_from(*v)
+                    .map_err(|e| format!("failed to convert advice map value `{v}` to Felt - {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        StackOutputs::new(stack, overflow_addrs)
-            .map_err(|e| format!("Construct stack outputs failed {e}"))
+        let first_position = *positions.entry(key).or_insert(position);
+        if first_position != position {
+            duplicates.push(format!(
+                "Duplicate advice map key 0x{}: first defined at position {first_position}, \
+                redefined at position {position}",
+                hex::encode(key)
+            ));
+        }
+        map.insert(key, values);
     }
-}
 
-// PROGRAM FILE
-// ================================================================================================
+    if strict && !duplicates.is_empty() {
+        return Err(duplicates.join("\n"));
+    }
 
-pub struct ProgramFile {
-    ast: ProgramAst,
-    path: PathBuf,
+    Ok(map)
 }
 
-/// Helper methods to interact with masm program file.
-impl ProgramFile {
-    /// Reads the masm file at the specified path and parses it into a [ProgramAst].
-    pub fn read(path: &PathBuf) -> Result<Self, String> {
-        // read program file to string
-        println!("Reading program file `{}`", path.display());
-        let source = fs::read_to_string(&path)
-            .map_err(|err| format!("Failed to open program file `{}` - {}", path.display(), err))?;
+// MEMORY IMAGE
+// ================================================================================================
 
-        // parse the program into an AST
-        print!("Parsing program... ");
-        let now = Instant::now();
-        let ast = ProgramAst::parse(&source).map_err(|err| {
-            format!("Failed to parse program file `{}` - {}", path.display(), err)
+/// Size in bytes of a record of a binary memory image.
+const MEMORY_RECORD_SIZE: usize = 5 * 8;
+
+/// Program which writes the words of a memory image into memory; the words are read from the
+/// advice stack, which starts with the number of words, followed by the address and the four
+/// elements of every word.
+const MEMORY_LOADER: &str = "
+begin
+    adv_push.1 dup neq.0
+    while.true
+        adv_push.1 padw adv_loadw movup.4 mem_storew dropw
+        sub.1 dup neq.0
+    end
+    drop
+end";
+
+/// Words which are written into the memory of the root context before a program is executed.
+///
+/// A memory image can be specified via the `memory` component of the input file, or read from a
+/// binary file, which is much faster to parse for large images. A binary memory image is a
+/// sequence of 40 byte records, each consisting of a memory address followed by the four elements
+/// of the word stored at the address, all encoded as little-endian u64s.
+///
+/// If an address is specified more than once, the word specified last is used.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryImage(BTreeMap<u32, Word>);
+
+impl MemoryImage {
+    /// Reads the binary memory image at the specified path.
+    pub fn read_binary(path: &Path) -> Result<Self, String> {
+        statusln!("Reading memory image `{}`", path.display());
+
+        let bytes = fs::read(path).map_err(|err| {
+            format!("Failed to open memory image file `{}` - {}", path.display(), err)
         })?;
-        println!("done ({} ms)", now.elapsed().as_millis());
 
-        Ok(Self {
-            ast,
-            path: path.clone(),
-        })
+        Self::from_bytes(&bytes)
+            .map_err(|err| format!("Failed to parse memory image `{}` - {}", path.display(), err))
     }
 
-    /// Compiles this program file into a [Program].
-    pub fn compile<I, L>(&self, debug: &Debug, libraries: I) -> Result<Program, String>
+    /// Parses a binary memory image from the specified bytes.
+    ///
+    /// # Errors
+    /// Returns an error if the number of bytes is not a multiple of the record size, if an address
+    /// does not fit into 32 bits, or if a value is not a valid field element.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() % MEMORY_RECORD_SIZE != 0 {
+            return Err(format!(
+                "image size of {} bytes is not a multiple of the record size of \
+                {MEMORY_RECORD_SIZE} bytes",
+                bytes.len()
+            ));
+        }
+
+        let entries = bytes.chunks_exact(MEMORY_RECORD_SIZE).map(|record| {
+            let mut values = [0u64; 5];
+            for (value, bytes) in values.iter_mut().zip(record.chunks_exact(8)) {
+                *value = u64::from_le_bytes(bytes.try_into().expect("chunk of 8 bytes"));
+            }
+            let [address, v0, v1, v2, v3] = values;
+            (address, [v0, v1, v2, v3])
+        });
+        Self::from_entries(entries)
+    }
+
+    /// Returns a memory image with the words specified as `(address, [v0, v1, v2, v3])` entries.
+    ///
+    /// # Errors
+    /// Returns an error if an address does not fit into 32 bits, or if a value is not a valid
+    /// field element.
+    pub fn from_entries<I>(entries: I) -> Result<Self, String>
     where
-        I: IntoIterator<Item = L>,
-        L: Library,
+        I: IntoIterator<Item = (u64, [u64; 4])>,
     {
-        print!("Compiling program... ");
-        let now = Instant::now();
+        let mut image = BTreeMap::new();
+        for (position, (address, values)) in entries.into_iter().enumerate() {
+            let address = u32::try_from(address).map_err(|_| {
+                format!("address {address} of entry {position} does not fit into 32 bits")
+            })?;
 
-        // compile program
-        let mut assembler = Assembler::default()
-            .with_debug_mode(debug.is_on())
-            .with_library(&StdLibrary::default())
-            .map_err(|err| format!("Failed to load stdlib - {}", err))?;
+            let mut word = Word::default();
+            for (element, value) in word.iter_mut().zip(values) {
+                if value >= Felt::MODULUS {
+                    return Err(format!(
+                        "value {value} of entry {position} is not a valid field element"
+                    ));
+                }
+                *element = Felt::new(value);
+            }
+            image.insert(address, word);
+        }
+        Ok(Self(image))
+    }
 
-        assembler = assembler
-            .with_libraries(libraries.into_iter())
-            .map_err(|err| format!("Failed to load libraries `{}`", err))?;
+    /// Returns the words of this image sorted by their addresses.
+    pub fn words(&self) -> impl Iterator<Item = (u32, Word)> + '_ {
+        self.0.iter().map(|(&address, &word)| (address, word))
+    }
 
-        let program = assembler
-            .compile_ast(&self.ast)
-            .map_err(|err| format!("Failed to compile program - {}", err))?;
+    /// Adds the words of the specified image to this image; words already present at the same
+    /// addresses are replaced.
+    pub fn extend(&mut self, other: MemoryImage) {
+        self.0.extend(other.0);
+    }
 
-        println!("done ({} ms)", now.elapsed().as_millis());
+    /// Returns a program which reads the words of this image from the advice stack, writes them
+    /// into memory, and then executes the specified program; the advice stack must start with the
+    /// values returned by [MemoryImage::advice_stack].
+    ///
+    /// The words are written via regular memory operations, and thus, the resulting program can be
+    /// proven. Its hash differs from the hash of the specified program unless the image is empty,
+    /// but it does not depend on the words of the image.
+    pub fn seed(&self, program: &Program) -> Program {
+        if self.0.is_empty() {
+            return program.clone();
+        }
 
-        Ok(program)
+        let loader =
+            Assembler::default().compile(MEMORY_LOADER).expect("memory loader must compile");
+        let root = CodeBlock::new_join([loader.root().clone(), program.root().clone()]);
+        Program::with_kernel(root, program.kernel().clone(), program.cb_table().clone())
     }
 
-    /// Writes this file into the specified path, if one is provided. If the path is not provided,
-    /// writes the file into the same directory as the source file, but with `.masb` extension.
-    pub fn write(&self, out_path: Option<PathBuf>) -> Result<(), String> {
-        let out_path = out_path.unwrap_or_else(|| {
-            let mut out_file = self.path.clone();
-            out_file.set_extension("masb");
-            out_file
-        });
-
-        self.ast
-            .write_to_file(out_path)
-            .map_err(|err| format!("Failed to write the compiled file: {err}"))
+    /// Returns the values read from the advice stack by a program seeded with this image: the
+    /// number of words, followed by the address and the four elements of every word.
+    pub fn advice_stack(&self) -> Vec<u64> {
+        let mut values = Vec::with_capacity(1 + self.0.len() * 5);
+        values.push(self.0.len() as u64);
+        for (address, word) in self.words() {
+            values.push(address as u64);
+            values.extend(word.iter().map(|value| value.as_int()));
+        }
+        values
     }
 }
 
-// PROOF FILE
+// NUMBER FORMAT
 // ================================================================================================
 
-pub struct ProofFile;
+/// Format in which numeric values are printed and written to output files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum NumberFormat {
+    /// Decimal integers; words are printed as lists of their elements.
+    #[default]
+    Dec,
+    /// `0x`-prefixed hexadecimal integers; words are printed as `0x`-prefixed hex strings of their
+    /// 32 bytes.
+    Hex,
+}
 
-/// Helper methods to interact with proof file
-impl ProofFile {
-    /// Read stark proof from file
-    pub fn read(
-        proof_path: &Option<PathBuf>,
-        program_path: &Path,
-    ) -> Result<ExecutionProof, String> {
-        // If proof_path has been provided then use this as path.  Alternatively we will
-        // replace the program_path extension with `.proof` and use this as a default.
-        let path = match proof_path {
-            Some(path) => path.clone(),
-            None => program_path.with_extension("proof"),
-        };
+impl NumberFormat {
+    /// Formats a single integer.
+    pub fn format(&self, value: u64) -> String {
+        match self {
+            Self::Dec => value.to_string(),
+            Self::Hex => format!("{value:#x}"),
+        }
+    }
 
-        println!("Reading proof file `{}`", path.display());
+    /// Formats a list of integers as `[a, b, ...]`.
+    pub fn format_values(&self, values: &[u64]) -> String {
+        let values = values.iter().map(|&value| self.format(value)).collect::<Vec<_>>();
+        format!("[{}]", values.join(", "))
+    }
 
-        // read the file to bytes
-        let file = fs::read(&path)
-            .map_err(|err| format!("Failed to open proof file `{}` - {}", path.display(), err))?;
+    /// Formats a word, such as the root of a Merkle tree.
+    pub fn format_word(&self, word: &RpoDigest) -> String {
+        match self {
+            Self::Dec => {
+                let elements = word.as_elements().iter().map(|e| e.as_int()).collect::<Vec<_>>();
+                self.format_values(&elements)
+            }
+            Self::Hex => word.to_string(),
+        }
+    }
+}
 
-        // deserialize bytes into a stark proof
-        ExecutionProof::from_bytes(&file)
-            .map_err(|err| format!("Failed to decode proof data - {}", err))
+/// Parses an integer written in either of the [NumberFormat]s.
+fn parse_number(value: &str) -> Result<u64, String> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse::<u64>(),
     }
+    .map_err(|err| format!("Invalid number {value} - {err}"))
+}
 
-    /// Write stark proof to file
-    pub fn write(
-        proof: ExecutionProof,
+// JSON FORMAT
+// ================================================================================================
+
+/// Layout of the JSON documents written by the CLI.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// Indented over multiple lines, with the keys of objects in the order of the fields of the
+    /// serialized structs.
+    #[default]
+    Pretty,
+    /// On a single line, with the keys of objects in the order of the fields of the serialized
+    /// structs.
+    Compact,
+    /// On a single line without any whitespace, with the keys of all objects sorted; serializing
+    /// the same values always yields the same bytes.
+    Canonical,
+}
+
+impl JsonFormat {
+    /// Returns the canonical format if `canonical` is set, and this format otherwise.
+    pub fn or_canonical(self, canonical: bool) -> Self {
+        if canonical {
+            Self::Canonical
+        } else {
+            self
+        }
+    }
+
+    /// Serializes the specified value as JSON in this format.
+    pub fn to_string<T: serde::Serialize>(self, value: &T) -> Result<String, serde_json::Error> {
+        match self {
+            Self::Pretty => serde_json::to_string_pretty(value),
+            Self::Compact => serde_json::to_string(value),
+            Self::Canonical => serde_json::to_string(&sort_keys(serde_json::to_value(value)?)),
+        }
+    }
+}
+
+/// Returns the specified JSON value with the keys of all its objects sorted.
+///
+/// The entries are re-inserted in sorted order, so that the keys are sorted regardless of whether
+/// JSON objects preserve the insertion order of their keys.
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let map = entries.into_iter().map(|(key, value)| (key, sort_keys(value))).collect();
+            serde_json::Value::Object(map)
+        }
+        serde_json::Value::Array(values) => {
+            serde_json::Value::Array(values.into_iter().map(sort_keys).collect())
+        }
+        value => value,
+    }
+}
+
+// OUTPUT FILE
+// ================================================================================================
+
+/// Format in which output files are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable JSON.
+    #[default]
+    Json,
+    /// Compact binary MessagePack.
+    Msgpack,
+}
+
+/// Way in which the outputs of a run are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Emit {
+    /// Write a JSON output file.
+    Json,
+    /// Write a MessagePack output file.
+    Msgpack,
+    /// Print the values on the stack to stdout, one per line.
+    Stdout,
+    /// Do not emit the outputs.
+    None,
+}
+
+impl Emit {
+    /// Returns the extension of the output file written in this format, or None if this format
+    /// does not write a file.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::Json => Some("outputs"),
+            Self::Msgpack => Some("msgpack"),
+            Self::Stdout | Self::None => None,
+        }
+    }
+}
+
+impl From<OutputFormat> for Emit {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Json => Self::Json,
+            OutputFormat::Msgpack => Self::Msgpack,
+        }
+    }
+}
+
+/// Output file struct
+#[derive(Deserialize, Serialize, Debug)]
+pub struct OutputFile {
+    #[serde(with = "number_values")]
+    pub stack: Vec<String>,
+    #[serde(with = "number_values")]
+    pub overflow_addrs: Vec<String>,
+    /// The stack values keyed by their labels, present only if output labels were declared; the
+    /// values are not read back, as they duplicate the `stack` values.
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub labeled_stack: Option<LabeledStack>,
+}
+
+/// Helper methods to interact with the output file
+impl OutputFile {
+    /// Returns a new [OutputFile] from the specified outputs vectors.
+    ///
+    /// Overflow addresses are emitted in the order defined by [StackOutputs::overflow_addrs()]:
+    /// the `prev` address of the row representing the deepest stack element first, followed by the
+    /// address of each overflow table row from the deepest element to the most recently added one.
+    /// This order is required to reconstruct the overflow table, and it is fully determined by the
+    /// execution, so identical runs produce byte-identical output files.
+    pub fn new(stack_outputs: &StackOutputs) -> Self {
+        Self::with_number_format(stack_outputs, NumberFormat::Dec)
+    }
+
+    /// Returns a new [OutputFile] from the specified outputs vectors, with the values written in
+    /// the specified format.
+    pub fn with_number_format(stack_outputs: &StackOutputs, number_format: NumberFormat) -> Self {
+        let format = |values: &[u64]| values.iter().map(|&v| number_format.format(v)).collect();
+        Self {
+            stack: format(stack_outputs.stack()),
+            overflow_addrs: format(stack_outputs.overflow_addrs()),
+            labeled_stack: None,
+        }
+    }
+
+    /// Returns this output file with the stack values additionally keyed by the specified labels,
+    /// if any.
+    pub fn with_labels(mut self, labels: Option<&OutputLabels>) -> Self {
+        self.labeled_stack = labels.map(|labels| labels.label(&self.stack));
+        self
+    }
+
+    /// Read the output file
+    pub fn read(outputs_path: &Option<PathBuf>, program_path: &Path) -> Result<Self, String> {
+        // If outputs_path has been provided then use this as path.  Alternatively we will
+        // replace the program_path extension with `.outputs` and use this as a default.
+        let path = match outputs_path {
+            Some(path) => path.clone(),
+            None => program_path.with_extension("outputs"),
+        };
+
+        // MessagePack output files are detected by their extension
+        if path.extension().is_some_and(|ext| ext == "msgpack") {
+            return Self::read_msgpack(&path);
+        }
+
+        statusln!("Reading output file `{}`", path.display());
+
+        // read outputs file to string
+        let outputs_file = fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to open outputs file `{}` - {}", path.display(), err))?;
+
+        // deserialize outputs data
+        let outputs: OutputFile = serde_json::from_str(&outputs_file)
+            .map_err(|err| format!("Failed to deserialize outputs data - {}", err))?;
+
+        Ok(outputs)
+    }
+
+    /// Write the output file laid out in the specified JSON format
+    pub fn write(
+        stack_outputs: &StackOutputs,
+        path: &Path,
+        json_format: JsonFormat,
+    ) -> Result<(), String> {
+        Self::write_with_number_format(stack_outputs, path, NumberFormat::Dec, json_format)
+    }
+
+    /// Write the output file with the values written in the specified number format, and the
+    /// file laid out in the specified JSON format.
+    pub fn write_with_number_format(
+        stack_outputs: &StackOutputs,
+        path: &Path,
+        number_format: NumberFormat,
+        json_format: JsonFormat,
+    ) -> Result<(), String> {
+        Self::with_number_format(stack_outputs, number_format).write_json(path, json_format)
+    }
+
+    /// Write this output file laid out in the specified JSON format.
+    pub fn write_json(&self, path: &Path, json_format: JsonFormat) -> Result<(), String> {
+        // if path provided, create output file
+        statusln!("Creating output file `{}`", path.display());
+
+        statusln!("Writing data to output file");
+
+        // write outputs to output file
+        let json = self.to_json(json_format)?;
+        write_atomic(path, |file| file.write_all(json.as_bytes()))
+    }
+
+    /// Returns this output file serialized as JSON in the specified format, which is the way in
+    /// which output files are written.
+    pub fn to_json(&self, json_format: JsonFormat) -> Result<String, String> {
+        json_format
+            .to_string(self)
+            .map_err(|err| format!("Failed to serialize outputs data - {}", err))
+    }
+
+    /// Read the output file encoded in the MessagePack format.
+    pub fn read_msgpack(path: &Path) -> Result<Self, String> {
+        statusln!("Reading output file `{}`", path.display());
+
+        let bytes = fs::read(path)
+            .map_err(|err| format!("Failed to open outputs file `{}` - {}", path.display(), err))?;
+
+        rmp_serde::from_slice(&bytes)
+            .map_err(|err| format!("Failed to deserialize outputs data - {}", err))
+    }
+
+    /// Write the output file in the MessagePack format.
+    ///
+    /// The file contains a map with `stack` and `overflow_addrs` keys, each mapped to an array of
+    /// unsigned integers. This is considerably more compact than the JSON format, in which the
+    /// values are stored as strings.
+    pub fn write_msgpack(stack_outputs: &StackOutputs, path: &Path) -> Result<(), String> {
+        statusln!("Creating output file `{}`", path.display());
+
+        let bytes = rmp_serde::to_vec_named(&Self::new(stack_outputs))
+            .map_err(|err| format!("Failed to serialize outputs data - {}", err))?;
+        write_atomic(path, |file| file.write_all(&bytes))
+    }
+
+    /// Converts outputs vectors for stack and overflow addresses to [StackOutputs].
+    ///
+    /// The values may be written in either of the [NumberFormat]s.
+    pub fn stack_outputs(&self) -> Result<StackOutputs, String> {
+        let stack = self.stack.iter().map(|v| parse_number(v).unwrap()).collect::<Vec<u64>>();
+
+        let overflow_addrs = self
+            .overflow_addrs
+            .iter()
+            .map(|v| parse_number(v).unwrap())
+            .collect::<Vec<u64>>();
+
+        StackOutputs::new(stack, overflow_addrs)
+            .map_err(|e| format!("Construct stack outputs failed {e}"))
+    }
+}
+
+/// Serialization of the values of output files, which are written as strings in human-readable
+/// formats (i.e., JSON), and as unsigned integers in binary formats (i.e., MessagePack).
+mod number_values {
+    use super::parse_number;
+    use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return values.serialize(serializer);
+        }
+        let values = values
+            .iter()
+            .map(|value| parse_number(value))
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(S::Error::custom)?;
+        values.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        if deserializer.is_human_readable() {
+            return Vec::<String>::deserialize(deserializer);
+        }
+        let values = Vec::<u64>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|value| value.to_string()).collect())
+    }
+}
+
+// OUTPUT LABELS
+// ================================================================================================
+
+/// Names of the stack output positions, top of the stack first, used to key the stack values of
+/// JSON output files.
+///
+/// Labels are declared either in a sidecar JSON file of the form `{ "stack": ["sum", "carry"] }`,
+/// or in the program itself via a comment of the form `# output_labels: [sum, carry]`. An empty
+/// label or `_` leaves a position unlabeled, in which case its value is keyed by its index.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutputLabels {
+    stack: Vec<String>,
+}
+
+impl OutputLabels {
+    /// Returns the output labels of the program at the specified path.
+    ///
+    /// The labels are read from the specified labels file if one was provided, and otherwise from
+    /// the `.labels` file next to the program if one exists. If neither of them is present, the
+    /// labels are read from an `output_labels` comment in the program. Returns None if no labels
+    /// were declared.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be read or parsed, or if a label is declared twice.
+    pub fn read(
+        labels_path: &Option<PathBuf>,
+        program_path: &Path,
+    ) -> Result<Option<Self>, String> {
+        let sidecar_path = program_path.with_extension("labels");
+        let path = match labels_path {
+            Some(path) => Some(path.clone()),
+            None if sidecar_path.exists() => Some(sidecar_path),
+            None => None,
+        };
+
+        let labels = match path {
+            Some(path) => {
+                statusln!("Reading output labels file `{}`", path.display());
+                let labels_file = fs::read_to_string(&path).map_err(|err| {
+                    format!("Failed to open output labels file `{}` - {}", path.display(), err)
+                })?;
+                serde_json::from_str(&labels_file)
+                    .map(Some)
+                    .map_err(|err| format!("Failed to deserialize output labels - {}", err))?
+            }
+            None => {
+                let source = fs::read_to_string(program_path).map_err(|err| {
+                    format!("Failed to open program file `{}` - {}", program_path.display(), err)
+                })?;
+                Self::from_source(&source)?
+            }
+        };
+
+        if let Some(labels) = &labels {
+            labels.check_unique()?;
+        }
+        Ok(labels)
+    }
+
+    /// Returns the labels declared by an `output_labels` comment in the specified program source,
+    /// or None if the program does not declare any.
+    ///
+    /// # Errors
+    /// Returns an error if the labels are not enclosed in brackets.
+    pub fn from_source(source: &str) -> Result<Option<Self>, String> {
+        let declaration = source.lines().find_map(|line| {
+            let comment = line.trim().strip_prefix('#')?.trim_start_matches('!').trim();
+            comment.strip_prefix("output_labels:").map(str::trim)
+        });
+        let Some(declaration) = declaration else {
+            return Ok(None);
+        };
+
+        let labels = declaration
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(|| format!("output labels `{declaration}` must be enclosed in brackets"))?;
+        let stack = labels.split(',').map(|label| label.trim().to_string()).collect::<Vec<_>>();
+        let labels = Self { stack };
+        labels.check_unique()?;
+        Ok(Some(labels))
+    }
+
+    /// Returns the specified stack values keyed by the labels of their positions; the values of
+    /// unlabeled positions are keyed by their index.
+    pub fn label(&self, stack: &[String]) -> LabeledStack {
+        let entries = stack
+            .iter()
+            .enumerate()
+            .map(|(idx, value)| {
+                let key = match self.stack.get(idx).map(String::as_str) {
+                    Some("") | Some("_") | None => idx.to_string(),
+                    Some(label) => label.to_string(),
+                };
+                (key, value.clone())
+            })
+            .collect();
+        LabeledStack(entries)
+    }
+
+    /// Returns an error if a label is declared more than once, or if it clashes with the index
+    /// key of another position.
+    fn check_unique(&self) -> Result<(), String> {
+        let mut seen = BTreeMap::new();
+        for (idx, label) in self.stack.iter().enumerate() {
+            if label.is_empty() || label == "_" {
+                continue;
+            }
+            if let Ok(other) = label.parse::<usize>() {
+                if other != idx {
+                    return Err(format!("output label `{label}` clashes with position {other}"));
+                }
+            }
+            if let Some(other) = seen.insert(label.as_str(), idx) {
+                return Err(format!(
+                    "output label `{label}` is declared for positions {other} and {idx}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Stack values keyed by their labels, serialized as a JSON object whose keys are in stack order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabeledStack(Vec<(String, String)>);
+
+impl serde::Serialize for LabeledStack {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(label, value)| (label, value)))
+    }
+}
+
+// TRACE FILE
+// ================================================================================================
+
+/// State of the VM after executing a single cycle, as written to a JSON Lines trace file.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TraceRow {
+    /// Clock cycle at which the operation was executed.
+    pub cycle: u32,
+    /// Execution context ID.
+    pub ctx: u32,
+    /// Executed VM operation.
+    pub op: Option<String>,
+    /// Assembly instruction the operation belongs to (available only in debug mode).
+    pub asmop: Option<String>,
+    /// Value of the free memory pointer.
+    pub fmp: u64,
+    /// Contents of the operand stack (top element first).
+    pub stack: Vec<u64>,
+}
+
+impl TraceRow {
+    /// Returns the state of the specified process right after the specified operation was
+    /// executed as part of the specified assembly instruction, if any.
+    pub fn new<S: ProcessState>(process: &S, op: Operation, instruction: Option<&str>) -> Self {
+        Self {
+            cycle: process.clk(),
+            ctx: process.ctx(),
+            op: Some(op.to_string()),
+            asmop: instruction.map(|instruction| instruction.to_string()),
+            fmp: process.fmp().as_int(),
+            stack: process.get_stack_state().iter().map(|value| value.as_int()).collect(),
+        }
+    }
+}
+
+/// JSON Lines trace file to which the state of the VM is written after each cycle.
+pub struct TraceFile {
+    file: fs::File,
+    canonical: bool,
+    num_lines: usize,
+}
+
+/// Helper methods to interact with the JSON Lines trace file
+impl TraceFile {
+    /// Creates the trace file at the specified path. Unless `canonical` is set, the keys of each
+    /// object written to the file are in the order of the fields of [TraceRow].
+    pub fn create(path: &Path, canonical: bool) -> Result<Self, String> {
+        statusln!("Creating trace file `{}`", path.display());
+
+        let file = fs::File::create(path)
+            .map_err(|err| format!("Failed to create trace file `{}` - {}", path.display(), err))?;
+        Ok(Self {
+            file,
+            canonical,
+            num_lines: 0,
+        })
+    }
+
+    /// Writes the specified state of the VM to the file as a single JSON object per line.
+    ///
+    /// Each line is flushed as soon as it is written so that the file can be consumed while it is
+    /// being generated.
+    pub fn write(&mut self, row: &TraceRow) -> Result<(), String> {
+        let line = JsonFormat::Compact
+            .or_canonical(self.canonical)
+            .to_string(row)
+            .map_err(|err| format!("Failed to serialize VM state - {}", err))?;
+        writeln!(self.file, "{line}")
+            .and_then(|_| self.file.flush())
+            .map_err(|err| format!("Failed to write trace file - {}", err))?;
+        self.num_lines += 1;
+        Ok(())
+    }
+
+    /// Returns the number of lines written to the file.
+    pub fn num_lines(&self) -> usize {
+        self.num_lines
+    }
+}
+
+// EVENTS FILE
+// ================================================================================================
+
+/// Trace event emitted by a program via the `trace` instruction.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceEvent {
+    /// Clock cycle at which the event was emitted.
+    pub cycle: u32,
+    /// ID of the emitted event.
+    pub id: u32,
+}
+
+pub struct EventsFile;
+
+/// Helper methods to interact with the events file
+impl EventsFile {
+    /// Writes the specified trace events, in the order in which they were emitted, as a JSON array
+    /// laid out in the specified format to the file at the specified path.
+    pub fn write(
+        events: &[TraceEvent],
+        path: &Path,
+        json_format: JsonFormat,
+    ) -> Result<(), String> {
+        statusln!("Writing {} trace events to file `{}`", events.len(), path.display());
+
+        let json = json_format
+            .to_string(&events)
+            .map_err(|err| format!("Failed to serialize trace events - {}", err))?;
+        write_atomic(path, |file| file.write_all(json.as_bytes()))
+    }
+}
+
+// PROOF OPTIONS FILE
+// ================================================================================================
+
+/// Proof options file struct that is used to deserialize STARK proof parameters from a JSON file.
+///
+/// All parameters are optional; parameters which are not specified keep the values of the
+/// security preset the options are applied to.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ProofOptionsFile {
+    /// Number of queries for a STARK proof.
+    pub num_queries: Option<usize>,
+    /// Blowup factor of the low-degree extension; must be a power of two between 2 and 128.
+    pub blowup_factor: Option<usize>,
+    /// Query seed grinding factor; must not be greater than 32.
+    pub grinding_factor: Option<u32>,
+    /// Field extension used for the composition polynomial: `none`, `quadratic`, or `cubic`.
+    pub field_extension: Option<String>,
+    /// FRI folding factor; must be one of 2, 4, 8, or 16.
+    pub fri_folding_factor: Option<usize>,
+    /// Maximum degree of the FRI remainder polynomial; must be one less than a power of two and
+    /// not greater than 255.
+    pub fri_remainder_max_degree: Option<usize>,
+    /// Hash function used for proof generation: `blake3_192`, `blake3_256` (or `blake3`), or
+    /// `rpo256` (or `rpo`).
+    pub hash_fn: Option<String>,
+}
+
+/// Helper methods to interact with the proof options file
+impl ProofOptionsFile {
+    /// Reads the proof options file at the specified path.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        statusln!("Reading proof options file `{}`", path.display());
+
+        let options_file = fs::read_to_string(path).map_err(|err| {
+            format!("Failed to open proof options file `{}` - {}", path.display(), err)
+        })?;
+
+        serde_json::from_str(&options_file)
+            .map_err(|err| format!("Failed to deserialize proof options - {}", err))
+    }
+
+    /// Returns the provided proving options with the parameters specified in this file applied
+    /// on top of them.
+    ///
+    /// # Errors
+    /// Returns an error if any of the resulting parameters is invalid.
+    pub fn apply(&self, options: ProvingOptions) -> Result<ProvingOptions, String> {
+        let base = &options.proof_options;
+        let num_queries = self.num_queries.unwrap_or(base.num_queries());
+        let blowup_factor = self.blowup_factor.unwrap_or(base.blowup_factor());
+        let grinding_factor = self.grinding_factor.unwrap_or(base.grinding_factor());
+        let fri_options = base.to_fri_options();
+        let fri_folding_factor = self.fri_folding_factor.unwrap_or(fri_options.folding_factor());
+        let fri_remainder_max_degree =
+            self.fri_remainder_max_degree.unwrap_or(fri_options.remainder_max_degree());
+
+        let field_extension = match self.field_extension.as_deref() {
+            None => base.field_extension(),
+            Some("none") => FieldExtension::None,
+            Some("quadratic") => FieldExtension::Quadratic,
+            Some("cubic") => FieldExtension::Cubic,
+            Some(other) => {
+                return Err(format!("Invalid proof options - unknown field extension `{other}`"))
+            }
+        };
+        let hash_fn = match self.hash_fn.as_deref() {
+            None => options.hash_fn(),
+            Some("blake3_192") => HashFunction::Blake3_192,
+            Some("blake3_256" | "blake3") => HashFunction::Blake3_256,
+            Some("rpo256" | "rpo") => HashFunction::Rpo256,
+            Some("poseidon") => {
+                return Err("Invalid proof options - the Poseidon hash function is not supported \
+                    by the prover; use `blake3_192`, `blake3_256`, or `rpo256`"
+                    .to_string())
+            }
+            Some(other) => {
+                return Err(format!("Invalid proof options - unknown hash function `{other}`"))
+            }
+        };
+
+        // validate the parameters upfront as the proof options constructor panics on invalid
+        // parameters
+        if num_queries == 0 || num_queries > 255 {
+            return Err(format!(
+                "Invalid proof options - number of queries must be between 1 and 255, but was \
+                {num_queries}"
+            ));
+        }
+        if !blowup_factor.is_power_of_two() || !(2..=128).contains(&blowup_factor) {
+            return Err(format!(
+                "Invalid proof options - blowup factor must be a power of two between 2 and 128, \
+                but was {blowup_factor}"
+            ));
+        }
+        if grinding_factor > 32 {
+            return Err(format!(
+                "Invalid proof options - grinding factor cannot be greater than 32, but was \
+                {grinding_factor}"
+            ));
+        }
+        if ![2, 4, 8, 16].contains(&fri_folding_factor) {
+            return Err(format!(
+                "Invalid proof options - FRI folding factor must be one of 2, 4, 8, or 16, but was \
+                {fri_folding_factor}"
+            ));
+        }
+        if fri_remainder_max_degree > 255 || !(fri_remainder_max_degree + 1).is_power_of_two() {
+            return Err(format!(
+                "Invalid proof options - FRI remainder max degree must be one less than a power of \
+                two and not greater than 255, but was {fri_remainder_max_degree}"
+            ));
+        }
+
+        Ok(ProvingOptions::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_remainder_max_degree,
+            hash_fn,
+        )
+        .with_execution_options(options.exec_options))
+    }
+}
+
+// ERROR REPORT
+// ================================================================================================
+
+/// Format in which errors are reported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Human-readable error messages.
+    #[default]
+    Text,
+    /// A single JSON object describing the error.
+    Json,
+}
+
+impl ErrorFormat {
+    /// Formats the specified error report; if `canonical` is set, JSON reports are written in the
+    /// canonical JSON format.
+    pub fn format(&self, report: ErrorReport, canonical: bool) -> String {
+        match self {
+            Self::Text => report.message,
+            Self::Json => report.to_json(JsonFormat::Compact.or_canonical(canonical)),
+        }
+    }
+}
+
+/// Machine-readable description of an error.
+///
+/// The kind of an error is the name of the variant of [ParsingError], [AssemblyError], or
+/// [ExecutionError] which caused it, or `Error` for all other errors. The clock cycle and the
+/// source location are included only where the underlying error provides them; the operand stack
+/// is included only if the state of the VM at the point of failure was requested.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    pub kind: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycle: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<u64>>,
+}
+
+impl ErrorReport {
+    /// Returns a report of an error which failed to parse a program with the specified message.
+    pub fn from_parsing_error(message: String, err: &ParsingError) -> Self {
+        Self {
+            kind: "ParsingError".to_string(),
+            message,
+            cycle: None,
+            line: Some(err.location().line()),
+            column: Some(err.location().column()),
+            stack: None,
+        }
+    }
+
+    /// Returns a report of an error which failed to compile a program with the specified message.
+    pub fn from_assembly_error(message: String, err: &AssemblyError) -> Self {
+        Self {
+            kind: variant_name(err),
+            message,
+            cycle: None,
+            line: None,
+            column: None,
+            stack: None,
+        }
+    }
+
+    /// Returns a report of an error which failed to execute a program with the specified message.
+    pub fn from_execution_error(message: String, err: &ExecutionError) -> Self {
+        Self {
+            kind: variant_name(err),
+            message,
+            cycle: execution_error_cycle(err),
+            line: None,
+            column: None,
+            stack: None,
+        }
+    }
+
+    /// Returns this report extended with the state of the VM at the point where the execution
+    /// failed; the clock cycle and the operand stack are appended to the message, and the values
+    /// in the message are printed in the specified format.
+    pub fn with_trap_state(mut self, state: &TrapState, number_format: NumberFormat) -> Self {
+        let stack = state.stack.iter().map(|value| value.as_int()).collect::<Vec<_>>();
+        self.message = format!(
+            "{}\nState at trap:\n├── clk: {}\n└── stack: {}",
+            self.message,
+            state.clk,
+            number_format.format_values(&stack)
+        );
+        self.cycle = self.cycle.or(Some(state.clk));
+        self.stack = Some(stack);
+        self
+    }
+
+    /// Serializes this report into a single-line JSON object in the specified format.
+    pub fn to_json(&self, json_format: JsonFormat) -> String {
+        json_format.to_string(self).expect("error report is always serializable")
+    }
+}
+
+impl From<String> for ErrorReport {
+    fn from(message: String) -> Self {
+        Self {
+            kind: "Error".to_string(),
+            message,
+            cycle: None,
+            line: None,
+            column: None,
+            stack: None,
+        }
+    }
+}
+
+impl From<ErrorReport> for String {
+    fn from(report: ErrorReport) -> Self {
+        report.message
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Returns the cycle at which the specified error occurred, if the error records one.
+pub fn execution_error_cycle(err: &ExecutionError) -> Option<u32> {
+    match err {
+        ExecutionError::AdviceReadLimitExceeded { clk, .. }
+        | ExecutionError::AdviceStackReadFailed(clk)
+        | ExecutionError::DivideByZero(clk)
+        | ExecutionError::ExecutionAborted { clk, .. }
+        | ExecutionError::FailedAssertion(clk, _)
+        | ExecutionError::MaxCallDepthExceeded { clk, .. }
+        | ExecutionError::StackError(StackError::Underflow { clk, .. }) => Some(*clk),
+        _ => None,
+    }
+}
+
+/// Returns the name of the enum variant of the specified error, as printed by its [fmt::Debug]
+/// implementation.
+fn variant_name<E: fmt::Debug>(err: &E) -> String {
+    format!("{err:?}")
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect()
+}
+
+// PROGRAM FILE
+// ================================================================================================
+
+/// Magic bytes at the start of compiled program files which contain a format version header.
+const COMPILED_PROGRAM_MAGIC: [u8; 4] = *b"MASB";
+
+/// Current version of the format of compiled program files.
+///
+/// The header of compiled program files consists of [COMPILED_PROGRAM_MAGIC], a single format
+/// version byte, and the version of the crate which wrote the file, prefixed with its length in
+/// a single byte; the header is followed by the serialized [ProgramAst]. Files without a header
+/// were written by versions of the CLI which did not version the format, and are treated as
+/// format version 0.
+const COMPILED_PROGRAM_VERSION: u8 = 1;
+
+pub struct ProgramFile {
+    ast: ProgramAst,
+    path: PathBuf,
+}
+
+/// Helper methods to interact with masm program file.
+impl ProgramFile {
+    /// Reads the masm file at the specified path and parses it into a [ProgramAst].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, or if it cannot be parsed; in the latter case,
+    /// the report includes the location of the parsing error.
+    pub fn read(path: &PathBuf) -> Result<Self, ErrorReport> {
+        // read program file to string
+        statusln!("Reading program file `{}`", path.display());
+        let source = fs::read_to_string(&path)
+            .map_err(|err| format!("Failed to open program file `{}` - {}", path.display(), err))?;
+
+        // parse the program into an AST
+        status!("Parsing program... ");
+        let now = Instant::now();
+        let ast = ProgramAst::parse(&source).map_err(|err| {
+            let message = format!("Failed to parse program file `{}` - {}", path.display(), err);
+            ErrorReport::from_parsing_error(message, &err)
+        })?;
+        statusln!("done ({} ms)", now.elapsed().as_millis());
+
+        Ok(Self {
+            ast,
+            path: path.clone(),
+        })
+    }
+
+    /// Reads the compiled (.masb) file at the specified path, as written by [ProgramFile::write],
+    /// and deserializes it into a [ProgramAst].
+    ///
+    /// Files written in an older format are upgraded in memory if their program can still be
+    /// deserialized.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or deserialized. If the file was written in a
+    /// format other than the current one, the error reports that the program must be recompiled,
+    /// naming the versions which wrote and which read the file.
+    pub fn read_compiled(path: &PathBuf) -> Result<Self, String> {
+        statusln!("Reading compiled program file `{}`", path.display());
+        let bytes = fs::read(path).map_err(|err| {
+            format!("Failed to open compiled program file `{}` - {}", path.display(), err)
+        })?;
+
+        let (version, written_by, ast_bytes) = split_compiled_header(&bytes).map_err(|err| {
+            format!("Failed to read compiled program file `{}` - {}", path.display(), err)
+        })?;
+        let recompile_required = || {
+            let written_by = match &written_by {
+                Some(written_by) => format!("miden {written_by}"),
+                None => "an older version of miden".to_string(),
+            };
+            format!(
+                "Compiled program file `{}` was written by {written_by} (format version {version}), \
+                but this is miden {} (format version {COMPILED_PROGRAM_VERSION}); recompile \
+                required - compile the program from source again using `miden compile`",
+                path.display(),
+                env!("CARGO_PKG_VERSION"),
+            )
+        };
+        if version > COMPILED_PROGRAM_VERSION {
+            return Err(recompile_required());
+        }
+
+        let ast = ProgramAst::from_bytes(ast_bytes).map_err(|err| {
+            if version < COMPILED_PROGRAM_VERSION {
+                recompile_required()
+            } else {
+                format!(
+                    "Failed to deserialize compiled program file `{}` - {}",
+                    path.display(),
+                    err
+                )
+            }
+        })?;
+
+        Ok(Self {
+            ast,
+            path: path.clone(),
+        })
+    }
+
+    /// Returns the AST of this program.
+    pub fn ast(&self) -> &ProgramAst {
+        &self.ast
+    }
+
+    /// Checks that the program does not exceed the specified number of instructions and local
+    /// procedures.
+    ///
+    /// Instructions are counted across the program body and all local procedures; control flow
+    /// statements count as a single instruction each, and bodies of `repeat` blocks are counted
+    /// once regardless of the number of iterations.
+    ///
+    /// # Errors
+    /// Returns an error if either of the limits is exceeded.
+    pub fn check_size(&self, max_instructions: usize, max_procedures: usize) -> Result<(), String> {
+        let num_procedures = self.ast.procedures().len();
+        if num_procedures > max_procedures {
+            return Err(format!(
+                "Program file `{}` defines {} procedures, but at most {} are allowed",
+                self.path.display(),
+                num_procedures,
+                max_procedures
+            ));
+        }
+
+        let num_instructions = self
+            .ast
+            .procedures()
+            .iter()
+            .map(|proc| &proc.body)
+            .chain([self.ast.body()])
+            .map(count_instructions)
+            .sum::<usize>();
+        if num_instructions > max_instructions {
+            return Err(format!(
+                "Program file `{}` contains {} instructions, but at most {} are allowed",
+                self.path.display(),
+                num_instructions,
+                max_instructions
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the compiled file of this program, as written by [ProgramFile::write], does
+    /// not exceed the specified number of bytes.
+    ///
+    /// # Errors
+    /// Returns an error reporting the actual and the allowed size if the limit is exceeded.
+    pub fn check_compiled_size(&self, max_size: usize) -> Result<(), String> {
+        let size = self.to_compiled_bytes().len();
+        if size > max_size {
+            return Err(format!(
+                "Compiled program `{}` is {} bytes, but at most {} bytes are allowed",
+                self.path.display(),
+                size,
+                max_size
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Compiles this program file into a [Program].
+    ///
+    /// If the standard library is pinned via [Libraries::with_stdlib_digest], compilation fails
+    /// when the digest of the standard library differs from the pinned one.
+    pub fn compile(&self, debug: &Debug, libraries: Libraries) -> Result<Program, ErrorReport> {
+        self.compile_with_stats(debug, Assembler::DEFAULT_OPT_LEVEL, libraries)
+            .map(|(program, _)| program)
+    }
+
+    /// Compiles this program file into a [Program] at the specified optimization level and
+    /// returns it together with statistics about the compilation.
+    pub fn compile_with_stats(
+        &self,
+        debug: &Debug,
+        opt_level: u8,
+        libraries: Libraries,
+    ) -> Result<(Program, CompileStats), ErrorReport> {
+        status!("Compiling program... ");
+        let now = Instant::now();
+        let _phase = Phase::Compile.enter();
+
+        // compile program
+        let stdlib = StdLibrary::default();
+        if let Some(expected) = libraries.stdlib_digest {
+            let actual = stdlib.digest();
+            if actual != expected {
+                return Err(format!(
+                    "Standard library hash {} does not match the expected hash {}",
+                    hex::encode(actual),
+                    hex::encode(expected)
+                )
+                .into());
+            }
+        }
+
+        let mut assembler = Assembler::default()
+            .with_debug_mode(debug.is_on())
+            .with_optimization_level(opt_level)
+            .with_library(&stdlib)
+            .map_err(|err| {
+                let message = format!("Failed to load stdlib - {}", err);
+                ErrorReport::from_assembly_error(message, &err)
+            })?;
+
+        assembler = assembler
+            .with_library_shadowing(libraries.allow_shadowing)
+            .with_libraries(libraries.libraries.into_iter())
+            .map_err(|err| {
+                let message = match &err {
+                    AssemblyError::LibraryLoadError(err) => {
+                        library_load_error_message(err, &stdlib)
+                    }
+                    err => format!("Failed to load libraries `{}`", err),
+                };
+                ErrorReport::from_assembly_error(message, &err)
+            })?;
+
+        let (program, stats) = assembler.compile_ast_with_stats(&self.ast).map_err(|err| {
+            let message = format!("Failed to compile program - {}", err);
+            ErrorReport::from_assembly_error(message, &err)
+        })?;
+
+        statusln!("done ({} ms)", now.elapsed().as_millis());
+
+        Ok((program, stats))
+    }
+
+    /// Writes this file into the specified path, if one is provided. If the path is not provided,
+    /// writes the file into the same directory as the source file, but with `.masb` extension.
+    pub fn write(&self, out_path: Option<PathBuf>) -> Result<(), String> {
+        let out_path = out_path.unwrap_or_else(|| {
+            let mut out_file = self.path.clone();
+            out_file.set_extension("masb");
+            out_file
+        });
+
+        if let Some(dir) = out_path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("Failed to write the compiled file: {err}"))?;
+        }
+
+        let bytes = self.to_compiled_bytes();
+        write_atomic(&out_path, |file| file.write_all(&bytes))
+            .map_err(|err| format!("Failed to write the compiled file: {err}"))
+    }
+
+    /// Returns the contents of the compiled file of this program.
+    fn to_compiled_bytes(&self) -> Vec<u8> {
+        let crate_version = env!("CARGO_PKG_VERSION");
+        let mut bytes = COMPILED_PROGRAM_MAGIC.to_vec();
+        bytes.push(COMPILED_PROGRAM_VERSION);
+        bytes.push(crate_version.len() as u8);
+        bytes.extend_from_slice(crate_version.as_bytes());
+        bytes.extend_from_slice(&self.ast.to_bytes(AstSerdeOptions {
+            serialize_imports: true,
+        }));
+        bytes
+    }
+}
+
+/// Splits the header off the provided compiled program file contents, returning the format
+/// version of the file and the version of the crate which wrote it.
+///
+/// Contents which do not start with [COMPILED_PROGRAM_MAGIC] are returned as is, with format
+/// version 0 and no crate version.
+fn split_compiled_header(bytes: &[u8]) -> Result<(u8, Option<String>, &[u8]), String> {
+    if !bytes.starts_with(&COMPILED_PROGRAM_MAGIC) {
+        return Ok((0, None, bytes));
+    }
+
+    let rest = &bytes[COMPILED_PROGRAM_MAGIC.len()..];
+    let (&version, rest) = rest.split_first().ok_or("unexpected end of file")?;
+    let (&len, rest) = rest.split_first().ok_or("unexpected end of file")?;
+    if rest.len() < len as usize {
+        return Err("unexpected end of file".to_string());
+    }
+    let (written_by, rest) = rest.split_at(len as usize);
+    let written_by = String::from_utf8_lossy(written_by).into_owned();
+
+    Ok((version, Some(written_by), rest))
+}
+
+/// Returns the number of instructions in the specified code body, including instructions in
+/// nested control flow blocks.
+fn count_instructions(body: &CodeBody) -> usize {
+    body.nodes()
+        .iter()
+        .map(|node| match node {
+            Node::Instruction(_) => 1,
+            Node::IfElse {
+                true_case,
+                false_case,
+            } => 1 + count_instructions(true_case) + count_instructions(false_case),
+            Node::Repeat { body, .. } | Node::While { body } => 1 + count_instructions(body),
+        })
+        .sum()
+}
+
+// PROOF FILE
+// ================================================================================================
+
+/// Magic bytes at the start of proof files which contain a metadata header.
+const PROOF_FILE_MAGIC: [u8; 4] = *b"MPRF";
+
+/// Current version of the proof file metadata header.
+const PROOF_FILE_VERSION: u8 = 1;
+
+/// Metadata header written at the start of proof files.
+///
+/// The header consists of [PROOF_FILE_MAGIC], a single version byte, and the time at which the
+/// proof file was created, encoded as a little-endian u64 number of seconds since the UNIX epoch.
+/// These are followed by the serialized stack outputs of the proven execution, the hash of the
+/// proven program, the serialized stack inputs of the execution, a single byte identifying the hash
+/// function used to generate the proof, and a single byte with the blowup factor of the proof. The
+/// header ends with the size of the serialized proof which follows it, encoded as a little-endian
+/// u64, which allows truncated proof files to be detected. Proof files without a header (i.e.,
+/// created by older versions of the CLI) are still accepted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofHeader {
+    timestamp: SystemTime,
+    stack_outputs: StackOutputs,
+    program_hash: Digest,
+    stack_inputs: StackInputs,
+    hash_fn: HashFunction,
+    blowup_factor: usize,
+    proof_size: usize,
+}
+
+impl ProofHeader {
+    /// Size of the fixed part of the serialized header in bytes.
+    const SIZE: usize = PROOF_FILE_MAGIC.len() + 1 + 8;
+
+    /// Returns a new header with the specified creation time, public inputs of the proven
+    /// execution, and hash function, blowup factor, and size in bytes of the serialized proof.
+    ///
+    /// # Panics
+    /// Panics if the blowup factor does not fit into a byte.
+    pub fn new(
+        timestamp: SystemTime,
+        program_hash: Digest,
+        stack_inputs: StackInputs,
+        stack_outputs: StackOutputs,
+        hash_fn: HashFunction,
+        blowup_factor: usize,
+        proof_size: usize,
+    ) -> Self {
+        assert!(blowup_factor <= u8::MAX as usize, "blowup factor {blowup_factor} is too large");
+        Self {
+            timestamp,
+            stack_outputs,
+            program_hash,
+            stack_inputs,
+            hash_fn,
+            blowup_factor,
+            proof_size,
+        }
+    }
+
+    /// Returns the time at which the proof file was created.
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+
+    /// Returns the stack outputs recorded when the proof file was created.
+    ///
+    /// The recorded outputs are not authenticated by the proof, and thus can only be used to
+    /// diagnose verification failures.
+    pub fn stack_outputs(&self) -> &StackOutputs {
+        &self.stack_outputs
+    }
+
+    /// Returns the hash of the proven program recorded when the proof file was created.
+    pub fn program_hash(&self) -> Digest {
+        self.program_hash
+    }
+
+    /// Returns the stack inputs recorded when the proof file was created.
+    ///
+    /// Similarly to the stack outputs, the recorded inputs are not authenticated by the proof.
+    pub fn stack_inputs(&self) -> &StackInputs {
+        &self.stack_inputs
+    }
+
+    /// Returns the hash function used to generate the proof.
+    pub fn hash_fn(&self) -> HashFunction {
+        self.hash_fn
+    }
+
+    /// Returns the blowup factor of the proof.
+    pub fn blowup_factor(&self) -> usize {
+        self.blowup_factor
+    }
+
+    /// Returns the size in bytes of the serialized proof which follows the header.
+    pub fn proof_size(&self) -> usize {
+        self.proof_size
+    }
+
+    /// Serializes this header into bytes; the timestamp is truncated to whole seconds.
+    fn to_bytes(&self) -> Vec<u8> {
+        let secs = self.timestamp.duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+
+        let mut bytes = Vec::with_capacity(Self::SIZE);
+        bytes.extend_from_slice(&PROOF_FILE_MAGIC);
+        bytes.push(PROOF_FILE_VERSION);
+        bytes.extend_from_slice(&secs.to_le_bytes());
+        self.stack_outputs.write_into(&mut bytes);
+        self.program_hash.write_into(&mut bytes);
+        self.stack_inputs.write_into(&mut bytes);
+        bytes.push(self.hash_fn as u8);
+        bytes.push(self.blowup_factor as u8);
+        bytes.extend_from_slice(&(self.proof_size as u64).to_le_bytes());
+        bytes
+    }
+
+    /// Splits the header off the provided proof file contents.
+    ///
+    /// Returns `None` as the header if the contents do not start with [PROOF_FILE_MAGIC].
+    ///
+    /// # Errors
+    /// Returns an error if the header is invalid, or if the contents end before the header does,
+    /// in which case the error reports that the file appears truncated.
+    fn split(bytes: &[u8]) -> Result<(Option<Self>, &[u8]), String> {
+        if !bytes.starts_with(&PROOF_FILE_MAGIC) {
+            return Ok((None, bytes));
+        }
+        if bytes.len() < Self::SIZE {
+            return Err(truncated_proof_file(Self::SIZE, bytes.len()));
+        }
+        // values which end past the end of the contents require at least one more byte
+        let decode_error = |value: &str, err: DeserializationError| match err {
+            DeserializationError::UnexpectedEOF => {
+                truncated_proof_file(bytes.len() + 1, bytes.len())
+            }
+            err => format!("Failed to decode {value} in proof file header - {err}"),
+        };
+
+        let version = bytes[PROOF_FILE_MAGIC.len()];
+        if version != PROOF_FILE_VERSION {
+            return Err(format!("Unsupported proof file version {version}"));
+        }
+
+        let secs = u64::from_le_bytes(
+            bytes[PROOF_FILE_MAGIC.len() + 1..Self::SIZE]
+                .try_into()
+                .expect("invalid slice length"),
+        );
+
+        // the public inputs are serialized with their lengths, so they are read with a reader
+        let mut reader = SliceReader::new(&bytes[Self::SIZE..]);
+        let stack_outputs = StackOutputs::read_from(&mut reader)
+            .map_err(|err| decode_error("stack outputs", err))?;
+        let program_hash =
+            Digest::read_from(&mut reader).map_err(|err| decode_error("program hash", err))?;
+        let stack_inputs =
+            StackInputs::read_from(&mut reader).map_err(|err| decode_error("stack inputs", err))?;
+        let mut header_size = Self::SIZE
+            + stack_outputs.to_bytes().len()
+            + program_hash.to_bytes().len()
+            + stack_inputs.to_bytes().len();
+
+        // the hash function and the blowup factor take a single byte each
+        let proof_params = bytes
+            .get(header_size..header_size + 2)
+            .ok_or_else(|| truncated_proof_file(header_size + 2, bytes.len()))?;
+        let hash_fn = HashFunction::try_from(proof_params[0]).map_err(|err| {
+            format!("Failed to decode hash function in proof file header - {err}")
+        })?;
+        let blowup_factor = proof_params[1] as usize;
+        header_size += 2;
+
+        let size_bytes = bytes
+            .get(header_size..header_size + 8)
+            .ok_or_else(|| truncated_proof_file(header_size + 8, bytes.len()))?;
+        let proof_size = u64::from_le_bytes(size_bytes.try_into().expect("invalid slice length"));
+        header_size += 8;
+
+        let header = Self {
+            timestamp: UNIX_EPOCH + Duration::from_secs(secs),
+            stack_outputs,
+            program_hash,
+            stack_inputs,
+            hash_fn,
+            blowup_factor,
+            proof_size: proof_size as usize,
+        };
+
+        Ok((Some(header), &bytes[header_size..]))
+    }
+}
+
+/// Encoding of the contents of a proof file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProofEncoding {
+    /// The header and the proof are written as raw bytes.
+    #[default]
+    Binary,
+    /// The bytes of the binary encoding are written as base64 text (with the standard alphabet
+    /// and padding), which can be embedded in text logs and JSON documents.
+    Base64,
+}
+
+impl ProofEncoding {
+    /// Extension appended to the names of base64-encoded proof files (e.g., `fib.proof.b64`).
+    pub const BASE64_EXTENSION: &'static str = "b64";
+
+    /// Returns the encoding of the proof file at the specified path: the specified encoding if
+    /// there is one, and otherwise base64 for files with the `.b64` extension and binary for all
+    /// other files.
+    pub fn resolve(encoding: Option<Self>, path: &Path) -> Self {
+        match encoding {
+            Some(encoding) => encoding,
+            None if path.extension().is_some_and(|ext| ext == Self::BASE64_EXTENSION) => {
+                Self::Base64
+            }
+            None => Self::Binary,
+        }
+    }
+
+    /// Returns the contents of a proof file with the specified bytes in this encoding.
+    fn encode(self, bytes: Vec<u8>) -> Vec<u8> {
+        match self {
+            Self::Binary => bytes,
+            Self::Base64 => {
+                let mut text = BASE64_STANDARD.encode(bytes);
+                text.push('\n');
+                text.into_bytes()
+            }
+        }
+    }
+
+    /// Returns the bytes of the proof file with the specified contents in this encoding.
+    ///
+    /// Whitespace in base64 text is ignored, so that proofs wrapped over several lines (e.g.,
+    /// when copied from a log) can be read as well.
+    fn decode(self, contents: Vec<u8>) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Binary => Ok(contents),
+            Self::Base64 => {
+                let text = contents
+                    .into_iter()
+                    .filter(|byte| !byte.is_ascii_whitespace())
+                    .collect::<Vec<_>>();
+                BASE64_STANDARD
+                    .decode(text)
+                    .map_err(|err| format!("Failed to decode base64 proof data - {err}"))
+            }
+        }
+    }
+}
+
+pub struct ProofFile;
+
+/// Helper methods to interact with proof file
+impl ProofFile {
+    /// Read stark proof from file
+    ///
+    /// Along with the proof, returns the metadata header of the file, if the file contains one.
+    pub fn read(
         proof_path: &Option<PathBuf>,
         program_path: &Path,
-    ) -> Result<(), String> {
-        // If proof_path has been provided then use this as path.  Alternatively we will
-        // replace the program_path extension with `.proof` and use this as a default.
-        let path = match proof_path {
-            Some(path) => path.clone(),
-            None => program_path.with_extension("proof"),
-        };
+    ) -> Result<(ExecutionProof, Option<ProofHeader>), String> {
+        Self::read_encoded(proof_path, program_path, None)
+    }
+
+    /// Read stark proof from file in the specified encoding, or in the encoding implied by the
+    /// extension of the file if no encoding is specified (see [ProofEncoding::resolve()])
+    ///
+    /// Along with the proof, returns the metadata header of the file, if the file contains one.
+    pub fn read_encoded(
+        proof_path: &Option<PathBuf>,
+        program_path: &Path,
+        encoding: Option<ProofEncoding>,
+    ) -> Result<(ExecutionProof, Option<ProofHeader>), String> {
+        let path = Self::path(proof_path, program_path, encoding);
+        let encoding = ProofEncoding::resolve(encoding, &path);
 
-        println!("Creating proof file `{}`", path.display());
+        statusln!("Reading proof file `{}`", path.display());
 
-        // create output fille
-        let mut file = fs::File::create(&path)
-            .map_err(|err| format!("Failed to create proof file `{}` - {}", path.display(), err))?;
+        // read the file to bytes
+        let file = fs::read(&path)
+            .map_err(|err| format!("Failed to open proof file `{}` - {}", path.display(), err))?;
+        let file = encoding.decode(file)?;
+
+        // split off the header and make sure the file contains the whole proof, so that truncated
+        // files (e.g., due to an interrupted transfer) are reported separately from corrupt ones
+        let (header, proof_bytes) = ProofHeader::split(&file)?;
+        if let Some(header) = &header {
+            if proof_bytes.len() < header.proof_size() {
+                let expected = file.len() - proof_bytes.len() + header.proof_size();
+                return Err(truncated_proof_file(expected, file.len()));
+            }
+        }
 
-        let proof_bytes = proof.to_bytes();
+        // deserialize the remaining bytes into a stark proof; files without a header are
+        // reported as truncated if they end before the proof does
+        let proof = ExecutionProof::from_bytes(proof_bytes).map_err(|err| match err {
+            DeserializationError::UnexpectedEOF => truncated_proof_file(file.len() + 1, file.len()),
+            err => format!("Failed to decode proof data - {}", err),
+        })?;
 
-        println!("Writing data to proof file - size {} KB", proof_bytes.len() / 1024);
+        // the proof must have been generated with the hash function and the blowup factor
+        // recorded in the header
+        if let Some(header) = &header {
+            if header.hash_fn() != proof.hash_fn() {
+                return Err(format!(
+                    "Proof file `{}` records the {:?} hash function, but the proof was generated \
+                    with {:?}",
+                    path.display(),
+                    header.hash_fn(),
+                    proof.hash_fn()
+                ));
+            }
 
-        // write proof bytes to file
-        file.write_all(&proof_bytes).unwrap();
+            let blowup_factor = proof.stark_proof().options().blowup_factor();
+            if header.blowup_factor() != blowup_factor {
+                return Err(format!(
+                    "Proof file `{}` records blowup factor {}, but the proof was generated with \
+                    blowup factor {blowup_factor}",
+                    path.display(),
+                    header.blowup_factor(),
+                ));
+            }
+        }
 
-        Ok(())
+        Ok((proof, header))
+    }
+
+    /// Write stark proof to file
+    ///
+    /// The provided public inputs of the proven execution (the program hash, and the stack inputs
+    /// and outputs), and the hash function and blowup factor of the proof are recorded in the file
+    /// header.
+    pub fn write(
+        proof: ExecutionProof,
+        program_hash: Digest,
+        stack_inputs: &StackInputs,
+        stack_outputs: &StackOutputs,
+        proof_path: &Option<PathBuf>,
+        program_path: &Path,
+    ) -> Result<(), String> {
+        Self::write_encoded(
+            proof,
+            program_hash,
+            stack_inputs,
+            stack_outputs,
+            proof_path,
+            program_path,
+            None,
+        )
+    }
+
+    /// Write stark proof to file in the specified encoding, or in the encoding implied by the
+    /// extension of the file if no encoding is specified (see [ProofEncoding::resolve()])
+    ///
+    /// The file header is recorded in the same way as by [ProofFile::write()].
+    pub fn write_encoded(
+        proof: ExecutionProof,
+        program_hash: Digest,
+        stack_inputs: &StackInputs,
+        stack_outputs: &StackOutputs,
+        proof_path: &Option<PathBuf>,
+        program_path: &Path,
+        encoding: Option<ProofEncoding>,
+    ) -> Result<(), String> {
+        let path = Self::path(proof_path, program_path, encoding);
+        let encoding = ProofEncoding::resolve(encoding, &path);
+
+        statusln!("Creating proof file `{}`", path.display());
+
+        let serialized_proof = proof.to_bytes();
+        let header = ProofHeader::new(
+            SystemTime::now(),
+            program_hash,
+            stack_inputs.clone(),
+            stack_outputs.clone(),
+            proof.hash_fn(),
+            proof.stark_proof().options().blowup_factor(),
+            serialized_proof.len(),
+        );
+        let mut proof_bytes = header.to_bytes();
+        proof_bytes.extend_from_slice(&serialized_proof);
+        let contents = encoding.encode(proof_bytes);
+
+        statusln!("Writing data to proof file - size {} KB", contents.len() / 1024);
+
+        // write header and proof bytes to file
+        write_atomic(&path, |file| file.write_all(&contents))
+    }
+
+    /// Returns the extension of proof files in the specified encoding: `proof` for binary
+    /// proofs, and `proof.b64` for base64 proofs.
+    pub fn extension(encoding: Option<ProofEncoding>) -> String {
+        match encoding {
+            Some(ProofEncoding::Base64) => format!("proof.{}", ProofEncoding::BASE64_EXTENSION),
+            _ => "proof".to_string(),
+        }
+    }
+
+    /// Returns the path of the proof file: the specified path if one has been provided, and
+    /// otherwise the path of the program with the extension of proof files in the specified
+    /// encoding.
+    fn path(
+        proof_path: &Option<PathBuf>,
+        program_path: &Path,
+        encoding: Option<ProofEncoding>,
+    ) -> PathBuf {
+        match proof_path {
+            Some(path) => path.clone(),
+            None => program_path.with_extension(Self::extension(encoding)),
+        }
+    }
+
+    /// Returns the path from which the paths of the files accompanying the specified proof file
+    /// (e.g., its inputs and outputs) are derived by replacing the extension; this is the path
+    /// of the proof file without the `.b64` extension of base64 proofs.
+    pub fn base_path(proof_path: &Path) -> PathBuf {
+        match proof_path.extension() {
+            Some(ext) if ext == ProofEncoding::BASE64_EXTENSION => proof_path.with_extension(""),
+            _ => proof_path.to_path_buf(),
+        }
     }
 }
 
+/// Returns the error reported for proof files which end before the expected number of bytes.
+fn truncated_proof_file(expected: usize, actual: usize) -> String {
+    format!("Proof file appears truncated (expected at least {expected} bytes, found {actual})")
+}
+
 // PROGRAM HASH
 // ================================================================================================
 
@@ -523,32 +2645,257 @@ impl ProgramHash {
 
         Ok(program_hash)
     }
+
+    /// Returns the hash of the specified program.
+    pub fn from_program(program: &Program) -> Digest {
+        program.hash()
+    }
+}
+
+// ARTIFACT NAME
+// ================================================================================================
+
+/// Name of the artifacts (proofs and outputs) generated for a program and a set of inputs.
+///
+/// The name has the form `<program hash>-<inputs hash>`, where both hashes are hex-encoded:
+/// - `<program hash>` is the hash of the compiled program, as reported by the CLI.
+/// - `<inputs hash>` is the BLAKE3-256 hash of the parsed inputs: the operand stack, the advice
+///   stack, the advice map entries ordered by key, the Merkle store data, and the entries of the
+///   advice map file (if any), in this order.
+///
+/// Since the inputs are hashed after parsing, the name doesn't depend on how the input files are
+/// formatted or on the order of advice map entries, but different inputs always produce different
+/// names. This allows proving the same program against many inputs without the artifacts
+/// overwriting each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactName(String);
+
+impl ArtifactName {
+    /// Returns the artifact name for the program with the specified hash executed with the inputs
+    /// from the input file and the advice map file.
+    pub fn new(
+        program_hash: Digest,
+        inputs: &InputFile,
+        advice_map_path: &Option<PathBuf>,
+    ) -> Result<Self, String> {
+        let program_hash: [u8; 32] = program_hash.into();
+        let inputs_hash = hash_inputs(inputs, advice_map_path)?;
+        Ok(Self(format!("{}-{}", hex::encode(program_hash), hex::encode(inputs_hash))))
+    }
+
+    /// Returns the path of the artifact with the specified extension, located in the same
+    /// directory as `sibling_path`.
+    pub fn path(&self, sibling_path: &Path, extension: &str) -> PathBuf {
+        sibling_path.with_file_name(format!("{}.{extension}", self.0))
+    }
+}
+
+impl fmt::Display for ArtifactName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Hashes the parsed inputs as described in [ArtifactName].
+fn hash_inputs(inputs: &InputFile, advice_map_path: &Option<PathBuf>) -> Result<[u8; 32], String> {
+    let mut bytes = Vec::new();
+
+    inputs.parse_stack_inputs()?.write_into(&mut bytes);
+
+    let advice_stack = inputs.parse_advice_stack()?;
+    bytes.write_u64(advice_stack.len() as u64);
+    advice_stack.iter().for_each(|value| bytes.write_u64(*value));
+
+    write_advice_map(&mut bytes, inputs.parse_advice_map(false)?.unwrap_or_default());
+
+    let merkle_data = inputs.merkle_store.as_deref().unwrap_or(&[]);
+    bytes.write_u64(merkle_data.len() as u64);
+    for data in merkle_data {
+        match data {
+            MerkleData::MerkleTree(data) => {
+                let leaves = InputFile::parse_merkle_tree(data)?;
+                bytes.write_u8(0);
+                bytes.write_u64(leaves.len() as u64);
+                leaves.into_iter().for_each(|leaf| RpoDigest::from(leaf).write_into(&mut bytes));
+            }
+            MerkleData::SparseMerkleTree(data) => {
+                let entries = InputFile::parse_sparse_merkle_tree(data, SPARSE_MERKLE_TREE_DEPTH)?;
+                bytes.write_u8(1);
+                bytes.write_u64(entries.len() as u64);
+                for (index, leaf) in entries {
+                    bytes.write_u64(index);
+                    RpoDigest::from(leaf).write_into(&mut bytes);
+                }
+            }
+            MerkleData::PartialMerkleTree(data) => {
+                let entries = InputFile::parse_partial_merkle_tree(data)?;
+                bytes.write_u8(2);
+                bytes.write_u64(entries.len() as u64);
+                for (index, leaf) in entries {
+                    bytes.write_u8(index.depth());
+                    bytes.write_u64(index.value());
+                    leaf.write_into(&mut bytes);
+                }
+            }
+        }
+    }
+
+    match advice_map_path {
+        Some(path) => {
+            bytes.write_u8(1);
+            write_advice_map(&mut bytes, parse_advice_map(&AdviceMapFile::read(path)?.0, false)?);
+        }
+        None => bytes.write_u8(0),
+    }
+
+    Ok(Blake3_256::hash(&bytes).into())
+}
+
+/// Writes the entries of the advice map into `target`, ordered by key.
+fn write_advice_map(target: &mut Vec<u8>, map: HashMap<[u8; 32], Vec<Felt>>) {
+    let mut entries = map.into_iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(key, _)| *key);
+
+    target.write_u64(entries.len() as u64);
+    for (key, values) in entries {
+        target.write_bytes(&key);
+        target.write_u64(values.len() as u64);
+        values.iter().for_each(|value| value.write_into(target));
+    }
 }
 
 // LIBRARY FILE
 // ================================================================================================
 pub struct Libraries {
     pub libraries: Vec<MaslLibrary>,
+    /// Indicates whether modules of libraries loaded later may shadow modules with the same path
+    /// from libraries loaded earlier.
+    pub allow_shadowing: bool,
+    /// Digest which the standard library is expected to have, if the standard library is pinned.
+    pub stdlib_digest: Option<[u8; 32]>,
 }
 
 impl Libraries {
     /// Creates a new instance of [Libraries] from a list of library paths.
-    pub fn new<P, I>(paths: I) -> Result<Self, String>
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The checksum of a library file doesn't match its contents, unless `skip_checksum` is set.
+    /// - Two of the libraries define a module with the same path, unless `allow_shadowing` is set.
+    ///   In this case, modules from libraries loaded later replace the modules from libraries
+    ///   loaded earlier.
+    pub fn new<P, I>(paths: I, allow_shadowing: bool, skip_checksum: bool) -> Result<Self, String>
     where
         P: AsRef<Path>,
         I: IntoIterator<Item = P>,
     {
         let mut libraries = Vec::new();
+        // map from module path to the file of the library which defines the module
+        let mut module_files = HashMap::<String, PathBuf>::new();
 
         for path in paths {
-            println!("Reading library file `{}`", path.as_ref().display());
+            statusln!("Reading library file `{}`", path.as_ref().display());
+
+            let library = if skip_checksum {
+                MaslLibrary::read_from_file_unchecked(&path)
+            } else {
+                MaslLibrary::read_from_file(&path)
+            }
+            .map_err(|e| format!("Failed to read library: {e}"))?;
+
+            for module in library.modules() {
+                let prev_file = module_files.insert(module.path.to_string(), path.as_ref().into());
+                match prev_file {
+                    Some(prev_file) if !allow_shadowing => {
+                        return Err(format!(
+                            "Namespace collision: {} defined in both {} and {}",
+                            collision_name(module),
+                            prev_file.display(),
+                            path.as_ref().display()
+                        ))
+                    }
+                    _ => (),
+                }
+            }
 
-            let library = MaslLibrary::read_from_file(path)
-                .map_err(|e| format!("Failed to read library: {e}"))?;
             libraries.push(library);
         }
 
-        Ok(Self { libraries })
+        Ok(Self {
+            libraries,
+            allow_shadowing,
+            stdlib_digest: None,
+        })
+    }
+
+    /// Checks that the loaded libraries comply with the specified policy, if one is provided.
+    ///
+    /// # Errors
+    /// Returns an error if a library has a namespace which is not allowed by the policy, or if it
+    /// defines more procedures than the policy allows.
+    pub fn with_policy(self, policy: Option<&LibraryPolicy>) -> Result<Self, String> {
+        if let Some(policy) = policy {
+            self.libraries.iter().try_for_each(|library| policy.check(library))?;
+        }
+        Ok(self)
+    }
+
+    /// Pins the standard library to the specified hex-encoded digest, if one is provided, so that
+    /// compiling against a standard library with a different digest fails.
+    ///
+    /// # Errors
+    /// Returns an error if the digest is not a hex encoding of 32 bytes.
+    pub fn with_stdlib_digest(mut self, digest: Option<&str>) -> Result<Self, String> {
+        self.stdlib_digest = digest
+            .map(|digest| {
+                let bytes = hex::decode(digest.strip_prefix("0x").unwrap_or(digest))
+                    .map_err(|err| format!("Invalid stdlib hash `{digest}` - {err}"))?;
+                bytes.try_into().map_err(|bytes: Vec<u8>| {
+                    format!(
+                        "Invalid stdlib hash `{digest}` - expected 32 bytes, but was {}",
+                        bytes.len()
+                    )
+                })
+            })
+            .transpose()?;
+        Ok(self)
+    }
+}
+
+/// Returns the name used to report a collision of the specified module with a module from another
+/// library. This is the fully-qualified name of the first procedure exported from the module or,
+/// if the module does not export any procedures, the path of the module.
+fn collision_name(module: &Module) -> String {
+    let reexported = module.ast.reexported_procs().iter().map(|proc| proc.name());
+    let exported = module.ast.procs().iter().filter(|proc| proc.is_export).map(|proc| &proc.name);
+
+    match reexported.chain(exported).next() {
+        Some(name) => module.path.append_unchecked(name),
+        None => module.path.to_string(),
+    }
+}
+
+/// Returns the error message for a library which could not be added to the assembler.
+///
+/// If a module of the library conflicts with a module which was added before, the message states
+/// whether the existing module is defined by the standard library or by another user library.
+fn library_load_error_message(err: &LibraryLoadError, stdlib: &StdLibrary) -> String {
+    match err.source {
+        LibraryError::DuplicateModulePath(_) => {
+            let origin = if stdlib.modules().any(|module| module.path == err.conflicting_path) {
+                "the standard library"
+            } else {
+                "a previously loaded library"
+            };
+            format!(
+                "Failed to load library `{}` - module `{}` is already defined by {origin}",
+                err.library_name, err.conflicting_path
+            )
+        }
+        ref source => format!(
+            "Failed to load library `{}` - module `{}`: {source}",
+            err.library_name, err.conflicting_path
+        ),
     }
 }
 
@@ -556,7 +2903,24 @@ impl Libraries {
 // ================================================================================================
 #[cfg(test)]
 mod test {
-    use super::InputFile;
+    use super::{
+        substitute_env_vars, write_atomic, AdviceMapKey, ArtifactName, Debug, InputFile,
+        InputPhase, JsonFormat, Libraries, NumberFormat, OutputFile, ProgramFile, ProofFile,
+        ProofHeader, ProofOptionsFile, StdLibrary,
+    };
+    use assembly::{ast::AstSerdeOptions, LibraryNamespace, MaslLibrary, Version};
+    use miden::{
+        crypto::MerkleTree,
+        math::{Felt, StarkField},
+        Assembler, DefaultHost, Digest, HashFunction, ProvingOptions, StackInputs, StackOutputs,
+    };
+    use processor::ExecutionOptions;
+    use std::{
+        env, fs,
+        io::{self, Write},
+        path::{Path, PathBuf},
+        time::{Duration, UNIX_EPOCH},
+    };
 
     #[test]
     fn test_merkle_data_parsing() {
@@ -630,4 +2994,754 @@ mod test {
         let merkle_store = inputs.parse_merkle_store().unwrap();
         assert!(merkle_store.is_some());
     }
+
+    #[test]
+    fn test_merkle_store_consistency() {
+        let node = |value: u8| format!("\"0x{value:02x}{}\"", "0".repeat(62));
+        let inputs = format!(
+            r#"{{
+                "operand_stack": [],
+                "merkle_store": [
+                    {{ "merkle_tree": [{}, {}, {}, {}] }},
+                    {{ "sparse_merkle_tree": [[0, {}], [3, {}]] }},
+                    {{ "partial_merkle_tree": [[[2, 0], {}], [[2, 1], {}], [[1, 1], {}]] }}
+                ]
+            }}"#,
+            node(20),
+            node(21),
+            node(22),
+            node(23),
+            node(20),
+            node(23),
+            node(20),
+            node(21),
+            node(11)
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+        assert_eq!(inputs.check_merkle_store(), Ok(3));
+
+        // the node at depth 1 and index 0 is declared with a value which is not the hash of its
+        // children, and thus, the children cannot be opened against the root of the tree
+        let inputs = format!(
+            r#"{{
+                "operand_stack": [],
+                "merkle_store": [
+                    {{ "merkle_tree": [{}, {}] }},
+                    {{ "partial_merkle_tree": [
+                        [[1, 0], {}], [[2, 0], {}], [[2, 1], {}], [[1, 1], {}]
+                    ] }}
+                ]
+            }}"#,
+            node(20),
+            node(21),
+            node(10),
+            node(20),
+            node(21),
+            node(11)
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+        let err = inputs.check_merkle_store().unwrap_err();
+        let expected = "Partial Merkle tree #1 is inconsistent: leaf [20, 0, 0, 0] at depth 2 and \
+            index 0 does not hash to the root";
+        assert!(err.starts_with(expected), "unexpected error: {err}");
+
+        // the same tree is accepted when the Merkle store is parsed
+        assert!(inputs.parse_merkle_store().is_ok());
+    }
+
+    #[test]
+    fn test_output_file_overflow_addrs_order() {
+        let stack_outputs = StackOutputs::new((1..=18).collect(), vec![0, 5, 7]).unwrap();
+        let output_file = OutputFile::new(&stack_outputs);
+        assert_eq!(output_file.overflow_addrs, vec!["0", "5", "7"]);
+        assert_eq!(output_file.stack_outputs().unwrap(), stack_outputs);
+    }
+
+    #[test]
+    fn test_output_file_deterministic() {
+        // the program leaves enough items on the stack for the overflow table to be non-empty
+        let program = Assembler::default().compile("begin repeat.20 push.1 end end").unwrap();
+
+        let run = || {
+            let trace = processor::execute(
+                &program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                ExecutionOptions::default(),
+            )
+            .unwrap();
+            serde_json::to_string_pretty(&OutputFile::new(trace.stack_outputs())).unwrap()
+        };
+
+        let first = run();
+        let output_file: OutputFile = serde_json::from_str(&first).unwrap();
+        assert_eq!(output_file.overflow_addrs.len(), 21);
+        assert_eq!(first, run());
+    }
+
+    #[test]
+    fn test_output_file_json_matches_file() {
+        let stack_outputs = StackOutputs::new((1..=18).collect(), vec![0, 5, 7]).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("outputs.json");
+        OutputFile::write_with_number_format(
+            &stack_outputs,
+            &path,
+            NumberFormat::Hex,
+            JsonFormat::Pretty,
+        )
+        .unwrap();
+
+        // the outputs printed to stdout are identical to the contents of the output file
+        let output_file = OutputFile::with_number_format(&stack_outputs, NumberFormat::Hex);
+        assert_eq!(
+            output_file.to_json(JsonFormat::Pretty).unwrap(),
+            fs::read_to_string(&path).unwrap()
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn input_parse_timings() {
+        let dir = tempfile::tempdir().unwrap();
+        let inputs_path = dir.path().join("program.inputs");
+        fs::write(
+            &inputs_path,
+            r#"{
+                "operand_stack": ["1"],
+                "advice_stack": ["2", "3"],
+                "advice_map": {
+                    "0000000000000000000000000000000000000000000000000000000000000000": [4]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        // only the phases which were executed are timed
+        let inputs = InputFile::read(&Some(inputs_path), dir.path(), false).unwrap();
+        assert!(inputs.parse_timings.total(InputPhase::Deserialize).is_some());
+        assert!(inputs.parse_timings.total(InputPhase::AdviceMap).is_none());
+
+        inputs.parse_advice_provider().unwrap();
+        for phase in InputPhase::ALL {
+            assert!(inputs.parse_timings.total(phase).is_some(), "{phase:?}");
+        }
+
+        let table = inputs.parse_timings.to_table();
+        let rows = table.lines().map(|line| line.split_whitespace().collect::<Vec<_>>());
+        let names = rows.map(|row| row[0]).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "Phase",
+                "parse_json",
+                "parse_advice_stack",
+                "parse_advice_map",
+                "parse_merkle_store",
+                "total"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_proof_header_roundtrip() {
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let stack_outputs = StackOutputs::new(vec![1, 2, 3], vec![]).unwrap();
+        let stack_inputs = StackInputs::try_from_values([4, 5]).unwrap();
+        let program_hash = Digest::new([7, 8, 9, 10].map(Felt::new));
+        let header = ProofHeader::new(
+            timestamp,
+            program_hash,
+            stack_inputs.clone(),
+            stack_outputs.clone(),
+            HashFunction::Rpo256,
+            16,
+            3,
+        );
+        let mut bytes = header.to_bytes();
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        let (parsed, rest) = ProofHeader::split(&bytes).unwrap();
+        assert_eq!(parsed.as_ref(), Some(&header));
+        let parsed = parsed.unwrap();
+        assert_eq!(parsed.timestamp(), timestamp);
+        assert_eq!(parsed.stack_outputs(), &stack_outputs);
+        assert_eq!(parsed.program_hash(), program_hash);
+        assert_eq!(parsed.stack_inputs(), &stack_inputs);
+        assert_eq!(parsed.hash_fn(), HashFunction::Rpo256);
+        assert_eq!(parsed.blowup_factor(), 16);
+        assert_eq!(parsed.proof_size(), 3);
+        assert_eq!(rest, &[1, 2, 3]);
+
+        // proof files without a header are returned as is
+        let (parsed, rest) = ProofHeader::split(&[1, 2, 3]).unwrap();
+        assert_eq!(parsed, None);
+        assert_eq!(rest, &[1, 2, 3]);
+
+        // truncated headers and unknown versions are rejected
+        assert_eq!(
+            ProofHeader::split(&bytes[..6]).unwrap_err(),
+            "Proof file appears truncated (expected at least 13 bytes, found 6)"
+        );
+        assert!(ProofHeader::split(&bytes[..ProofHeader::SIZE + 2])
+            .unwrap_err()
+            .starts_with("Proof file appears truncated"));
+        assert_eq!(
+            ProofHeader::split(&bytes[..bytes.len() - 5]).unwrap_err(),
+            format!(
+                "Proof file appears truncated (expected at least {} bytes, found {})",
+                bytes.len() - 3,
+                bytes.len() - 5
+            )
+        );
+        let mut invalid_hash_fn = bytes.clone();
+        invalid_hash_fn[bytes.len() - 13] = 0xff;
+        assert!(ProofHeader::split(&invalid_hash_fn).is_err());
+        bytes[4] = 2;
+        assert!(ProofHeader::split(&bytes).is_err());
+    }
+
+    #[test]
+    fn truncated_proof_file_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let proof_path = dir.path().join("program.proof");
+
+        let program = Assembler::default().compile("begin push.1 push.2 add end").unwrap();
+        let (stack_outputs, proof) = miden::prove(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+        let proof_bytes = proof.to_bytes();
+        ProofFile::write(
+            proof,
+            program.hash(),
+            &StackInputs::default(),
+            &stack_outputs,
+            &Some(proof_path.clone()),
+            dir.path(),
+        )
+        .unwrap();
+        let bytes = fs::read(&proof_path).unwrap();
+
+        // files which end before the recorded proof size are reported as truncated
+        let truncated_path = dir.path().join("truncated.proof");
+        fs::write(&truncated_path, &bytes[..bytes.len() - 100]).unwrap();
+        let err = ProofFile::read(&Some(truncated_path.clone()), dir.path()).unwrap_err();
+        assert_eq!(
+            err,
+            format!(
+                "Proof file appears truncated (expected at least {} bytes, found {})",
+                bytes.len(),
+                bytes.len() - 100
+            )
+        );
+
+        // so are files without a header which end before the proof does
+        fs::write(&truncated_path, &proof_bytes[..proof_bytes.len() - 100]).unwrap();
+        let err = ProofFile::read(&Some(truncated_path.clone()), dir.path()).unwrap_err();
+        assert!(err.starts_with("Proof file appears truncated"), "{err}");
+
+        // while complete files with corrupt contents are not
+        let mut corrupt = bytes.clone();
+        let proof_start = bytes.len() - proof_bytes.len();
+        corrupt[proof_start] = 0xff;
+        fs::write(&truncated_path, &corrupt).unwrap();
+        let err = ProofFile::read(&Some(truncated_path), dir.path()).unwrap_err();
+        assert!(err.starts_with("Failed to decode proof data"), "{err}");
+    }
+
+    #[test]
+    fn proof_options_hash_fn() {
+        let apply = |hash_fn: &str| {
+            let options_file = ProofOptionsFile {
+                hash_fn: Some(hash_fn.to_string()),
+                ..Default::default()
+            };
+            options_file.apply(ProvingOptions::default()).map(|options| options.hash_fn())
+        };
+
+        assert_eq!(apply("blake3_192"), Ok(HashFunction::Blake3_192));
+        assert_eq!(apply("blake3"), Ok(HashFunction::Blake3_256));
+        assert_eq!(apply("rpo"), Ok(HashFunction::Rpo256));
+        assert!(apply("poseidon").unwrap_err().contains("not supported"));
+        assert!(apply("sha256").unwrap_err().contains("unknown hash function"));
+    }
+
+    #[test]
+    fn test_parse_word_optional_prefix() {
+        let hex = "1400000000000000150000000000000016000000000000001700000000000000";
+        let expected = [20, 21, 22, 23].map(miden::math::Felt::new);
+
+        // prefixed and unprefixed strings produce the same word
+        assert_eq!(InputFile::parse_word(&format!("0x{hex}")).unwrap(), expected);
+        assert_eq!(InputFile::parse_word(hex).unwrap(), expected);
+
+        // strings with an invalid length are rejected (including ones which are too short to
+        // contain a prefix) rather than misparsed
+        assert!(InputFile::parse_word(&hex[2..]).is_err());
+        assert!(InputFile::parse_word(&format!("0x{hex}00")).is_err());
+        assert!(InputFile::parse_word("0").is_err());
+        assert!(InputFile::parse_word("").is_err());
+
+        // elements which are not valid field elements are rejected
+        assert!(InputFile::parse_word(&"f".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_operand_words_expand_into_stack() {
+        let word = |values: [u64; 4]| {
+            format!("0x{}", values.iter().map(|v| hex::encode(v.to_le_bytes())).collect::<String>())
+        };
+        let inputs = format!(
+            r#"{{ "operand_stack": ["1", "2"], "operand_words": ["{}", "{}"] }}"#,
+            word([3, 4, 5, 6]),
+            word([7, 8, 9, 10])
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+
+        // the words are appended to the flat operand stack element by element
+        let expected = StackInputs::try_from_values(1..=10).unwrap();
+        assert_eq!(inputs.parse_stack_inputs().unwrap(), expected);
+
+        // the operand stack may be omitted when words are provided
+        let inputs = format!(r#"{{ "operand_words": ["{}"] }}"#, word([3, 4, 5, 6]));
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+        assert_eq!(
+            inputs.parse_stack_inputs().unwrap(),
+            StackInputs::try_from_values(3..=6).unwrap()
+        );
+
+        // invalid words are rejected
+        let inputs: InputFile = serde_json::from_str(r#"{ "operand_words": ["0x01"] }"#).unwrap();
+        assert!(inputs.parse_stack_inputs().is_err());
+    }
+
+    #[test]
+    fn test_derive_advice_key() {
+        let expected = AdviceMapKey::from_felts(&[Felt::new(1), Felt::new(0x2a)]);
+
+        // values may optionally be prefixed, and the key is a valid word
+        let key = InputFile::derive_advice_key(&["1", "0x2a"]).unwrap();
+        assert_eq!(key, format!("0x{}", hex::encode(expected)));
+        assert!(InputFile::parse_word(&key).is_ok());
+
+        // values are parsed as hex rather than decimal
+        let key = InputFile::derive_advice_key(&["10"]).unwrap();
+        assert_eq!(key, format!("0x{}", hex::encode(AdviceMapKey::from_felts(&[Felt::new(16)]))));
+        assert!(InputFile::derive_advice_key(&["0xz"]).is_err());
+        assert!(InputFile::derive_advice_key(&["ffffffffffffffff"]).is_err());
+    }
+
+    #[test]
+    fn test_library_namespace_collision() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // build two libraries with the same namespace which define the same module
+        let build_library = |name: &str, value: u32| -> PathBuf {
+            let src_dir = dir.path().join(name).join("src");
+            fs::create_dir_all(&src_dir).unwrap();
+            fs::write(src_dir.join("math.masm"), format!("export.foo push.{value} end")).unwrap();
+
+            let namespace = LibraryNamespace::try_from("mylib".to_string()).unwrap();
+            let library =
+                MaslLibrary::read_from_dir(&src_dir, namespace, false, Version::default()).unwrap();
+            library.write_to_dir(dir.path().join(name)).unwrap();
+            dir.path().join(name).join("mylib.masl")
+        };
+        let paths = [build_library("a", 1), build_library("b", 2)];
+
+        // by default, the collision is reported
+        let err = Libraries::new(&paths, false, false).err().unwrap();
+        assert_eq!(
+            err,
+            format!(
+                "Namespace collision: mylib::math::foo defined in both {} and {}",
+                paths[0].display(),
+                paths[1].display()
+            )
+        );
+
+        // with shadowing allowed, the module from the library loaded last is used
+        let libraries = Libraries::new(&paths, true, false).unwrap();
+        let program_path = dir.path().join("program.masm");
+        fs::write(&program_path, "use.mylib::math begin exec.math::foo end").unwrap();
+        let program = ProgramFile::read(&program_path).unwrap();
+        let program = program.compile(&Debug::Off, libraries).unwrap();
+        assert_eq!(format!("{program}"), "begin span push(2) end end");
+    }
+
+    #[test]
+    fn test_library_conflicts_with_stdlib() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_dir = dir.path().join("src").join("math");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("u64.masm"), "export.foo push.1 end").unwrap();
+
+        let namespace = LibraryNamespace::try_from("std".to_string()).unwrap();
+        let library = MaslLibrary::read_from_dir(
+            dir.path().join("src"),
+            namespace,
+            false,
+            Version::default(),
+        )
+        .unwrap();
+        library.write_to_dir(dir.path()).unwrap();
+
+        let libraries = Libraries::new([dir.path().join("std.masl")], false, false).unwrap();
+        let program_path = dir.path().join("program.masm");
+        fs::write(&program_path, "begin push.1 end").unwrap();
+        let program = ProgramFile::read(&program_path).unwrap();
+        let err = String::from(program.compile(&Debug::Off, libraries).unwrap_err());
+        assert_eq!(
+            err,
+            "Failed to load library `std` - module `std::math::u64` is already defined by the \
+            standard library"
+        );
+    }
+
+    #[test]
+    fn test_pinned_stdlib_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let program_path = dir.path().join("program.masm");
+        fs::write(
+            &program_path,
+            "use.std::math::u64 begin push.1 push.2 exec.u64::checked_add end",
+        )
+        .unwrap();
+        let program = ProgramFile::read(&program_path).unwrap();
+        let digest = hex::encode(StdLibrary::default().digest());
+
+        // the program compiles against the pinned standard library
+        let libraries = Libraries::new(Vec::<PathBuf>::new(), false, false)
+            .unwrap()
+            .with_stdlib_digest(Some(&digest))
+            .unwrap();
+        program.compile(&Debug::Off, libraries).unwrap();
+
+        // a different pinned hash fails the compilation
+        let other = "0x".to_string() + &"ab".repeat(32);
+        let libraries = Libraries::new(Vec::<PathBuf>::new(), false, false)
+            .unwrap()
+            .with_stdlib_digest(Some(&other))
+            .unwrap();
+        let err = String::from(program.compile(&Debug::Off, libraries).unwrap_err());
+        assert_eq!(
+            err,
+            format!(
+                "Standard library hash {digest} does not match the expected hash {}",
+                "ab".repeat(32)
+            )
+        );
+
+        // a hash of the wrong length is rejected
+        let libraries = Libraries::new(Vec::<PathBuf>::new(), false, false).unwrap();
+        assert!(libraries.with_stdlib_digest(Some("abcd")).is_err());
+    }
+
+    #[test]
+    fn test_program_size_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("program.masm");
+
+        // 2 procedures; 3 instructions in procedures and 7 in the program body, counting control
+        // flow statements as one instruction and repeat bodies once
+        let source = "
+            proc.foo push.1 drop end
+            proc.bar add end
+            begin
+                exec.foo
+                if.true push.1 else push.2 push.3 end
+                repeat.10 exec.bar end
+            end";
+        fs::write(&path, source).unwrap();
+        let program = ProgramFile::read(&path).unwrap();
+
+        assert!(program.check_size(10, 2).is_ok());
+
+        let err = program.check_size(9, 2).unwrap_err();
+        assert!(err.contains("contains 10 instructions, but at most 9 are allowed"));
+
+        let err = program.check_size(10, 1).unwrap_err();
+        assert!(err.contains("defines 2 procedures, but at most 1 are allowed"));
+    }
+
+    #[test]
+    fn test_compiled_program_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("program.masm");
+        fs::write(&source_path, "begin push.1 push.2 add end").unwrap();
+        let program = ProgramFile::read(&source_path).unwrap();
+        let compiled_path = dir.path().join("program.masb");
+        program.write(Some(compiled_path.clone())).unwrap();
+
+        // files of the current version are read as is
+        let compiled = ProgramFile::read_compiled(&compiled_path).unwrap();
+        assert_eq!(compiled.ast(), program.ast());
+
+        // files without a header are upgraded in memory if their program can be deserialized
+        let ast_bytes = program.ast().to_bytes(AstSerdeOptions {
+            serialize_imports: true,
+        });
+        fs::write(&compiled_path, &ast_bytes).unwrap();
+        let compiled = ProgramFile::read_compiled(&compiled_path).unwrap();
+        assert_eq!(compiled.ast(), program.ast());
+
+        // otherwise, a recompile is required; emulate a file of an older format whose program
+        // cannot be deserialized anymore by cutting its last instruction short
+        fs::write(&compiled_path, &ast_bytes[..ast_bytes.len() - 1]).unwrap();
+        let err = ProgramFile::read_compiled(&compiled_path).err().unwrap();
+        assert_eq!(
+            err,
+            format!(
+                "Compiled program file `{}` was written by an older version of miden (format \
+                version 0), but this is miden {} (format version 1); recompile required - \
+                compile the program from source again using `miden compile`",
+                compiled_path.display(),
+                env!("CARGO_PKG_VERSION")
+            )
+        );
+
+        // files written in a newer format name the version which wrote them
+        let mut newer = b"MASB\x02\x059.9.9".to_vec();
+        newer.extend_from_slice(&ast_bytes);
+        fs::write(&compiled_path, &newer).unwrap();
+        let err = ProgramFile::read_compiled(&compiled_path).err().unwrap();
+        assert!(err.contains("written by miden 9.9.9 (format version 2)"), "{err}");
+        assert!(err.contains("recompile required"), "{err}");
+    }
+
+    #[test]
+    fn test_merkle_node_index_validation() {
+        let leaf = "0x1400000000000000000000000000000000000000000000000000000000000000".to_string();
+
+        // sparse Merkle tree entries must fit into the depth of the tree
+        let entries = vec![(3, leaf.clone()), (8, leaf.clone())];
+        let err = InputFile::parse_sparse_merkle_tree(&entries, 3).unwrap_err();
+        assert_eq!(err, "index 8 exceeds max index 7 for depth 3");
+        assert!(InputFile::parse_sparse_merkle_tree(&entries, 4).is_ok());
+        assert!(InputFile::parse_sparse_merkle_tree(&[(u64::MAX, leaf.clone())], 64).is_ok());
+
+        // partial Merkle tree nodes must fit into their depths
+        let err = InputFile::parse_partial_merkle_tree(&[((2, 4), leaf.clone())]).unwrap_err();
+        assert_eq!(err, "index 4 exceeds max index 3 for depth 2");
+        let err = InputFile::parse_partial_merkle_tree(&[((0, 1), leaf.clone())]).unwrap_err();
+        assert_eq!(err, "index 1 exceeds max index 0 for depth 0");
+        let err = InputFile::parse_partial_merkle_tree(&[((65, 0), leaf)]).unwrap_err();
+        assert_eq!(err, "depth 65 exceeds max depth 64 of a Merkle tree");
+    }
+
+    #[test]
+    fn test_advice_map_duplicate_keys() {
+        let key_a = "aa".repeat(32);
+        let key_b = "bb".repeat(32);
+        let inputs = format!(
+            r#"{{
+                "operand_stack": [],
+                "advice_map": {{
+                    "{key_a}": [1],
+                    "{key_b}": [2],
+                    "{key_a}": [3],
+                    "{}": [4]
+                }}
+            }}"#,
+            key_b.to_uppercase()
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+
+        // by default, values defined last are used
+        let map = inputs.parse_advice_map(false).unwrap().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&[0xaa; 32]], [miden::math::Felt::new(3)]);
+        assert_eq!(map[&[0xbb; 32]], [miden::math::Felt::new(4)]);
+
+        // in strict mode, all redefined keys are reported
+        let err = inputs.parse_advice_map(true).unwrap_err();
+        assert_eq!(
+            err,
+            format!(
+                "Duplicate advice map key 0x{key_a}: first defined at position 0, redefined at \
+                position 2\nDuplicate advice map key 0x{key_b}: first defined at position 1, \
+                redefined at position 3"
+            )
+        );
+    }
+
+    #[test]
+    fn test_atomic_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("program.proof");
+        let tmp_path = dir.path().join("program.proof.tmp");
+
+        write_atomic(&path, |file| file.write_all(b"original")).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"original");
+        assert!(!tmp_path.exists());
+
+        // a failure in the middle of writing leaves the existing file untouched
+        let err = write_atomic(&path, |file| {
+            file.write_all(b"partial")?;
+            Err(io::Error::new(io::ErrorKind::Other, "disk full"))
+        })
+        .unwrap_err();
+        assert_eq!(err, format!("Failed to write file `{}` - disk full", path.display()));
+        assert_eq!(fs::read(&path).unwrap(), b"original");
+        assert!(!tmp_path.exists());
+
+        // output files are written through the same mechanism
+        let outputs_path = dir.path().join("program.outputs");
+        let stack_outputs = StackOutputs::new(vec![1, 2], vec![]).unwrap();
+        OutputFile::write(&stack_outputs, &outputs_path, JsonFormat::Pretty).unwrap();
+        let outputs = OutputFile::read(&Some(outputs_path.clone()), &path).unwrap();
+        assert_eq!(outputs.stack[..2], ["1", "2"]);
+        assert!(!dir.path().join("program.outputs.tmp").exists());
+    }
+
+    #[test]
+    fn test_strict_hex_words() {
+        // a leaf consisting only of decimal digits is also a valid hex string
+        let leaf = "1400000000000000000000000000000000000000000000000000000000000000";
+        let inputs = format!(
+            r#"{{
+                "operand_stack": ["1"],
+                "merkle_store": [{{ "merkle_tree": ["{leaf}", "0x{leaf}"] }}]
+            }}"#
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+        assert!(inputs.parse_advice_provider().is_ok());
+        assert_eq!(
+            inputs.check_strict_hex().unwrap_err(),
+            format!("Invalid Merkle store node `{leaf}` - strict hex mode requires a `0x` prefix")
+        );
+
+        // prefixed words pass the check, while scalar values are not affected by it
+        let inputs = format!(
+            r#"{{
+                "operand_stack": ["1"],
+                "advice_stack": ["2"],
+                "advice_map": {{ "0x{leaf}": [3] }},
+                "merkle_store": [{{ "sparse_merkle_tree": [[0, "0x{leaf}"]] }}]
+            }}"#
+        );
+        let inputs: InputFile = serde_json::from_str(&inputs).unwrap();
+        assert!(inputs.check_strict_hex().is_ok());
+    }
+
+    #[test]
+    fn test_output_file_msgpack_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("program.outputs");
+        let msgpack_path = dir.path().join("program.msgpack");
+
+        // large stack outputs with values of every integer encoding size
+        let stack = (0..1000).map(|i| u64::MAX - 2 * (u32::MAX as u64) - i).collect::<Vec<_>>();
+        let mut stack_outputs = vec![0, 0x7f, 0x80, 0xff, 0x100, 0x10000, 0x1_0000_0000];
+        stack_outputs.extend(stack);
+        let overflow_addrs = (1..stack_outputs.len() as u64 - 14).collect();
+        let stack_outputs = StackOutputs::new(stack_outputs, overflow_addrs).unwrap();
+
+        OutputFile::write(&stack_outputs, &json_path, JsonFormat::Pretty).unwrap();
+        OutputFile::write_msgpack(&stack_outputs, &msgpack_path).unwrap();
+
+        // the format is detected from the file extension
+        let outputs = OutputFile::read(&Some(msgpack_path.clone()), dir.path()).unwrap();
+        assert_eq!(outputs.stack_outputs().unwrap(), stack_outputs);
+        let outputs = OutputFile::read_msgpack(&msgpack_path).unwrap();
+        assert_eq!(outputs.stack_outputs().unwrap(), stack_outputs);
+
+        let json_len = fs::metadata(&json_path).unwrap().len();
+        let msgpack_len = fs::metadata(&msgpack_path).unwrap().len();
+        assert!(msgpack_len * 3 < json_len, "{msgpack_len} vs {json_len}");
+
+        // truncated data is rejected
+        let bytes = fs::read(&msgpack_path).unwrap();
+        fs::write(&msgpack_path, &bytes[..bytes.len() - 1]).unwrap();
+        let err = OutputFile::read_msgpack(&msgpack_path).unwrap_err();
+        assert!(err.starts_with("Failed to deserialize outputs data"), "{err}");
+    }
+
+    #[test]
+    fn test_input_template_substitution() {
+        let dir = tempfile::tempdir().unwrap();
+        let inputs_path = dir.path().join("program.inputs");
+        let var = format!("MIDEN_TEST_ACCOUNT_ID_{}", std::process::id());
+        let inputs = format!(
+            r#"{{ "operand_stack": ["${{{var}}}", "${{{var}_UNSET:-7}}"], "advice_stack": ["$5"] }}"#
+        );
+        fs::write(&inputs_path, inputs).unwrap();
+
+        // an undefined variable without a default value is an error
+        let err = InputFile::read(&Some(inputs_path.clone()), dir.path(), true).unwrap_err();
+        assert_eq!(err, format!("Environment variable `{var}` used in input file is not set"));
+
+        env::set_var(&var, "12345");
+        let inputs = InputFile::read(&Some(inputs_path.clone()), dir.path(), true).unwrap();
+        assert_eq!(inputs.operand_stack, ["12345", "7"]);
+        assert_eq!(inputs.advice_stack.as_deref().unwrap(), ["$5"]);
+        let stack_inputs = inputs.parse_stack_inputs().unwrap();
+        assert_eq!(
+            stack_inputs.values()[..2],
+            [miden::math::Felt::new(7), miden::math::Felt::new(12345)]
+        );
+
+        // without the template flag the file is read as is
+        let inputs = InputFile::read(&Some(inputs_path), dir.path(), false).unwrap();
+        assert_eq!(inputs.operand_stack[0], format!("${{{var}}}"));
+        assert!(inputs.parse_stack_inputs().is_err());
+        env::remove_var(&var);
+
+        assert_eq!(
+            substitute_env_vars("a ${X} b", |_| None).unwrap_err(),
+            "Environment variable `X` used in input file is not set"
+        );
+        assert_eq!(
+            substitute_env_vars("a ${X", |_| Some("1".to_string())).unwrap_err(),
+            "Unterminated variable reference `${X`"
+        );
+    }
+
+    #[test]
+    fn test_artifact_names_depend_on_inputs() {
+        let program_hash = Assembler::default().compile("begin add end").unwrap().hash();
+        let name = |inputs: &str| {
+            let inputs: InputFile = serde_json::from_str(inputs).unwrap();
+            ArtifactName::new(program_hash, &inputs, &None).unwrap()
+        };
+
+        let first = name(r#"{ "operand_stack": ["1", "2"] }"#);
+        let second = name(r#"{ "operand_stack": ["1", "3"] }"#);
+        assert_ne!(first, second);
+        assert_ne!(first, name(r#"{ "operand_stack": ["1", "2"], "advice_stack": ["3"] }"#));
+
+        // the name is computed from the parsed values, so formatting does not affect it
+        assert_eq!(first, name(r#"{"operand_stack":["01","2"],"advice_stack":[]}"#));
+
+        // artifacts are placed next to the program, and the name starts with the program hash
+        let program_hash: [u8; 32] = program_hash.into();
+        let path = first.path(Path::new("dir/program.masm"), "proof");
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+        assert_eq!(path.parent(), Some(Path::new("dir")));
+        assert!(file_name.starts_with(&format!("{}-", hex::encode(program_hash))));
+        assert!(file_name.ends_with(".proof"));
+        assert_eq!(file_name.len(), 64 + 1 + 64 + ".proof".len());
+    }
+
+    #[test]
+    fn test_number_format() {
+        assert_eq!(NumberFormat::Dec.format_values(&[0, 10, 255]), "[0, 10, 255]");
+        assert_eq!(NumberFormat::Hex.format_values(&[0, 10, 255]), "[0x0, 0xa, 0xff]");
+
+        let root =
+            MerkleTree::new(vec![[Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)]; 2])
+                .unwrap()
+                .root();
+        let elements = root.as_elements().iter().map(|e| e.as_int()).collect::<Vec<_>>();
+        assert_eq!(NumberFormat::Dec.format_word(&root), format!("{elements:?}"));
+        assert_eq!(
+            NumberFormat::Hex.format_word(&root),
+            format!("0x{}", hex::encode(root.as_bytes()))
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use super::data::{Debug, InputFile, Libraries, ProgramFile};
+use super::digest::display_digest;
 use clap::Parser;
 use rustyline::{error::ReadlineError, Config, DefaultEditor, EditMode};
 use std::path::PathBuf;
@@ -12,18 +13,33 @@ use executor::DebugExecutor;
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Debug a miden program")]
 pub struct DebugCmd {
+    /// Allow modules of libraries loaded later to shadow modules with the same path from
+    /// libraries loaded earlier
+    #[clap(long = "allow-shadowing")]
+    allow_shadowing: bool,
     /// Path to .masm assembly file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
     /// Path to input file
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
+    /// Substitute `${VAR}` and `${VAR:-default}` references to environment variables in the
+    /// input file
+    #[clap(long = "input-template")]
+    input_template: bool,
     /// Enable vi edit mode
     #[clap(long = "vi", long = "vim_edit_mode")]
     vim_edit_mode: Option<String>,
     /// Paths to .masl library files
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
+    /// Skip verification of the checksums of library files
+    #[clap(long = "skip-checksum")]
+    skip_checksum: bool,
+    /// Require words in the input file (Merkle store nodes and advice map keys) to be prefixed
+    /// with 0x
+    #[clap(long = "strict-hex")]
+    strict_hex: bool,
 }
 
 impl DebugCmd {
@@ -33,17 +49,21 @@ impl DebugCmd {
         println!("============================================================");
 
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let libraries =
+            Libraries::new(&self.library_paths, self.allow_shadowing, self.skip_checksum)?;
 
         // load program from file and compile
-        let program =
-            ProgramFile::read(&self.assembly_file)?.compile(&Debug::On, libraries.libraries)?;
+        let program = ProgramFile::read(&self.assembly_file)?.compile(&Debug::On, libraries)?;
 
         let program_hash: [u8; 32] = program.hash().into();
-        println!("Debugging program with hash {}... ", hex::encode(program_hash));
+        println!("Debugging program with hash {}... ", display_digest(program_hash));
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let input_data =
+            InputFile::read(&self.input_file, &self.assembly_file, self.input_template)?;
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
 
         // fetch the stack and program inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
@@ -0,0 +1,298 @@
+use super::data::ProgramFile;
+use assembly::ast::ProgramAst;
+use clap::Parser;
+use std::{fmt, path::PathBuf};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Show the instruction-level differences between two miden programs")]
+pub struct DiffProgramsCmd {
+    /// Path to the original .masm assembly file
+    #[clap(value_parser)]
+    old_file: PathBuf,
+
+    /// Path to the modified .masm assembly file
+    #[clap(value_parser)]
+    new_file: PathBuf,
+}
+
+impl DiffProgramsCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let old_program = ProgramFile::read(&self.old_file)?;
+        let new_program = ProgramFile::read(&self.new_file)?;
+
+        let diff = ProgramDiff::new(old_program.ast(), new_program.ast());
+        if diff.is_empty() {
+            println!("Programs are identical");
+        } else {
+            println!("--- {}", self.old_file.display());
+            println!("+++ {}", self.new_file.display());
+            print!("{diff}");
+        }
+
+        Ok(())
+    }
+}
+
+// PROGRAM DIFF
+// ================================================================================================
+
+/// A line of a diff between two programs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Unchanged(String),
+    Removed(String),
+    Added(String),
+}
+
+/// Differences between the sections of two programs.
+///
+/// The imports, each procedure, and the program body are compared separately; procedures are
+/// matched by name, so a procedure present in only one of the programs is shown as entirely
+/// added or removed. The sections are compared after formatting both programs in the same way
+/// (see [ProgramSections]), and thus, the diff does not depend on the formatting of the source
+/// files. Only the sections which differ are included.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgramDiff {
+    sections: Vec<(String, Vec<DiffLine>)>,
+}
+
+impl ProgramDiff {
+    /// Returns the differences between the specified programs.
+    pub fn new(old: &ProgramAst, new: &ProgramAst) -> Self {
+        let old = ProgramSections::new(old);
+        let new = ProgramSections::new(new);
+        let mut diff = Self::default();
+
+        diff.add_section("imports", &old.imports, &new.imports);
+
+        // procedures of the old program in order, followed by the ones added in the new program
+        for (name, old_lines) in old.procedures.iter() {
+            let new_lines = new.procedure(name).unwrap_or_default();
+            diff.add_section(&format!("proc {name}"), old_lines, new_lines);
+        }
+        for (name, new_lines) in new.procedures.iter() {
+            if old.procedure(name).is_none() {
+                diff.add_section(&format!("proc {name}"), &[], new_lines);
+            }
+        }
+
+        diff.add_section("begin", &old.body, &new.body);
+        diff
+    }
+
+    /// Returns true if the programs have no differences.
+    pub fn is_empty(&self) -> bool {
+        self.sections.is_empty()
+    }
+
+    /// Adds the diff of the specified section, if the old and the new lines differ.
+    fn add_section(&mut self, title: &str, old: &[String], new: &[String]) {
+        if old != new {
+            self.sections.push((title.to_string(), diff_lines(old, new)));
+        }
+    }
+}
+
+impl fmt::Display for ProgramDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (title, lines) in self.sections.iter() {
+            writeln!(f, "@@ {title} @@")?;
+            for line in lines {
+                match line {
+                    DiffLine::Unchanged(line) => writeln!(f, " {line}")?,
+                    DiffLine::Removed(line) => writeln!(f, "-{line}")?,
+                    DiffLine::Added(line) => writeln!(f, "+{line}")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// PROGRAM SECTIONS
+// ================================================================================================
+
+/// Lines of the formatted source of a program, split into imports, procedures, and the program
+/// body.
+///
+/// The program is formatted by its [fmt::Display] implementation, which puts each instruction on a
+/// separate line, indents nested blocks, and refers to invoked procedures by name; the
+/// terminating `end` of each procedure and of the program body is omitted.
+struct ProgramSections {
+    imports: Vec<String>,
+    procedures: Vec<(String, Vec<String>)>,
+    body: Vec<String>,
+}
+
+impl ProgramSections {
+    fn new(program: &ProgramAst) -> Self {
+        let mut sections = Self {
+            imports: Vec::new(),
+            procedures: Vec::new(),
+            body: Vec::new(),
+        };
+
+        // lines which precede a procedure declaration (i.e., its docs), and the section which the
+        // current line belongs to, if any
+        let mut pending = Vec::new();
+        let mut current: Option<&mut Vec<String>> = None;
+        for line in program.to_string().lines().filter(|line| !line.trim().is_empty()) {
+            if let Some(lines) = current.as_mut() {
+                if line == "end" {
+                    current = None;
+                } else {
+                    lines.push(line.to_string());
+                }
+            } else if line.starts_with("use.") {
+                sections.imports.push(line.to_string());
+            } else if line == "begin" {
+                current = Some(&mut sections.body);
+            } else if line.starts_with("proc.") || line.starts_with("export.") {
+                let name = line.split('.').nth(1).unwrap_or_default().to_string();
+                pending.push(line.to_string());
+                sections.procedures.push((name, core::mem::take(&mut pending)));
+                current = sections.procedures.last_mut().map(|(_, lines)| lines);
+            } else {
+                pending.push(line.to_string());
+            }
+        }
+        sections.imports.sort();
+        sections
+    }
+
+    /// Returns the lines of the procedure with the specified name, if the program defines it.
+    fn procedure(&self, name: &str) -> Option<&[String]> {
+        self.procedures
+            .iter()
+            .find(|(proc_name, _)| proc_name == name)
+            .map(|(_, lines)| lines.as_slice())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a minimal diff between the old and the new lines, computed from their longest common
+/// subsequence; removals are listed before additions at each point of difference.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Unchanged(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i].clone()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j].clone()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().cloned().map(DiffLine::Removed));
+    lines.extend(new[j..].iter().cloned().map(DiffLine::Added));
+    lines
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffLine, ProgramDiff};
+    use assembly::ast::ProgramAst;
+
+    fn diff(old: &str, new: &str) -> ProgramDiff {
+        ProgramDiff::new(&ProgramAst::parse(old).unwrap(), &ProgramAst::parse(new).unwrap())
+    }
+
+    #[test]
+    fn formatting_differences_are_ignored() {
+        let old = "proc.foo push.1 add end begin exec.foo if.true drop end end";
+        let new = "
+            proc.foo
+                push.1
+                add
+            end
+
+            begin
+                exec.foo
+                if.true
+                    drop
+                end
+            end";
+        assert!(diff(old, new).is_empty());
+    }
+
+    #[test]
+    fn instruction_and_procedure_differences() {
+        let old = "
+            use.std::math::u64
+            proc.foo push.1 add end
+            proc.bar drop end
+            begin exec.foo exec.bar exec.u64::wrapping_add end";
+        let new = "
+            use.std::math::u256
+            proc.foo push.1 mul end
+            proc.baz.2 dup end
+            begin exec.foo exec.baz exec.u256::wrapping_add end";
+        let diff = diff(old, new);
+
+        let titles = diff.sections.iter().map(|(title, _)| title.as_str()).collect::<Vec<_>>();
+        assert_eq!(titles, ["imports", "proc foo", "proc bar", "proc baz", "begin"]);
+
+        // the changed instruction is shown within its procedure
+        let foo = &diff.sections[1].1;
+        assert_eq!(
+            foo,
+            &[
+                DiffLine::Unchanged("proc.foo.0".to_string()),
+                DiffLine::Unchanged("    push.1".to_string()),
+                DiffLine::Removed("    add".to_string()),
+                DiffLine::Added("    mul".to_string()),
+            ]
+        );
+
+        // procedures present in only one of the programs are entirely removed or added
+        assert!(diff.sections[2].1.iter().all(|line| matches!(line, DiffLine::Removed(_))));
+        assert!(diff.sections[3].1.iter().all(|line| matches!(line, DiffLine::Added(_))));
+
+        // invoked procedures are referred to by name
+        let expected = "\
+@@ imports @@
+-use.std::math::u64
++use.std::math::u256
+@@ proc foo @@
+ proc.foo.0
+     push.1
+-    add
++    mul
+@@ proc bar @@
+-proc.bar.0
+-    drop
+@@ proc baz @@
++proc.baz.2
++    dup.0
+@@ begin @@
+     exec.foo
+-    exec.bar
+-    exec.u64::wrapping_add
++    exec.baz
++    exec.u256::wrapping_add
+";
+        assert_eq!(diff.to_string(), expected);
+    }
+}
@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// DIGEST DISPLAY
+// ================================================================================================
+
+/// Number of hex characters kept at each end of the digests displayed on the console; 0 if the
+/// digests are displayed in full.
+static DIGEST_ABBREV: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the number of hex characters kept at each end of the digests displayed by
+/// [display_digest()], or displays the digests in full if `chars` is None.
+pub fn set_digest_abbrev(chars: Option<usize>) {
+    DIGEST_ABBREV.store(chars.unwrap_or(0), Ordering::Relaxed);
+}
+
+/// Returns the specified digest hex-encoded for display on the console, abbreviated as set by
+/// [set_digest_abbrev()].
+///
+/// The result is meant to be read by people only, and must never be written into files or used
+/// where the full digest is required (e.g., as an input for verification); use [hex::encode()]
+/// there instead.
+pub fn display_digest(digest: impl AsRef<[u8]>) -> String {
+    abbreviate(&hex::encode(digest), DIGEST_ABBREV.load(Ordering::Relaxed))
+}
+
+/// Abbreviates the specified hex string to its first and last `chars` characters separated by an
+/// ellipsis; the string is returned unchanged if `chars` is 0 or the abbreviation would not be
+/// shorter than the string.
+fn abbreviate(hex: &str, chars: usize) -> String {
+    if chars == 0 || chars.saturating_mul(2) >= hex.len() {
+        return hex.to_string();
+    }
+    format!("{}…{}", &hex[..chars], &hex[hex.len() - chars..])
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::abbreviate;
+
+    #[test]
+    fn abbreviate_digests() {
+        let digest = hex::encode([0xab; 32]);
+        assert_eq!(abbreviate(&digest, 0), digest);
+        assert_eq!(abbreviate(&digest, 4), "abab…abab");
+        assert_eq!(abbreviate("0123456789", 3), "012…789");
+
+        // digests are never padded or truncated beyond their length
+        assert_eq!(abbreviate("0123456789", 5), "0123456789");
+        assert_eq!(abbreviate("0123456789", usize::MAX), "0123456789");
+    }
+}
@@ -0,0 +1,137 @@
+use super::data::{JsonFormat, OutputFile, ProofFile};
+use clap::Parser;
+use miden::{math::StarkField, Digest, StackInputs, StackOutputs};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Print the public inputs recorded in a proof file")]
+pub struct ExtractPublicInputsCmd {
+    /// Path to proof file
+    #[clap(value_parser)]
+    proof_file: PathBuf,
+}
+
+impl ExtractPublicInputsCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Extract public inputs");
+        println!("============================================================");
+
+        let public_inputs = PublicInputs::read(&self.proof_file)?;
+        let operand_stack = public_inputs
+            .stack_inputs
+            .values()
+            .iter()
+            .rev()
+            .map(|value| value.as_int().to_string())
+            .collect::<Vec<_>>();
+        let stack_inputs =
+            serde_json::to_string_pretty(&serde_json::json!({ "operand_stack": operand_stack }))
+                .map_err(|err| format!("Failed to serialize stack inputs - {err}"))?;
+
+        println!("Program hash: {}", hex::encode(public_inputs.program_hash.as_bytes()));
+        println!("Stack inputs:\n{stack_inputs}");
+        println!(
+            "Stack outputs:\n{}",
+            OutputFile::new(&public_inputs.stack_outputs).to_json(JsonFormat::Pretty)?
+        );
+
+        Ok(())
+    }
+}
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of a proven execution, as recorded in the header of a proof file.
+///
+/// The STARK proof itself does not contain the public inputs, and thus, they are read from the
+/// header of the proof file. The values are not authenticated by the proof: a proof is valid for
+/// them only if it verifies against them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicInputs {
+    pub program_hash: Digest,
+    pub stack_inputs: StackInputs,
+    pub stack_outputs: StackOutputs,
+}
+
+impl PublicInputs {
+    /// Reads the public inputs recorded in the proof file at the specified path.
+    ///
+    /// # Errors
+    /// Returns an error if the proof file cannot be read, or if it was created by a version of
+    /// the CLI which did not record the public inputs.
+    pub fn read(proof_file: &Path) -> Result<Self, String> {
+        let (_, header) = ProofFile::read(&Some(proof_file.to_path_buf()), proof_file)?;
+        let header = header.ok_or_else(|| {
+            format!(
+                "Proof file `{}` does not record the public inputs of the proof",
+                proof_file.display()
+            )
+        })?;
+
+        Ok(Self {
+            program_hash: header.program_hash(),
+            stack_inputs: header.stack_inputs().clone(),
+            stack_outputs: header.stack_outputs().clone(),
+        })
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ProofFile, PublicInputs};
+    use miden::{Assembler, DefaultHost, ProgramInfo, ProvingOptions, StackInputs};
+    use std::fs;
+
+    #[test]
+    fn extracted_public_inputs_match_proof() {
+        let dir = tempfile::tempdir().unwrap();
+        let proof_path = dir.path().join("program.proof");
+
+        let program = Assembler::default().compile("begin add mul end").unwrap();
+        let stack_inputs = StackInputs::try_from_values([2, 3, 4]).unwrap();
+        let (stack_outputs, proof) = miden::prove(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+        let proof_len = proof.to_bytes().len();
+        ProofFile::write(
+            proof.clone(),
+            program.hash(),
+            &stack_inputs,
+            &stack_outputs,
+            &Some(proof_path.clone()),
+            dir.path(),
+        )
+        .unwrap();
+
+        // the extracted values are the ones used to generate the proof
+        let public_inputs = PublicInputs::read(&proof_path).unwrap();
+        assert_eq!(public_inputs.program_hash, program.hash());
+        assert_eq!(public_inputs.stack_inputs, stack_inputs);
+        assert_eq!(public_inputs.stack_outputs, stack_outputs);
+        assert_eq!(public_inputs.stack_outputs.stack()[0], 14);
+
+        // and the proof verifies against them
+        miden::verify(
+            ProgramInfo::from(program),
+            public_inputs.stack_inputs,
+            public_inputs.stack_outputs,
+            proof,
+        )
+        .unwrap();
+
+        // proof files without a header do not record the public inputs
+        let bare_path = dir.path().join("bare.proof");
+        let bytes = fs::read(&proof_path).unwrap();
+        fs::write(&bare_path, &bytes[bytes.len() - proof_len..]).unwrap();
+        assert!(PublicInputs::read(&bare_path).is_err());
+    }
+}
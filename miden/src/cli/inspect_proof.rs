@@ -0,0 +1,139 @@
+use super::data::ProofFile;
+use clap::Parser;
+use miden::{ExecutionProof, HashFunction};
+use std::{fmt, path::PathBuf};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Print the structure of a proof without verifying it")]
+pub struct InspectProofCmd {
+    /// Path to proof file
+    #[clap(value_parser)]
+    proof_file: PathBuf,
+}
+
+impl InspectProofCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Inspect proof");
+        println!("============================================================");
+
+        let (proof, _) = ProofFile::read(&Some(self.proof_file.clone()), &self.proof_file)?;
+        println!("{}", ProofStructure::new(&proof));
+
+        Ok(())
+    }
+}
+
+// PROOF STRUCTURE
+// ================================================================================================
+
+/// Parameters describing the structure of an [ExecutionProof], as read from a deserialized proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofStructure {
+    pub hash_fn: HashFunction,
+    pub trace_length: usize,
+    pub blowup_factor: usize,
+    pub lde_domain_size: usize,
+    pub num_queries: usize,
+    pub fri_folding_factor: usize,
+    /// Sizes of the evaluation domains of the FRI layers, starting with the first layer.
+    pub fri_layer_sizes: Vec<usize>,
+    /// Size of the FRI proof in bytes.
+    pub fri_proof_size: usize,
+}
+
+impl ProofStructure {
+    /// Returns the structure of the specified proof.
+    ///
+    /// The domain of every FRI layer is smaller than the domain of the previous layer by the FRI
+    /// folding factor, starting with the LDE domain for the first layer.
+    pub fn new(proof: &ExecutionProof) -> Self {
+        let stark_proof = proof.stark_proof();
+        let options = stark_proof.options();
+        let lde_domain_size = stark_proof.lde_domain_size();
+        let fri_folding_factor = options.to_fri_options().folding_factor();
+        let fri_layer_sizes = (0..stark_proof.fri_proof.num_layers())
+            .scan(lde_domain_size, |domain_size, _| {
+                let layer_size = *domain_size;
+                *domain_size /= fri_folding_factor;
+                Some(layer_size)
+            })
+            .collect();
+
+        Self {
+            hash_fn: proof.hash_fn(),
+            trace_length: stark_proof.trace_length(),
+            blowup_factor: options.blowup_factor(),
+            lde_domain_size,
+            num_queries: options.num_queries(),
+            fri_folding_factor,
+            fri_layer_sizes,
+            fri_proof_size: stark_proof.fri_proof.size(),
+        }
+    }
+}
+
+impl fmt::Display for ProofStructure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Hash function: {:?}", self.hash_fn)?;
+        writeln!(f, "Trace length: {}", self.trace_length)?;
+        writeln!(f, "LDE domain size: {} ({}x blowup)", self.lde_domain_size, self.blowup_factor)?;
+        writeln!(f, "Queries: {}", self.num_queries)?;
+        writeln!(
+            f,
+            "FRI layers: {} (folding factor {}, {} KB)",
+            self.fri_layer_sizes.len(),
+            self.fri_folding_factor,
+            self.fri_proof_size / 1024
+        )?;
+        for (i, size) in self.fri_layer_sizes.iter().enumerate() {
+            let branch = if i + 1 == self.fri_layer_sizes.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            write!(f, "\n{branch} layer {i}: domain size {size}")?;
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ProofStructure;
+    use miden::{
+        Assembler, DefaultHost, FieldExtension, HashFunction, ProvingOptions, StackInputs,
+    };
+
+    #[test]
+    fn proof_structure_matches_options() {
+        let program = Assembler::default().compile("begin push.3 push.5 add end").unwrap();
+        let options = ProvingOptions::new(
+            20,
+            16,
+            0,
+            FieldExtension::Quadratic,
+            4,
+            7,
+            HashFunction::Blake3_192,
+        );
+        let (_, proof) =
+            miden::prove(&program, StackInputs::default(), DefaultHost::default(), options)
+                .unwrap();
+
+        let structure = ProofStructure::new(&proof);
+        assert_eq!(structure.hash_fn, HashFunction::Blake3_192);
+        assert_eq!(structure.trace_length, 64);
+        assert_eq!(structure.blowup_factor, 16);
+        assert_eq!(structure.lde_domain_size, 64 * 16);
+        assert_eq!(structure.num_queries, 20);
+        assert_eq!(structure.fri_folding_factor, 4);
+
+        // the domain is folded by a factor of 4 until the remainder fits into 8 * 16 elements
+        assert_eq!(structure.fri_layer_sizes, vec![1024, 256]);
+        assert!(structure.fri_proof_size > 0);
+    }
+}
@@ -0,0 +1,143 @@
+use assembly::{Library, MaslLibrary};
+use serde_derive::Deserialize;
+use std::{fs, path::Path};
+
+// LIBRARY POLICY
+// ================================================================================================
+
+/// Restrictions on the libraries which may be loaded, used when compiling untrusted programs
+/// against untrusted libraries.
+///
+/// The policy is read from a TOML file of the following form, in which `max_procedure_count` is
+/// optional and limits the number of procedures defined across all modules of each library:
+///
+/// ```toml
+/// allowed_namespaces = ["std", "crypto"]
+/// max_procedure_count = 1000
+/// ```
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct LibraryPolicy {
+    pub allowed_namespaces: Vec<String>,
+    #[serde(default = "unlimited_procedure_count")]
+    pub max_procedure_count: usize,
+}
+
+impl LibraryPolicy {
+    /// Reads the library policy from the specified TOML file.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        statusln!("Reading library policy file `{}`", path.display());
+        let source = fs::read_to_string(path).map_err(|err| {
+            format!("Failed to open library policy file `{}` - {}", path.display(), err)
+        })?;
+        Self::parse(&source).map_err(|err| {
+            format!("Failed to parse library policy file `{}` - {}", path.display(), err)
+        })
+    }
+
+    /// Returns an error if the library violates this policy.
+    pub fn check(&self, library: &MaslLibrary) -> Result<(), String> {
+        let namespace = library.root_ns().as_ref();
+        if !self.allowed_namespaces.iter().any(|allowed| allowed == namespace) {
+            return Err(format!(
+                "Library namespace `{namespace}` is not allowed by the library policy"
+            ));
+        }
+
+        let num_procedures =
+            library.modules().map(|module| module.ast.procs().len()).sum::<usize>();
+        if num_procedures > self.max_procedure_count {
+            return Err(format!(
+                "Library `{namespace}` defines {num_procedures} procedures, but the library \
+                policy allows at most {}",
+                self.max_procedure_count
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Parses a library policy from TOML source.
+    fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|err| err.message().to_string())
+    }
+}
+
+/// Returns the procedure count used when a policy does not limit the number of procedures.
+fn unlimited_procedure_count() -> usize {
+    usize::MAX
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::super::data::Libraries;
+    use super::LibraryPolicy;
+    use assembly::{LibraryNamespace, MaslLibrary, Version};
+    use std::fs;
+
+    #[test]
+    fn library_policy_parsing() {
+        let policy = LibraryPolicy::parse(
+            "# libraries allowed in proving jobs
+            allowed_namespaces = [
+                \"std\",
+                \"crypto\", # trailing commas and comments are allowed
+            ]
+            max_procedure_count = 2",
+        )
+        .unwrap();
+        assert_eq!(policy.allowed_namespaces, ["std", "crypto"]);
+        assert_eq!(policy.max_procedure_count, 2);
+
+        // the procedure count is optional, but the namespaces are required
+        let policy = LibraryPolicy::parse("allowed_namespaces = []").unwrap();
+        assert_eq!(policy.max_procedure_count, usize::MAX);
+        assert!(LibraryPolicy::parse("max_procedure_count = 2").is_err());
+        assert!(LibraryPolicy::parse("allowed_namespaces = \"std\"").is_err());
+        assert!(LibraryPolicy::parse("allowed_namespaces = []\nunknown = 1").is_err());
+        assert!(LibraryPolicy::parse("allowed_namespaces = []\nmax_procedure_count = -1").is_err());
+    }
+
+    #[test]
+    fn library_policy_checks() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_dir = dir.path().join("src");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::write(src_dir.join("utils.masm"), "export.foo push.1 end export.bar push.2 end")
+            .unwrap();
+        let namespace = LibraryNamespace::try_from("custom".to_string()).unwrap();
+        let library =
+            MaslLibrary::read_from_dir(src_dir, namespace, false, Version::default()).unwrap();
+        library.write_to_dir(dir.path()).unwrap();
+        let libraries = || Libraries::new([dir.path().join("custom.masl")], false, false).unwrap();
+
+        // a library with a namespace which is not allowed is rejected
+        let policy = LibraryPolicy {
+            allowed_namespaces: vec!["std".to_string()],
+            max_procedure_count: usize::MAX,
+        };
+        let err = libraries().with_policy(Some(&policy)).err().unwrap();
+        assert_eq!(err, "Library namespace `custom` is not allowed by the library policy");
+
+        // an allowed library must not define more procedures than allowed
+        let policy = LibraryPolicy {
+            allowed_namespaces: vec!["std".to_string(), "custom".to_string()],
+            max_procedure_count: 1,
+        };
+        let err = libraries().with_policy(Some(&policy)).err().unwrap();
+        assert_eq!(
+            err,
+            "Library `custom` defines 2 procedures, but the library policy allows at most 1"
+        );
+
+        let policy = LibraryPolicy {
+            max_procedure_count: 2,
+            ..policy
+        };
+        assert!(libraries().with_policy(Some(&policy)).is_ok());
+        assert!(libraries().with_policy(None).is_ok());
+    }
+}
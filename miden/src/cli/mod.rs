@@ -1,17 +1,50 @@
+#[macro_use]
+mod status;
+
+mod advice_key;
+mod build_store;
 mod bundle;
+mod check_merkle;
 mod compile;
+mod compile_all;
+mod compile_to_verifier;
+mod coverage;
 mod data;
 mod debug;
+mod diff_programs;
+mod digest;
+mod extract_public_inputs;
+mod inspect_proof;
+mod library_policy;
+mod pipeline;
+mod profiling;
 mod prove;
 mod repl;
 mod run;
+mod spans;
+mod telemetry;
 mod verify;
+mod verify_stack_effects;
 
+pub use advice_key::AdviceKeyCmd;
+pub use build_store::BuildStoreCmd;
 pub use bundle::BundleCmd;
+pub use check_merkle::CheckMerkleCmd;
 pub use compile::CompileCmd;
+pub use compile_all::CompileAllCmd;
+pub use compile_to_verifier::CompileToVerifierCmd;
 pub use data::InputFile;
 pub use debug::DebugCmd;
+pub use diff_programs::DiffProgramsCmd;
+pub use digest::set_digest_abbrev;
+pub use extract_public_inputs::ExtractPublicInputsCmd;
+pub use inspect_proof::InspectProofCmd;
+pub use pipeline::PipelineCmd;
+pub use profiling::Profiler;
 pub use prove::ProveCmd;
 pub use repl::ReplCmd;
 pub use run::RunCmd;
+#[cfg(feature = "tracing")]
+pub use spans::init_subscriber;
 pub use verify::VerifyCmd;
+pub use verify_stack_effects::VerifyStackEffectsCmd;
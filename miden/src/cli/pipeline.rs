@@ -0,0 +1,255 @@
+use super::data::{
+    execution_error_cycle, Debug, InputFile, JsonFormat, Libraries, OutputFile, ProgramFile,
+    ProofFile,
+};
+use clap::Parser;
+use miden::{DefaultHost, Program, ProvingOptions, StackInputs, StackOutputs};
+use processor::{ExecutionError, ExecutionOptions, Host};
+use std::{
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(about = "Run a sequence of miden programs, feeding the outputs of each into the next")]
+pub struct PipelineCmd {
+    /// Write the output file in the canonical JSON form: on a single line without whitespace,
+    /// with the keys of all objects sorted
+    #[clap(long = "canonical-json")]
+    canonical_json: bool,
+
+    /// Path to input file for the first stage; the advice inputs are shared by all stages
+    #[clap(short = 'i', long = "input", alias = "inputs", value_parser)]
+    input_file: Option<PathBuf>,
+
+    /// Paths to .masl library files
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+
+    /// Maximum number of cycles each stage is allowed to consume
+    #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
+    max_cycles: u32,
+
+    /// Number of outputs
+    #[clap(short = 'n', long = "num-outputs", default_value = "16")]
+    num_outputs: usize,
+
+    /// Path to output file for the outputs of the last stage
+    #[clap(short = 'o', long = "output", value_parser)]
+    output_file: Option<PathBuf>,
+
+    /// Generate a proof of every stage, written next to the .masm file of the stage; the stack
+    /// inputs of a stage's proof are all stack outputs of the previous stage
+    #[clap(long = "prove")]
+    prove: bool,
+
+    /// Comma-separated paths to .masm assembly files of the stages, in the order in which they
+    /// are executed
+    #[clap(long = "stages", value_parser, value_delimiter = ',')]
+    stages: Vec<PathBuf>,
+
+    /// Paths to .masm assembly files of the stages, executed after the stages specified by
+    /// `--stages`
+    #[clap(value_parser, required_unless_present = "stages")]
+    programs: Vec<PathBuf>,
+}
+
+impl PipelineCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        println!("============================================================");
+        println!("Run program pipeline");
+        println!("============================================================");
+
+        let stack_outputs = self.run_pipeline()?;
+
+        if let Some(output_path) = &self.output_file {
+            let json_format = JsonFormat::Pretty.or_canonical(self.canonical_json);
+            OutputFile::write(&stack_outputs, output_path, json_format)?;
+        } else {
+            println!("Output: {:?}", stack_outputs.stack_truncated(self.num_outputs));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the paths to the .masm files of the stages in the order in which they are executed.
+    fn stage_paths(&self) -> impl Iterator<Item = &PathBuf> {
+        self.stages.iter().chain(self.programs.iter())
+    }
+
+    /// Executes the stages in sequence and returns the stack outputs of the last stage.
+    ///
+    /// All stages are compiled before any of them is executed. The stack outputs of each stage
+    /// become the stack inputs of the next one, and all stages are executed against the same
+    /// advice provider, so that advice consumed or injected by a stage is visible to the stages
+    /// which follow it.
+    fn run_pipeline(&self) -> Result<StackOutputs, String> {
+        let stages = self
+            .stage_paths()
+            .map(|path| {
+                let libraries = Libraries::new(&self.library_paths, false, false)?;
+                let program = ProgramFile::read(path)?.compile(&Debug::Off, libraries)?;
+                Ok((path, program))
+            })
+            .collect::<Result<Vec<(&PathBuf, Program)>, String>>()?;
+
+        // the inputs of the pipeline are read for the first stage
+        let first_stage = stages.first().ok_or("The pipeline must have at least one stage")?.0;
+        let input_data = InputFile::read(&self.input_file, first_stage, false)?;
+        let mut stack_inputs = input_data.parse_stack_inputs()?;
+        let mut host = DefaultHost::new(input_data.parse_advice_provider()?);
+
+        let mut stack_outputs = StackOutputs::default();
+        for (i, (path, program)) in stages.iter().enumerate() {
+            print!("Executing stage {} `{}`... ", i + 1, path.display());
+            let now = Instant::now();
+            stack_outputs = self.run_stage(i + 1, path, program, stack_inputs, &mut host)?;
+            println!("done ({} ms)", now.elapsed().as_millis());
+
+            // the outputs are listed from the top of the stack, while inputs are listed in the
+            // order in which they are pushed onto the stack
+            stack_inputs =
+                StackInputs::try_from_values(stack_outputs.stack().iter().rev().copied())
+                    .map_err(|err| format!("{err}"))?;
+        }
+
+        Ok(stack_outputs)
+    }
+
+    /// Executes the specified stage and returns its outputs, writing a proof of the execution
+    /// next to the stage if proving is enabled.
+    fn run_stage<H: Host>(
+        &self,
+        stage: usize,
+        path: &Path,
+        program: &Program,
+        stack_inputs: StackInputs,
+        host: &mut H,
+    ) -> Result<StackOutputs, String> {
+        let execution_options =
+            ExecutionOptions::new(Some(self.max_cycles), 64).map_err(|err| format!("{err}"))?;
+
+        if !self.prove {
+            return processor::execute_outputs(program, stack_inputs, host, execution_options)
+                .map_err(|err| stage_error(stage, path, err));
+        }
+
+        let proving_options =
+            ProvingOptions::with_96_bit_security(false).with_execution_options(execution_options);
+        let (stack_outputs, proof) =
+            miden::prove(program, stack_inputs.clone(), host, proving_options)
+                .map_err(|err| stage_error(stage, path, err))?;
+        ProofFile::write(proof, program.hash(), &stack_inputs, &stack_outputs, &None, path)?;
+        Ok(stack_outputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a message identifying the stage which failed and, if known, the cycle at which it
+/// failed.
+fn stage_error(stage: usize, path: &Path, err: ExecutionError) -> String {
+    match execution_error_cycle(&err) {
+        Some(clk) => {
+            format!("Stage {stage} `{}` failed at cycle {clk} - {:?}", path.display(), err)
+        }
+        None => format!("Stage {stage} `{}` failed - {:?}", path.display(), err),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{PipelineCmd, ProofFile};
+    use miden::{Assembler, StackInputs};
+    use std::{fs, path::PathBuf};
+
+    /// Returns a pipeline command which runs the specified stages against the specified inputs.
+    fn pipeline(input_file: PathBuf, stages: Vec<PathBuf>) -> PipelineCmd {
+        PipelineCmd {
+            canonical_json: false,
+            input_file: Some(input_file),
+            library_paths: Vec::new(),
+            max_cycles: u32::MAX,
+            num_outputs: 16,
+            output_file: None,
+            prove: false,
+            stages,
+            programs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pipe_feeds_outputs_forward() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // both programs read from the advice stack, which is shared by the pipeline
+        let first = dir.path().join("first.masm");
+        let second = dir.path().join("second.masm");
+        let input_file = dir.path().join("first.inputs");
+        fs::write(&first, "begin adv_push.1 add end").unwrap();
+        fs::write(&second, "begin adv_push.1 mul push.1 add end").unwrap();
+        fs::write(&input_file, r#"{ "operand_stack": ["2"], "advice_stack": ["7", "11"] }"#)
+            .unwrap();
+
+        let cmd = PipelineCmd {
+            programs: vec![first, second],
+            ..pipeline(input_file, Vec::new())
+        };
+        let outputs = cmd.run_pipeline().unwrap();
+
+        // (2 + 7) * 11 + 1
+        assert_eq!(outputs.stack()[0], 100);
+        assert!(outputs.stack()[1..16].iter().all(|&value| value == 0));
+    }
+
+    #[test]
+    fn pipeline_proves_stages() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let input_file = dir.path().join("pipeline.inputs");
+        let stages = vec![dir.path().join("a.masm"), dir.path().join("b.masm")];
+        fs::write(&input_file, r#"{ "operand_stack": ["4"] }"#).unwrap();
+        fs::write(&stages[0], "begin dup mul end").unwrap();
+        fs::write(&stages[1], "begin add.3 end").unwrap();
+
+        let cmd = PipelineCmd {
+            prove: true,
+            ..pipeline(input_file, stages.clone())
+        };
+        assert_eq!(cmd.run_pipeline().unwrap().stack()[0], 19);
+
+        // the proof of each stage verifies against the full outputs of the previous stage
+        let mut stack_inputs = StackInputs::try_from_values([4]).unwrap();
+        for (stage, output) in stages.iter().zip([16, 19]) {
+            let program = Assembler::default().compile(fs::read_to_string(stage).unwrap()).unwrap();
+            let (proof, header) = ProofFile::read(&None, stage).unwrap();
+            let stack_outputs = header.unwrap().stack_outputs().clone();
+            assert_eq!(stack_outputs.stack()[0], output);
+            miden::verify(program.into(), stack_inputs, stack_outputs.clone(), proof).unwrap();
+            stack_inputs =
+                StackInputs::try_from_values(stack_outputs.stack().iter().rev().copied()).unwrap();
+        }
+    }
+
+    #[test]
+    fn pipeline_reports_failed_stage() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let input_file = dir.path().join("pipeline.inputs");
+        let stages =
+            vec![dir.path().join("a.masm"), dir.path().join("b.masm"), dir.path().join("c.masm")];
+        fs::write(&input_file, r#"{ "operand_stack": ["1"] }"#).unwrap();
+        fs::write(&stages[0], "begin add.1 end").unwrap();
+        fs::write(&stages[1], "begin push.3 assert_eq end").unwrap();
+        fs::write(&stages[2], "begin add.1 end").unwrap();
+
+        let err = pipeline(input_file, stages.clone()).run_pipeline().unwrap_err();
+        let expected = format!("Stage 2 `{}` failed at cycle", stages[1].display());
+        assert!(err.starts_with(&expected), "unexpected error: {err}");
+        assert!(err.contains("FailedAssertion"), "unexpected error: {err}");
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "profiling")]
+use puffin::GlobalFrameView;
+use std::env;
+
+// PROFILER
+// ================================================================================================
+
+/// Name of the environment variable which enables profiling when set to a value other than `0`.
+const PROFILE_ENV_VAR: &str = "MIDEN_PROFILE";
+
+/// Path of the file into which the recorded profile is written.
+#[cfg(feature = "profiling")]
+const PROFILE_PATH: &str = "miden.puffin";
+
+/// Records the puffin scopes of the proving phases while a command is executed if the
+/// `MIDEN_PROFILE` environment variable is set; otherwise, the profiler does nothing.
+///
+/// The recorded profile is written into a `miden.puffin` file in the current directory, which can
+/// be opened in `puffin_viewer`.
+pub struct Profiler {
+    #[cfg(feature = "profiling")]
+    view: Option<GlobalFrameView>,
+}
+
+impl Profiler {
+    /// Returns a new [Profiler] which records the puffin scopes if `MIDEN_PROFILE` is set.
+    #[cfg(feature = "profiling")]
+    pub fn start() -> Self {
+        let view = is_enabled().then(|| {
+            puffin::set_scopes_on(true);
+            GlobalFrameView::default()
+        });
+        Self { view }
+    }
+
+    /// Returns a new [Profiler] which records the puffin scopes if `MIDEN_PROFILE` is set.
+    ///
+    /// Without the `profiling` feature, nothing is recorded, and a warning is printed if
+    /// `MIDEN_PROFILE` is set.
+    #[cfg(not(feature = "profiling"))]
+    pub fn start() -> Self {
+        if is_enabled() {
+            eprintln!(
+                "warning: {PROFILE_ENV_VAR} is ignored, profiling requires miden to be built with \
+                the `profiling` feature"
+            );
+        }
+        Self {}
+    }
+
+    /// Writes the recorded profile into `miden.puffin`, if profiling is enabled.
+    ///
+    /// # Errors
+    /// Returns an error if the profile cannot be written.
+    pub fn finish(self) -> Result<(), String> {
+        #[cfg(feature = "profiling")]
+        if let Some(view) = self.view {
+            puffin::GlobalProfiler::lock().new_frame();
+            let mut file = std::fs::File::create(PROFILE_PATH)
+                .map_err(|err| format!("Failed to create file `{PROFILE_PATH}` - {err}"))?;
+            view.lock()
+                .write(&mut file)
+                .map_err(|err| format!("Failed to write profile to `{PROFILE_PATH}` - {err}"))?;
+            eprintln!("Profile written to `{PROFILE_PATH}`");
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if `MIDEN_PROFILE` is set to a value other than an empty string or `0`.
+fn is_enabled() -> bool {
+    env::var_os(PROFILE_ENV_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
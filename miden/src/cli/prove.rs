@@ -1,37 +1,147 @@
-use super::data::{Debug, InputFile, Libraries, OutputFile, ProgramFile, ProofFile};
+use super::data::{
+    ArtifactName, ConflictPolicy, Debug, InputFile, JsonFormat, Libraries, OutputFile, ProgramFile,
+    ProofEncoding, ProofFile, ProofOptionsFile,
+};
+use super::digest::display_digest;
+use super::library_policy::LibraryPolicy;
+use super::spans::Phase;
+use super::telemetry::Telemetry;
 use clap::Parser;
-use miden::ProvingOptions;
-use processor::{DefaultHost, ExecutionOptions, ExecutionOptionsError};
-use std::{io::Write, path::PathBuf, time::Instant};
+use miden::{DomainCache, ExecutionProof, Program, ProvingOptions, StackInputs, StackOutputs};
+use processor::{
+    AdviceInputs, DefaultHost, ExecutionError, ExecutionOptions, Host, MemAdviceProvider,
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Instant,
+};
 
 // TODO check if clap is supporting automatic generation of list values of hash function
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Prove a miden program")]
 pub struct ProveCmd {
+    /// Path to advice map file which is merged into the advice inputs
+    #[clap(long = "advice-map", value_parser)]
+    advice_map_file: Option<PathBuf>,
+
+    /// Allow modules of libraries loaded later to shadow modules with the same path from
+    /// libraries loaded earlier
+    #[clap(long = "allow-shadowing")]
+    allow_shadowing: bool,
+
     /// Path to .masm assembly file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
 
+    /// Blowup factor of the low-degree extension: a power of two between 8 and 64 which, together
+    /// with the number of queries, must achieve the selected security level; overrides the value
+    /// from the proof options file
+    #[clap(long = "blowup-factor", value_parser = parse_blowup_factor)]
+    blowup_factor: Option<usize>,
+
+    /// Write the output file in the canonical JSON form: on a single line without whitespace,
+    /// with the keys of all objects sorted
+    #[clap(long = "canonical-json")]
+    canonical_json: bool,
+
+    /// Policy for advice map keys present in both the input file and the advice map file
+    #[clap(long = "conflict-policy", value_enum, default_value_t = ConflictPolicy::Error)]
+    conflict_policy: ConflictPolicy,
+
+    /// Encoding of the proof file: `binary` or `base64`; if not specified, the proof is written as
+    /// base64 text if the proof file has the .b64 extension, and as binary otherwise; default
+    /// proof files of base64 proofs are named with the .proof.b64 extension
+    #[clap(long = "encoding", value_enum)]
+    encoding: Option<ProofEncoding>,
+
+    /// Expected values at the top of the stack after execution (comma-separated); proving fails
+    /// before the proof is written if the outputs do not match
+    #[clap(long = "expect", value_delimiter = ',')]
+    expected_outputs: Vec<u64>,
+
     /// Number of cycles the program is expected to consume
     #[clap(short = 'e', long = "exp-cycles", default_value = "64")]
     expected_cycles: u32,
 
-    /// Path to input file
+    /// Degree of the extension of the base field used by FRI: 2 (quadratic) or 3 (cubic); overrides
+    /// the value from the proof options file and the security level default
+    #[clap(long = "extension", value_parser = clap::value_parser!(u8).range(2..=3))]
+    extension: Option<u8>,
+
+    /// Hash function used for the commitments of the STARK proof: `blake3_192`, `blake3_256` (or
+    /// `blake3`), or `rpo256` (or `rpo`); overrides the value from the proof options file and the
+    /// `--recursive` flag
+    #[clap(long = "hash-fn")]
+    hash_fn: Option<String>,
+
+    /// Name the default proof and output files after the program hash and a hash of the inputs
+    /// (`<program hash>-<inputs hash>.proof`) instead of after the program file
+    #[clap(long = "hash-names")]
+    hash_names: bool,
+
+    /// Path to input file, or `-` to read the inputs from stdin
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
 
+    /// Substitute `${VAR}` and `${VAR:-default}` references to environment variables in the
+    /// input file
+    #[clap(long = "input-template")]
+    input_template: bool,
+
+    /// Path to a directory with .inputs files; a proof and an outputs file is generated for each
+    /// of the files next to it
+    #[clap(long = "inputs-dir", value_parser)]
+    inputs_dir: Option<PathBuf>,
+
+    /// Number of threads used to prove the cases from the inputs directory in parallel
+    #[clap(short = 'j', long = "jobs", default_value = "1")]
+    jobs: usize,
+
     /// Paths to .masl library files
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
 
+    /// Path to a TOML file with the policy the libraries must comply with: the namespaces the
+    /// libraries may have and the maximum number of procedures each library may define
+    #[clap(long = "library-policy", value_parser)]
+    library_policy: Option<PathBuf>,
+
+    /// Maximum depth of nested procedure calls
+    #[clap(long = "max-call-depth", default_value = "1024")]
+    max_call_depth: usize,
+
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
     max_cycles: u32,
 
+    /// Maximum number of instructions the program may contain
+    #[clap(long = "max-instructions", default_value = "10000000")]
+    max_instructions: usize,
+
+    /// Maximum number of procedures the program may define
+    #[clap(long = "max-procedures", default_value = "65535")]
+    max_procedures: usize,
+
+    /// Maximum size of the proof in bytes; proving is aborted before the proof is generated if
+    /// the size estimated from the trace length and the proof options exceeds it
+    #[clap(long = "max-proof-bytes")]
+    max_proof_bytes: Option<usize>,
+
     /// Number of outputs
     #[clap(short = 'n', long = "num-outputs", default_value = "16")]
     num_outputs: usize,
 
+    /// Number of queries for the STARK proof; overrides the value from the proof options file
+    #[clap(long = "num-queries")]
+    num_queries: Option<usize>,
+
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
@@ -40,24 +150,82 @@ pub struct ProveCmd {
     #[clap(short = 'p', long = "proof", value_parser)]
     proof_file: Option<PathBuf>,
 
+    /// Path to a JSON file with STARK proof parameters which are applied on top of the selected
+    /// security level
+    #[clap(long = "proof-options", value_parser)]
+    proof_options_file: Option<PathBuf>,
+
     /// Enable generation of proofs suitable for recursive verification
     #[clap(short = 'r', long = "recursive")]
     recursive: bool,
 
+    /// URL pattern from which advice map entries missing from the inputs are fetched, with
+    /// `{key_hex}` standing for the hex-encoded key; requires the `remote-advice` feature
+    #[clap(long = "remote-advice-url")]
+    remote_advice_url: Option<String>,
+
     /// Security level for execution proofs generated by the VM
     #[clap(short = 's', long = "security", default_value = "96bits")]
     security: String,
+
+    /// Skip verification of the checksums of library files
+    #[clap(long = "skip-checksum")]
+    skip_checksum: bool,
+
+    /// Fail if an advice map defines the same key more than once
+    #[clap(long = "strict-advice")]
+    strict_advice: bool,
+
+    /// Require words in the input file (Merkle store nodes and advice map keys) to be prefixed
+    /// with 0x
+    #[clap(long = "strict-hex")]
+    strict_hex: bool,
+
+    /// URL of an OTLP collector to which the proving time and the proof size are exported;
+    /// requires the `telemetry` feature
+    #[clap(long = "telemetry-endpoint")]
+    telemetry_endpoint: Option<String>,
+
+    /// Print the time spent in each phase of parsing the input file to stderr
+    #[clap(long = "time-input-parse")]
+    time_input_parse: bool,
 }
 
 impl ProveCmd {
-    pub fn get_proof_options(&self) -> Result<ProvingOptions, ExecutionOptionsError> {
-        let exec_options = ExecutionOptions::new(Some(self.max_cycles), self.expected_cycles)?;
-        Ok(match self.security.as_str() {
-            "96bits" => ProvingOptions::with_96_bit_security(self.recursive),
-            "128bits" => ProvingOptions::with_128_bit_security(self.recursive),
+    /// Returns proving options for the selected security level with the parameters from the proof
+    /// options file and the command line applied on top of them; parameters specified on the
+    /// command line take precedence over the ones specified in the file.
+    pub fn get_proof_options(&self) -> Result<ProvingOptions, String> {
+        let exec_options = ExecutionOptions::new(Some(self.max_cycles), self.expected_cycles)
+            .map_err(|err| format!("{err}"))?
+            .with_max_call_depth(self.max_call_depth);
+        let (proving_options, security_bits) = match self.security.as_str() {
+            "96bits" => (ProvingOptions::with_96_bit_security(self.recursive), 96),
+            "128bits" => (ProvingOptions::with_128_bit_security(self.recursive), 128),
             other => panic!("{} is not a valid security setting", other),
+        };
+        let proving_options = proving_options.with_execution_options(exec_options);
+
+        let mut options_file = match &self.proof_options_file {
+            Some(path) => ProofOptionsFile::read(path)?,
+            None => ProofOptionsFile::default(),
+        };
+        options_file.blowup_factor = self.blowup_factor.or(options_file.blowup_factor);
+        options_file.num_queries = self.num_queries.or(options_file.num_queries);
+        options_file.hash_fn = self.hash_fn.clone().or(options_file.hash_fn);
+        if let Some(degree) = self.extension {
+            let field_extension = if degree == 2 { "quadratic" } else { "cubic" };
+            options_file.field_extension = Some(field_extension.to_string());
         }
-        .with_execution_options(exec_options))
+
+        let proving_options = options_file.apply(proving_options)?;
+        check_extension_security(
+            proving_options.proof_options.field_extension().degree(),
+            security_bits,
+        )?;
+        check_min_blowup_factor(&proving_options)?;
+        check_blowup_security(&proving_options, security_bits)?;
+        Ok(proving_options)
     }
 
     pub fn execute(&self) -> Result<(), String> {
@@ -71,55 +239,796 @@ impl ProveCmd {
             .filter_level(log::LevelFilter::Debug)
             .init();
 
+        // load program from file and make sure it is not too large to compile
+        let program_file = ProgramFile::read(&self.assembly_file)?;
+        program_file.check_size(self.max_instructions, self.max_procedures)?;
+
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let library_policy = self.library_policy.as_deref().map(LibraryPolicy::read).transpose()?;
+        let libraries =
+            Libraries::new(&self.library_paths, self.allow_shadowing, self.skip_checksum)?
+                .with_policy(library_policy.as_ref())?;
+
+        // compile the program
+        let program = program_file.compile(&Debug::Off, libraries)?;
 
-        // load program from file and compile
-        let program =
-            ProgramFile::read(&self.assembly_file)?.compile(&Debug::Off, libraries.libraries)?;
+        let telemetry = Telemetry::connect(self.telemetry_endpoint.as_deref())?;
+
+        // in batch mode, prove every case from the inputs directory
+        if let Some(inputs_dir) = &self.inputs_dir {
+            self.prove_batch(&program, inputs_dir, &telemetry)?;
+            return telemetry.shutdown();
+        }
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let input_data =
+            InputFile::read(&self.input_file, &self.assembly_file, self.input_template)?;
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
+
+        // write the initial memory of the program, if any, via a loader which reads the words from
+        // the advice stack before the program is executed; the loader is part of the proven program
+        let memory = input_data.parse_memory()?;
+        let program = memory.seed(&program);
+        let input_data = input_data.with_memory(&memory);
 
         let program_hash: [u8; 32] = program.hash().into();
-        println!("Proving program with hash {}...", hex::encode(program_hash));
+        println!("Proving program with hash {}...", display_digest(program_hash));
         let now = Instant::now();
 
         // fetch the stack and program inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
-        let host = DefaultHost::new(input_data.parse_advice_provider()?);
+        let advice_inputs = input_data.parse_advice_inputs_with_map(
+            self.advice_map_file.as_deref(),
+            self.conflict_policy,
+            self.strict_advice,
+        )?;
+        if self.time_input_parse {
+            eprint!("{}", input_data.parse_timings.to_table());
+        }
 
-        let proving_options = self.get_proof_options().map_err(|err| format!("{err}"))?;
+        let proving_options = self.get_proof_options()?;
 
         // execute program and generate proof
-        let (stack_outputs, proof) =
-            prover::prove(&program, stack_inputs, host, proving_options)
-                .map_err(|err| format!("Failed to prove program - {:?}", err))?;
+        let (stack_outputs, proof) = self.prove_with_advice(
+            &program,
+            stack_inputs.clone(),
+            advice_inputs,
+            proving_options,
+            &DomainCache::new(),
+        )?;
 
         println!(
             "Program with hash {} proved in {} ms",
-            hex::encode(program_hash),
+            display_digest(program_hash),
             now.elapsed().as_millis()
         );
+        telemetry.record_proving(now.elapsed(), proof.to_bytes().len());
+
+        // make sure the outputs are as expected before writing the proof
+        check_expected_outputs(&self.expected_outputs, &stack_outputs)?;
+
+        // when naming artifacts by hash, the default proof and output files are named after the
+        // program and the inputs rather than after the program file
+        let artifact_name = if self.hash_names {
+            let name = ArtifactName::new(program.hash(), &input_data, &self.advice_map_file)?;
+            println!("Artifact name is {name}");
+            Some(name)
+        } else {
+            None
+        };
 
         // write proof to file
-        ProofFile::write(proof, &self.proof_file, &self.assembly_file)?;
+        let proof_path = match &artifact_name {
+            Some(name) if self.proof_file.is_none() => {
+                Some(name.path(&self.assembly_file, &ProofFile::extension(self.encoding)))
+            }
+            _ => self.proof_file.clone(),
+        };
+        ProofFile::write_encoded(
+            proof,
+            program.hash(),
+            &stack_inputs,
+            &stack_outputs,
+            &proof_path,
+            &self.assembly_file,
+            self.encoding,
+        )?;
 
         // provide outputs
         if let Some(output_path) = &self.output_file {
             // write all outputs to specified file.
-            OutputFile::write(&stack_outputs, output_path)?;
+            self.write_outputs(&stack_outputs, output_path)?;
         } else {
             // if no output path was provided, get the stack outputs for printing to the screen.
             let stack = stack_outputs.stack_truncated(self.num_outputs).to_vec();
 
             // write all outputs to default location if none was provided
-            OutputFile::write(&stack_outputs, &self.assembly_file.with_extension("outputs"))?;
+            let output_path = match &artifact_name {
+                Some(name) => name.path(&self.assembly_file, "outputs"),
+                None => self.assembly_file.with_extension("outputs"),
+            };
+            self.write_outputs(&stack_outputs, &output_path)?;
 
             // print stack outputs to screen.
             println!("Output: {:?}", stack);
         }
 
-        Ok(())
+        telemetry.shutdown()
+    }
+
+    /// Proves the program against every `.inputs` file in the specified directory, using a pool
+    /// of `jobs` threads which share the compiled program.
+    ///
+    /// For each `<case>.inputs` file, the proof is written to `<case>.proof` and the outputs to
+    /// `<case>.outputs` in the same directory. The proof of every case is identical to the proof
+    /// generated when proving the case on its own.
+    ///
+    /// # Errors
+    /// Returns an error listing all cases which failed to be proven; all other cases are proven
+    /// regardless of such failures.
+    fn prove_batch(
+        &self,
+        program: &Program,
+        inputs_dir: &Path,
+        telemetry: &Telemetry,
+    ) -> Result<(), String> {
+        if self.jobs == 0 {
+            return Err("Number of jobs must be greater than 0".to_string());
+        }
+        if self.input_file.is_some() || self.proof_file.is_some() || self.output_file.is_some() {
+            return Err(
+                "Input, proof, and output files cannot be specified together with an inputs \
+                directory"
+                    .to_string(),
+            );
+        }
+
+        let mut cases = fs::read_dir(inputs_dir)
+            .map_err(|err| {
+                format!("Failed to read inputs directory `{}` - {}", inputs_dir.display(), err)
+            })?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "inputs"))
+            .collect::<Vec<_>>();
+        cases.sort();
+
+        let program_hash: [u8; 32] = program.hash().into();
+        println!(
+            "Proving program with hash {} for {} cases using {} threads...",
+            display_digest(program_hash),
+            cases.len(),
+            self.jobs
+        );
+        let now = Instant::now();
+
+        // each thread takes the next unproven case until all cases are proven; cases with traces
+        // of the same length share the parameters of their domains
+        let domain_cache = DomainCache::new();
+        let next_case = AtomicUsize::new(0);
+        let failures = Mutex::new(Vec::new());
+        thread::scope(|scope| {
+            for _ in 0..self.jobs.min(cases.len()) {
+                scope.spawn(|| {
+                    while let Some(case) = cases.get(next_case.fetch_add(1, Ordering::Relaxed)) {
+                        if let Err(err) = self.prove_case(program, case, telemetry, &domain_cache) {
+                            failures.lock().unwrap().push(format!("{}: {err}", case.display()));
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut failures = failures.into_inner().unwrap();
+        println!(
+            "Proved {} of {} cases in {} ms",
+            cases.len() - failures.len(),
+            cases.len(),
+            now.elapsed().as_millis()
+        );
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            failures.sort();
+            Err(format!("Failed to prove {} cases:\n{}", failures.len(), failures.join("\n")))
+        }
+    }
+
+    /// Executes the program against the specified inputs and generates a proof of the execution.
+    ///
+    /// Advice map entries missing from the advice inputs are fetched from `--remote-advice-url`,
+    /// if one was specified.
+    fn prove_with_advice(
+        &self,
+        program: &Program,
+        stack_inputs: StackInputs,
+        advice_inputs: AdviceInputs,
+        proving_options: ProvingOptions,
+        domain_cache: &DomainCache,
+    ) -> Result<(StackOutputs, ExecutionProof), String> {
+        match &self.remote_advice_url {
+            Some(url_pattern) => {
+                let host = DefaultHost::new(remote_advice_provider(advice_inputs, url_pattern)?);
+                self.prove_program(program, stack_inputs, host, proving_options, domain_cache)
+            }
+            None => {
+                let host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+                self.prove_program(program, stack_inputs, host, proving_options, domain_cache)
+            }
+        }
+    }
+
+    /// Executes and proves the program, enforcing the proof size budget if one was specified.
+    ///
+    /// Unless a budget was specified, the domain parameters are taken from the specified cache.
+    fn prove_program<H: Host>(
+        &self,
+        program: &Program,
+        stack_inputs: StackInputs,
+        host: H,
+        proving_options: ProvingOptions,
+        domain_cache: &DomainCache,
+    ) -> Result<(StackOutputs, ExecutionProof), String> {
+        let _phase = Phase::Prove.enter();
+        let result = match self.max_proof_bytes {
+            Some(max_proof_bytes) => prover::prove_with_size_budget(
+                program,
+                stack_inputs,
+                host,
+                proving_options,
+                max_proof_bytes,
+            ),
+            None => prover::prove_with_domain_cache(
+                program,
+                stack_inputs,
+                host,
+                proving_options,
+                domain_cache,
+            ),
+        };
+        result.map_err(|err| match err {
+            ExecutionError::ProofSizeExceeded { .. } => format!("{err}"),
+            err => format!("Failed to prove program - {:?}", err),
+        })
+    }
+
+    /// Proves the program against the inputs from the specified file and writes the proof and the
+    /// outputs next to it.
+    fn prove_case(
+        &self,
+        program: &Program,
+        inputs_path: &Path,
+        telemetry: &Telemetry,
+        domain_cache: &DomainCache,
+    ) -> Result<(), String> {
+        let input_data = InputFile::read(
+            &Some(inputs_path.to_path_buf()),
+            &self.assembly_file,
+            self.input_template,
+        )?;
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
+        let memory = input_data.parse_memory()?;
+        let program = &memory.seed(program);
+        let input_data = input_data.with_memory(&memory);
+
+        let stack_inputs = input_data.parse_stack_inputs()?;
+        let advice_inputs = input_data.parse_advice_inputs_with_map(
+            self.advice_map_file.as_deref(),
+            self.conflict_policy,
+            self.strict_advice,
+        )?;
+        if self.time_input_parse {
+            let table = input_data.parse_timings.to_table();
+            eprint!("Input parse timings of `{}`:\n{table}", inputs_path.display());
+        }
+
+        let proving_options = self.get_proof_options()?;
+        let now = Instant::now();
+        let (stack_outputs, proof) = self.prove_with_advice(
+            program,
+            stack_inputs.clone(),
+            advice_inputs,
+            proving_options,
+            domain_cache,
+        )?;
+        telemetry.record_proving(now.elapsed(), proof.to_bytes().len());
+        check_expected_outputs(&self.expected_outputs, &stack_outputs)?;
+
+        let proof_path = inputs_path.with_extension(ProofFile::extension(self.encoding));
+        ProofFile::write_encoded(
+            proof,
+            program.hash(),
+            &stack_inputs,
+            &stack_outputs,
+            &Some(proof_path),
+            &self.assembly_file,
+            self.encoding,
+        )?;
+        self.write_outputs(&stack_outputs, &inputs_path.with_extension("outputs"))
+    }
+
+    /// Writes the specified stack outputs to the output file at the specified path.
+    fn write_outputs(&self, stack_outputs: &StackOutputs, path: &Path) -> Result<(), String> {
+        OutputFile::write(stack_outputs, path, JsonFormat::Pretty.or_canonical(self.canonical_json))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an advice provider which fetches the advice map entries missing from the specified
+/// inputs from URLs built from the specified pattern.
+///
+/// # Errors
+/// Returns an error if the URL pattern is invalid, or if the binary was built without the
+/// `remote-advice` feature.
+#[cfg(feature = "remote-advice")]
+fn remote_advice_provider(
+    inputs: AdviceInputs,
+    url_pattern: &str,
+) -> Result<processor::RemoteAdviceProvider, String> {
+    processor::RemoteAdviceProvider::new(inputs, url_pattern).map_err(|err| format!("{err}"))
+}
+
+/// Returns an advice provider which fetches the advice map entries missing from the specified
+/// inputs from URLs built from the specified pattern.
+///
+/// # Errors
+/// Returns an error if the URL pattern is invalid, or if the binary was built without the
+/// `remote-advice` feature.
+#[cfg(not(feature = "remote-advice"))]
+fn remote_advice_provider(
+    _inputs: AdviceInputs,
+    _url_pattern: &str,
+) -> Result<MemAdviceProvider, String> {
+    Err("Remote advice requires miden to be built with the `remote-advice` feature".to_string())
+}
+
+/// Checks that an extension of the base field of the specified degree can provide the security
+/// targeted by the selected security level, and at least 96 bits.
+///
+/// The security of a proof is bounded by the size of the field used by FRI minus the logarithm of
+/// the size of the LDE domain. The base field has 64 bits, so a degree 1 extension (i.e., the base
+/// field itself) cannot provide 96-bit security, a degree 2 extension (128 bits) suffices for 96-bit
+/// security only, and a degree 3 extension (192 bits) suffices for both security levels.
+///
+/// # Errors
+/// Returns an error if the extension field is too small for the targeted security.
+fn check_extension_security(degree: u32, security_bits: u32) -> Result<(), String> {
+    let target_bits = security_bits.max(96);
+    let field_bits = u64::BITS * degree;
+    if field_bits <= target_bits {
+        return Err(format!(
+            "Field extension of degree {degree} ({field_bits}-bit field) cannot provide \
+            {target_bits}-bit security; use an extension of degree {}",
+            if target_bits < 128 { "2 or 3" } else { "3" }
+        ));
+    }
+    Ok(())
+}
+
+/// Parses the value of the `--blowup-factor` flag, which must be a power of two between the
+/// minimum blowup factor of the VM (8) and 64.
+fn parse_blowup_factor(value: &str) -> Result<usize, String> {
+    let blowup_factor = value.parse::<usize>().map_err(|err| err.to_string())?;
+    if !blowup_factor.is_power_of_two()
+        || !(ProvingOptions::MIN_BLOWUP_FACTOR..=64).contains(&blowup_factor)
+    {
+        return Err(format!(
+            "blowup factor must be a power of two between {} and 64, but was {blowup_factor}",
+            ProvingOptions::MIN_BLOWUP_FACTOR
+        ));
+    }
+    Ok(blowup_factor)
+}
+
+/// Checks that the blowup factor of the provided options is supported by the constraints of the
+/// VM; proving with a smaller blowup factor is impossible.
+fn check_min_blowup_factor(options: &ProvingOptions) -> Result<(), String> {
+    let blowup_factor = options.proof_options.blowup_factor();
+    if blowup_factor < ProvingOptions::MIN_BLOWUP_FACTOR {
+        return Err(format!(
+            "Blowup factor {blowup_factor} is smaller than the minimum of {} required by the \
+            constraints of the VM",
+            ProvingOptions::MIN_BLOWUP_FACTOR
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the queries of the provided options achieve the specified security level.
+///
+/// Every query contributes log2(blowup factor) bits of conjectured security, and grinding adds the
+/// grinding factor on top of that.
+fn check_blowup_security(options: &ProvingOptions, security_bits: u32) -> Result<(), String> {
+    let proof_options = &options.proof_options;
+    let blowup_bits = proof_options.blowup_factor().ilog2();
+    let grinding_factor = proof_options.grinding_factor();
+    let num_queries = proof_options.num_queries() as u32;
+    let query_bits = num_queries * blowup_bits + grinding_factor;
+    if query_bits < security_bits {
+        let min_queries = (security_bits - grinding_factor).div_ceil(blowup_bits);
+        return Err(format!(
+            "Blowup factor {} with {num_queries} queries and grinding factor {grinding_factor} \
+            provides only {query_bits}-bit security, but {security_bits} bits were requested; use \
+            at least {min_queries} queries",
+            proof_options.blowup_factor()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the top of the stack in the provided outputs matches the expected values.
+///
+/// # Errors
+/// Returns an error describing all mismatched stack positions if the outputs differ from the
+/// expected values.
+fn check_expected_outputs(expected: &[u64], stack_outputs: &StackOutputs) -> Result<(), String> {
+    let actual = stack_outputs.stack_truncated(expected.len());
+    if actual == expected {
+        return Ok(());
+    }
+
+    let mut diff = String::new();
+    for (i, expected_value) in expected.iter().enumerate() {
+        match actual.get(i) {
+            Some(actual_value) if actual_value == expected_value => (),
+            Some(actual_value) => diff.push_str(&format!(
+                "\n  stack[{i}]: expected {expected_value}, got {actual_value}"
+            )),
+            None => {
+                diff.push_str(&format!("\n  stack[{i}]: expected {expected_value}, got nothing"))
+            }
+        }
+    }
+
+    Err(format!("Stack outputs do not match the expected values:{diff}"))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_expected_outputs, check_extension_security, parse_blowup_factor, OutputFile,
+        ProofFile, ProveCmd, Telemetry,
+    };
+    use clap::Parser;
+    use miden::{
+        AdviceInputs, Assembler, DefaultHost, DomainCache, FieldExtension, StackInputs,
+        StackOutputs,
+    };
+    use std::{ffi::OsStr, fs, path::PathBuf};
+
+    #[test]
+    fn expected_outputs_diff() {
+        let stack_outputs = StackOutputs::new(vec![8, 2, 3], vec![]).unwrap();
+
+        assert!(check_expected_outputs(&[], &stack_outputs).is_ok());
+        assert!(check_expected_outputs(&[8, 2], &stack_outputs).is_ok());
+
+        let err = check_expected_outputs(&[8, 5, 3], &stack_outputs).unwrap_err();
+        assert_eq!(
+            err,
+            "Stack outputs do not match the expected values:\n  stack[1]: expected 5, got 2"
+        );
+    }
+
+    #[test]
+    fn extension_security() {
+        assert!(check_extension_security(1, 96).unwrap_err().contains("degree 2 or 3"));
+        assert!(check_extension_security(2, 96).is_ok());
+        assert!(check_extension_security(3, 96).is_ok());
+
+        assert!(check_extension_security(1, 128).is_err());
+        assert!(check_extension_security(2, 128).unwrap_err().contains("128-bit security"));
+        assert!(check_extension_security(3, 128).is_ok());
+    }
+
+    #[test]
+    fn mismatched_expected_outputs_abort_proving() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let proof_file = dir.path().join("program.proof");
+        fs::write(&assembly_file, "begin push.3 push.5 add end").unwrap();
+
+        let cmd = ProveCmd {
+            expected_outputs: vec![9],
+            proof_file: Some(proof_file.clone()),
+            ..prove_cmd(assembly_file)
+        };
+
+        let err = cmd.execute().unwrap_err();
+        assert!(err.contains("stack[0]: expected 9, got 8"));
+        assert!(!proof_file.exists());
+    }
+
+    #[test]
+    fn memory_inputs_are_proven() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let input_file = dir.path().join("program.inputs");
+        let source = "begin padw mem_loadw.7 adv_push.1 end";
+        fs::write(&assembly_file, source).unwrap();
+
+        // the memory is loaded ahead of the advice stack values of the input file
+        let inputs = serde_json::json!({
+            "operand_stack": [],
+            "advice_stack": ["9"],
+            "memory": [[7, [1, 2, 3, 4]]],
+        });
+        fs::write(&input_file, inputs.to_string()).unwrap();
+
+        let cmd = ProveCmd {
+            expected_outputs: vec![9, 4, 3, 2, 1],
+            ..prove_cmd(assembly_file)
+        };
+        let program = Assembler::default().compile(source).unwrap();
+        let telemetry = Telemetry::connect(None).unwrap();
+        cmd.prove_case(&program, &input_file, &telemetry, &DomainCache::new()).unwrap();
+        assert!(input_file.with_extension("proof").exists());
+    }
+
+    #[test]
+    fn proof_size_budget_aborts_proving() {
+        let mut cmd = ProveCmd {
+            max_proof_bytes: Some(1024),
+            ..prove_cmd(PathBuf::from("program.masm"))
+        };
+
+        // the budget is checked against the estimated size before the proof is generated
+        let program = Assembler::default().compile("begin push.3 push.5 add end").unwrap();
+        let options = cmd.get_proof_options().unwrap();
+        let err = cmd
+            .prove_program(
+                &program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                options.clone(),
+                &DomainCache::new(),
+            )
+            .unwrap_err();
+        assert!(err.starts_with("Estimated proof size"), "{err}");
+        assert!(err.ends_with("bytes exceeds budget 1024"), "{err}");
+
+        // programs within the budget are proved as usual
+        cmd.max_proof_bytes = Some(1 << 20);
+        let (stack_outputs, _) = cmd
+            .prove_program(
+                &program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                options,
+                &DomainCache::new(),
+            )
+            .unwrap();
+        assert_eq!(stack_outputs.stack()[0], 8);
+    }
+
+    #[test]
+    fn proof_options_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let options_file = dir.path().join("options.json");
+        fs::write(
+            &options_file,
+            r#"{ "blowup_factor": 16, "num_queries": 20, "field_extension": "cubic" }"#,
+        )
+        .unwrap();
+
+        let build_cmd = |proof_options_file: PathBuf, num_queries| ProveCmd {
+            num_queries,
+            proof_options_file: Some(proof_options_file),
+            ..prove_cmd(dir.path().join("program.masm"))
+        };
+
+        // values from the file are applied, but command line flags take precedence
+        let options = build_cmd(options_file.clone(), Some(24)).get_proof_options().unwrap();
+        assert_eq!(options.proof_options.blowup_factor(), 16);
+        assert_eq!(options.proof_options.num_queries(), 24);
+        assert_eq!(options.proof_options.field_extension(), FieldExtension::Cubic);
+        assert_eq!(options.execution_options().max_call_depth(), 1024);
+
+        // the options shape the generated proof
+        let program = Assembler::default().compile("begin push.3 push.5 add end").unwrap();
+        let (_, proof) =
+            miden::prove(&program, StackInputs::default(), DefaultHost::default(), options)
+                .unwrap();
+        let proof_options = proof.stark_proof().options();
+        assert_eq!(proof_options.blowup_factor(), 16);
+        assert_eq!(proof_options.num_queries(), 24);
+        assert_eq!(proof_options.field_extension(), FieldExtension::Cubic);
+
+        // the extension degree flag overrides the field extension from the file
+        let mut cmd = build_cmd(options_file.clone(), None);
+        cmd.extension = Some(2);
+        let options = cmd.get_proof_options().unwrap();
+        assert_eq!(options.proof_options.field_extension(), FieldExtension::Quadratic);
+        let args = ["prove", "-a", "program.masm", "--extension", "1"];
+        assert!(ProveCmd::try_parse_from(args).is_err());
+
+        // the extension from the file must achieve the security level as well
+        fs::write(&options_file, r#"{ "field_extension": "none" }"#).unwrap();
+        let err = build_cmd(options_file.clone(), None).get_proof_options().unwrap_err();
+        assert!(err.contains("cannot provide 96-bit security"), "{err}");
+        fs::write(&options_file, r#"{ "field_extension": "quadratic" }"#).unwrap();
+        let mut cmd = build_cmd(options_file.clone(), None);
+        cmd.security = "128bits".to_string();
+        let err = cmd.get_proof_options().unwrap_err();
+        assert!(err.contains("cannot provide 128-bit security"), "{err}");
+
+        // invalid combinations are reported as errors
+        fs::write(&options_file, r#"{ "blowup_factor": 6 }"#).unwrap();
+        let err = build_cmd(options_file.clone(), None).get_proof_options().unwrap_err();
+        assert!(err.contains("blowup factor must be a power of two"));
+
+        fs::write(&options_file, r#"{ "fri_remainder_max_degree": 100 }"#).unwrap();
+        let err = build_cmd(options_file, None).get_proof_options().unwrap_err();
+        assert!(err.contains("FRI remainder max degree"));
+    }
+
+    #[test]
+    fn blowup_factor_flag() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let build_cmd = |blowup_factor, security: &str| ProveCmd {
+            blowup_factor: Some(blowup_factor),
+            security: security.to_string(),
+            ..prove_cmd(dir.path().join("program.masm"))
+        };
+
+        // only powers of two between the minimum of the VM and 64 are accepted
+        assert_eq!(parse_blowup_factor("8"), Ok(8));
+        assert_eq!(parse_blowup_factor("64"), Ok(64));
+        for value in ["1", "2", "4", "12", "128", "x"] {
+            assert!(parse_blowup_factor(value).is_err(), "{value}");
+        }
+
+        // blowup factors from the proof options file are checked in the same way
+        let options_file = dir.path().join("options.json");
+        fs::write(&options_file, r#"{ "blowup_factor": 4 }"#).unwrap();
+        let cmd = ProveCmd {
+            proof_options_file: Some(options_file.clone()),
+            ..prove_cmd(dir.path().join("program.masm"))
+        };
+        let err = cmd.get_proof_options().unwrap_err();
+        assert!(err.contains("smaller than the minimum of 8"), "{err}");
+        fs::write(&options_file, r#"{ "blowup_factor": 8, "num_queries": 20 }"#).unwrap();
+        let err = cmd.get_proof_options().unwrap_err();
+        assert!(err.contains("provides only 76-bit security"), "{err}");
+
+        // the queries must achieve the requested security level with the requested blowup
+        let err = build_cmd(8, "128bits").get_proof_options().unwrap_err();
+        assert!(err.contains("provides only 102-bit security"), "{err}");
+        assert!(err.contains("at least 36 queries"), "{err}");
+        let mut cmd = build_cmd(8, "128bits");
+        cmd.num_queries = Some(36);
+        assert!(cmd.get_proof_options().is_ok());
+
+        // the blowup factor is recorded in the header of the proof file
+        let options = build_cmd(32, "96bits").get_proof_options().unwrap();
+        let program = Assembler::default().compile("begin push.3 push.5 add end").unwrap();
+        let (stack_outputs, proof) =
+            miden::prove(&program, StackInputs::default(), DefaultHost::default(), options)
+                .unwrap();
+        let proof_file = Some(dir.path().join("program.proof"));
+        let stack_inputs = StackInputs::default();
+        ProofFile::write(
+            proof,
+            program.hash(),
+            &stack_inputs,
+            &stack_outputs,
+            &proof_file,
+            dir.path(),
+        )
+        .unwrap();
+        let (proof, header) = ProofFile::read(&proof_file, dir.path()).unwrap();
+        assert_eq!(proof.stark_proof().options().blowup_factor(), 32);
+        assert_eq!(header.unwrap().blowup_factor(), 32);
+    }
+
+    #[test]
+    fn batch_proofs_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let source = "begin push.5 add end";
+        fs::write(&assembly_file, source).unwrap();
+        for (i, value) in [3, 8, 13, 21].iter().enumerate() {
+            let inputs = format!(r#"{{ "operand_stack": ["{value}"] }}"#);
+            fs::write(dir.path().join(format!("case{i}.inputs")), inputs).unwrap();
+        }
+
+        let cmd = ProveCmd {
+            inputs_dir: Some(dir.path().to_path_buf()),
+            jobs: 3,
+            ..prove_cmd(assembly_file.clone())
+        };
+        let program = Assembler::default().compile(source).unwrap();
+        cmd.prove_batch(&program, dir.path(), &Telemetry::connect(None).unwrap())
+            .unwrap();
+
+        for (i, value) in [3, 8, 13, 21].iter().enumerate() {
+            let proof_path = dir.path().join(format!("case{i}.proof"));
+            let (proof, _) = ProofFile::read(&Some(proof_path), &assembly_file).unwrap();
+            let outputs_path = dir.path().join(format!("case{i}.outputs"));
+            let outputs = OutputFile::read(&Some(outputs_path), &assembly_file).unwrap();
+            let stack_outputs = outputs.stack_outputs().unwrap();
+            assert_eq!(stack_outputs.stack()[0], value + 5);
+
+            let stack_inputs = StackInputs::try_from_values([*value]).unwrap();
+            miden::verify(
+                program.clone().into(),
+                stack_inputs.clone(),
+                stack_outputs,
+                proof.clone(),
+            )
+            .unwrap();
+
+            // the proof is the same as the one generated when proving the case alone
+            let options = cmd.get_proof_options().unwrap();
+            let (_, expected) =
+                miden::prove(&program, stack_inputs, DefaultHost::default(), options).unwrap();
+            assert_eq!(proof.to_bytes(), expected.to_bytes());
+        }
+    }
+
+    #[test]
+    fn remote_advice_url_is_checked_before_proving() {
+        let cmd = ProveCmd {
+            remote_advice_url: Some("http://localhost:8080/advice".to_string()),
+            ..prove_cmd(PathBuf::from("program.masm"))
+        };
+        let program = Assembler::default().compile("begin push.1 end").unwrap();
+        let options = cmd.get_proof_options().unwrap();
+        let err = cmd
+            .prove_with_advice(
+                &program,
+                StackInputs::default(),
+                AdviceInputs::default(),
+                options,
+                &DomainCache::new(),
+            )
+            .unwrap_err();
+
+        // the URL pattern must contain the key placeholder, and fetching requires the feature
+        #[cfg(feature = "remote-advice")]
+        assert!(err.contains("does not contain {key_hex}"), "{err}");
+        #[cfg(not(feature = "remote-advice"))]
+        assert!(err.contains("`remote-advice` feature"), "{err}");
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn proving_is_recorded_in_span() {
+        let cmd = ProveCmd {
+            ..prove_cmd(PathBuf::from("program.masm"))
+        };
+
+        let program = Assembler::default().compile("begin push.3 push.5 add end").unwrap();
+        let options = cmd.get_proof_options().unwrap();
+        let (result, spans) = super::super::spans::capture_spans(|| {
+            cmd.prove_program(
+                &program,
+                StackInputs::default(),
+                DefaultHost::default(),
+                options,
+                &DomainCache::new(),
+            )
+        });
+        result.unwrap();
+        assert_eq!(spans, ["prove"]);
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a `prove` command for the specified program with all other options set to their
+    /// default values.
+    fn prove_cmd(assembly_file: PathBuf) -> ProveCmd {
+        ProveCmd::parse_from([OsStr::new("prove"), OsStr::new("-a"), assembly_file.as_os_str()])
     }
 }
@@ -1,80 +1,421 @@
-use super::data::{Debug, InputFile, Libraries, OutputFile, ProgramFile};
+use super::coverage::InputCoverage;
+use super::data::{
+    ArtifactName, ConflictPolicy, Debug, Emit, ErrorFormat, ErrorReport, EventsFile, InputFile,
+    JsonFormat, Libraries, MemoryImage, NumberFormat, OutputFile, OutputFormat, OutputLabels,
+    ProgramFile, TraceEvent, TraceFile, TraceRow,
+};
+use super::digest::display_digest;
+use super::spans::Phase;
+use super::status;
+use super::telemetry::Telemetry;
 use clap::Parser;
-use processor::{DefaultHost, ExecutionOptions};
-use std::{path::PathBuf, time::Instant};
+use miden::{Program, StackInputs, StackOutputs};
+use processor::{
+    AdviceExtractor, AdviceInjector, CappedAdviceProvider, DebugOptions, DefaultHost,
+    ExecutionError, ExecutionOptions, Host, HostResponse, Operation, ProcessState,
+};
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Run a miden program")]
 pub struct RunCmd {
+    /// Path to an output file of a previous run whose stack outputs are appended to the advice
+    /// stack, with the top of the stack read first
+    #[clap(long = "advice-from-outputs", value_parser)]
+    advice_from_outputs: Option<PathBuf>,
+
+    /// Path to advice map file which is merged into the advice inputs
+    #[clap(long = "advice-map", value_parser)]
+    advice_map_file: Option<PathBuf>,
+
+    /// Allow modules of libraries loaded later to shadow modules with the same path from
+    /// libraries loaded earlier
+    #[clap(long = "allow-shadowing")]
+    allow_shadowing: bool,
+
     /// Path to .masm assembly file
     #[clap(short = 'a', long = "assembly", value_parser)]
     assembly_file: PathBuf,
 
+    /// Write all JSON (the output file, the events file, the trace file, and JSON error reports)
+    /// in the canonical form: on a single line without whitespace, with the keys of all objects
+    /// sorted
+    #[clap(long = "canonical-json")]
+    canonical_json: bool,
+
+    /// Path to an output file with the expected outputs of the program; the execution fails and
+    /// the differing values are reported if the outputs of the program differ from them
+    #[clap(long = "compare-outputs", value_parser)]
+    compare_outputs: Option<PathBuf>,
+
+    /// Policy for advice map keys present in both the input file and the advice map file
+    #[clap(long = "conflict-policy", value_enum, default_value_t = ConflictPolicy::Error)]
+    conflict_policy: ConflictPolicy,
+
+    /// Fail if the program reads from the advice provider
+    #[clap(long = "deny-advice")]
+    deny_advice: bool,
+
+    /// Fail if the program accesses memory
+    #[clap(long = "deny-memory")]
+    deny_memory: bool,
+
+    /// Comma-separated ways in which the outputs are emitted: `json` and `msgpack` write an output
+    /// file, `stdout` prints the stack values one per line, and `none` emits nothing; if not
+    /// specified, the outputs are written in the output format if an output file is specified
+    /// (or `--hash-names` is set), and printed to the screen otherwise
+    #[clap(
+        long = "emit",
+        value_enum,
+        value_delimiter = ',',
+        conflicts_with = "output_format"
+    )]
+    emit: Vec<Emit>,
+
+    /// Format of the error reported if the program fails to parse, compile, or execute
+    #[clap(long = "error-format", value_enum, default_value_t = ErrorFormat::Text)]
+    error_format: ErrorFormat,
+
+    /// Path to a JSON file to which trace events emitted by the program are written; if not
+    /// specified, the events are printed to the screen
+    #[clap(long = "events", value_parser)]
+    events_file: Option<PathBuf>,
+
     /// Number of cycles the program is expected to consume
     #[clap(short = 'e', long = "exp-cycles", default_value = "64")]
     expected_cycles: u32,
 
-    /// Path to input file
+    /// Write the outputs to a file named after the program hash and a hash of the inputs
+    /// (`<program hash>-<inputs hash>.outputs`) if no output file is specified
+    #[clap(long = "hash-names")]
+    hash_names: bool,
+
+    /// Report how many of the operand stack inputs, advice stack elements, and advice map entries
+    /// were consumed by the program, listing the unused ones; this executes the program a second
+    /// time in debug mode
+    #[clap(long = "input-coverage")]
+    input_coverage: bool,
+
+    /// Path to input file, or `-` to read the inputs from stdin
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
 
+    /// Substitute `${VAR}` and `${VAR:-default}` references to environment variables in the
+    /// input file
+    #[clap(long = "input-template")]
+    input_template: bool,
+
     /// Paths to .masl library files
     #[clap(short = 'l', long = "libraries", value_parser)]
     library_paths: Vec<PathBuf>,
 
+    /// Maximum number of elements a single operation may read from the advice stack; an
+    /// operation reading more elements fails with the cycle at which it was executed
+    #[clap(long = "max-advice-read")]
+    max_advice_read: Option<usize>,
+
+    /// Maximum depth of nested procedure calls
+    #[clap(long = "max-call-depth", default_value = "1024")]
+    max_call_depth: usize,
+
     /// Maximum number of cycles a program is allowed to consume
     #[clap(short = 'm', long = "max-cycles", default_value = "4294967295")]
     max_cycles: u32,
 
+    /// Maximum number of instructions the program may contain
+    #[clap(long = "max-instructions", default_value = "10000000")]
+    max_instructions: usize,
+
+    /// Maximum number of procedures the program may define
+    #[clap(long = "max-procedures", default_value = "65535")]
+    max_procedures: usize,
+
     /// Number of outputs
     #[clap(short = 'n', long = "num-outputs", default_value = "16")]
     num_outputs: usize,
 
+    /// Format of the numbers in the stack outputs, the output file, and the printed Merkle roots
+    #[clap(long = "number-format", value_enum, default_value_t = NumberFormat::Dec)]
+    number_format: NumberFormat,
+
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
+
+    /// Format of the output file
+    #[clap(long = "output-format", value_enum, default_value_t = OutputFormat::Json)]
+    output_format: OutputFormat,
+
+    /// Path to a JSON file declaring a label for each stack output position, used to key the
+    /// stack values in JSON outputs; defaults to the `.labels` file next to the program, or to
+    /// the labels declared by an `# output_labels: [...]` comment in the program
+    #[clap(long = "output-labels", value_parser)]
+    output_labels: Option<PathBuf>,
+
+    /// Number of additional executions of the program used to measure its execution time; the
+    /// minimum, mean, and maximum execution times are reported
+    #[clap(long = "repeat")]
+    repeat: Option<usize>,
+
+    /// Path to a binary memory image whose words are written into memory before the program is
+    /// executed; the image is a sequence of records, each consisting of an address followed by
+    /// the four elements of a word, all encoded as little-endian u64s
+    #[clap(long = "seed-memory-from-file", value_parser)]
+    seed_memory_from_file: Option<PathBuf>,
+
+    /// Report the clock cycle and the operand stack at the point where the execution failed
+    /// together with the error
+    #[clap(long = "show-state-on-error")]
+    show_state_on_error: bool,
+
+    /// Skip verification of the checksums of library files
+    #[clap(long = "skip-checksum")]
+    skip_checksum: bool,
+
+    /// Print the outputs to stdout as JSON (in addition to writing the output file, if one is
+    /// specified); all other messages are printed to stderr
+    #[clap(long = "stdout")]
+    stdout: bool,
+
+    /// Fail if an advice map defines the same key more than once
+    #[clap(long = "strict-advice")]
+    strict_advice: bool,
+
+    /// Require words in the input file (Merkle store nodes and advice map keys) to be prefixed
+    /// with 0x
+    #[clap(long = "strict-hex")]
+    strict_hex: bool,
+
+    /// Fail if the program reads from a memory address which has never been written to
+    #[clap(long = "strict-memory")]
+    strict_memory: bool,
+
+    /// Fail if the program reads more items from the stack than were placed onto it
+    #[clap(long = "strict-stack")]
+    strict_stack: bool,
+
+    /// Fail if a wrapping u32 addition, subtraction, or multiplication overflows 32 bits
+    #[clap(long = "strict-u32")]
+    strict_u32: bool,
+
+    /// URL of an OTLP collector to which the number of cycles consumed by the execution is
+    /// exported; requires the `telemetry` feature
+    #[clap(long = "telemetry-endpoint")]
+    telemetry_endpoint: Option<String>,
+
+    /// Print the time spent in each phase of parsing the input file to stderr
+    #[clap(long = "time-input-parse")]
+    time_input_parse: bool,
+
+    /// Maximum wall-clock time in seconds the program is allowed to execute for
+    #[clap(long = "timeout")]
+    timeout: Option<f64>,
+
+    /// Path to a JSON Lines file to which the state of the VM is written after every cycle
+    #[clap(long = "trace-jsonl", value_parser)]
+    trace_jsonl: Option<PathBuf>,
+
+    /// Number of executions run before the ones measured with `--repeat`; their execution times
+    /// are discarded
+    #[clap(long = "warmup", default_value = "0", requires = "repeat")]
+    warmup: usize,
 }
 
 impl RunCmd {
     pub fn execute(&self) -> Result<(), String> {
-        println!("============================================================");
-        println!("Run program");
-        println!("============================================================");
+        self.run()
+            .map_err(|report| self.error_format.format(report, self.canonical_json))
+    }
+
+    fn run(&self) -> Result<(), ErrorReport> {
+        // when the outputs are printed to stdout, all other messages are moved out of the way
+        let emit = self.emit_formats()?;
+        status::set_status_to_stderr(self.stdout || emit.contains(&Emit::Stdout));
+
+        statusln!("============================================================");
+        statusln!("Run program");
+        statusln!("============================================================");
+
+        // load program from file and make sure it is not too large to compile
+        let program_file = ProgramFile::read(&self.assembly_file)?;
+        program_file.check_size(self.max_instructions, self.max_procedures)?;
 
         // load libraries from files
-        let libraries = Libraries::new(&self.library_paths)?;
+        let libraries =
+            Libraries::new(&self.library_paths, self.allow_shadowing, self.skip_checksum)?;
 
-        // load program from file and compile
-        let program =
-            ProgramFile::read(&self.assembly_file)?.compile(&Debug::Off, libraries.libraries)?;
+        // compile the program; in strict stack and u32 modes, the program is compiled in debug
+        // mode so that stack underflows and overflows can be attributed to assembly instructions,
+        // and so are the operations written to the trace file
+        let debug = if self.strict_stack || self.strict_u32 || self.trace_jsonl.is_some() {
+            Debug::On
+        } else {
+            Debug::Off
+        };
+        let program = program_file.compile(&debug, libraries)?;
+
+        // load the labels of the stack outputs, if any were declared
+        let output_labels = OutputLabels::read(&self.output_labels, &self.assembly_file)?;
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let mut input_data =
+            InputFile::read(&self.input_file, &self.assembly_file, self.input_template)?
+                .with_number_format(self.number_format);
+        if let Some(outputs_path) = &self.advice_from_outputs {
+            let outputs = OutputFile::read(&Some(outputs_path.clone()), &self.assembly_file)?;
+            input_data = input_data.with_advice_from_outputs(&outputs)?;
+        }
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
+
+        // write the initial memory of the program, if any, via a loader which reads the words from
+        // the advice stack before the program is executed
+        let memory = self.read_memory(&input_data)?;
+        let program = memory.seed(&program);
+        input_data = input_data.with_memory(&memory);
 
         // get execution options
         let execution_options = ExecutionOptions::new(Some(self.max_cycles), self.expected_cycles)
-            .map_err(|err| format!("{err}"))?;
+            .map_err(|err| format!("{err}"))?
+            .with_max_call_depth(self.max_call_depth)
+            .with_strict_stack_depth(self.strict_stack)
+            .with_strict_u32_overflow(self.strict_u32)
+            .with_strict_memory(self.strict_memory)
+            .with_deny_memory(self.deny_memory)
+            .with_deny_advice(self.deny_advice)
+            .with_heartbeat(self.timeout.is_some())
+            .with_step_hook(self.trace_jsonl.is_some());
 
         // fetch the stack and program inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
-        let host = DefaultHost::new(input_data.parse_advice_provider()?);
+        let advice_provider = input_data.parse_advice_provider_with_map(
+            self.advice_map_file.as_deref(),
+            self.conflict_policy,
+            self.strict_advice,
+        )?;
+        if self.time_input_parse {
+            eprint!("{}", input_data.parse_timings.to_table());
+        }
+        let max_advice_read = self.max_advice_read.unwrap_or(usize::MAX);
+        let advice_provider = CappedAdviceProvider::new(advice_provider, max_advice_read);
+        let mut host = EventHost::new(DefaultHost::new(advice_provider));
+
+        // write the state of the VM at every cycle of the execution to the trace file, if one was
+        // specified
+        if let Some(trace_path) = &self.trace_jsonl {
+            host = host.with_trace_file(TraceFile::create(trace_path, self.canonical_json)?);
+        }
+
+        // the time limit applies to the execution only, and thus, the deadline is set right before
+        // the program is executed
+        if let Some(timeout) = self.timeout {
+            let timeout = Duration::try_from_secs_f64(timeout)
+                .map_err(|err| format!("Invalid timeout {timeout} - {err}"))?;
+            host = host.with_timeout(timeout);
+        }
+
+        let telemetry = Telemetry::connect(self.telemetry_endpoint.as_deref())?;
 
         let program_hash: [u8; 32] = program.hash().into();
-        print!("Executing program with hash {}... ", hex::encode(program_hash));
+        status!("Executing program with hash {}... ", display_digest(program_hash));
         let now = Instant::now();
 
         // execute program and generate outputs
-        let trace = processor::execute(&program, stack_inputs, host, execution_options)
-            .map_err(|err| format!("Failed to generate execution trace = {:?}", err))?;
+        let phase = Phase::Execute.enter();
+        let trace = processor::execute_with_trap_state(
+            &program,
+            stack_inputs.clone(),
+            &mut host,
+            execution_options,
+        )
+        .map_err(|(err, state)| {
+            let message = match err {
+                ExecutionError::AdviceReadLimitExceeded { .. }
+                | ExecutionError::DeniedOperation(_)
+                | ExecutionError::ExecutionAborted { .. }
+                | ExecutionError::StackError(_)
+                | ExecutionError::MaxCallDepthExceeded { .. }
+                | ExecutionError::U32Overflow { .. }
+                | ExecutionError::UninitializedMemoryRead { .. } => format!("{err}"),
+                _ => format!("Failed to generate execution trace = {:?}", err),
+            };
+            let report = ErrorReport::from_execution_error(message, &err);
+            if self.show_state_on_error {
+                report.with_trap_state(&state, self.number_format)
+            } else {
+                report
+            }
+        })?;
+
+        phase.exit();
+        statusln!("done ({} ms)", now.elapsed().as_millis());
+        if let Some(trace_file) = &host.trace_file {
+            statusln!("Wrote {} cycles to trace file", trace_file.num_lines());
+        }
+        telemetry.record_execution(trace.trace_len_summary().trace_len());
 
-        println!("done ({} ms)", now.elapsed().as_millis());
+        // when naming artifacts by hash, the outputs are written to a file named after the
+        // program and the inputs unless an output file was specified
+        let artifact_name = match &self.output_file {
+            None if self.hash_names => {
+                Some(ArtifactName::new(program.hash(), &input_data, &self.advice_map_file)?)
+            }
+            _ => None,
+        };
 
-        if let Some(output_path) = &self.output_file {
-            // write outputs to file if one was specified
-            OutputFile::write(trace.stack_outputs(), output_path)?;
-        } else {
+        for &format in emit.iter() {
+            let stack_outputs = trace.stack_outputs();
+            match (format, self.output_path(format, &emit, &artifact_name)) {
+                (Emit::Json, Some(output_path)) => {
+                    OutputFile::with_number_format(stack_outputs, self.number_format)
+                        .with_labels(output_labels.as_ref())
+                        .write_json(&output_path, self.json_format())?
+                }
+                (Emit::Msgpack, Some(output_path)) => {
+                    OutputFile::write_msgpack(stack_outputs, &output_path)?
+                }
+                (Emit::Stdout, _) => {
+                    for &value in stack_outputs.stack_truncated(self.num_outputs) {
+                        println!("{}", self.number_format.format(value));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if self.stdout {
+            // write the outputs to stdout in the format of the output file
+            let outputs = OutputFile::with_number_format(trace.stack_outputs(), self.number_format)
+                .with_labels(output_labels.as_ref());
+            println!("{}", outputs.to_json(self.json_format())?);
+        } else if self.emit.is_empty() && self.output_file.is_none() && !self.hash_names {
             // write the stack outputs to the screen.
-            println!("Output: {:?}", trace.stack_outputs().stack_truncated(self.num_outputs));
+            let stack = trace.stack_outputs().stack_truncated(self.num_outputs);
+            statusln!("Output: {}", self.number_format.format_values(stack));
+        }
+
+        // compare the outputs against the expected ones; the comparison is done before the stats
+        // are printed so that a mismatch is reported right after the outputs
+        if let Some(expected_path) = &self.compare_outputs {
+            let expected = OutputFile::read(&Some(expected_path.clone()), &self.assembly_file)?
+                .stack_outputs()?;
+            let diff = diff_outputs(&expected, trace.stack_outputs(), self.number_format);
+            if let Some((last, rest)) = diff.split_last() {
+                let mut message = format!(
+                    "Outputs differ from the expected outputs in `{}`:",
+                    expected_path.display()
+                );
+                for line in rest {
+                    message.push_str(&format!("\n├── {line}"));
+                }
+                message.push_str(&format!("\n└── {last}"));
+                return Err(message.into());
+            }
+            statusln!("Outputs match the expected outputs in `{}`", expected_path.display());
         }
 
         // calculate the percentage of padded rows
@@ -83,7 +424,7 @@ impl RunCmd {
             * 100
             / trace.trace_len_summary().padded_trace_len();
         // print the required cycles for each component
-        println!(
+        statusln!(
             "VM cycles: {} extended to {} steps ({}% padding).
 ├── Stack rows: {}
 ├── Range checker rows: {}
@@ -103,7 +444,968 @@ impl RunCmd {
             trace.trace_len_summary().chiplets_trace_len().memory_chiplet_len(),
             trace.trace_len_summary().chiplets_trace_len().kernel_rom_len(),
         );
+        statusln!("Max call depth: {}", trace.max_call_depth());
+
+        // measure the execution time over repeated executions of the program; these executions
+        // are neither traced nor time-limited, and thus, do not notify the host about their steps
+        if let Some(repeat) = self.repeat {
+            let execution_options = execution_options.with_heartbeat(false).with_step_hook(false);
+            let times = self.measure_executions(
+                &program,
+                &stack_inputs,
+                &input_data,
+                execution_options,
+                repeat,
+            )?;
+            statusln!(
+                "Execution time over {} runs ({} warmup runs discarded): min {:.3} ms, mean {:.3} \
+                ms, max {:.3} ms",
+                times.num_samples(),
+                self.warmup,
+                times.min().as_secs_f64() * 1000.0,
+                times.mean().as_secs_f64() * 1000.0,
+                times.max().as_secs_f64() * 1000.0,
+            );
+        }
+
+        // report the inputs left unused by the program; this executes the program in debug mode,
+        // and thus requires a separate instance of the host
+        if self.input_coverage {
+            let advice_inputs = input_data.parse_advice_inputs_with_map(
+                self.advice_map_file.as_deref(),
+                self.conflict_policy,
+                self.strict_advice,
+            )?;
+            let coverage = InputCoverage::measure(&program, stack_inputs.clone(), advice_inputs)
+                .map_err(|err| ErrorReport::from_execution_error(format!("{err}"), &err))?;
+            for line in coverage.report(self.number_format) {
+                statusln!("{line}");
+            }
+        }
+
+        // report the trace events emitted by the program
+        if let Some(events_path) = &self.events_file {
+            EventsFile::write(&host.events, events_path, self.json_format())?;
+        } else if !host.events.is_empty() {
+            statusln!("Trace events:");
+            for event in host.events.iter() {
+                statusln!("├── cycle {}: trace.{}", event.cycle, event.id);
+            }
+        }
+
+        telemetry.shutdown()?;
 
         Ok(())
     }
+
+    /// Returns the memory specified in the input file and in the binary memory image; words in the
+    /// binary image replace the words specified in the input file at the same addresses.
+    fn read_memory(&self, input_data: &InputFile) -> Result<MemoryImage, String> {
+        let mut memory = input_data.parse_memory()?;
+        if let Some(image_path) = &self.seed_memory_from_file {
+            memory.extend(MemoryImage::read_binary(image_path)?);
+        }
+        Ok(memory)
+    }
+
+    /// Executes the program `repeat` times after `--warmup` discarded executions, and returns the
+    /// execution times of the measured executions.
+    ///
+    /// Every execution, including the warmup ones, starts from a fresh host with the advice
+    /// provider built from the inputs, so that the executions do not affect each other. Since the
+    /// execution trace was already built by the first execution, the measured executions compute
+    /// only the stack outputs.
+    fn measure_executions(
+        &self,
+        program: &Program,
+        stack_inputs: &StackInputs,
+        input_data: &InputFile,
+        execution_options: ExecutionOptions,
+        repeat: usize,
+    ) -> Result<ExecutionTimes, ErrorReport> {
+        let mut samples = Vec::with_capacity(repeat);
+        for i in 0..self.warmup + repeat {
+            let advice_provider = input_data.parse_advice_provider_with_map(
+                self.advice_map_file.as_deref(),
+                self.conflict_policy,
+                self.strict_advice,
+            )?;
+            let host = DefaultHost::new(advice_provider);
+
+            let now = Instant::now();
+            miden::execute_outputs(program, stack_inputs.clone(), host, execution_options)
+                .map_err(|err| ErrorReport::from_execution_error(format!("{err}"), &err))?;
+            if i >= self.warmup {
+                samples.push(now.elapsed());
+            }
+        }
+        Ok(ExecutionTimes { samples })
+    }
+
+    /// Returns the ways in which the outputs are emitted.
+    ///
+    /// # Errors
+    /// Returns an error if `none` is combined with other formats.
+    fn emit_formats(&self) -> Result<Vec<Emit>, String> {
+        if self.emit.is_empty() {
+            return Ok(vec![self.output_format.into()]);
+        }
+        if self.emit.len() > 1 && self.emit.contains(&Emit::None) {
+            return Err("--emit none cannot be combined with other formats".to_string());
+        }
+        Ok(self.emit.clone())
+    }
+
+    /// Returns the format in which JSON documents other than error reports are written.
+    fn json_format(&self) -> JsonFormat {
+        JsonFormat::Pretty.or_canonical(self.canonical_json)
+    }
+
+    /// Returns the path of the file to which the outputs are written in the specified format, or
+    /// None if the outputs are not written to a file in this format.
+    ///
+    /// The outputs are written to the output file if one was specified; if MessagePack outputs
+    /// are emitted together with JSON outputs, they are written next to it with the .msgpack
+    /// extension. Otherwise, the file is named after the program hash and the inputs hash if
+    /// `--hash-names` is set, or after the program if the formats were specified via `--emit`.
+    fn output_path(
+        &self,
+        format: Emit,
+        emit: &[Emit],
+        artifact_name: &Option<ArtifactName>,
+    ) -> Option<PathBuf> {
+        let extension = format.extension()?;
+        match (&self.output_file, artifact_name) {
+            (Some(path), _) if format == Emit::Msgpack && emit.contains(&Emit::Json) => {
+                Some(path.with_extension(extension))
+            }
+            (Some(path), _) => Some(path.clone()),
+            (None, Some(name)) => Some(name.path(&self.assembly_file, extension)),
+            (None, None) if !self.emit.is_empty() => {
+                Some(self.assembly_file.with_extension(extension))
+            }
+            (None, None) => None,
+        }
+    }
+}
+
+// EXECUTION TIMES
+// ================================================================================================
+
+/// Execution times of the measured executions of a program in repeat mode.
+struct ExecutionTimes {
+    samples: Vec<Duration>,
+}
+
+impl ExecutionTimes {
+    /// Returns the number of measured executions.
+    fn num_samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns the shortest execution time, or zero if no executions were measured.
+    fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    /// Returns the longest execution time, or zero if no executions were measured.
+    fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+
+    /// Returns the mean execution time, or zero if no executions were measured.
+    fn mean(&self) -> Duration {
+        match self.num_samples() {
+            0 => Duration::ZERO,
+            n => self.samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+// EVENT HOST
+// ================================================================================================
+
+/// Host which records the trace events emitted by a program, writes the state of the VM after
+/// every cycle to the trace file, if one is set, aborts the execution once the deadline passes,
+/// if one is set, and forwards all other requests to the wrapped host.
+struct EventHost<H> {
+    host: H,
+    events: Vec<TraceEvent>,
+    trace_file: Option<TraceFile>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl<H: Host> EventHost<H> {
+    fn new(host: H) -> Self {
+        Self {
+            host,
+            events: Vec::new(),
+            trace_file: None,
+            deadline: None,
+        }
+    }
+
+    /// Writes the state of the VM after every cycle to the specified trace file; this requires the
+    /// step hook to be enabled in the execution options.
+    fn with_trace_file(mut self, trace_file: TraceFile) -> Self {
+        self.trace_file = Some(trace_file);
+        self
+    }
+
+    /// Aborts the execution once the specified time passes from now.
+    fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some((Instant::now() + timeout, timeout));
+        self
+    }
+}
+
+impl<H: Host> Host for EventHost<H> {
+    fn get_advice<S: ProcessState>(
+        &mut self,
+        process: &S,
+        extractor: AdviceExtractor,
+    ) -> Result<HostResponse, ExecutionError> {
+        self.host.get_advice(process, extractor)
+    }
+
+    fn set_advice<S: ProcessState>(
+        &mut self,
+        process: &S,
+        injector: AdviceInjector,
+    ) -> Result<HostResponse, ExecutionError> {
+        self.host.set_advice(process, injector)
+    }
+
+    fn on_debug<S: ProcessState>(
+        &mut self,
+        process: &S,
+        options: &DebugOptions,
+    ) -> Result<HostResponse, ExecutionError> {
+        self.host.on_debug(process, options)
+    }
+
+    fn on_trace<S: ProcessState>(
+        &mut self,
+        process: &S,
+        trace_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        self.events.push(TraceEvent {
+            cycle: process.clk(),
+            id: trace_id,
+        });
+        self.host.on_trace(process, trace_id)
+    }
+
+    fn on_heartbeat<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        match self.deadline {
+            Some((deadline, timeout)) if Instant::now() > deadline => {
+                Err(ExecutionError::ExecutionAborted {
+                    clk: process.clk(),
+                    reason: format!("time limit of {} s exceeded", timeout.as_secs_f64()),
+                })
+            }
+            _ => self.host.on_heartbeat(process),
+        }
+    }
+
+    fn on_step<S: ProcessState>(
+        &mut self,
+        process: &S,
+        op: Operation,
+        instruction: Option<&str>,
+    ) -> Result<HostResponse, ExecutionError> {
+        if let Some(trace_file) = &mut self.trace_file {
+            let row = TraceRow::new(process, op, instruction);
+            trace_file.write(&row).map_err(|reason| ExecutionError::ExecutionAborted {
+                clk: process.clk(),
+                reason,
+            })?;
+        }
+        self.host.on_step(process, op, instruction)
+    }
+}
+
+// OUTPUTS COMPARISON
+// ================================================================================================
+
+/// Returns a line for every value of the stack outputs or the overflow addresses which differs
+/// between the expected and the actual outputs; the list is empty if the outputs are equal.
+fn diff_outputs(
+    expected: &StackOutputs,
+    actual: &StackOutputs,
+    number_format: NumberFormat,
+) -> Vec<String> {
+    let mut diff = diff_values("stack", expected.stack(), actual.stack(), number_format);
+    diff.extend(diff_values(
+        "overflow_addrs",
+        expected.overflow_addrs(),
+        actual.overflow_addrs(),
+        number_format,
+    ));
+    diff
+}
+
+/// Returns a line for every index at which the expected and the actual values differ, including
+/// the indexes present in only one of them.
+fn diff_values(
+    field: &str,
+    expected: &[u64],
+    actual: &[u64],
+    number_format: NumberFormat,
+) -> Vec<String> {
+    let format = |value: Option<&u64>| match value {
+        Some(&value) => number_format.format(value),
+        None => "nothing".to_string(),
+    };
+    (0..expected.len().max(actual.len()))
+        .filter(|&i| expected.get(i) != actual.get(i))
+        .map(|i| {
+            format!(
+                "{field}[{i}]: expected {}, got {}",
+                format(expected.get(i)),
+                format(actual.get(i))
+            )
+        })
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::data::{TraceEvent, TraceRow},
+        Emit, ErrorFormat, ErrorReport, InputFile, MemoryImage, NumberFormat, OutputFile, RunCmd,
+    };
+    use clap::Parser;
+    use miden::{
+        math::{Felt, StarkField},
+        Assembler, DefaultHost, StackInputs,
+    };
+    use processor::{ExecutionOptions, HEARTBEAT_INTERVAL};
+    use std::{ffi::OsStr, fs, path::PathBuf};
+    use vm_core::stack::STACK_TOP_SIZE;
+
+    #[test]
+    fn execute_outputs_matches_run_cmd() {
+        let source = "begin push.3 push.5 add repeat.20 dup end end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let output_file = dir.path().join("program.outputs");
+        fs::write(&assembly_file, source).unwrap();
+
+        let cmd = RunCmd {
+            output_file: Some(output_file.clone()),
+            ..run_cmd(assembly_file)
+        };
+        cmd.execute().unwrap();
+        let expected = OutputFile::read(&Some(output_file), dir.path())
+            .unwrap()
+            .stack_outputs()
+            .unwrap();
+
+        let program = Assembler::default().compile(source).unwrap();
+        let outputs = miden::execute_outputs(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(outputs, expected);
+    }
+
+    #[test]
+    fn warmup_runs_excluded_from_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let input_file = dir.path().join("program.inputs");
+        let source = "begin adv_push.1 push.1 add end";
+        fs::write(&assembly_file, source).unwrap();
+        fs::write(&input_file, r#"{ "operand_stack": [], "advice_stack": ["4"] }"#).unwrap();
+
+        let cmd = RunCmd {
+            input_file: Some(input_file.clone()),
+            repeat: Some(5),
+            warmup: 3,
+            ..run_cmd(assembly_file.clone())
+        };
+        cmd.execute().unwrap();
+
+        // only the measured executions are sampled, and every execution (including the warmup
+        // ones) consumes the advice stack from the inputs afresh
+        let program = Assembler::default().compile(source).unwrap();
+        let input_data = InputFile::read(&Some(input_file), &assembly_file, false).unwrap();
+        let times = cmd
+            .measure_executions(
+                &program,
+                &StackInputs::default(),
+                &input_data,
+                ExecutionOptions::default(),
+                5,
+            )
+            .unwrap();
+        assert_eq!(times.num_samples(), 5);
+        assert!(times.min() <= times.mean() && times.mean() <= times.max());
+    }
+
+    #[test]
+    fn hex_number_format_in_outputs() {
+        let source = "begin push.3 push.5 add push.255 repeat.20 dup end end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, source).unwrap();
+
+        let run = |number_format, output_file| {
+            RunCmd {
+                number_format,
+                output_file: Some(output_file),
+                ..run_cmd(assembly_file.clone())
+            }
+            .execute()
+            .unwrap()
+        };
+        let dec_file = dir.path().join("dec.outputs");
+        let hex_file = dir.path().join("hex.outputs");
+        run(NumberFormat::Dec, dec_file.clone());
+        run(NumberFormat::Hex, hex_file.clone());
+
+        // all values in the hex output file are written in the 0x format
+        let hex_outputs = OutputFile::read(&Some(hex_file), dir.path()).unwrap();
+        assert!(!hex_outputs.overflow_addrs.is_empty());
+        assert!(hex_outputs.stack.iter().all(|v| v.starts_with("0x")));
+        assert!(hex_outputs.overflow_addrs.iter().all(|v| v.starts_with("0x")));
+        assert_eq!(hex_outputs.stack[0], "0xff");
+
+        // both files describe the same outputs
+        let dec_outputs = OutputFile::read(&Some(dec_file), dir.path()).unwrap();
+        assert_eq!(dec_outputs.stack[0], "255");
+        assert_eq!(hex_outputs.stack_outputs().unwrap(), dec_outputs.stack_outputs().unwrap());
+    }
+
+    #[test]
+    fn emit_writes_requested_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, "begin push.3 push.5 add end").unwrap();
+
+        let run = |emit: Vec<Emit>| RunCmd {
+            emit,
+            ..run_cmd(assembly_file.clone())
+        };
+        let json_file = assembly_file.with_extension("outputs");
+        let msgpack_file = assembly_file.with_extension("msgpack");
+
+        // without an output file, nothing is written by default or with `none`
+        run(Vec::new()).execute().unwrap();
+        run(vec![Emit::None]).execute().unwrap();
+        assert!(!json_file.exists() && !msgpack_file.exists());
+
+        // the requested files are written next to the program
+        run(vec![Emit::Json, Emit::Stdout, Emit::Msgpack]).execute().unwrap();
+        let json_outputs = OutputFile::read(&None, &assembly_file).unwrap();
+        let msgpack_outputs = OutputFile::read(&Some(msgpack_file), dir.path()).unwrap();
+        assert_eq!(json_outputs.stack[0], "8");
+        assert_eq!(json_outputs.stack_outputs().unwrap(), msgpack_outputs.stack_outputs().unwrap());
+
+        // `none` cannot be combined with other formats
+        let err = run(vec![Emit::None, Emit::Json]).execute().unwrap_err();
+        assert!(err.contains("--emit none"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn labeled_outputs_in_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let output_file = dir.path().join("program.outputs");
+        fs::write(
+            &assembly_file,
+            "# output_labels: [sum, _, product]\nbegin push.6 push.0 push.2 push.3 add end",
+        )
+        .unwrap();
+
+        let run = |output_labels: Option<std::path::PathBuf>| RunCmd {
+            output_file: Some(output_file.clone()),
+            output_labels,
+            ..run_cmd(assembly_file.clone())
+        };
+
+        // labels declared in the program key the values by name, and unlabeled positions by index
+        run(None).execute().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_file).unwrap()).unwrap();
+        let labeled = &json["labeled_stack"];
+        assert_eq!(labeled["sum"], "5");
+        assert_eq!(labeled["1"], "0");
+        assert_eq!(labeled["product"], "6");
+        assert_eq!(labeled["15"], "0");
+        assert_eq!(labeled.as_object().unwrap().len(), json["stack"].as_array().unwrap().len());
+
+        // the output file can still be read back
+        let outputs = OutputFile::read(&Some(output_file.clone()), dir.path()).unwrap();
+        assert_eq!(outputs.stack[..3], ["5", "0", "6"]);
+
+        // labels in a sidecar file take precedence over the ones in the program
+        let labels_file = dir.path().join("labels.json");
+        fs::write(&labels_file, r#"{ "stack": ["total"] }"#).unwrap();
+        run(Some(labels_file.clone())).execute().unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_file).unwrap()).unwrap();
+        assert_eq!(json["labeled_stack"]["total"], "5");
+        assert_eq!(json["labeled_stack"]["2"], "6");
+
+        // a label cannot be declared twice
+        fs::write(&labels_file, r#"{ "stack": ["x", "x"] }"#).unwrap();
+        let err = run(Some(labels_file)).execute().unwrap_err();
+        assert!(err.contains("declared for positions 0 and 1"), "unexpected error: {err}");
+    }
+
+    #[test]
+    fn json_error_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+
+        let run = |source: &str, error_format| {
+            fs::write(&assembly_file, source).unwrap();
+            RunCmd {
+                error_format,
+                ..run_cmd(assembly_file.clone())
+            }
+            .execute()
+            .unwrap_err()
+        };
+
+        // a trapping program reports the kind of the execution error and the cycle it failed at
+        let trapping = "begin push.1 push.0 assert end";
+        let err = run(trapping, ErrorFormat::Json);
+        let report: ErrorReport = serde_json::from_str(&err).unwrap();
+        assert_eq!(report.kind, "FailedAssertion");
+        assert!(report.cycle.is_some());
+        assert_eq!(report.message, run(trapping, ErrorFormat::Text));
+
+        // parsing errors carry the source location
+        let err = run("begin\n    push.1 foo\nend", ErrorFormat::Json);
+        let report: ErrorReport = serde_json::from_str(&err).unwrap();
+        assert_eq!(report.kind, "ParsingError");
+        assert_eq!(report.line, Some(2));
+        assert_eq!(report.cycle, None);
+    }
+
+    #[test]
+    fn trap_state_shown_on_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+
+        let run = |show_state_on_error, error_format| {
+            RunCmd {
+                error_format,
+                show_state_on_error,
+                ..run_cmd(assembly_file.clone())
+            }
+            .execute()
+            .unwrap_err()
+        };
+
+        // the stack at the trap is the state before the failing assertion is executed
+        fs::write(&assembly_file, "begin push.1 push.2 push.0 assert end").unwrap();
+        let err = run(true, ErrorFormat::Json);
+        let report: ErrorReport = serde_json::from_str(&err).unwrap();
+        assert_eq!(report.kind, "FailedAssertion");
+        let stack = report.stack.unwrap();
+        assert_eq!(stack[..4], [0, 2, 1, 0]);
+
+        let message = run(true, ErrorFormat::Text);
+        assert!(message.contains(&format!("clk: {}", report.cycle.unwrap())));
+        assert!(message.contains("stack: [0, 2, 1, 0"));
+
+        // the state is not reported unless requested
+        let err = run(false, ErrorFormat::Json);
+        let report: ErrorReport = serde_json::from_str(&err).unwrap();
+        assert_eq!(report.stack, None);
+        assert!(!report.message.contains("State at trap"));
+    }
+
+    #[test]
+    fn timeout_aborts_long_running_program() {
+        // the loop runs for millions of cycles, which takes far longer than the time limit
+        let source = "begin push.1000000 dup neq.0 while.true sub.1 dup neq.0 end drop end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, source).unwrap();
+
+        let cmd = RunCmd {
+            error_format: ErrorFormat::Json,
+            timeout: Some(0.001),
+            ..run_cmd(assembly_file)
+        };
+        let err = cmd.execute().unwrap_err();
+
+        // the execution is aborted at a heartbeat and reports the number of completed cycles
+        let report: ErrorReport = serde_json::from_str(&err).unwrap();
+        assert_eq!(report.kind, "ExecutionAborted");
+        let cycles = report.cycle.unwrap();
+        assert!(cycles >= HEARTBEAT_INTERVAL);
+        assert!(report.message.contains(&format!("after {cycles} cycles")));
+    }
+
+    #[test]
+    fn strict_u32_allows_comparisons() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let run = |source: &str| {
+            fs::write(&assembly_file, source).unwrap();
+            let cmd = RunCmd {
+                strict_u32: true,
+                ..run_cmd(assembly_file.clone())
+            };
+            cmd.execute()
+        };
+
+        // the subtractions the comparisons are compiled to underflow, which is not an error
+        let source = "begin
+            push.1.2 u32checked_lt push.2.1 u32checked_lt
+            push.1.2 u32checked_gt push.2.1 u32checked_gt
+            push.1.2 u32checked_min push.2.1 u32checked_min
+            push.1.2 u32checked_max push.2.1 u32checked_max
+        end";
+        run(source).unwrap();
+
+        let err = run("begin push.1.2 u32wrapping_sub end").unwrap_err();
+        assert!(err.contains("Operation u32wrapping_sub overflowed u32 range"), "{err}");
+    }
+
+    #[test]
+    fn strict_stack_reports_underflow_location() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let source = "begin\n    push.1\n    drop\n    drop\nend\n";
+        fs::write(&assembly_file, source).unwrap();
+
+        let cmd = RunCmd {
+            strict_stack: true,
+            ..run_cmd(assembly_file)
+        };
+        let err = cmd.execute().unwrap_err();
+        assert!(
+            err.contains("Stack underflow at line 4: 'drop' requires 1 element but stack has 0"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn program_size_limits_checked_before_compilation() {
+        // the program imports a module which does not exist, so it would fail to compile
+        let source = "use.missing::module proc.foo push.1 end begin exec.foo exec.module::bar end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, source).unwrap();
+
+        let build_cmd = |max_instructions, max_procedures| RunCmd {
+            max_instructions,
+            max_procedures,
+            ..run_cmd(assembly_file.clone())
+        };
+
+        let err = build_cmd(2, 65535).execute().unwrap_err();
+        assert!(err.contains("contains 3 instructions, but at most 2 are allowed"));
+
+        let err = build_cmd(10_000_000, 0).execute().unwrap_err();
+        assert!(err.contains("defines 1 procedures, but at most 0 are allowed"));
+
+        // within the limits, the program reaches compilation
+        let err = build_cmd(10_000_000, 65535).execute().unwrap_err();
+        assert!(err.starts_with("Failed to compile program"));
+    }
+
+    #[test]
+    fn trace_jsonl_has_line_per_cycle() {
+        let source = "begin push.3 push.5 add repeat.4 dup mul end end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let trace_file = dir.path().join("program.jsonl");
+        fs::write(&assembly_file, source).unwrap();
+
+        let cmd = RunCmd {
+            trace_jsonl: Some(trace_file.clone()),
+            ..run_cmd(assembly_file)
+        };
+        cmd.execute().unwrap();
+
+        let program = Assembler::default().compile(source).unwrap();
+        let trace = processor::execute(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        let num_cycles = trace.trace_len_summary().main_trace_len();
+
+        let contents = fs::read_to_string(&trace_file).unwrap();
+        let rows = contents
+            .lines()
+            .map(|line| serde_json::from_str::<TraceRow>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), num_cycles);
+        assert_eq!(rows.last().unwrap().cycle as usize, num_cycles);
+        assert_eq!(rows.last().unwrap().stack[..1], trace.stack_outputs().stack()[..1]);
+
+        // every line matches the state of the VM reported by the debugger for the same cycle; only
+        // the top of the stack is compared since the debugger reads the overflow table as of the
+        // end of the cycle
+        let program = Assembler::default().with_debug_mode(true).compile(source).unwrap();
+        let states =
+            processor::execute_iter(&program, StackInputs::default(), DefaultHost::default());
+        for (row, state) in rows.iter().zip(states.skip(1)) {
+            let state = state.unwrap();
+            assert_eq!(row.cycle, state.clk);
+            assert_eq!(row.ctx, state.ctx);
+            assert_eq!(row.op, state.op.map(|op| op.to_string()));
+            assert_eq!(row.asmop, state.asmop.map(|asmop| asmop.op().to_string()));
+            assert_eq!(row.fmp, state.fmp.as_int());
+            let stack = state.stack.iter().map(|value| value.as_int()).collect::<Vec<_>>();
+            assert_eq!(row.stack[..STACK_TOP_SIZE], stack[..STACK_TOP_SIZE]);
+        }
+    }
+
+    #[test]
+    fn trace_jsonl_written_by_capped_execution() {
+        let source = "begin push.1 padw adv_loadw end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let input_file = dir.path().join("program.inputs");
+        let trace_file = dir.path().join("program.jsonl");
+        fs::write(&assembly_file, source).unwrap();
+        fs::write(&input_file, r#"{ "operand_stack": [], "advice_stack": ["1", "2", "3", "4"] }"#)
+            .unwrap();
+
+        // the trace is written by the execution which fails once the advice read limit is hit
+        let cmd = RunCmd {
+            input_file: Some(input_file),
+            max_advice_read: Some(3),
+            trace_jsonl: Some(trace_file.clone()),
+            ..run_cmd(assembly_file)
+        };
+        let err = cmd.execute().unwrap_err();
+        assert!(err.contains("exceeds the limit of 3 elements"), "{err}");
+
+        // the trace ends with the last cycle executed before the failure
+        let contents = fs::read_to_string(&trace_file).unwrap();
+        let rows = contents
+            .lines()
+            .map(|line| serde_json::from_str::<TraceRow>(line).unwrap())
+            .collect::<Vec<_>>();
+        let last = rows.last().unwrap();
+        assert_eq!(last.op.as_deref(), Some("pad"));
+        assert_eq!(last.stack[..5], [0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn trace_events_captured_in_order() {
+        let source = "begin push.1 trace.7 push.2 add trace.3 drop trace.7 trace.9 end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let events_file = dir.path().join("program.events");
+        fs::write(&assembly_file, source).unwrap();
+
+        let cmd = RunCmd {
+            events_file: Some(events_file.clone()),
+            ..run_cmd(assembly_file)
+        };
+        cmd.execute().unwrap();
+
+        let events: Vec<TraceEvent> =
+            serde_json::from_str(&fs::read_to_string(events_file).unwrap()).unwrap();
+        let events: Vec<_> = events.into_iter().map(|event| (event.cycle, event.id)).collect();
+        // all decorators following the last operation of the span are executed after it ends
+        assert_eq!(events, [(3, 7), (5, 3), (7, 7), (7, 9)]);
+    }
+
+    #[test]
+    fn canonical_json_is_reproducible() {
+        let source = "begin push.1 trace.7 push.2 add end";
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, source).unwrap();
+
+        // runs the program, writing all JSON files with the specified name
+        let run = |name: &str| {
+            let cmd = RunCmd {
+                canonical_json: true,
+                events_file: Some(dir.path().join(name).with_extension("events")),
+                output_file: Some(dir.path().join(name).with_extension("outputs")),
+                trace_jsonl: Some(dir.path().join(name).with_extension("jsonl")),
+                ..run_cmd(assembly_file.clone())
+            };
+            cmd.execute().unwrap();
+        };
+        run("first");
+        run("second");
+
+        // both runs wrote byte-identical files
+        for extension in ["outputs", "events", "jsonl"] {
+            let first = fs::read(dir.path().join("first").with_extension(extension)).unwrap();
+            let second = fs::read(dir.path().join("second").with_extension(extension)).unwrap();
+            assert_eq!(first, second, "{extension} files differ");
+        }
+
+        // the keys of all objects are sorted, and there is no whitespace
+        let outputs = fs::read_to_string(dir.path().join("first.outputs")).unwrap();
+        let expected =
+            format!(r#"{{"overflow_addrs":["0","1"],"stack":["3"{}]}}"#, r#","0""#.repeat(16));
+        assert_eq!(outputs, expected);
+        let events = fs::read_to_string(dir.path().join("first.events")).unwrap();
+        assert_eq!(events, r#"[{"cycle":3,"id":7}]"#);
+        let trace = fs::read_to_string(dir.path().join("first.jsonl")).unwrap();
+        assert!(trace.starts_with(r#"{"asmop":null,"ctx":0,"cycle":1,"fmp":"#), "{trace}");
+    }
+
+    #[test]
+    fn compare_outputs_against_golden_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        let golden_file = dir.path().join("golden.outputs");
+        fs::write(&assembly_file, "begin push.3 push.5 add end").unwrap();
+
+        let run = |output_file, compare_outputs| {
+            RunCmd {
+                compare_outputs,
+                output_file,
+                ..run_cmd(assembly_file.clone())
+            }
+            .execute()
+        };
+
+        // the outputs of a previous run match
+        run(Some(golden_file.clone()), None).unwrap();
+        run(None, Some(golden_file.clone())).unwrap();
+
+        // every differing value of the golden file is reported
+        let mut golden = OutputFile::read(&Some(golden_file.clone()), dir.path()).unwrap();
+        golden.stack[0] = "9".to_string();
+        golden.stack[2] = "1".to_string();
+        fs::write(&golden_file, serde_json::to_string(&golden).unwrap()).unwrap();
+        let err = run(None, Some(golden_file.clone())).unwrap_err();
+        assert!(err.contains("├── stack[0]: expected 9, got 8"), "{err}");
+        assert!(err.contains("└── stack[2]: expected 1, got 0"), "{err}");
+        assert!(!err.contains("stack[1]"), "{err}");
+    }
+
+    #[test]
+    fn binary_memory_image_matches_json_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, "begin padw mem_loadw.7 end").unwrap();
+
+        let entries = [
+            (0u64, [1u64, 2, 3, 4]),
+            (7, [5, 6, 7, 8]),
+            (u32::MAX as u64, [u64::MAX - u32::MAX as u64, 0, 0, 9]),
+        ];
+
+        // the same memory specified in an input file, and in a binary image
+        let input_file = dir.path().join("memory.inputs");
+        let inputs = serde_json::json!({ "operand_stack": [], "memory": entries });
+        fs::write(&input_file, inputs.to_string()).unwrap();
+        let image_file = dir.path().join("memory.bin");
+        let mut image = Vec::new();
+        for (address, word) in entries.iter() {
+            image.extend(address.to_le_bytes());
+            word.iter().for_each(|value| image.extend(value.to_le_bytes()));
+        }
+        fs::write(&image_file, &image).unwrap();
+
+        let json_cmd = RunCmd {
+            input_file: Some(input_file),
+            output_file: Some(dir.path().join("json.outputs")),
+            ..run_cmd(assembly_file.clone())
+        };
+        let binary_cmd = RunCmd {
+            input_coverage: false,
+            input_file: None,
+            output_file: Some(dir.path().join("binary.outputs")),
+            seed_memory_from_file: Some(image_file.clone()),
+            ..json_cmd.clone()
+        };
+
+        // the programs seeded from both sources leave the same words in memory; the words are read
+        // from the advice stack, and thus, the hash of the seeded program does not depend on them
+        let program = Assembler::default().compile("begin push.1 drop end").unwrap();
+        let final_memory = |cmd: &RunCmd| {
+            let input_data = InputFile::read(&cmd.input_file, &assembly_file, false).unwrap();
+            let memory = cmd.read_memory(&input_data).unwrap();
+            let seeded = memory.seed(&program);
+            let other = MemoryImage::from_entries([(3, [1, 1, 1, 1])]).unwrap();
+            assert_eq!(seeded.hash(), other.seed(&program).hash());
+
+            let advice_provider = input_data.with_memory(&memory).parse_advice_provider().unwrap();
+            let host = DefaultHost::new(advice_provider);
+            let states = processor::execute_iter(&seeded, StackInputs::default(), host);
+            states.last().unwrap().unwrap().memory
+        };
+        let expected = entries
+            .iter()
+            .map(|&(address, word)| (address, word.map(Felt::new)))
+            .collect::<Vec<_>>();
+        assert_eq!(final_memory(&json_cmd), expected);
+        assert_eq!(final_memory(&binary_cmd), expected);
+
+        // the program reads the seeded memory in both cases
+        json_cmd.execute().unwrap();
+        binary_cmd.execute().unwrap();
+        let json_outputs =
+            OutputFile::read(&Some(dir.path().join("json.outputs")), dir.path()).unwrap();
+        let binary_outputs =
+            OutputFile::read(&Some(dir.path().join("binary.outputs")), dir.path()).unwrap();
+        assert_eq!(json_outputs.stack[..4], ["8", "7", "6", "5"]);
+        assert_eq!(binary_outputs.stack, json_outputs.stack);
+
+        // truncated images and values which are not field elements are rejected
+        fs::write(&image_file, &image[..image.len() - 1]).unwrap();
+        let err = binary_cmd.execute().unwrap_err();
+        assert!(err.contains("not a multiple of the record size"), "{err}");
+        image[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+        fs::write(&image_file, &image).unwrap();
+        let err = binary_cmd.execute().unwrap_err();
+        assert!(err.contains("is not a valid field element"), "{err}");
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn phases_are_recorded_in_spans() {
+        let dir = tempfile::tempdir().unwrap();
+        let assembly_file = dir.path().join("program.masm");
+        fs::write(&assembly_file, "begin push.3 push.5 add end").unwrap();
+
+        let cmd = RunCmd {
+            output_file: Some(dir.path().join("program.outputs")),
+            ..run_cmd(assembly_file)
+        };
+
+        let (result, spans) = super::super::spans::capture_spans(|| cmd.execute());
+        result.unwrap();
+        assert_eq!(
+            spans,
+            [
+                "compile",
+                "parse_advice_stack",
+                "parse_advice_map",
+                "parse_merkle_store",
+                "execute"
+            ]
+        );
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a `run` command for the specified program with all other options set to their
+    /// default values.
+    fn run_cmd(assembly_file: PathBuf) -> RunCmd {
+        RunCmd::parse_from([OsStr::new("run"), OsStr::new("-a"), assembly_file.as_os_str()])
+    }
 }
@@ -0,0 +1,141 @@
+// PHASES
+// ================================================================================================
+
+/// Major phases of the commands.
+///
+/// When the `tracing` feature is enabled, every phase is recorded in a span with the name of the
+/// phase (e.g., `compile`), and the spans are written to stderr as JSON lines together with their
+/// timings when they close. Otherwise, entering a phase does nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Compile,
+    Execute,
+    Prove,
+    Verify,
+}
+
+impl Phase {
+    /// Enters the span of this phase; the span is exited when the returned guard is dropped.
+    pub fn enter(self) -> PhaseGuard {
+        PhaseGuard {
+            #[cfg(feature = "tracing")]
+            _span: self.span().entered(),
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn span(self) -> tracing::Span {
+        match self {
+            Phase::Compile => tracing::info_span!("compile"),
+            Phase::Execute => tracing::info_span!("execute"),
+            Phase::Prove => tracing::info_span!("prove"),
+            Phase::Verify => tracing::info_span!("verify"),
+        }
+    }
+}
+
+/// Phases of parsing an input file.
+///
+/// Similarly to the phases of the commands, every phase is recorded in a span with the name of
+/// the phase (e.g., `parse_advice_map`) when the `tracing` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputPhase {
+    Deserialize,
+    AdviceStack,
+    AdviceMap,
+    MerkleStore,
+}
+
+impl InputPhase {
+    /// All phases in the order in which they are executed.
+    pub const ALL: [InputPhase; 4] = [
+        InputPhase::Deserialize,
+        InputPhase::AdviceStack,
+        InputPhase::AdviceMap,
+        InputPhase::MerkleStore,
+    ];
+
+    /// Returns the name of the span of this phase.
+    pub fn name(self) -> &'static str {
+        match self {
+            InputPhase::Deserialize => "parse_json",
+            InputPhase::AdviceStack => "parse_advice_stack",
+            InputPhase::AdviceMap => "parse_advice_map",
+            InputPhase::MerkleStore => "parse_merkle_store",
+        }
+    }
+
+    /// Enters the span of this phase; the span is exited when the returned guard is dropped.
+    pub fn enter(self) -> PhaseGuard {
+        PhaseGuard {
+            #[cfg(feature = "tracing")]
+            _span: self.span().entered(),
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn span(self) -> tracing::Span {
+        match self {
+            InputPhase::Deserialize => tracing::info_span!("parse_json"),
+            InputPhase::AdviceStack => tracing::info_span!("parse_advice_stack"),
+            InputPhase::AdviceMap => tracing::info_span!("parse_advice_map"),
+            InputPhase::MerkleStore => tracing::info_span!("parse_merkle_store"),
+        }
+    }
+}
+
+/// Keeps the span of a phase entered until it is dropped.
+pub struct PhaseGuard {
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl PhaseGuard {
+    /// Exits the span of the phase before the end of the enclosing scope.
+    pub fn exit(self) {}
+}
+
+// SUBSCRIBER
+// ================================================================================================
+
+/// Installs a subscriber which writes the spans of the phases to stderr as JSON lines.
+///
+/// A line is written when a span closes, and includes the time spent in the span (`time.busy`)
+/// and between its entries (`time.idle`). The output printed to stdout is not affected.
+#[cfg(feature = "tracing")]
+pub fn init_subscriber() {
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    // if a subscriber was already installed (e.g., by an embedding application), it is kept
+    let _ = tracing_subscriber::fmt()
+        .json()
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .try_init();
+}
+
+// TESTS
+// ================================================================================================
+
+/// Runs `f` and returns its result together with the names of the spans created while it ran.
+#[cfg(all(test, feature = "tracing"))]
+pub fn capture_spans<T>(f: impl FnOnce() -> T) -> (T, Vec<&'static str>) {
+    use std::sync::{Arc, Mutex};
+    use tracing::{span, Subscriber};
+    use tracing_subscriber::{layer::Context, prelude::*, registry::Registry, Layer};
+
+    struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+    impl<S: Subscriber> Layer<S> for SpanNames {
+        fn on_new_span(&self, attrs: &span::Attributes<'_>, _id: &span::Id, _ctx: Context<'_, S>) {
+            self.0.lock().unwrap().push(attrs.metadata().name());
+        }
+    }
+
+    let names = Arc::new(Mutex::new(Vec::new()));
+    let subscriber = Registry::default().with(SpanNames(names.clone()));
+    let result = tracing::subscriber::with_default(subscriber, f);
+
+    let names = names.lock().unwrap().clone();
+    (result, names)
+}
@@ -0,0 +1,44 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// STATUS MESSAGES
+// ================================================================================================
+
+/// Whether status messages are written to stderr instead of stdout.
+static STATUS_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether status messages (i.e., progress and informational messages printed by the
+/// commands) are written to stderr instead of stdout.
+///
+/// This is used when the result of a command is written to stdout, so that the result can be
+/// consumed by other tools without being interleaved with status messages.
+pub fn set_status_to_stderr(enabled: bool) {
+    STATUS_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns true if status messages are written to stderr instead of stdout.
+pub fn status_to_stderr() -> bool {
+    STATUS_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Prints a status message, to stdout or to stderr depending on [set_status_to_stderr()].
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if $crate::cli::status::status_to_stderr() {
+            eprint!($($arg)*);
+        } else {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// Prints a status message followed by a newline, to stdout or to stderr depending on
+/// [set_status_to_stderr()].
+macro_rules! statusln {
+    ($($arg:tt)*) => {
+        if $crate::cli::status::status_to_stderr() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
@@ -0,0 +1,100 @@
+#[cfg(feature = "telemetry")]
+use miden::telemetry::MidenMeter;
+use std::time::Duration;
+
+// TELEMETRY
+// ================================================================================================
+
+/// Exports metrics of the commands to an OTLP collector if `--telemetry-endpoint` was specified;
+/// otherwise, recording the metrics does nothing.
+pub struct Telemetry {
+    #[cfg(feature = "telemetry")]
+    meter: Option<MidenMeter>,
+}
+
+impl Telemetry {
+    /// Returns a new [Telemetry] which exports the metrics to the specified endpoint, if one is
+    /// provided.
+    ///
+    /// # Errors
+    /// Returns an error if the exporter cannot be built, or if an endpoint is provided but the
+    /// binary was built without the `telemetry` feature.
+    #[cfg(feature = "telemetry")]
+    pub fn connect(endpoint: Option<&str>) -> Result<Self, String> {
+        let meter = endpoint
+            .map(|endpoint| {
+                MidenMeter::new(endpoint).map_err(|err| {
+                    format!("Failed to connect to telemetry endpoint `{endpoint}` - {err}")
+                })
+            })
+            .transpose()?;
+        Ok(Self { meter })
+    }
+
+    /// Returns a new [Telemetry] which exports the metrics to the specified endpoint, if one is
+    /// provided.
+    ///
+    /// # Errors
+    /// Returns an error if the exporter cannot be built, or if an endpoint is provided but the
+    /// binary was built without the `telemetry` feature.
+    #[cfg(not(feature = "telemetry"))]
+    pub fn connect(endpoint: Option<&str>) -> Result<Self, String> {
+        match endpoint {
+            Some(_) => {
+                Err("Telemetry requires miden to be built with the `telemetry` feature".to_string())
+            }
+            None => Ok(Self {}),
+        }
+    }
+
+    /// Records the number of cycles consumed by an execution.
+    pub fn record_execution(&self, _num_cycles: usize) {
+        #[cfg(feature = "telemetry")]
+        if let Some(meter) = &self.meter {
+            meter.record_execution(_num_cycles);
+        }
+    }
+
+    /// Records the time it took to generate a proof and the size of the serialized proof.
+    pub fn record_proving(&self, _duration: Duration, _proof_size: usize) {
+        #[cfg(feature = "telemetry")]
+        if let Some(meter) = &self.meter {
+            meter.record_proving(_duration, _proof_size);
+        }
+    }
+
+    /// Exports the metrics which have not been exported yet.
+    ///
+    /// # Errors
+    /// Returns an error if the metrics could not be exported.
+    pub fn shutdown(self) -> Result<(), String> {
+        #[cfg(feature = "telemetry")]
+        if let Some(meter) = self.meter {
+            meter.shutdown().map_err(|err| format!("Failed to export telemetry - {err}"))?;
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::Telemetry;
+
+    #[test]
+    fn connect_without_endpoint_records_nothing() {
+        let telemetry = Telemetry::connect(None).unwrap();
+        telemetry.record_execution(64);
+        telemetry.record_proving(std::time::Duration::from_millis(10), 1024);
+        telemetry.shutdown().unwrap();
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    #[test]
+    fn endpoint_requires_telemetry_feature() {
+        let err = Telemetry::connect(Some("http://localhost:4318/v1/metrics")).err().unwrap();
+        assert!(err.contains("`telemetry` feature"), "{err}");
+    }
+}
@@ -1,59 +1,747 @@
-use super::data::{InputFile, OutputFile, ProgramHash, ProofFile};
+use super::data::{
+    Debug, InputFile, Libraries, OutputFile, ProgramFile, ProgramHash, ProofEncoding, ProofFile,
+    ProofHeader,
+};
+use super::digest::display_digest;
+use super::spans::Phase;
 use clap::Parser;
-use miden::{Kernel, ProgramInfo};
-use std::{path::PathBuf, time::Instant};
+use miden::{
+    math::StarkField, Digest, Kernel, ProgramHashCache, ProgramInfo, PublicWitness, StackOutputs,
+};
+use std::{
+    fs, iter,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Verify a miden program")]
 pub struct VerifyCmd {
+    /// Path to a directory with .proof (or base64 .proof.b64) files to verify against the same
+    /// program; inputs and outputs for each proof are read from files with the same name as the
+    /// proof
+    #[clap(long = "batch", value_parser, conflicts_with_all = ["input_file", "output_file", "proof_file"])]
+    batch_dir: Option<PathBuf>,
+    /// Path to a compiled .masb program file from which the program hash is computed
+    #[clap(long = "compiled", value_parser, conflicts_with_all = ["program_file", "program_hash"])]
+    compiled_file: Option<PathBuf>,
+    /// Encoding of the proof files: `binary` or `base64`; if not specified, files with the .b64
+    /// extension (e.g., `fib.proof.b64`) are read as base64 text and all other files as binary
+    #[clap(long = "encoding", value_enum)]
+    encoding: Option<ProofEncoding>,
+    /// Stop batch verification on the first proof which fails to verify
+    #[clap(long = "fail-fast", requires = "batch_dir")]
+    fail_fast: bool,
     /// Path to input file
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
+    /// Substitute `${VAR}` and `${VAR:-default}` references to environment variables in the
+    /// input file
+    #[clap(long = "input-template")]
+    input_template: bool,
+    /// Paths to .masl library files used to compile the program specified by `--program` or
+    /// `--compiled`
+    #[clap(short = 'l', long = "libraries", value_parser)]
+    library_paths: Vec<PathBuf>,
+    /// Reject proofs which were created more than the specified number of seconds ago
+    #[clap(long = "max-proof-age-secs")]
+    max_proof_age_secs: Option<u64>,
     /// Path to output file
     #[clap(short = 'o', long = "output", value_parser)]
     output_file: Option<PathBuf>,
     /// Path to proof file
-    #[clap(short = 'p', long = "proof", value_parser)]
-    proof_file: PathBuf,
+    #[clap(short = 'p', long = "proof", value_parser, required_unless_present = "batch_dir")]
+    proof_file: Option<PathBuf>,
+    /// Path to a .masm program file from which the program hash is computed
+    #[clap(long = "program", value_parser, conflicts_with = "program_hash")]
+    program_file: Option<PathBuf>,
     /// Program hash (hex)
-    #[clap(short = 'h', long = "program-hash")]
-    program_hash: String,
+    #[clap(
+        short = 'h',
+        long = "program-hash",
+        required_unless_present_any = ["compiled_file", "program_file"]
+    )]
+    program_hash: Option<String>,
+    /// Fail if verifying a proof takes more than the specified number of milliseconds
+    #[clap(long = "timing-budget")]
+    timing_budget_ms: Option<u64>,
+    /// Print a summary of the public inputs and the parameters of every verified proof
+    #[clap(short = 'v', long = "verbose")]
+    verbose: bool,
+    /// Path to a directory in which the program hashes computed from program files are cached,
+    /// so that repeated verifications of the same program compile it only once; cached hashes are
+    /// trusted, so the directory must be writable only by the verifier
+    #[clap(long = "vk-cache", value_parser)]
+    vk_cache: Option<PathBuf>,
+    /// Cache of the program hashes computed from program files shared with other commands
+    #[clap(skip)]
+    cache: Option<Arc<ProgramHashCache>>,
 }
 
 impl VerifyCmd {
+    /// Returns this command with the specified cache of the program hashes computed from program
+    /// files; the cache takes precedence over the one specified by `--vk-cache`.
+    pub fn with_cache(mut self, cache: Arc<ProgramHashCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
     pub fn execute(&self) -> Result<(), String> {
         println!("============================================================");
         println!("Verify program");
         println!("============================================================");
 
-        // read program hash from input
-        let program_hash = ProgramHash::read(&self.program_hash)?;
+        // open the cache directory, unless a cache was already provided
+        let cache = match (&self.cache, &self.vk_cache) {
+            (Some(cache), _) => Some(cache.clone()),
+            (None, Some(dir)) => Some(Arc::new(ProgramHashCache::open(dir)?)),
+            (None, None) => None,
+        };
+
+        // read program hash from input, or compute it from the program file
+        let program_hash = self.program_hash(cache.as_deref())?;
+
+        // TODO accept kernel as CLI argument
+        let kernel = Kernel::default();
+        let program_info = ProgramInfo::new(program_hash, kernel);
+
+        match (&self.batch_dir, &self.proof_file) {
+            (Some(batch_dir), _) => self.verify_batch(&program_info, batch_dir),
+            (None, Some(proof_file)) => {
+                self.verify_proof(&program_info, &self.input_file, &self.output_file, proof_file)
+            }
+            (None, None) => {
+                Err("Either a proof file or a batch directory must be specified".into())
+            }
+        }
+    }
+
+    /// Returns the hash of the program against which the proofs are verified.
+    ///
+    /// The hash is either read from the arguments, or computed by compiling the specified .masm
+    /// program file or the deserialized .masb program file. If a cache is provided, hashes
+    /// computed from program files are looked up in and inserted into it.
+    fn program_hash(&self, cache: Option<&ProgramHashCache>) -> Result<Digest, String> {
+        let (path, compiled) = match (&self.program_hash, &self.program_file, &self.compiled_file) {
+            (Some(program_hash), ..) => return ProgramHash::read(program_hash),
+            (None, Some(path), _) => (path, false),
+            (None, None, Some(path)) => (path, true),
+            (None, None, None) => {
+                return Err("Either a program hash or a program file must be specified".into())
+            }
+        };
 
-        // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.proof_file)?;
+        let compile = || {
+            let program_file = if compiled {
+                ProgramFile::read_compiled(path)?
+            } else {
+                ProgramFile::read(path)?
+            };
+            let libraries = Libraries::new(&self.library_paths, false, false)?;
+            let program = program_file.compile(&Debug::Off, libraries)?;
+            Ok(ProgramHash::from_program(&program))
+        };
+
+        match cache {
+            Some(cache) => {
+                let key = program_hash_cache_key(path, &self.library_paths)?;
+                cache.get_or_insert_with(key, compile)
+            }
+            None => compile(),
+        }
+    }
+
+    /// Verifies all proofs in the specified directory and prints a summary of the results.
+    ///
+    /// Returns an error if any of the proofs failed to verify.
+    fn verify_batch(&self, program_info: &ProgramInfo, batch_dir: &Path) -> Result<(), String> {
+        let proof_files = read_proof_paths(batch_dir)?;
+        let results = self.verify_proofs(program_info, &proof_files);
+
+        println!("============================================================");
+        let mut num_failed = 0;
+        for (path, result) in results.iter() {
+            match result {
+                Ok(()) => println!("ok      {}", path.display()),
+                Err(err) => {
+                    num_failed += 1;
+                    println!("FAILED  {} - {}", path.display(), err);
+                }
+            }
+        }
+
+        let num_skipped = proof_files.len() - results.len();
+        println!(
+            "{} passed; {} failed; {} skipped",
+            results.len() - num_failed,
+            num_failed,
+            num_skipped
+        );
+
+        match results.iter().find(|(_, result)| result.is_err()) {
+            Some((path, _)) if self.fail_fast => {
+                Err(format!("Batch verification aborted: `{}` failed verification", path.display()))
+            }
+            Some(_) => Err(format!("Batch verification failed for {num_failed} proof(s)")),
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies the specified proofs in order and returns the result for each of them. If
+    /// `fail_fast` is set, verification stops after the first failure and the proofs which were
+    /// not attempted are not included in the results.
+    fn verify_proofs(
+        &self,
+        program_info: &ProgramInfo,
+        proof_files: &[PathBuf],
+    ) -> Vec<(PathBuf, Result<(), String>)> {
+        let mut results = Vec::with_capacity(proof_files.len());
+        for proof_file in proof_files {
+            let result = self.verify_proof(program_info, &None, &None, proof_file);
+            let failed = result.is_err();
+            results.push((proof_file.clone(), result));
+
+            if failed && self.fail_fast {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Verifies a single proof against the specified program.
+    fn verify_proof(
+        &self,
+        program_info: &ProgramInfo,
+        input_file: &Option<PathBuf>,
+        output_file: &Option<PathBuf>,
+        proof_file: &Path,
+    ) -> Result<(), String> {
+        // load input data from file; the default inputs and outputs files are named after the
+        // proof without the .b64 extension of base64 proofs
+        let base_path = ProofFile::base_path(proof_file);
+        let input_data = InputFile::read(input_file, &base_path, self.input_template)?;
 
         // fetch the stack inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
 
         // load outputs data from file
-        let outputs_data = OutputFile::read(&self.output_file, &self.proof_file)?;
+        let outputs_data = OutputFile::read(output_file, &base_path)?;
 
         // load proof from file
-        let proof = ProofFile::read(&Some(self.proof_file.clone()), &self.proof_file)?;
+        let (proof, header) =
+            ProofFile::read_encoded(&Some(proof_file.to_path_buf()), proof_file, self.encoding)?;
+
+        // make sure the proof is not too old
+        if let Some(max_age_secs) = self.max_proof_age_secs {
+            check_proof_age(header.as_ref(), Duration::from_secs(max_age_secs), SystemTime::now())?;
+        }
+
+        // make sure the outputs match the ones recorded when the proof was created; this lets us
+        // report tampered outputs separately from a generic verification failure
+        let stack_outputs = outputs_data.stack_outputs()?;
+        if let Some(header) = &header {
+            check_committed_outputs(header.stack_outputs(), &stack_outputs)?;
+        }
 
         println!("verifying program...");
         let now = Instant::now();
 
-        // TODO accept kernel as CLI argument
-        let kernel = Kernel::default();
-        let program_info = ProgramInfo::new(program_hash, kernel);
-
         // verify proof
-        verifier::verify(program_info, stack_inputs, outputs_data.stack_outputs()?, proof)
-            .map_err(|err| format!("Program failed verification! - {}", err))?;
+        let phase = Phase::Verify.enter();
+        let witness =
+            verifier::verify_with_witness(program_info.clone(), stack_inputs, stack_outputs, proof)
+                .map_err(|err| format!("Program failed verification! - {}", err))?;
+        phase.exit();
 
-        println!("Verification complete in {} ms", now.elapsed().as_millis());
+        let elapsed = now.elapsed();
+        println!("Verification complete in {} ms", elapsed.as_millis());
+        if let Some(budget_ms) = self.timing_budget_ms {
+            check_timing_budget(elapsed, Duration::from_millis(budget_ms))?;
+        }
+        if self.verbose {
+            print!("{}", format_witness(&witness));
+        }
 
         Ok(())
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the proof file with the specified header was created no more than `max_age` before
+/// `now`.
+///
+/// # Errors
+/// Returns an error if the proof is older than `max_age` or if the proof file has no header and,
+/// thus, its creation time is unknown.
+fn check_proof_age(
+    header: Option<&ProofHeader>,
+    max_age: Duration,
+    now: SystemTime,
+) -> Result<(), String> {
+    let header = header.ok_or("Proof file does not contain a creation timestamp")?;
+
+    // proofs created in the future (e.g., due to clock skew) are treated as fresh
+    let age = now.duration_since(header.timestamp()).unwrap_or_default();
+    if age > max_age {
+        return Err(format!(
+            "Proof is too old - created {} seconds ago, but the maximum allowed age is {} seconds",
+            age.as_secs(),
+            max_age.as_secs()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that verifying a proof took no more than `budget`.
+///
+/// # Errors
+/// Returns an error reporting the actual verification time if it exceeds the budget.
+fn check_timing_budget(elapsed: Duration, budget: Duration) -> Result<(), String> {
+    if elapsed > budget {
+        return Err(format!(
+            "Verification took {} ms, which exceeds the timing budget of {} ms",
+            elapsed.as_millis(),
+            budget.as_millis()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the provided stack outputs match the outputs recorded in the proof file.
+///
+/// # Errors
+/// Returns an "outputs mismatch" error listing all differences if the outputs do not match.
+fn check_committed_outputs(
+    committed: &StackOutputs,
+    provided: &StackOutputs,
+) -> Result<(), String> {
+    if committed == provided {
+        return Ok(());
+    }
+
+    let mut diff = String::new();
+    let stack_len = committed.stack().len().max(provided.stack().len());
+    for i in 0..stack_len {
+        let committed_value = committed.stack().get(i);
+        let provided_value = provided.stack().get(i);
+        if committed_value != provided_value {
+            diff.push_str(&format!(
+                "\n  stack[{i}]: proof has {}, outputs file has {}",
+                format_value(committed_value),
+                format_value(provided_value)
+            ));
+        }
+    }
+    if committed.overflow_addrs() != provided.overflow_addrs() {
+        diff.push_str(&format!(
+            "\n  overflow_addrs: proof has {:?}, outputs file has {:?}",
+            committed.overflow_addrs(),
+            provided.overflow_addrs()
+        ));
+    }
+
+    Err(format!("Program failed verification! - outputs mismatch:{diff}"))
+}
+
+/// Formats a summary of the public inputs and the parameters of a verified proof.
+fn format_witness(witness: &PublicWitness) -> String {
+    let params = &witness.proof_parameters;
+    let format_values = |values: &[u64]| {
+        values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(", ")
+    };
+    // stack inputs are listed in the order of the inputs file, i.e., with the top of the stack last
+    let stack_inputs = witness
+        .stack_inputs
+        .values()
+        .iter()
+        .rev()
+        .map(|value| value.as_int())
+        .collect::<Vec<_>>();
+    format!(
+        "program hash:    {}
+stack inputs:    [{}]
+stack outputs:   [{}]
+hash function:   {:?}
+security level:  {} bits
+trace length:    {}
+queries:         {}
+blowup factor:   {}
+grinding factor: {}
+field extension: {:?}
+",
+        display_digest(witness.program_hash.as_bytes()),
+        format_values(&stack_inputs),
+        format_values(witness.stack_outputs.stack()),
+        params.hash_fn,
+        params.security_level,
+        params.trace_length,
+        params.num_queries,
+        params.blowup_factor,
+        params.grinding_factor,
+        params.field_extension,
+    )
+}
+
+/// Formats an optional stack value for an outputs diff.
+fn format_value(value: Option<&u64>) -> String {
+    value.map_or("nothing".to_string(), |value| value.to_string())
+}
+
+/// Returns paths of all .proof and .proof.b64 files in the specified directory sorted by name.
+fn read_proof_paths(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|err| format!("Failed to read batch directory `{}` - {}", dir.display(), err))?;
+
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| format!("Failed to read batch directory `{}` - {}", dir.display(), err))?
+            .path();
+        let base_path = ProofFile::base_path(&path);
+        if path.is_file() && base_path.extension().is_some_and(|ext| ext == "proof") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Returns the key under which the hash of the program in the specified program file compiled
+/// with the specified libraries is cached; the key covers the full contents of all files.
+fn program_hash_cache_key(
+    program_path: &Path,
+    library_paths: &[PathBuf],
+) -> Result<[u8; 32], String> {
+    let mut sources = Vec::with_capacity(library_paths.len() + 1);
+    for path in iter::once(program_path).chain(library_paths.iter().map(PathBuf::as_path)) {
+        let source = fs::read(path)
+            .map_err(|err| format!("Failed to read file `{}` - {}", path.display(), err))?;
+        sources.push(source);
+    }
+    Ok(ProgramHashCache::key(sources.iter().map(Vec::as_slice)))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        check_proof_age, check_timing_budget, format_witness, program_hash_cache_key,
+        read_proof_paths, OutputFile, ProgramFile, ProofEncoding, ProofFile, VerifyCmd,
+    };
+    use crate::cli::data::JsonFormat;
+    use miden::{
+        Assembler, DefaultHost, ProgramHashCache, ProgramInfo, ProvingOptions, StackInputs,
+    };
+    use std::{fs, path::Path, sync::Arc, time::Duration};
+    use tempfile::TempDir;
+
+    /// Creates a batch directory with an invalid proof which is verified first, followed by a
+    /// valid proof of the returned program.
+    fn build_batch_dir() -> (TempDir, ProgramInfo) {
+        let dir = tempfile::tempdir().unwrap();
+
+        let program = Assembler::default().compile("begin push.1 push.2 add end").unwrap();
+        let (stack_outputs, proof) = miden::prove(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+
+        for name in ["a", "b"] {
+            let proof_path = dir.path().join(name).with_extension("proof");
+            ProofFile::write(
+                proof.clone(),
+                program.hash(),
+                &StackInputs::default(),
+                &stack_outputs,
+                &Some(proof_path),
+                dir.path(),
+            )
+            .unwrap();
+            let outputs_path = dir.path().join(name).with_extension("outputs");
+            OutputFile::write(&stack_outputs, &outputs_path, JsonFormat::Pretty).unwrap();
+        }
+        // corrupt the outputs of the first proof so that it fails verification
+        fs::write(dir.path().join("a.outputs"), r#"{ "stack": ["4"], "overflow_addrs": [] }"#)
+            .unwrap();
+
+        (dir, ProgramInfo::from(program))
+    }
+
+    fn build_batch_cmd(dir: &Path, fail_fast: bool) -> VerifyCmd {
+        VerifyCmd {
+            batch_dir: Some(dir.to_path_buf()),
+            compiled_file: None,
+            encoding: None,
+            fail_fast,
+            input_file: None,
+            input_template: false,
+            library_paths: Vec::new(),
+            max_proof_age_secs: None,
+            output_file: None,
+            proof_file: None,
+            program_file: None,
+            program_hash: None,
+            timing_budget_ms: None,
+            verbose: false,
+            vk_cache: None,
+            cache: None,
+        }
+    }
+
+    #[test]
+    fn program_hash_from_program_files() {
+        let (dir, program_info) = build_batch_dir();
+        let program_file = dir.path().join("program.masm");
+        fs::write(&program_file, "begin push.1 push.2 add end").unwrap();
+        ProgramFile::read(&program_file).unwrap().write(None).unwrap();
+
+        // the hash is the same whether it is passed as hex or computed from a program file
+        let base = VerifyCmd {
+            batch_dir: None,
+            proof_file: Some(dir.path().join("b.proof")),
+            ..build_batch_cmd(dir.path(), false)
+        };
+        let from_hex = VerifyCmd {
+            program_hash: Some(hex::encode(program_info.program_hash().as_bytes())),
+            ..base.clone()
+        };
+        let from_source = VerifyCmd {
+            program_file: Some(program_file.clone()),
+            ..base.clone()
+        };
+        let from_compiled = VerifyCmd {
+            compiled_file: Some(program_file.with_extension("masb")),
+            encoding: None,
+            ..base.clone()
+        };
+        for cmd in [&from_hex, &from_source, &from_compiled] {
+            assert_eq!(cmd.program_hash(None).unwrap(), *program_info.program_hash());
+        }
+        from_source.execute().unwrap();
+
+        // one of the hash or a program file must be specified
+        assert!(base.program_hash(None).is_err());
+    }
+
+    #[test]
+    fn vk_cache_computes_program_hash_once() {
+        let (dir, program_info) = build_batch_dir();
+        let program_file = dir.path().join("program.masm");
+        fs::write(&program_file, "begin push.1 push.2 add end").unwrap();
+        let cache_dir = dir.path().join("cache");
+
+        let cmd = VerifyCmd {
+            batch_dir: None,
+            proof_file: Some(dir.path().join("b.proof")),
+            program_file: Some(program_file.clone()),
+            vk_cache: Some(cache_dir.clone()),
+            ..build_batch_cmd(dir.path(), false)
+        };
+        cmd.execute().unwrap();
+
+        // the program hash is persisted in the cache directory
+        let key = program_hash_cache_key(&program_file, &[]).unwrap();
+        let cache = Arc::new(ProgramHashCache::open(&cache_dir).unwrap());
+        let program_hash = cache.get_or_insert_with::<_, String>(key, || unreachable!()).unwrap();
+        assert_eq!(program_hash, *program_info.program_hash());
+
+        // cached hashes are trusted and used instead of compiling the program again
+        let entry = fs::read_dir(&cache_dir).unwrap().next().unwrap().unwrap().path();
+        fs::write(&entry, String::from(miden::Digest::default())).unwrap();
+        let cmd = cmd.with_cache(Arc::new(ProgramHashCache::open(&cache_dir).unwrap()));
+        assert!(cmd.execute().is_err());
+
+        // changing the program changes the key
+        fs::write(&program_file, "begin push.1 push.3 add end").unwrap();
+        assert_ne!(program_hash_cache_key(&program_file, &[]).unwrap(), key);
+    }
+
+    #[test]
+    fn batch_verify_reports_all_failures() {
+        let (dir, program_info) = build_batch_dir();
+        let cmd = build_batch_cmd(dir.path(), false);
+
+        let results = cmd.verify_proofs(&program_info, &read_proof_paths(dir.path()).unwrap());
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_err());
+        assert!(results[1].1.is_ok());
+        assert!(cmd.verify_batch(&program_info, dir.path()).is_err());
+    }
+
+    #[test]
+    fn batch_verify_fail_fast_stops_on_first_failure() {
+        let (dir, program_info) = build_batch_dir();
+        let cmd = build_batch_cmd(dir.path(), true);
+
+        let results = cmd.verify_proofs(&program_info, &read_proof_paths(dir.path()).unwrap());
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, dir.path().join("a.proof"));
+        assert!(results[0].1.is_err());
+
+        let err = cmd.verify_batch(&program_info, dir.path()).unwrap_err();
+        assert!(err.contains("a.proof"));
+    }
+
+    #[test]
+    fn tampered_outputs_reported_as_mismatch() {
+        let (dir, program_info) = build_batch_dir();
+        let cmd = build_batch_cmd(dir.path(), false);
+
+        // change the top stack value of an otherwise valid outputs file
+        let outputs = fs::read_to_string(dir.path().join("b.outputs")).unwrap();
+        fs::write(dir.path().join("b.outputs"), outputs.replacen(r#""3""#, r#""4""#, 1)).unwrap();
+
+        let err = cmd
+            .verify_proof(&program_info, &None, &None, &dir.path().join("b.proof"))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            "Program failed verification! - outputs mismatch:\n  stack[0]: proof has 3, outputs file has 4"
+        );
+    }
+
+    #[test]
+    fn base64_proof_round_trip() {
+        let (dir, program_info) = build_batch_dir();
+        let (proof, _) = ProofFile::read(&Some(dir.path().join("b.proof")), dir.path()).unwrap();
+        let stack_outputs = OutputFile::read(&Some(dir.path().join("b.outputs")), dir.path())
+            .unwrap()
+            .stack_outputs()
+            .unwrap();
+
+        // proofs in .proof.b64 files are written as base64 text
+        let base64_path = dir.path().join("c.proof.b64");
+        ProofFile::write(
+            proof.clone(),
+            *program_info.program_hash(),
+            &StackInputs::default(),
+            &stack_outputs,
+            &Some(base64_path.clone()),
+            dir.path(),
+        )
+        .unwrap();
+        let text = fs::read_to_string(&base64_path).unwrap();
+        assert!(text
+            .trim_end()
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'+' | b'/' | b'=')));
+
+        // the proof read back is identical to the binary one, also when the text is wrapped
+        let (decoded, _) = ProofFile::read(&Some(base64_path.clone()), dir.path()).unwrap();
+        assert_eq!(decoded.to_bytes(), proof.to_bytes());
+        let wrapped_path = dir.path().join("wrapped.txt");
+        let wrapped = text.as_bytes().chunks(76).map(|line| std::str::from_utf8(line).unwrap());
+        fs::write(&wrapped_path, wrapped.collect::<Vec<_>>().join("\n")).unwrap();
+        let (decoded, _) =
+            ProofFile::read_encoded(&Some(wrapped_path), dir.path(), Some(ProofEncoding::Base64))
+                .unwrap();
+        assert_eq!(decoded.to_bytes(), proof.to_bytes());
+
+        // the base64 proof verifies with the outputs file named after the proof
+        fs::copy(dir.path().join("b.outputs"), dir.path().join("c.outputs")).unwrap();
+        let cmd = build_batch_cmd(dir.path(), false);
+        cmd.verify_proof(&program_info, &None, &None, &base64_path).unwrap();
+        assert!(read_proof_paths(dir.path()).unwrap().contains(&base64_path));
+
+        // reading a binary proof as base64 fails
+        let err = ProofFile::read_encoded(
+            &Some(dir.path().join("b.proof")),
+            dir.path(),
+            Some(ProofEncoding::Base64),
+        )
+        .unwrap_err();
+        assert!(err.starts_with("Failed to decode base64 proof data"));
+    }
+
+    #[test]
+    fn proof_age_check() {
+        let (dir, _) = build_batch_dir();
+        let (_, header) = ProofFile::read(&Some(dir.path().join("b.proof")), dir.path()).unwrap();
+        let header = header.expect("proof file header");
+        let max_age = Duration::from_secs(60);
+
+        // the check passes right after the proof was created and until it is max_age old
+        let now = header.timestamp();
+        assert!(check_proof_age(Some(&header), max_age, now).is_ok());
+        assert!(check_proof_age(Some(&header), max_age, now + max_age).is_ok());
+
+        // advancing time past max_age makes the check fail
+        let err = check_proof_age(Some(&header), max_age, now + max_age + Duration::from_secs(1))
+            .unwrap_err();
+        assert!(err.contains("Proof is too old"));
+
+        // proofs without a header have no timestamp and are rejected
+        assert!(check_proof_age(None, max_age, now).is_err());
+    }
+
+    #[test]
+    fn verify_with_witness_reports_public_inputs() {
+        let program = Assembler::default().compile("begin push.1 push.2 add end").unwrap();
+        let stack_inputs = StackInputs::try_from_values([7, 8]).unwrap();
+        let (stack_outputs, proof) = miden::prove(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+        let trace_length = proof.stark_proof().trace_length();
+
+        let witness = miden::verify_with_witness(
+            ProgramInfo::from(program.clone()),
+            stack_inputs.clone(),
+            stack_outputs.clone(),
+            proof,
+        )
+        .unwrap();
+        assert_eq!(witness.program_hash, program.hash());
+        assert_eq!(witness.stack_inputs, stack_inputs);
+        assert_eq!(witness.stack_outputs, stack_outputs);
+        assert_eq!(witness.proof_parameters.trace_length, trace_length);
+        assert_eq!(witness.proof_parameters.num_queries, 27);
+        assert_eq!(witness.proof_parameters.blowup_factor, 8);
+
+        let summary = format_witness(&witness);
+        let program_hash = hex::encode(program.hash().as_bytes());
+        assert!(summary.starts_with(&format!("program hash:    {program_hash}\n")), "{summary}");
+        assert!(summary.contains("stack inputs:    [7, 8]\n"), "{summary}");
+        assert!(summary.contains("stack outputs:   [3, 8, 7, 0,"), "{summary}");
+        assert!(summary.contains("hash function:   Blake3_192\n"), "{summary}");
+    }
+
+    #[test]
+    fn timing_budget_gates_verification() {
+        let (dir, program_info) = build_batch_dir();
+        let proof_path = dir.path().join("b.proof");
+
+        // a generous budget is met
+        let cmd = VerifyCmd {
+            timing_budget_ms: Some(60_000),
+            ..build_batch_cmd(dir.path(), false)
+        };
+        cmd.verify_proof(&program_info, &None, &None, &proof_path).unwrap();
+
+        // a tiny budget is exceeded, and the actual verification time is reported
+        let cmd = VerifyCmd {
+            timing_budget_ms: Some(0),
+            ..cmd
+        };
+        let err = cmd.verify_proof(&program_info, &None, &None, &proof_path).unwrap_err();
+        assert!(err.starts_with("Verification took "), "{err}");
+        assert!(err.ends_with(" ms, which exceeds the timing budget of 0 ms"), "{err}");
+
+        assert!(check_timing_budget(Duration::from_millis(5), Duration::from_millis(5)).is_ok());
+        assert!(check_timing_budget(Duration::from_millis(6), Duration::from_millis(5)).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+use assembly::{
+    ast::{ModuleAst, ProcedureAst, ProgramAst},
+    Assembler,
+};
+use clap::Parser;
+use core::fmt;
+use std::{fs, path::PathBuf};
+
+#[derive(Debug, Clone, Parser)]
+#[clap(
+    about = "Verify the stack effects annotated in the comments of procedures",
+    long_about = "Verify the stack effects annotated in the comments of procedures.
+
+A stack effect is written as `[inputs -> outputs]` in a comment directly above a procedure \
+declaration, or in a comment at the end of the declaration line, optionally prefixed with \
+`stack:`, e.g., `# [a, b -> a+b]` or `#! stack: [x -> x, x]`. Inputs and outputs are \
+comma-separated lists in which every item stands for a single stack element; either list can be \
+empty. The annotated net change of the stack depth (outputs minus inputs) is compared against the \
+change computed statically from the body of the procedure."
+)]
+pub struct VerifyStackEffectsCmd {
+    /// Path to the .masm assembly file
+    #[clap(value_parser)]
+    assembly_file: PathBuf,
+}
+
+impl VerifyStackEffectsCmd {
+    pub fn execute(&self) -> Result<(), String> {
+        let source = fs::read_to_string(&self.assembly_file).map_err(|err| {
+            format!("Failed to read file `{}` - {}", self.assembly_file.display(), err)
+        })?;
+
+        let checks = check_stack_effects(&source)?;
+        if checks.is_empty() {
+            println!("No stack effect annotations found");
+            return Ok(());
+        }
+        for check in checks.iter() {
+            println!("{check}");
+        }
+
+        let mismatches = checks.iter().filter(|check| check.is_mismatch()).count();
+        if mismatches > 0 {
+            return Err(format!(
+                "{mismatches} of {} annotated procedure(s) do not match their stack effect",
+                checks.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+// STACK EFFECT
+// ================================================================================================
+
+/// A stack effect annotation of the form `[inputs -> outputs]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackEffect {
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+}
+
+impl StackEffect {
+    /// Parses a stack effect annotation from the specified comment text (without the leading
+    /// `#` or `#!`).
+    ///
+    /// Returns None if the comment is not an annotation, i.e., if it does not start with `[` or
+    /// `stack:`.
+    ///
+    /// # Errors
+    /// Returns an error if the comment starts like an annotation but is not well-formed.
+    pub fn parse(comment: &str) -> Option<Result<Self, String>> {
+        let comment = comment.trim();
+        let notation = match comment.strip_prefix("stack:") {
+            Some(rest) => rest.trim(),
+            None if comment.starts_with('[') => comment,
+            None => return None,
+        };
+
+        let inner = notation
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(|| format!("stack effect `{notation}` must be enclosed in brackets"));
+        let effect = inner.and_then(|inner| {
+            let (inputs, outputs) = inner
+                .split_once("->")
+                .ok_or_else(|| format!("stack effect `{notation}` is missing `->`"))?;
+            Ok(Self {
+                inputs: split_items(inputs),
+                outputs: split_items(outputs),
+            })
+        });
+        Some(effect)
+    }
+
+    /// Returns the net change of the stack depth stated by this annotation.
+    pub fn net_change(&self) -> i32 {
+        self.outputs.len() as i32 - self.inputs.len() as i32
+    }
+}
+
+impl fmt::Display for StackEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} -> {}]", self.inputs.join(", "), self.outputs.join(", "))
+    }
+}
+
+/// Splits a comma-separated list of stack items, ignoring the commas nested in brackets or
+/// parentheses.
+fn split_items(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0_usize;
+    let mut item = String::new();
+    for c in list.chars() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(item.trim().to_string());
+                item.clear();
+                continue;
+            }
+            _ => (),
+        }
+        item.push(c);
+    }
+    items.push(item.trim().to_string());
+    items.retain(|item| !item.is_empty());
+    items
+}
+
+// STACK EFFECT CHECKS
+// ================================================================================================
+
+/// The result of verifying the stack effect annotation of a single procedure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StackEffectCheck {
+    /// Name of the procedure.
+    proc_name: String,
+    /// Line of the procedure declaration.
+    line: usize,
+    /// The outcome of the verification.
+    outcome: CheckOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CheckOutcome {
+    /// The annotated net change matches the computed one.
+    Match(StackEffect),
+    /// The annotated net change differs from the computed one.
+    Mismatch(StackEffect, i32),
+    /// The net change of the procedure cannot be computed statically.
+    Unknown(StackEffect),
+    /// The annotation is not well-formed.
+    Malformed(String),
+}
+
+impl StackEffectCheck {
+    /// Returns true if the annotation does not match the body of the procedure, or is malformed.
+    pub fn is_mismatch(&self) -> bool {
+        matches!(self.outcome, CheckOutcome::Mismatch(..) | CheckOutcome::Malformed(_))
+    }
+}
+
+impl fmt::Display for StackEffectCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            proc_name, line, ..
+        } = self;
+        match &self.outcome {
+            CheckOutcome::Match(effect) => {
+                write!(f, "ok       {proc_name} (line {line}): {effect}")
+            }
+            CheckOutcome::Mismatch(effect, actual) => write!(
+                f,
+                "error    {proc_name} (line {line}): {effect} changes the stack depth by {}, \
+                but the procedure changes it by {actual}",
+                effect.net_change()
+            ),
+            CheckOutcome::Unknown(effect) => write!(
+                f,
+                "skipped  {proc_name} (line {line}): {effect} cannot be verified statically"
+            ),
+            CheckOutcome::Malformed(err) => write!(f, "error    {proc_name} (line {line}): {err}"),
+        }
+    }
+}
+
+/// Verifies the stack effects annotated on the procedures of the specified program or module
+/// source, and returns the results for every annotated procedure in the order of declaration.
+///
+/// # Errors
+/// Returns an error if the source cannot be parsed, or if the stack effects of its procedures
+/// cannot be computed.
+pub fn check_stack_effects(source: &str) -> Result<Vec<StackEffectCheck>, String> {
+    let assembler = Assembler::default();
+    let (procs, effects) = match ProgramAst::parse(source) {
+        Ok(ast) => {
+            let effects = ast.procedure_stack_effects(&assembler);
+            (ast.procedures().to_vec(), effects)
+        }
+        Err(program_err) => {
+            let ast = ModuleAst::parse(source)
+                .map_err(|_| format!("Failed to parse program - {program_err}"))?;
+            let effects = ast.procedure_stack_effects(&assembler);
+            (ast.procs().to_vec(), effects)
+        }
+    };
+    let effects = effects.map_err(|err| format!("Failed to compute the stack effects - {err}"))?;
+
+    let lines = source.lines().collect::<Vec<_>>();
+    let checks = procs
+        .iter()
+        .zip(effects)
+        .filter_map(|(proc, actual)| {
+            let line = proc.start.line() as usize;
+            let outcome = match find_annotation(&lines, proc)? {
+                Err(err) => CheckOutcome::Malformed(err),
+                Ok(effect) => match actual {
+                    Some(actual) if actual == effect.net_change() => CheckOutcome::Match(effect),
+                    Some(actual) => CheckOutcome::Mismatch(effect, actual),
+                    None => CheckOutcome::Unknown(effect),
+                },
+            };
+            Some(StackEffectCheck {
+                proc_name: proc.name.to_string(),
+                line,
+                outcome,
+            })
+        })
+        .collect();
+    Ok(checks)
+}
+
+/// Returns the stack effect annotation of the specified procedure, looking first at the comment
+/// at the end of its declaration line, and then at the comments directly above the declaration.
+fn find_annotation(lines: &[&str], proc: &ProcedureAst) -> Option<Result<StackEffect, String>> {
+    let decl_idx = (proc.start.line() as usize).checked_sub(1)?;
+    let trailing = lines
+        .get(decl_idx)
+        .and_then(|line| line.split_once('#'))
+        .map(|(_, comment)| comment.trim_start_matches('!'));
+    if let Some(effect) = trailing.and_then(StackEffect::parse) {
+        return Some(effect);
+    }
+
+    lines[..decl_idx]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .take_while(|line| line.starts_with('#'))
+        .find_map(|line| StackEffect::parse(line.trim_start_matches('#').trim_start_matches('!')))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{check_stack_effects, StackEffect};
+
+    #[test]
+    fn parse_stack_effect() {
+        let effect = StackEffect::parse(" [a, b -> a+b]").unwrap().unwrap();
+        assert_eq!(effect.net_change(), -1);
+        assert_eq!(effect.to_string(), "[a, b -> a+b]");
+
+        let effect = StackEffect::parse("stack: [ -> hash(a, b), x]").unwrap().unwrap();
+        assert_eq!(effect.net_change(), 2);
+
+        // comments which do not look like annotations are ignored
+        assert!(StackEffect::parse(" adds two numbers").is_none());
+        assert!(StackEffect::parse("stack: [a, b]").unwrap().is_err());
+        assert!(StackEffect::parse("[a, b -> c").unwrap().is_err());
+    }
+
+    #[test]
+    fn check_annotated_procedures() {
+        let source = "\
+#! Adds two numbers.
+#! stack: [a, b -> a+b]
+proc.add_two
+    add
+end
+
+proc.dup_top # [x -> x, x]
+    dup
+end
+
+# [a -> a, a, a]
+proc.wrong
+    dup
+end
+
+proc.unannotated
+    drop
+end
+
+# [a -> ]
+proc.branchy
+    if.true push.1 else swap end
+end
+
+begin
+    exec.add_two
+end";
+        let checks = check_stack_effects(source).unwrap();
+        let report = checks.iter().map(|check| check.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            report,
+            [
+                "ok       add_two (line 3): [a, b -> a+b]",
+                "ok       dup_top (line 7): [x -> x, x]",
+                "error    wrong (line 12): [a -> a, a, a] changes the stack depth by 2, but the \
+                procedure changes it by 1",
+                "skipped  branchy (line 21): [a -> ] cannot be verified statically",
+            ]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Helpers for running the VM under a fuzzer.
+//!
+//! This module is available only when the `std` feature is enabled.
+
+use core::fmt;
+use processor::{ExecutionError, ExecutionOptions, ExecutionTrace, Host, Program, StackInputs};
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    string::{String, ToString},
+};
+
+// EXECUTE AND CATCH PANICS
+// ================================================================================================
+
+/// Returns an execution trace resulting from executing the provided program against the provided
+/// inputs, converting any panic raised during the execution into an error.
+///
+/// This function is intended for fuzzing, where a panic inside the VM is a finding to be reported
+/// rather than a reason to abort the fuzzer; it should not be used to execute programs otherwise,
+/// as a panic indicates a bug in the VM and the state of the host after a panic is unspecified.
+/// Note that the panic is still reported by the panic hook (by default, printed to stderr), and
+/// that panics are caught only if the binary is built with `panic = "unwind"`.
+///
+/// # Errors
+/// Returns an error if the execution fails, or if it panics.
+pub fn execute_catch_unwind<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ExecutionOptions,
+) -> Result<ExecutionTrace, CatchUnwindError>
+where
+    H: Host,
+{
+    // the host and the program are not used after a panic, and thus, cannot be observed in a
+    // broken state
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        processor::execute(program, stack_inputs, host, options)
+    }));
+    match result {
+        Ok(result) => result.map_err(CatchUnwindError::ExecutionFailed),
+        Err(payload) => Err(CatchUnwindError::Panicked {
+            message: panic_message(payload.as_ref()),
+        }),
+    }
+}
+
+/// Returns the message of a panic from its payload, which is a string for panics raised via
+/// `panic!()` and friends.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+// CATCH UNWIND ERROR
+// ================================================================================================
+
+/// An error returned by [execute_catch_unwind()].
+#[derive(Debug)]
+pub enum CatchUnwindError {
+    /// The execution failed with an error.
+    ExecutionFailed(ExecutionError),
+    /// The execution panicked with the specified message.
+    Panicked { message: String },
+}
+
+impl CatchUnwindError {
+    /// Returns true if the execution panicked.
+    pub fn is_panic(&self) -> bool {
+        matches!(self, Self::Panicked { .. })
+    }
+}
+
+impl fmt::Display for CatchUnwindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExecutionFailed(err) => write!(f, "execution failed: {err}"),
+            Self::Panicked { message } => write!(f, "execution panicked: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for CatchUnwindError {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{execute_catch_unwind, CatchUnwindError};
+    use assembly::Assembler;
+    use processor::{
+        AdviceExtractor, AdviceInjector, DefaultHost, ExecutionError, ExecutionOptions, Host,
+        HostResponse, ProcessState, StackInputs,
+    };
+
+    /// A host which panics whenever advice is requested from it.
+    struct PanickingHost;
+
+    impl Host for PanickingHost {
+        fn get_advice<S: ProcessState>(
+            &mut self,
+            _process: &S,
+            _extractor: AdviceExtractor,
+        ) -> Result<HostResponse, ExecutionError> {
+            panic!("advice requested from a panicking host")
+        }
+
+        fn set_advice<S: ProcessState>(
+            &mut self,
+            _process: &S,
+            _injector: AdviceInjector,
+        ) -> Result<HostResponse, ExecutionError> {
+            Ok(HostResponse::None)
+        }
+    }
+
+    #[test]
+    fn execute_catch_unwind_catches_panics() {
+        let program = Assembler::default().compile("begin adv_push.1 end").unwrap();
+        let result = execute_catch_unwind(
+            &program,
+            StackInputs::default(),
+            PanickingHost,
+            ExecutionOptions::default(),
+        );
+        match result.err() {
+            Some(CatchUnwindError::Panicked { message }) => {
+                assert_eq!(message, "advice requested from a panicking host")
+            }
+            other => panic!("expected a panic to be caught, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn execute_catch_unwind_returns_execution_errors() {
+        let program = Assembler::default().compile("begin push.0 assert end").unwrap();
+        let err = execute_catch_unwind(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ExecutionOptions::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(!err.is_panic());
+        assert!(err.to_string().starts_with("execution failed"));
+
+        let program = Assembler::default().compile("begin push.1 assert end").unwrap();
+        let trace = execute_catch_unwind(
+            &program,
+            StackInputs::default(),
+            DefaultHost::default(),
+            ExecutionOptions::default(),
+        );
+        assert!(trace.is_ok());
+    }
+}
@@ -1,6 +1,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../README.md")]
 
+pub mod opcodes;
+
+#[cfg(feature = "std")]
+mod fuzzing;
+
+#[cfg(feature = "std")]
+mod program_hash_cache;
+
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+
 // EXPORTS
 // ================================================================================================
 
@@ -8,13 +19,22 @@ pub use assembly::{
     ast::{ModuleAst, ProgramAst},
     Assembler, AssemblyError, ParsingError,
 };
+#[cfg(feature = "std")]
+pub use fuzzing::{execute_catch_unwind, CatchUnwindError};
 pub use processor::{
-    crypto, execute, execute_iter, utils, AdviceInputs, AdviceProvider, AsmOpInfo, DefaultHost,
-    ExecutionError, ExecutionTrace, Host, Kernel, MemAdviceProvider, Operation, Program,
-    ProgramInfo, StackInputs, VmState, VmStateIterator, ZERO,
+    code_blocks, crypto, execute, execute_iter, execute_outputs, utils, AdviceInputs, AdviceMapKey,
+    AdviceProvider, AsmOpInfo, AtomicExecutor, DefaultHost, ExecutionError, ExecutionTrace, Host,
+    Kernel, MemAdviceProvider, Operation, Program, ProgramInfo, StackInputs, UpdatedAdvice,
+    VmState, VmStateIterator, ZERO,
 };
+#[cfg(feature = "std")]
+pub use program_hash_cache::{ProgramHashCache, ProgramHashCacheError};
 pub use prover::{
-    math, prove, Digest, ExecutionProof, FieldExtension, HashFunction, InputError, ProvingOptions,
-    StackOutputs, StarkProof, Word,
+    estimate_proof_size, math, prove, prove_with_size_budget, Digest, ExecutionProof,
+    FieldExtension, HashFunction, InputError, ProvingOptions, StackOutputs, StarkProof, Word,
+};
+#[cfg(feature = "std")]
+pub use prover::{prove_with_domain_cache, DomainCache};
+pub use verifier::{
+    verify, verify_with_witness, ProofParameters, PublicWitness, VerificationError,
 };
-pub use verifier::{verify, VerificationError};
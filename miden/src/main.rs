@@ -13,19 +13,34 @@ mod tools;
 pub struct Cli {
     #[clap(subcommand)]
     action: Actions,
+    /// Abbreviate the digests (e.g., program hashes) printed to the console to their first and
+    /// last N hex characters; files and verification inputs always contain full digests
+    #[clap(long = "digest-abbrev", global = true, value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    digest_abbrev: Option<u16>,
 }
 
 /// CLI actions
 #[derive(Debug, Parser)]
 pub enum Actions {
+    AdviceKey(cli::AdviceKeyCmd),
     Analyze(tools::Analyze),
+    BuildStore(cli::BuildStoreCmd),
+    CheckMerkle(cli::CheckMerkleCmd),
     Compile(cli::CompileCmd),
+    CompileAll(cli::CompileAllCmd),
+    CompileToVerifier(cli::CompileToVerifierCmd),
     Bundle(cli::BundleCmd),
     Debug(cli::DebugCmd),
+    DiffPrograms(cli::DiffProgramsCmd),
     Example(examples::ExampleOptions),
+    ExtractPublicInputs(cli::ExtractPublicInputsCmd),
+    InspectProof(cli::InspectProofCmd),
+    #[clap(alias = "pipe")]
+    Pipeline(cli::PipelineCmd),
     Prove(cli::ProveCmd),
     Run(cli::RunCmd),
     Verify(cli::VerifyCmd),
+    VerifyStackEffects(cli::VerifyStackEffectsCmd),
     #[cfg(feature = "std")]
     Repl(cli::ReplCmd),
 }
@@ -33,15 +48,26 @@ pub enum Actions {
 /// CLI entry point
 impl Cli {
     pub fn execute(&self) -> Result<(), String> {
+        cli::set_digest_abbrev(self.digest_abbrev.map(usize::from));
         match &self.action {
+            Actions::AdviceKey(advice_key) => advice_key.execute(),
             Actions::Analyze(analyze) => analyze.execute(),
+            Actions::BuildStore(build_store) => build_store.execute(),
+            Actions::CheckMerkle(check_merkle) => check_merkle.execute(),
             Actions::Compile(compile) => compile.execute(),
+            Actions::CompileAll(compile_all) => compile_all.execute(),
+            Actions::CompileToVerifier(compile_to_verifier) => compile_to_verifier.execute(),
             Actions::Bundle(compile) => compile.execute(),
             Actions::Debug(debug) => debug.execute(),
+            Actions::DiffPrograms(diff_programs) => diff_programs.execute(),
             Actions::Example(example) => example.execute(),
+            Actions::ExtractPublicInputs(extract) => extract.execute(),
+            Actions::InspectProof(inspect_proof) => inspect_proof.execute(),
+            Actions::Pipeline(pipeline) => pipeline.execute(),
             Actions::Prove(prove) => prove.execute(),
             Actions::Run(run) => run.execute(),
             Actions::Verify(verify) => verify.execute(),
+            Actions::VerifyStackEffects(verify_stack_effects) => verify_stack_effects.execute(),
             #[cfg(feature = "std")]
             Actions::Repl(repl) => repl.execute(),
         }
@@ -53,9 +79,18 @@ pub fn main() {
     // read command-line args
     let cli = Cli::parse();
 
+    // emit the spans of the command phases as JSON to stderr
+    #[cfg(feature = "tracing")]
+    cli::init_subscriber();
+
+    // record the puffin scopes of the proving phases if MIDEN_PROFILE is set
+    let profiler = cli::Profiler::start();
+
     // execute cli action
-    if let Err(error) = cli.execute() {
+    let result = cli.execute().and_then(|_| profiler.finish());
+    if let Err(error) = result {
         println!("{}", error);
+        std::process::exit(1);
     }
 }
 
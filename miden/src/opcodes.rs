@@ -0,0 +1,34 @@
+//! Cycle costs of VM operations and Miden assembly instructions.
+//!
+//! These can be used to analyze the cost of a program statically, without executing it. See also
+//! [ProgramAst::estimated_cycle_count()](crate::ProgramAst::estimated_cycle_count).
+
+use crate::{Assembler, AssemblyError, Operation};
+
+pub use assembly::ast::{Instruction, InstructionCost};
+
+// CYCLE COSTS
+// ================================================================================================
+
+/// Returns the number of VM cycles it takes to execute the specified operation.
+///
+/// Every operation of the VM, including the control flow operations (e.g., SPAN, JOIN, and END),
+/// is executed in a single cycle.
+pub fn cycle_cost(_op: &Operation) -> u64 {
+    1
+}
+
+/// Returns the number of VM cycles it takes to execute the specified assembly instruction when it
+/// is compiled by the default assembler in a procedure with the specified number of locals.
+///
+/// See [Assembler::instruction_cycle_cost()] for details on how the cost is computed.
+///
+/// # Errors
+/// Returns an error if the instruction cannot be compiled in isolation, e.g., if it accesses a
+/// local with an index which is out of bounds.
+pub fn instruction_cycle_cost(
+    instruction: &Instruction,
+    num_locals: u16,
+) -> Result<u64, AssemblyError> {
+    Assembler::default().instruction_cycle_cost(instruction, num_locals)
+}
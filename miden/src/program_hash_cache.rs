@@ -0,0 +1,228 @@
+//! A cache of the hashes of programs compiled from source, used to avoid recompiling the same
+//! program when verifying many proofs of it.
+//!
+//! This module is available only when the `std` feature is enabled.
+
+use core::fmt;
+use processor::{crypto::Blake3_256, utils::ByteWriter};
+use prover::Digest;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    string::{String, ToString},
+    sync::Mutex,
+    vec::Vec,
+};
+
+// PROGRAM HASH CACHE
+// ================================================================================================
+
+/// Cache of the hashes of programs compiled from source.
+///
+/// Miden proofs are verified against the hash of the proven program. If the program is given as
+/// source code, it has to be compiled to compute this hash, which dominates the cost of verifying
+/// many proofs of the same program. The cache maps the key of a program (see
+/// [ProgramHashCache::key]) to its hash, so that each program is compiled only once.
+///
+/// The key is a hash of the full contents of the program source and of the libraries it is
+/// compiled with, and thus, a cached hash is never returned for a program whose source differs
+/// from the one for which the hash was computed.
+///
+/// A default cache keeps its entries in memory only. If the cache is backed by a directory, each
+/// entry is also stored in a `<key>.hash` file in it, which contains the hex-encoded program hash,
+/// so that the entries outlive the process.
+///
+/// # Trust assumption
+/// Entries read from the cache directory are not validated against the program: doing so would
+/// require compiling the program, which is what the cache avoids. A proof is thus verified against
+/// whatever hash the entry contains, and anyone who can write to the cache directory can make
+/// proofs of arbitrary programs pass verification. The directory must be writable only by the
+/// verifier.
+#[derive(Debug, Default)]
+pub struct ProgramHashCache {
+    dir: Option<PathBuf>,
+    entries: Mutex<HashMap<[u8; 32], Digest>>,
+}
+
+impl ProgramHashCache {
+    /// Returns a new cache backed by the specified directory; the directory is created if it
+    /// doesn't exist.
+    ///
+    /// # Errors
+    /// Returns an error if the directory cannot be created.
+    pub fn open(dir: &Path) -> Result<Self, ProgramHashCacheError> {
+        fs::create_dir_all(dir).map_err(|error| ProgramHashCacheError::Io {
+            path: dir.to_path_buf(),
+            error,
+        })?;
+        Ok(Self {
+            dir: Some(dir.to_path_buf()),
+            entries: Mutex::default(),
+        })
+    }
+
+    /// Returns the key of a program compiled from the specified sources: the source of the
+    /// program, followed by the serialized libraries it is compiled with, in order.
+    ///
+    /// The key is the BLAKE3-256 hash of the version of this crate (which determines the bundled
+    /// standard library), followed by the length and the contents of each of the sources.
+    pub fn key<'a, I>(sources: I) -> [u8; 32]
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let mut bytes = Vec::new();
+        bytes.write_bytes(env!("CARGO_PKG_VERSION").as_bytes());
+        for source in sources {
+            bytes.write_u64(source.len() as u64);
+            bytes.write_bytes(source);
+        }
+        Blake3_256::hash(&bytes).into()
+    }
+
+    /// Returns the program hash cached for the specified key; if there is no such entry, the hash
+    /// is computed by `compute` and inserted into the cache.
+    ///
+    /// # Errors
+    /// Returns an error if `compute` fails, or if the entry cannot be read from or written to the
+    /// cache directory.
+    pub fn get_or_insert_with<F, E>(&self, key: [u8; 32], compute: F) -> Result<Digest, E>
+    where
+        F: FnOnce() -> Result<Digest, E>,
+        E: From<ProgramHashCacheError>,
+    {
+        let mut entries = self.entries.lock().expect("program hash cache is poisoned");
+        if let Some(program_hash) = entries.get(&key) {
+            return Ok(*program_hash);
+        }
+
+        let path = self.dir.as_ref().map(|dir| dir.join(entry_name(&key)));
+        let program_hash = match &path {
+            Some(path) if path.exists() => read_entry(path)?,
+            _ => {
+                let program_hash = compute()?;
+                if let Some(path) = &path {
+                    write_entry(path, program_hash)?;
+                }
+                program_hash
+            }
+        };
+
+        entries.insert(key, program_hash);
+        Ok(program_hash)
+    }
+}
+
+// PROGRAM HASH CACHE ERROR
+// ================================================================================================
+
+/// Errors raised when reading from or writing to the directory of a [ProgramHashCache].
+#[derive(Debug)]
+pub enum ProgramHashCacheError {
+    Io { path: PathBuf, error: io::Error },
+    InvalidEntry { path: PathBuf },
+}
+
+impl fmt::Display for ProgramHashCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, error } => {
+                write!(f, "failed to access program hash cache entry `{}`: {error}", path.display())
+            }
+            Self::InvalidEntry { path } => {
+                write!(f, "program hash cache entry `{}` is not a valid hash", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProgramHashCacheError {}
+
+impl From<ProgramHashCacheError> for String {
+    fn from(error: ProgramHashCacheError) -> Self {
+        error.to_string()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the name of the file in which the entry with the specified key is stored.
+fn entry_name(key: &[u8; 32]) -> String {
+    let mut name: String = key.iter().map(|byte| format!("{byte:02x}")).collect();
+    name.push_str(".hash");
+    name
+}
+
+/// Reads the program hash stored in the specified entry file.
+fn read_entry(path: &Path) -> Result<Digest, ProgramHashCacheError> {
+    let contents = fs::read_to_string(path).map_err(|error| ProgramHashCacheError::Io {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    Digest::try_from(contents.trim()).map_err(|_| ProgramHashCacheError::InvalidEntry {
+        path: path.to_path_buf(),
+    })
+}
+
+/// Writes the program hash into the specified entry file.
+///
+/// The hash is first written into a temporary file, which is then renamed into place, so that
+/// concurrent readers never observe a partially written entry.
+fn write_entry(path: &Path, program_hash: Digest) -> Result<(), ProgramHashCacheError> {
+    let tmp_path = path.with_extension("hash.tmp");
+    fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(String::from(program_hash).as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|error| {
+            let _ = fs::remove_file(&tmp_path);
+            ProgramHashCacheError::Io {
+                path: path.to_path_buf(),
+                error,
+            }
+        })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_name, ProgramHashCache, ProgramHashCacheError};
+    use prover::Digest;
+    use std::fs;
+
+    #[test]
+    fn program_hash_cache_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let program_hash = Digest::default();
+
+        // the key depends on the full contents and the boundaries of the sources
+        let key = ProgramHashCache::key([b"begin push.1 end".as_slice()]);
+        assert_ne!(ProgramHashCache::key([b"begin push.2 end".as_slice()]), key);
+        assert_ne!(ProgramHashCache::key([b"begin push.1".as_slice(), b" end"]), key);
+
+        // computed hashes are persisted in the cache directory
+        let cache = ProgramHashCache::open(dir.path()).unwrap();
+        let hash = cache.get_or_insert_with::<_, String>(key, || Ok(program_hash)).unwrap();
+        assert_eq!(hash, program_hash);
+        assert!(dir.path().join(entry_name(&key)).exists());
+
+        // entries are read from the directory instead of being computed again
+        let cache = ProgramHashCache::open(dir.path()).unwrap();
+        let hash = cache.get_or_insert_with::<_, String>(key, || unreachable!()).unwrap();
+        assert_eq!(hash, program_hash);
+
+        // malformed entries are rejected
+        let key = ProgramHashCache::key([b"begin push.2 end".as_slice()]);
+        fs::write(dir.path().join(entry_name(&key)), "not a hash").unwrap();
+        let err = cache
+            .get_or_insert_with::<_, ProgramHashCacheError>(key, || unreachable!())
+            .unwrap_err();
+        assert!(matches!(err, ProgramHashCacheError::InvalidEntry { .. }));
+    }
+}
@@ -0,0 +1,242 @@
+use rustyline::{
+    completion::{Completer, Pair},
+    highlight::Highlighter,
+    hint::Hinter,
+    validate::Validator,
+    Context, Helper,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Mnemonics of Miden assembly instructions and control flow statements which can be typed into
+/// the REPL. Instruction parameters (e.g., `.2` in `movup.2`) are not included.
+const INSTRUCTIONS: &[&str] = &[
+    "add",
+    "adv",
+    "adv_loadw",
+    "adv_pipe",
+    "adv_push",
+    "and",
+    "assert",
+    "assert_eq",
+    "assert_eqw",
+    "assertz",
+    "breakpoint",
+    "call",
+    "caller",
+    "cdrop",
+    "cdropw",
+    "clk",
+    "cswap",
+    "cswapw",
+    "debug",
+    "div",
+    "drop",
+    "dropw",
+    "dup",
+    "dupw",
+    "dyncall",
+    "dynexec",
+    "else",
+    "end",
+    "eq",
+    "eqw",
+    "exec",
+    "exp",
+    "ext2add",
+    "ext2div",
+    "ext2inv",
+    "ext2mul",
+    "ext2neg",
+    "ext2sub",
+    "fri_ext2fold4",
+    "gt",
+    "gte",
+    "hash",
+    "hmerge",
+    "hperm",
+    "if.true",
+    "inv",
+    "is_odd",
+    "loc_load",
+    "loc_loadw",
+    "loc_store",
+    "loc_storew",
+    "locaddr",
+    "lt",
+    "lte",
+    "mem_load",
+    "mem_loadw",
+    "mem_store",
+    "mem_storew",
+    "mem_stream",
+    "movdn",
+    "movdnw",
+    "movup",
+    "movupw",
+    "mtree_get",
+    "mtree_member",
+    "mtree_merge",
+    "mtree_set",
+    "mtree_verify",
+    "mul",
+    "neg",
+    "neq",
+    "not",
+    "or",
+    "padw",
+    "pow2",
+    "push",
+    "repeat",
+    "sdepth",
+    "sub",
+    "swap",
+    "swapdw",
+    "swapw",
+    "syscall",
+    "trace",
+    "u32assert",
+    "u32assert2",
+    "u32assertw",
+    "u32cast",
+    "u32checked_add",
+    "u32checked_and",
+    "u32checked_div",
+    "u32checked_divmod",
+    "u32checked_eq",
+    "u32checked_gt",
+    "u32checked_gte",
+    "u32checked_lt",
+    "u32checked_lte",
+    "u32checked_max",
+    "u32checked_min",
+    "u32checked_mod",
+    "u32checked_mul",
+    "u32checked_neq",
+    "u32checked_not",
+    "u32checked_or",
+    "u32checked_popcnt",
+    "u32checked_rotl",
+    "u32checked_rotr",
+    "u32checked_shl",
+    "u32checked_shr",
+    "u32checked_sub",
+    "u32checked_xor",
+    "u32overflowing_add",
+    "u32overflowing_add3",
+    "u32overflowing_madd",
+    "u32overflowing_mul",
+    "u32overflowing_sub",
+    "u32split",
+    "u32test",
+    "u32testw",
+    "u32unchecked_div",
+    "u32unchecked_divmod",
+    "u32unchecked_gt",
+    "u32unchecked_gte",
+    "u32unchecked_lt",
+    "u32unchecked_lte",
+    "u32unchecked_max",
+    "u32unchecked_min",
+    "u32unchecked_mod",
+    "u32unchecked_popcnt",
+    "u32unchecked_rotl",
+    "u32unchecked_rotr",
+    "u32unchecked_shl",
+    "u32unchecked_shr",
+    "u32wrapping_add",
+    "u32wrapping_add3",
+    "u32wrapping_madd",
+    "u32wrapping_mul",
+    "u32wrapping_sub",
+    "while.true",
+    "xor",
+];
+
+/// Commands native to the REPL.
+const COMMANDS: &[&str] = &["!help", "!mem", "!program", "!stack", "!undo"];
+
+// REPL HELPER
+// ================================================================================================
+
+/// Line editor helper which completes instruction mnemonics and REPL commands on Tab.
+#[derive(Debug, Default)]
+pub struct ReplHelper;
+
+impl ReplHelper {
+    /// Returns all instruction mnemonics and REPL commands which start with the specified prefix,
+    /// sorted alphabetically.
+    pub fn candidates(prefix: &str) -> Vec<&'static str> {
+        let mut candidates: Vec<_> = INSTRUCTIONS
+            .iter()
+            .chain(COMMANDS)
+            .copied()
+            .filter(|candidate| candidate.starts_with(prefix))
+            .collect();
+        candidates.sort_unstable();
+        candidates
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        // complete the word which ends at the cursor position
+        let start = line[..pos].rfind(char::is_whitespace).map_or(0, |idx| idx + 1);
+        let candidates = Self::candidates(&line[start..pos])
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.to_string(),
+                replacement: candidate.to_string(),
+            })
+            .collect();
+
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::ReplHelper;
+    use rustyline::{completion::Completer, history::DefaultHistory, Context};
+
+    #[test]
+    fn complete_instruction_prefix() {
+        let history = DefaultHistory::new();
+        let ctx = Context::new(&history);
+
+        let line = "push.1 push.2 ad";
+        let (start, candidates) = ReplHelper.complete(line, line.len(), &ctx).unwrap();
+        let candidates: Vec<_> = candidates.into_iter().map(|pair| pair.replacement).collect();
+        assert_eq!(start, 14);
+        assert_eq!(candidates, ["add", "adv", "adv_loadw", "adv_pipe", "adv_push"]);
+
+        // REPL commands are completed as well
+        let (start, candidates) = ReplHelper.complete("!st", 3, &ctx).unwrap();
+        assert_eq!(start, 0);
+        assert_eq!(candidates[0].replacement, "!stack");
+
+        // unknown prefixes produce no candidates
+        assert!(ReplHelper::candidates("xyz").is_empty());
+    }
+}
@@ -3,7 +3,10 @@ use miden::{
     math::{Felt, StarkField},
     DefaultHost, StackInputs, Word,
 };
-use rustyline::{error::ReadlineError, DefaultEditor};
+use rustyline::{error::ReadlineError, history::DefaultHistory, Editor};
+
+mod completion;
+use completion::ReplHelper;
 
 /// This work is in continuation to the amazing work done by team `Scribe`
 /// [here](https://github.com/ControlCplusControlV/Scribe/blob/main/transpiler/src/repl.rs#L8)
@@ -122,6 +125,11 @@ use rustyline::{error::ReadlineError, DefaultEditor};
 /// If the `addr` has not been initialized:
 /// >> !mem[87]
 /// Memory at address 87 is empty
+///
+/// Tab completion
+/// Pressing Tab completes the partially typed instruction mnemonic or REPL command under the
+/// cursor. If there are multiple candidates, all of them are listed; e.g., typing `ad` and pressing
+/// Tab lists `add`, `adv`, `adv_loadw`, `adv_pipe`, and `adv_push`.
 
 /// Initiates the Miden Repl tool.
 pub fn start_repl() {
@@ -139,8 +147,10 @@ pub fn start_repl() {
     // state of the entire memory at the latest clock cycle.
     let mut memory: Vec<(u64, Word)> = Vec::new();
 
-    // initializing readline.
-    let mut rl = DefaultEditor::new().expect("Readline couldn't be initialized");
+    // initializing readline with tab completion of instructions and commands.
+    let mut rl =
+        Editor::<ReplHelper, DefaultHistory>::new().expect("Readline couldn't be initialized");
+    rl.set_helper(Some(ReplHelper));
     loop {
         let program = format!(
             "begin\n{}\nend",
@@ -0,0 +1,109 @@
+//! Export of execution, proving, and verification metrics via [OpenTelemetry](https://opentelemetry.io).
+//!
+//! This module is available only when the `telemetry` feature is enabled.
+
+use core::time::Duration;
+use opentelemetry::metrics::{Histogram, MeterProvider};
+use opentelemetry_otlp::{ExporterBuildError, MetricExporter, WithExportConfig};
+use opentelemetry_sdk::{error::OTelSdkResult, metrics::SdkMeterProvider};
+
+// MIDEN METER
+// ================================================================================================
+
+/// Records metrics of Miden VM executions, proofs, and verifications, and exports them via
+/// OpenTelemetry.
+///
+/// The following histograms are recorded:
+/// - `miden.execution.cycles` - number of cycles consumed by an execution.
+/// - `miden.prove.duration_ms` - time it took to generate a proof, in milliseconds.
+/// - `miden.proof.size_bytes` - size of a serialized proof, in bytes.
+/// - `miden.verify.duration_ms` - time it took to verify a proof, in milliseconds.
+///
+/// Metrics are exported periodically in the background; [MidenMeter::shutdown()] should be
+/// invoked before the meter is dropped so that the metrics recorded since the last export are
+/// not lost.
+pub struct MidenMeter {
+    provider: SdkMeterProvider,
+    execution_cycles: Histogram<u64>,
+    prove_duration: Histogram<f64>,
+    proof_size: Histogram<u64>,
+    verify_duration: Histogram<f64>,
+}
+
+impl MidenMeter {
+    /// Name of the meter under which the metrics are recorded.
+    pub const METER_NAME: &'static str = "miden";
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [MidenMeter] which exports the metrics to the OTLP collector at the specified
+    /// HTTP endpoint (e.g., `http://localhost:4318/v1/metrics`).
+    ///
+    /// # Errors
+    /// Returns an error if the OTLP exporter cannot be built.
+    pub fn new(endpoint: &str) -> Result<Self, ExporterBuildError> {
+        let exporter = MetricExporter::builder().with_http().with_endpoint(endpoint).build()?;
+        let provider = SdkMeterProvider::builder().with_periodic_exporter(exporter).build();
+        Ok(Self::with_provider(provider))
+    }
+
+    /// Returns a new [MidenMeter] which records the metrics via the specified meter provider.
+    ///
+    /// This can be used to export the metrics with an exporter other than OTLP.
+    pub fn with_provider(provider: SdkMeterProvider) -> Self {
+        let meter = provider.meter(Self::METER_NAME);
+        Self {
+            execution_cycles: meter
+                .u64_histogram("miden.execution.cycles")
+                .with_description("Number of cycles consumed by an execution")
+                .build(),
+            prove_duration: meter
+                .f64_histogram("miden.prove.duration_ms")
+                .with_description("Time it took to generate a proof")
+                .with_unit("ms")
+                .build(),
+            proof_size: meter
+                .u64_histogram("miden.proof.size_bytes")
+                .with_description("Size of a serialized proof")
+                .with_unit("By")
+                .build(),
+            verify_duration: meter
+                .f64_histogram("miden.verify.duration_ms")
+                .with_description("Time it took to verify a proof")
+                .with_unit("ms")
+                .build(),
+            provider,
+        }
+    }
+
+    // RECORDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the number of cycles consumed by an execution.
+    pub fn record_execution(&self, num_cycles: usize) {
+        self.execution_cycles.record(num_cycles as u64, &[]);
+    }
+
+    /// Records the time it took to generate a proof and the size of the serialized proof.
+    pub fn record_proving(&self, duration: Duration, proof_size: usize) {
+        self.prove_duration.record(duration.as_secs_f64() * 1000.0, &[]);
+        self.proof_size.record(proof_size as u64, &[]);
+    }
+
+    /// Records the time it took to verify a proof.
+    pub fn record_verification(&self, duration: Duration) {
+        self.verify_duration.record(duration.as_secs_f64() * 1000.0, &[]);
+    }
+
+    // SHUTDOWN
+    // --------------------------------------------------------------------------------------------
+
+    /// Exports the metrics recorded since the last export and shuts down the exporter.
+    ///
+    /// # Errors
+    /// Returns an error if the metrics could not be exported.
+    pub fn shutdown(self) -> OTelSdkResult {
+        self.provider.shutdown()
+    }
+}
@@ -0,0 +1,259 @@
+use super::ProgramError;
+use core::fmt;
+use miden::{
+    math::StarkField,
+    utils::collections::{BTreeMap, BTreeSet, Vec},
+    Assembler, Host, Operation, StackInputs,
+};
+use processor::VmState;
+use serde_derive::Serialize;
+use stdlib::StdLibrary;
+
+/// Name under which writes that are not attributed to any procedure are reported.
+const UNKNOWN_PROC: &str = "<unknown>";
+
+// MEMORY LAYOUT
+// ================================================================================================
+
+/// Describes which memory addresses were written while executing a program, and by which
+/// procedures.
+#[derive(Debug, Default, Eq, PartialEq, Serialize)]
+pub struct MemoryLayout {
+    /// Ranges of consecutive written addresses, sorted by execution context and start address.
+    ranges: Vec<MemoryRange>,
+}
+
+/// A range of consecutive memory addresses of a single execution context which were first written
+/// by the same procedure, and were written by the same set of procedures.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct MemoryRange {
+    /// Execution context of the addresses.
+    ctx: u32,
+    /// First address of the range.
+    start: u32,
+    /// Last address of the range (inclusive).
+    end: u32,
+    /// Number of word-sized cells in the range.
+    cells: u32,
+    /// Procedure which first wrote to the addresses of the range.
+    first_writer: String,
+    /// All procedures which wrote to the addresses of the range, sorted by name.
+    writers: Vec<String>,
+    /// True if the addresses of the range were written by more than one procedure.
+    shared: bool,
+}
+
+impl MemoryLayout {
+    /// Returns the ranges of addresses which were written by more than one procedure.
+    pub fn shared_ranges(&self) -> impl Iterator<Item = &MemoryRange> {
+        self.ranges.iter().filter(|range| range.shared)
+    }
+
+    /// Returns this layout serialized as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| format!("Failed to serialize memory layout - {err}"))
+    }
+
+    /// Builds the layout from the writes recorded for every context/address pair, merging
+    /// consecutive addresses with the same writers into ranges.
+    fn from_writes(writes: BTreeMap<(u32, u32), AddressWrites>) -> Self {
+        let mut ranges: Vec<MemoryRange> = Vec::new();
+        for ((ctx, addr), writes) in writes {
+            if let Some(range) = ranges.last_mut() {
+                if range.ctx == ctx
+                    && range.end.checked_add(1) == Some(addr)
+                    && range.first_writer == writes.first_writer
+                    && range.writers.iter().eq(writes.writers.iter())
+                {
+                    range.end = addr;
+                    range.cells += 1;
+                    continue;
+                }
+            }
+            ranges.push(MemoryRange {
+                ctx,
+                start: addr,
+                end: addr,
+                cells: 1,
+                shared: writes.writers.len() > 1,
+                first_writer: writes.first_writer,
+                writers: writes.writers.into_iter().collect(),
+            });
+        }
+        Self { ranges }
+    }
+}
+
+impl fmt::Display for MemoryLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ranges.is_empty() {
+            return writeln!(f, "No memory was written");
+        }
+
+        let start_width = self.ranges.iter().map(|r| r.start.to_string().len()).max().unwrap_or(0);
+        let end_width = self.ranges.iter().map(|r| r.end.to_string().len()).max().unwrap_or(0);
+        let start_width = start_width.max("Start".len());
+        let end_width = end_width.max("End".len());
+
+        let mut ctx = None;
+        for range in self.ranges.iter() {
+            if ctx != Some(range.ctx) {
+                if ctx.is_some() {
+                    writeln!(f)?;
+                }
+                ctx = Some(range.ctx);
+                writeln!(f, "Context {}:", range.ctx)?;
+                writeln!(f, "  {:>start_width$}   {:>end_width$}  Cells  Writers", "Start", "End")?;
+            }
+
+            // each range is drawn as a bar of one block per cell, capped at 16 blocks; ranges
+            // written by multiple procedures are drawn with `!` and flagged
+            let block = if range.shared { "!" } else { "#" };
+            let bar = format!(
+                "[{}{}]",
+                block.repeat(range.cells.min(16) as usize),
+                if range.cells > 16 { "..." } else { "" }
+            );
+            write!(
+                f,
+                "  {:>start_width$} - {:>end_width$}  {:>5}  {} {bar}",
+                range.start, range.end, range.cells, range.first_writer
+            )?;
+            if range.shared {
+                let others = range
+                    .writers
+                    .iter()
+                    .filter(|writer| **writer != range.first_writer)
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                write!(f, " SHARED with {}", others.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+
+        let shared = self.shared_ranges().count();
+        if shared > 0 {
+            writeln!(f, "\n{shared} address range(s) written by multiple procedures")?;
+        }
+        Ok(())
+    }
+}
+
+/// Procedures which wrote to a single memory address.
+struct AddressWrites {
+    first_writer: String,
+    writers: BTreeSet<String>,
+}
+
+// MEMORY LAYOUT ANALYSIS
+// ================================================================================================
+
+/// Executes the specified program and returns the layout of the memory written during execution.
+///
+/// Writes are attributed to procedures using the debug info of the instructions, and thus, writes
+/// made by the instructions of a procedure are attributed to that procedure even if they write to
+/// the locals of the procedure.
+pub fn memory_layout<H>(
+    program: &str,
+    stack_inputs: StackInputs,
+    host: H,
+) -> Result<MemoryLayout, ProgramError>
+where
+    H: Host,
+{
+    let program = Assembler::default()
+        .with_debug_mode(true)
+        .with_library(&StdLibrary::default())
+        .map_err(ProgramError::AssemblyError)?
+        .compile(program)
+        .map_err(ProgramError::AssemblyError)?;
+
+    let mut writes: BTreeMap<(u32, u32), AddressWrites> = BTreeMap::new();
+    let mut prev_state: Option<VmState> = None;
+    for state in processor::execute_iter(&program, stack_inputs, host) {
+        let state = state.map_err(ProgramError::ExecutionError)?;
+        if let Some(prev) = &prev_state {
+            let proc = state.asmop.as_ref().map_or(UNKNOWN_PROC, |asmop| asmop.context_name());
+            for addr in written_addresses(prev, state.op) {
+                let entry = writes.entry((prev.ctx, addr)).or_insert_with(|| AddressWrites {
+                    first_writer: proc.to_string(),
+                    writers: BTreeSet::new(),
+                });
+                entry.writers.insert(proc.to_string());
+            }
+        }
+        prev_state = Some(state);
+    }
+
+    Ok(MemoryLayout::from_writes(writes))
+}
+
+/// Returns the memory addresses written by the specified operation when executed against the
+/// specified VM state.
+fn written_addresses(state: &VmState, op: Option<Operation>) -> Vec<u32> {
+    let addr_at = |idx: usize| state.stack.get(idx).map(|addr| addr.as_int() as u32);
+    match op {
+        Some(Operation::MStore) | Some(Operation::MStoreW) => addr_at(0).into_iter().collect(),
+        Some(Operation::Pipe) => {
+            addr_at(12).into_iter().flat_map(|addr| [addr, addr.wrapping_add(1)]).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::memory_layout;
+    use processor::{DefaultHost, StackInputs};
+
+    #[test]
+    fn memory_layout_ranges() {
+        let source = "
+            proc.writer push.1 mem_store.10 push.2 mem_store.11 push.3 mem_store.12 end
+            proc.other push.4 mem_store.12 end
+            begin exec.writer exec.other push.5 mem_store.20 end";
+        let layout = memory_layout(source, StackInputs::default(), DefaultHost::default()).unwrap();
+
+        let ranges = layout
+            .ranges
+            .iter()
+            .map(|r| (r.start, r.end, r.cells, r.first_writer.as_str(), r.shared))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                (10, 11, 2, "writer", false),
+                (12, 12, 1, "writer", true),
+                (20, 20, 1, "#main", false),
+            ]
+        );
+        assert_eq!(layout.shared_ranges().next().unwrap().writers, ["other", "writer"]);
+
+        let expected = "\
+Context 0:
+  Start   End  Cells  Writers
+     10 -  11      2  writer [##]
+     12 -  12      1  writer [!] SHARED with other
+     20 -  20      1  #main [#]
+
+1 address range(s) written by multiple procedures
+";
+        assert_eq!(layout.to_string(), expected);
+
+        let json: serde_json::Value = serde_json::from_str(&layout.to_json().unwrap()).unwrap();
+        assert_eq!(json["ranges"][1]["shared"], true);
+        assert_eq!(json["ranges"][1]["writers"], serde_json::json!(["other", "writer"]));
+    }
+
+    #[test]
+    fn memory_layout_reads_are_ignored() {
+        let source = "begin mem_load.5 drop mem_loadw.6 dropw end";
+        let layout = memory_layout(source, StackInputs::default(), DefaultHost::default()).unwrap();
+        assert!(layout.ranges.is_empty());
+        assert_eq!(layout.to_string(), "No memory was written\n");
+    }
+}
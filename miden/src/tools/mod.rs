@@ -1,11 +1,23 @@
 use super::{cli::InputFile, ProgramError};
 use clap::Parser;
 use core::fmt;
-use miden::{utils::collections::Vec, Assembler, DefaultHost, Host, Operation, StackInputs};
+use miden::{
+    utils::collections::{BTreeMap, Vec},
+    Assembler, DefaultHost, Host, Operation, ProgramAst, StackInputs,
+};
 use processor::{AsmOpInfo, TraceLenSummary};
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use stdlib::StdLibrary;
 
+mod memory_layout;
+use memory_layout::memory_layout;
+
+mod op_category;
+pub use op_category::OpCategory;
+
 // CLI
 // ================================================================================================
 
@@ -14,21 +26,71 @@ use stdlib::StdLibrary;
 #[clap(about = "Analyze a miden program")]
 pub struct Analyze {
     /// Path to .masm assembly file
-    #[clap(short = 'a', long = "assembly", value_parser)]
-    assembly_file: PathBuf,
+    #[clap(
+        short = 'a',
+        long = "assembly",
+        value_parser,
+        required_unless_present_any = ["cost_table", "memory_layout"]
+    )]
+    assembly_file: Option<PathBuf>,
+    /// Path to a .masm file whose instructions are printed together with their cycle costs and
+    /// source locations; the program is not executed
+    #[clap(long = "cost-table", value_parser, conflicts_with = "assembly_file")]
+    cost_table: Option<PathBuf>,
+    /// Path to a .masm file which is executed to print a map of the memory ranges written during
+    /// execution, together with the procedures which wrote them
+    #[clap(
+        long = "memory-layout",
+        value_parser,
+        conflicts_with_all = ["assembly_file", "cost_table"]
+    )]
+    memory_layout: Option<PathBuf>,
+    /// Path to a file into which the memory map is additionally written as JSON
+    #[clap(long = "memory-layout-json", value_parser, requires = "memory_layout")]
+    memory_layout_json: Option<PathBuf>,
     /// Path to .inputs file
     #[clap(short = 'i', long = "input", value_parser)]
     input_file: Option<PathBuf>,
+    /// Substitute `${VAR}` and `${VAR:-default}` references to environment variables in the
+    /// input file
+    #[clap(long = "input-template")]
+    input_template: bool,
+    /// Fail if the maximum stack depth reached during execution exceeds the specified value
+    #[clap(long = "max-depth-budget")]
+    max_depth_budget: Option<usize>,
+    /// Fail if the instructions of the named procedure consume more than the specified number of
+    /// cycles, in the form `name=cycles`; can be repeated
+    #[clap(long = "proc-budget", value_parser = parse_proc_budget)]
+    proc_budgets: Vec<(String, usize)>,
+    /// Require words in the input file (Merkle store nodes and advice map keys) to be prefixed
+    /// with 0x
+    #[clap(long = "strict-hex")]
+    strict_hex: bool,
+    /// Print the percentage of cycles spent on each category of operations (e.g., arithmetic,
+    /// memory, hashing)
+    #[clap(long = "trace-summary")]
+    trace_summary: bool,
 }
 
 /// Implements CLI execution logic
 impl Analyze {
     pub fn execute(&self) -> Result<(), String> {
-        let program = fs::read_to_string(&self.assembly_file)
+        if let Some(cost_table_file) = &self.cost_table {
+            return print_cost_table(cost_table_file);
+        }
+        if let Some(memory_layout_file) = &self.memory_layout {
+            return self.print_memory_layout(memory_layout_file);
+        }
+
+        let assembly_file = self.assembly_file.as_ref().expect("assembly file is required");
+        let program = fs::read_to_string(assembly_file)
             .map_err(|e| format!("could not read masm file: {e}"))?;
 
         // load input data from file
-        let input_data = InputFile::read(&self.input_file, &self.assembly_file)?;
+        let input_data = InputFile::read(&self.input_file, assembly_file, self.input_template)?;
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
 
         // fetch the stack and program inputs from the arguments
         let stack_inputs = input_data.parse_stack_inputs()?;
@@ -36,8 +98,7 @@ impl Analyze {
 
         let execution_details: ExecutionDetails = analyze(program.as_str(), stack_inputs, host)
             .expect("Could not retrieve execution details");
-        let program_name = self
-            .assembly_file
+        let program_name = assembly_file
             .file_name()
             .expect("provided file path is incorrect")
             .to_str()
@@ -56,7 +117,192 @@ impl Analyze {
 
         println!("{}", execution_details);
 
+        if self.trace_summary {
+            println!("Cycles by operation category:");
+            let categories = execution_details.category_percentages();
+            for (i, (category, percentage)) in categories.iter().enumerate() {
+                let branch = if i + 1 == categories.len() {
+                    "└──"
+                } else {
+                    "├──"
+                };
+                let cycles = execution_details.category_cycles(*category);
+                println!("{branch} {category}: {cycles} ({percentage:.2}%)");
+            }
+        }
+
+        // make sure the stack did not grow deeper than allowed
+        if let Some(max_depth_budget) = self.max_depth_budget {
+            check_depth_budget(&execution_details, max_depth_budget)?;
+        }
+
+        // make sure the procedures stayed within their cycle budgets
+        if !self.proc_budgets.is_empty() {
+            println!("Procedure cycles:");
+            for (i, (name, budget)) in self.proc_budgets.iter().enumerate() {
+                let branch = if i + 1 == self.proc_budgets.len() {
+                    "└──"
+                } else {
+                    "├──"
+                };
+                let cycles = execution_details.proc_cycles(name);
+                println!("{branch} {name}: {cycles} (budget {budget})");
+            }
+            check_proc_budgets(&execution_details, &self.proc_budgets)?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes the program in the specified file and prints the memory ranges written during
+    /// execution, optionally writing them to the JSON file as well.
+    fn print_memory_layout(&self, path: &Path) -> Result<(), String> {
+        let program = fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read file `{}` - {}", path.display(), err))?;
+
+        let input_data = InputFile::read(&self.input_file, path, self.input_template)?;
+        if self.strict_hex {
+            input_data.check_strict_hex()?;
+        }
+        let stack_inputs = input_data.parse_stack_inputs()?;
+        let host = DefaultHost::new(input_data.parse_advice_provider()?);
+
+        let layout = memory_layout(&program, stack_inputs, host).map_err(|err| err.to_string())?;
+
+        println!("============================================================");
+        println!("Memory layout of {}", path.display());
+        println!("============================================================");
+        print!("{layout}");
+
+        if let Some(json_path) = &self.memory_layout_json {
+            fs::write(json_path, layout.to_json()?).map_err(|err| {
+                format!("Failed to write memory layout to `{}` - {}", json_path.display(), err)
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints every instruction of the program in the specified file together with its cycle cost and
+/// source location, followed by the estimated cycle count of the program.
+fn print_cost_table(path: &Path) -> Result<(), String> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read file `{}` - {}", path.display(), err))?;
+
+    println!("============================================================");
+    println!("Cost table of {}", path.display());
+    println!("============================================================");
+    print!("{}", cost_table(&source)?);
+    Ok(())
+}
+
+/// Returns a table listing every instruction of the specified program together with its cycle
+/// cost and source location, followed by the estimated cycle count of the program.
+fn cost_table(source: &str) -> Result<String, String> {
+    let ast =
+        ProgramAst::parse(source).map_err(|err| format!("Failed to parse program - {err}"))?;
+    let costs = ast
+        .instruction_costs(&Assembler::default())
+        .map_err(|err| format!("Failed to compute instruction costs - {err}"))?;
+
+    let lines = source.lines().collect::<Vec<_>>();
+    let rows = costs
+        .iter()
+        .map(|cost| {
+            // instructions are shown as written in the source, so that invoked procedures are
+            // shown by name rather than by index
+            let (location, instruction) = match cost.location {
+                Some(location) => {
+                    let token = lines
+                        .get(location.line() as usize - 1)
+                        .and_then(|line| line.get(location.column() as usize - 1..))
+                        .and_then(|rest| rest.split_whitespace().next());
+                    let instruction = match token {
+                        Some(token) => token.to_string(),
+                        None => cost.instruction.to_string(),
+                    };
+                    (format!("{}:{}", location.line(), location.column()), instruction)
+                }
+                None => ("-".to_string(), cost.instruction.to_string()),
+            };
+            (location, cost.procedure.to_string(), cost.cycles.to_string(), instruction)
+        })
+        .collect::<Vec<_>>();
+
+    let width = |header: &str, column: fn(&(String, String, String, String)) -> &String| {
+        rows.iter().map(|row| column(row).len()).max().unwrap_or(0).max(header.len())
+    };
+    let location_width = width("Location", |row| &row.0);
+    let procedure_width = width("Procedure", |row| &row.1);
+    let cycles_width = width("Cycles", |row| &row.2);
+
+    let mut table = format!(
+        "{:location_width$}  {:procedure_width$}  {:>cycles_width$}  Instruction\n",
+        "Location", "Procedure", "Cycles"
+    );
+    for (location, procedure, cycles, instruction) in rows.iter() {
+        table.push_str(&format!(
+            "{location:location_width$}  {procedure:procedure_width$}  {cycles:>cycles_width$}  \
+            {instruction}\n"
+        ));
+    }
+    let total = costs.iter().map(|cost| cost.cycles).sum::<u64>();
+    table.push_str(&format!(
+        "Estimated cycle count: {total} (every instruction counted once, loops not unrolled)\n"
+    ));
+    Ok(table)
+}
+
+/// Parses a procedure cycle budget of the form `name=cycles`.
+fn parse_proc_budget(budget: &str) -> Result<(String, usize), String> {
+    let (name, cycles) = budget
+        .split_once('=')
+        .ok_or_else(|| format!("invalid procedure budget `{budget}`, expected `name=cycles`"))?;
+    let cycles = cycles.trim().parse().map_err(|err| {
+        format!("invalid number of cycles in procedure budget `{budget}` - {err}")
+    })?;
+    Ok((name.trim().to_string(), cycles))
+}
+
+/// Checks that the maximum stack depth reached during execution does not exceed the budget.
+fn check_depth_budget(
+    execution_details: &ExecutionDetails,
+    max_depth_budget: usize,
+) -> Result<(), String> {
+    let max_stack_depth = execution_details.max_stack_depth();
+    if max_stack_depth > max_depth_budget {
+        return Err(format!(
+            "Maximum stack depth of {max_stack_depth} exceeds the budget of {max_depth_budget}"
+        ));
+    }
+    Ok(())
+}
+
+/// Checks that the instructions of each of the specified procedures consumed no more cycles than
+/// the budget of the procedure.
+///
+/// Returns an error reporting the actual number of cycles of every procedure which exceeded its
+/// budget.
+fn check_proc_budgets(
+    execution_details: &ExecutionDetails,
+    proc_budgets: &[(String, usize)],
+) -> Result<(), String> {
+    let exceeded = proc_budgets
+        .iter()
+        .filter_map(|(name, budget)| {
+            let cycles = execution_details.proc_cycles(name);
+            (cycles > *budget).then(|| {
+                format!(
+                    "Procedure `{name}` consumed {cycles} cycles, exceeding its budget of {budget}"
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if exceeded.is_empty() {
         Ok(())
+    } else {
+        Err(exceeded.join("\n"))
     }
 }
 
@@ -72,6 +318,12 @@ pub struct ExecutionDetails {
     asm_op_stats: Vec<AsmOpStats>,
     /// Information about VM components trace lengths.
     trace_len_summary: TraceLenSummary,
+    /// Maximum depth of the stack (including the overflow table) reached during execution.
+    max_stack_depth: usize,
+    /// Number of cycles consumed by the instructions of each procedure, keyed by procedure name.
+    proc_cycles: BTreeMap<String, usize>,
+    /// Number of cycles spent on each category of operations, see [OpCategory].
+    category_cycles: BTreeMap<OpCategory, usize>,
 }
 
 impl ExecutionDetails {
@@ -91,6 +343,36 @@ impl ExecutionDetails {
         self.trace_len_summary
     }
 
+    /// Returns the maximum depth of the stack reached during execution.
+    pub fn max_stack_depth(&self) -> usize {
+        self.max_stack_depth
+    }
+
+    /// Returns the number of cycles consumed by the instructions in the body of the procedure
+    /// with the specified name (`#main` for the program body).
+    ///
+    /// Cycles are attributed using the debug info of the instructions, and thus, the cycles of
+    /// the procedures invoked by the procedure are not included, and neither are the cycles of
+    /// control flow operations. Returns 0 if the procedure was not executed.
+    pub fn proc_cycles(&self, name: &str) -> usize {
+        self.proc_cycles.get(name).copied().unwrap_or(0)
+    }
+
+    /// Returns the number of cycles spent executing operations of the specified category.
+    pub fn category_cycles(&self, category: OpCategory) -> usize {
+        self.category_cycles.get(&category).copied().unwrap_or(0)
+    }
+
+    /// Returns the percentage of cycles spent on each category of operations which was executed
+    /// at least once, sorted by category.
+    pub fn category_percentages(&self) -> Vec<(OpCategory, f64)> {
+        let total = self.category_cycles.values().sum::<usize>();
+        self.category_cycles
+            .iter()
+            .map(|(&category, &cycles)| (category, cycles as f64 * 100.0 / total as f64))
+            .collect()
+    }
+
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
@@ -128,6 +410,22 @@ impl ExecutionDetails {
         }
     }
 
+    /// Increments the number of cycles consumed by the procedure with the specified name by one.
+    pub fn incr_proc_cycles(&mut self, name: &str) {
+        *self.proc_cycles.entry(name.to_string()).or_default() += 1;
+    }
+
+    /// Increments the number of cycles spent on operations of the category of the specified
+    /// operation by one.
+    pub fn incr_category_cycles(&mut self, op: &Operation) {
+        *self.category_cycles.entry(OpCategory::of(op)).or_default() += 1;
+    }
+
+    /// Updates the maximum stack depth if the specified depth exceeds it.
+    pub fn record_stack_depth(&mut self, depth: usize) {
+        self.max_stack_depth = self.max_stack_depth.max(depth);
+    }
+
     /// Sets the information about lengths of the trace parts.
     pub fn set_trace_len_summary(&mut self, extended_cycles_info: &TraceLenSummary) {
         self.trace_len_summary = *extended_cycles_info;
@@ -196,6 +494,7 @@ impl fmt::Display for ExecutionDetails {
         }
 
         writeln!(f, "\nTotal number of NOOPs executed: {}", total_noops)?;
+        writeln!(f, "Maximum stack depth: {}", self.max_stack_depth())?;
 
         Ok(())
     }
@@ -223,10 +522,15 @@ where
 
     for state in vm_state_iterator {
         let vm_state = state.map_err(ProgramError::ExecutionError)?;
+        execution_details.record_stack_depth(vm_state.stack.len());
         if matches!(vm_state.op, Some(Operation::Noop)) {
             execution_details.incr_noop_count();
         }
+        if let Some(op) = &vm_state.op {
+            execution_details.incr_category_cycles(op);
+        }
         if let Some(asmop_info) = vm_state.asmop {
+            execution_details.incr_proc_cycles(asmop_info.context_name());
             execution_details.record_asmop(asmop_info);
         }
     }
@@ -290,7 +594,10 @@ impl AsmOpStats {
 
 #[cfg(test)]
 mod tests {
-    use super::{AsmOpStats, ExecutionDetails, StackInputs};
+    use super::{
+        check_depth_budget, check_proc_budgets, cost_table, parse_proc_budget, AsmOpStats,
+        BTreeMap, ExecutionDetails, OpCategory, StackInputs,
+    };
     use processor::{ChipletsLengths, DefaultHost, TraceLenSummary};
 
     #[test]
@@ -315,6 +622,15 @@ mod tests {
                 39,
                 ChipletsLengths::from_parts(8, 0, 2, 0),
             ),
+            max_stack_depth: 19,
+            proc_cycles: BTreeMap::from([("#main".to_string(), 11), ("foo".to_string(), 4)]),
+            category_cycles: BTreeMap::from([
+                (OpCategory::Arithmetic, 2),
+                (OpCategory::Stack, 12),
+                (OpCategory::Memory, 2),
+                (OpCategory::ControlFlow, 2),
+                (OpCategory::System, 5),
+            ]),
         };
         assert_eq!(execution_details, expected_details);
     }
@@ -339,4 +655,105 @@ mod tests {
         let expected_error = "Assembly Error: ParsingError(\"unexpected token: expected 'begin' but was 'mem_storew.1'\")";
         assert_eq!(execution_details.err().unwrap().to_string(), expected_error);
     }
+
+    #[test]
+    fn analyze_test_depth_budget() {
+        // pushing 4 values onto the stack grows it to a depth of 20
+        let source = "begin push.1 push.2 push.3 push.4 dropw end";
+        let execution_details =
+            super::analyze(source, StackInputs::default(), DefaultHost::default()).unwrap();
+        assert_eq!(execution_details.max_stack_depth(), 20);
+
+        assert!(check_depth_budget(&execution_details, 20).is_ok());
+        let err = check_depth_budget(&execution_details, 19).unwrap_err();
+        assert_eq!(err, "Maximum stack depth of 20 exceeds the budget of 19");
+    }
+
+    #[test]
+    fn analyze_test_proc_budget() {
+        let source = "
+            proc.cheap push.1 drop end
+            proc.costly repeat.10 push.1 drop end end
+            begin exec.cheap exec.costly end";
+        let execution_details =
+            super::analyze(source, StackInputs::default(), DefaultHost::default()).unwrap();
+        assert_eq!(execution_details.proc_cycles("cheap"), 3);
+        assert_eq!(execution_details.proc_cycles("costly"), 30);
+        assert_eq!(execution_details.proc_cycles("unknown"), 0);
+
+        let budgets =
+            [parse_proc_budget("cheap=3").unwrap(), parse_proc_budget("costly=30").unwrap()];
+        assert!(check_proc_budgets(&execution_details, &budgets).is_ok());
+
+        // only the procedure over its budget is reported
+        let budgets =
+            [parse_proc_budget("cheap=3").unwrap(), parse_proc_budget("costly=25").unwrap()];
+        let err = check_proc_budgets(&execution_details, &budgets).unwrap_err();
+        assert_eq!(err, "Procedure `costly` consumed 30 cycles, exceeding its budget of 25");
+
+        // budgets must have the form name=cycles
+        assert!(parse_proc_budget("costly").is_err());
+        assert!(parse_proc_budget("costly=many").is_err());
+    }
+
+    #[test]
+    fn analyze_test_trace_summary() {
+        let source = "
+            proc.hash hperm end
+            begin
+                push.1 push.2 u32checked_add
+                mem_store.0
+                repeat.2 exec.hash end
+            end";
+        let execution_details =
+            super::analyze(source, StackInputs::default(), DefaultHost::default()).unwrap();
+        for category in [
+            OpCategory::Stack,
+            OpCategory::U32,
+            OpCategory::Memory,
+            OpCategory::Hashing,
+            OpCategory::ControlFlow,
+        ] {
+            assert!(execution_details.category_cycles(category) > 0, "no {category} cycles");
+        }
+        assert_eq!(execution_details.category_cycles(OpCategory::Hashing), 2);
+        assert_eq!(execution_details.category_cycles(OpCategory::Advice), 0);
+
+        // every executed cycle falls into exactly one category
+        let percentages = execution_details.category_percentages();
+        let total = percentages.iter().map(|(_, percentage)| percentage).sum::<f64>();
+        assert!((total - 100.0).abs() < 1e-9, "percentages sum to {total}");
+        let cycles = percentages
+            .iter()
+            .map(|(category, _)| execution_details.category_cycles(*category))
+            .sum::<usize>();
+        assert_eq!(cycles, execution_details.trace_len_summary().main_trace_len());
+    }
+
+    #[test]
+    fn analyze_test_cost_table() {
+        let source = "\
+proc.foo.1
+    loc_store.0
+end
+begin
+    push.1 exec.foo
+    repeat.4 push.2 mul end
+end";
+        let table = cost_table(source).unwrap();
+        let expected = "\
+Location  Procedure  Cycles  Instruction
+2:5       foo             4  loc_store.0
+5:5       #main           2  push.1
+5:12      #main           0  exec.foo
+6:14      #main           1  push.2
+6:21      #main           1  mul
+Estimated cycle count: 8 (every instruction counted once, loops not unrolled)
+";
+        assert_eq!(table, expected);
+
+        // the estimate is available from the AST without executing the program
+        let ast = miden::ProgramAst::parse(source).unwrap();
+        assert_eq!(ast.estimated_cycle_count().unwrap(), 8);
+    }
 }
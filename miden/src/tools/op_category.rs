@@ -0,0 +1,66 @@
+use core::fmt;
+use miden::Operation;
+
+// OPERATION CATEGORY
+// ================================================================================================
+
+/// Category of a VM operation, used to summarize which kinds of operations the cycles of a
+/// program were spent on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+pub enum OpCategory {
+    /// Field arithmetic and comparison operations.
+    Arithmetic,
+    /// Operations on 32-bit integers.
+    U32,
+    /// Operations which only rearrange or push elements on the stack.
+    Stack,
+    /// Operations which read or write memory.
+    Memory,
+    /// Operations which read from the advice provider.
+    Advice,
+    /// Hashing and Merkle tree operations.
+    Hashing,
+    /// Control flow operations (e.g., the start and end of code blocks).
+    ControlFlow,
+    /// System operations (e.g., assertions and reading the clock or the free memory pointer).
+    System,
+}
+
+impl OpCategory {
+    /// Returns the category of the specified operation.
+    pub fn of(op: &Operation) -> Self {
+        use Operation::*;
+        match op {
+            Join | Split | Loop | Call | Dyn | SysCall | Span | End | Repeat | Respan | Halt => {
+                Self::ControlFlow
+            }
+            Noop | Assert(_) | FmpAdd | FmpUpdate | SDepth | Caller | Clk => Self::System,
+            Add | Neg | Mul | Inv | Incr | And | Or | Not | Eq | Eqz | Expacc | Ext2Mul
+            | FriE2F4 => Self::Arithmetic,
+            U32split | U32add | U32assert2(_) | U32add3 | U32sub | U32mul | U32madd | U32div
+            | U32and | U32xor => Self::U32,
+            Pad | Drop | Dup0 | Dup1 | Dup2 | Dup3 | Dup4 | Dup5 | Dup6 | Dup7 | Dup9 | Dup11
+            | Dup13 | Dup15 | Swap | SwapW | SwapW2 | SwapW3 | SwapDW | MovUp2 | MovUp3
+            | MovUp4 | MovUp5 | MovUp6 | MovUp7 | MovUp8 | MovDn2 | MovDn3 | MovDn4 | MovDn5
+            | MovDn6 | MovDn7 | MovDn8 | CSwap | CSwapW | Push(_) => Self::Stack,
+            MLoadW | MStoreW | MLoad | MStore | MStream | Pipe => Self::Memory,
+            AdvPop | AdvPopW => Self::Advice,
+            HPerm | MpVerify | MrUpdate => Self::Hashing,
+        }
+    }
+}
+
+impl fmt::Display for OpCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arithmetic => write!(f, "arithmetic"),
+            Self::U32 => write!(f, "u32"),
+            Self::Stack => write!(f, "stack"),
+            Self::Memory => write!(f, "memory"),
+            Self::Advice => write!(f, "advice"),
+            Self::Hashing => write!(f, "hashing"),
+            Self::ControlFlow => write!(f, "control flow"),
+            Self::System => write!(f, "system"),
+        }
+    }
+}
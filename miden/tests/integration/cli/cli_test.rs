@@ -1,18 +1,30 @@
 use assert_cmd::prelude::*;
+use miden::{
+    crypto::{MerkleStore, MerkleTree, NodeIndex, RpoDigest},
+    math::Felt,
+    utils::Deserializable,
+    Word, ZERO,
+};
 use predicates::prelude::*;
+use std::fs;
 extern crate escargot;
 
-#[test]
-// Tt test might be an overkill to test only that the 'run' cli command
-// outputs steps and ms.
-fn cli_run() -> Result<(), Box<dyn std::error::Error>> {
-    let bin_under_test = escargot::CargoBuild::new()
+/// Builds the `miden` binary and returns a handle for running it.
+fn bin_under_test() -> escargot::CargoRun {
+    escargot::CargoBuild::new()
         .bin("miden")
         .features("executable")
         .current_release()
         .current_target()
         .run()
-        .unwrap();
+        .unwrap()
+}
+
+#[test]
+// Tt test might be an overkill to test only that the 'run' cli command
+// outputs steps and ms.
+fn cli_run() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
 
     let mut cmd = bin_under_test.command();
 
@@ -35,3 +47,272 @@ fn cli_run() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn cli_digest_abbrev() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
+
+    let dir = tempfile::tempdir()?;
+    let program_file = dir.path().join("program.masm");
+    let proof_file = dir.path().join("program.proof");
+    let source = "begin push.1 push.2 add end";
+    fs::write(&program_file, source)?;
+
+    let program_hash = miden::Assembler::default().compile(source)?.hash();
+    let full_hash = program_hash
+        .as_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let abbreviated_hash = format!("{}…{}", &full_hash[..4], &full_hash[60..]);
+
+    // the program hash printed to the console is abbreviated
+    let output = bin_under_test
+        .command()
+        .arg("prove")
+        .arg("-a")
+        .arg(&program_file)
+        .arg("-p")
+        .arg(&proof_file)
+        .arg("--digest-abbrev")
+        .arg("4")
+        .unwrap();
+    output
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Proving program with hash {abbreviated_hash}..."
+        )))
+        .stdout(predicate::str::contains(full_hash.as_str()).not());
+
+    // the proof file contains the full hash, and commands printing values to be used as
+    // verification inputs print the full hash
+    let proof_bytes = fs::read(&proof_file)?;
+    assert!(proof_bytes.windows(32).any(|window| window == program_hash.as_bytes()));
+
+    let output = bin_under_test
+        .command()
+        .arg("extract-public-inputs")
+        .arg(&proof_file)
+        .arg("--digest-abbrev")
+        .arg("4")
+        .unwrap();
+    output
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Program hash: {full_hash}")));
+
+    Ok(())
+}
+
+#[test]
+fn cli_run_advice_from_outputs() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
+    let dir = tempfile::tempdir()?;
+    let first_file = dir.path().join("first.masm");
+    let second_file = dir.path().join("second.masm");
+    let first_outputs = dir.path().join("first.outputs");
+    let second_outputs = dir.path().join("second.outputs");
+    fs::write(&first_file, "begin push.2 push.8 end")?;
+    fs::write(&second_file, "begin adv_push.1 adv_push.1 sub end")?;
+
+    // the first run leaves 8 on top of 2 and writes its outputs in hex
+    bin_under_test
+        .command()
+        .arg("run")
+        .arg("-a")
+        .arg(&first_file)
+        .arg("-o")
+        .arg(&first_outputs)
+        .arg("--number-format")
+        .arg("hex")
+        .assert()
+        .success();
+
+    // the second run reads 8 and then 2 from the advice stack
+    bin_under_test
+        .command()
+        .arg("run")
+        .arg("-a")
+        .arg(&second_file)
+        .arg("-o")
+        .arg(&second_outputs)
+        .arg("--advice-from-outputs")
+        .arg(&first_outputs)
+        .arg("--canonical-json")
+        .assert()
+        .success();
+    let outputs = fs::read_to_string(&second_outputs)?;
+    assert!(
+        outputs.starts_with(r#"{"overflow_addrs":["0","1"],"stack":["6","0""#),
+        "{outputs}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn cli_pipeline_example() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
+    let dir = tempfile::tempdir()?;
+    let output_file = dir.path().join("pipeline.outputs");
+
+    bin_under_test
+        .command()
+        .arg("pipeline")
+        .arg("--stages")
+        .arg("examples/pipeline/square.masm,examples/pipeline/increment.masm,examples/pipeline/double.masm")
+        .arg("-i")
+        .arg("examples/pipeline/pipeline.inputs")
+        .arg("-o")
+        .arg(&output_file)
+        .arg("--canonical-json")
+        .assert()
+        .success();
+
+    // (10^2 + 1) * 2
+    let outputs = fs::read_to_string(&output_file)?;
+    assert!(outputs.contains(r#""stack":["202","0""#), "{outputs}");
+
+    Ok(())
+}
+
+#[test]
+fn cli_compile_to_verifier() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
+    let dir = tempfile::tempdir()?;
+    let program_file = dir.path().join("foo.masm");
+    let input_file = dir.path().join("foo.inputs");
+    let proof_file = dir.path().join("foo.proof");
+    fs::write(&program_file, "begin add mul end")?;
+    fs::write(&input_file, r#"{ "operand_stack": ["2", "3", "4"] }"#)?;
+
+    bin_under_test
+        .command()
+        .arg("prove")
+        .arg("-a")
+        .arg(&program_file)
+        .arg("-i")
+        .arg(&input_file)
+        .arg("-p")
+        .arg(&proof_file)
+        .assert()
+        .success();
+
+    // a single source file is written if the output path is a .rs file
+    let source_file = dir.path().join("verify_foo.rs");
+    bin_under_test
+        .command()
+        .arg("compile-to-verifier")
+        .arg("--program")
+        .arg(&program_file)
+        .arg("--proof")
+        .arg(&proof_file)
+        .arg("--out")
+        .arg(&source_file)
+        .assert()
+        .success();
+    let source = fs::read_to_string(&source_file)?;
+    assert!(
+        source.contains("const STACK_INPUTS: [u64; 3] = [\n    2, 3, 4,\n];"),
+        "{source}"
+    );
+    assert!(source.contains("const STACK_OUTPUTS: [u64; 16] = [\n    14, 0,"), "{source}");
+    assert!(source.contains("const PROOF: &[u8] = &["), "{source}");
+    assert!(source.contains("miden::verify(program_info, stack_inputs, stack_outputs, proof)"));
+
+    // otherwise, a Cargo package is created
+    let package_dir = dir.path().join("verify_foo");
+    bin_under_test
+        .command()
+        .arg("compile-to-verifier")
+        .arg("--program")
+        .arg(&program_file)
+        .arg("--proof")
+        .arg(&proof_file)
+        .arg("--out")
+        .arg(&package_dir)
+        .assert()
+        .success();
+    assert_eq!(fs::read_to_string(package_dir.join("src/main.rs"))?, source);
+    let manifest = fs::read_to_string(package_dir.join("Cargo.toml"))?;
+    assert!(manifest.contains("name = \"verify_foo\""), "{manifest}");
+    assert!(manifest.contains("default-features = false"), "{manifest}");
+
+    // proofs of other programs are rejected
+    fs::write(&program_file, "begin add add end")?;
+    bin_under_test
+        .command()
+        .arg("compile-to-verifier")
+        .arg("--program")
+        .arg(&program_file)
+        .arg("--proof")
+        .arg(&proof_file)
+        .arg("--out")
+        .arg(&source_file)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("was generated for program"));
+
+    Ok(())
+}
+
+#[test]
+fn cli_build_store() -> Result<(), Box<dyn std::error::Error>> {
+    let bin_under_test = bin_under_test();
+    let dir = tempfile::tempdir()?;
+
+    // the second input file repeats the tree of the first one, and adds another tree
+    let leaves = |values: [u64; 4]| {
+        values
+            .map(|value| format!(r#""0x{:016x}{}""#, value.swap_bytes(), "0".repeat(48)))
+            .join(", ")
+    };
+    let first_leaves = leaves([1, 2, 3, 4]);
+    let second_leaves = leaves([5, 6, 7, 8]);
+    let first_file = dir.path().join("first.inputs");
+    let second_file = dir.path().join("second.inputs");
+    fs::write(
+        &first_file,
+        format!(
+            r#"{{ "operand_stack": [], "merkle_store": [{{ "merkle_tree": [{first_leaves}] }}] }}"#
+        ),
+    )?;
+    fs::write(
+        &second_file,
+        format!(
+            r#"{{ "operand_stack": [], "merkle_store": [{{ "merkle_tree": [{first_leaves}] }}, {{ "merkle_tree": [{second_leaves}] }}] }}"#
+        ),
+    )?;
+
+    // the repeated tree is reported as a collision
+    let output_file = dir.path().join("store.bin");
+    bin_under_test
+        .command()
+        .arg("build-store")
+        .arg(&first_file)
+        .arg(&second_file)
+        .arg("-o")
+        .arg(&output_file)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "of `{}` collides with a root of `{}`",
+            second_file.display(),
+            first_file.display()
+        )))
+        .stdout(predicate::str::contains("Wrote Merkle store with 2 roots"));
+
+    // the serialized store contains the leaves of both trees
+    let store: MerkleStore = MerkleStore::read_from_bytes(&fs::read(&output_file)?).unwrap();
+    for values in [[1, 2, 3, 4], [5, 6, 7, 8]] {
+        let leaves = values.map(|value| -> Word { [Felt::new(value), ZERO, ZERO, ZERO] });
+        let tree = MerkleTree::new(leaves.to_vec())?;
+        for (position, leaf) in tree.leaves() {
+            let index = NodeIndex::new(tree.depth(), position)?;
+            assert_eq!(store.get_node(tree.root(), index)?, RpoDigest::from(*leaf));
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use processor::{AsmOpInfo, VmState};
 use test_utils::{build_debug_test, Felt, ToElements, ONE};
-use vm_core::{AssemblyOp, Operation};
+use vm_core::{AssemblyOp, Operation, SourceLocation};
 
 // EXEC ITER TESTS
 // =================================================================
@@ -40,7 +40,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Pad),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 34)),
                 1,
             )),
             stack: [0, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1].to_elements(),
@@ -52,7 +53,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Incr),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 34)),
                 2,
             )),
             stack: [1, 16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2].to_elements(),
@@ -64,7 +66,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::MStoreW),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 3, "mem_storew.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 34)),
                 3,
             )),
             stack: [16, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1].to_elements(),
@@ -76,7 +79,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Drop),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false)
+                    .with_location(SourceLocation::new(1, 47)),
                 1,
             )),
             stack: [15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0].to_elements(),
@@ -88,7 +92,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Drop),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false)
+                    .with_location(SourceLocation::new(1, 47)),
                 2,
             )),
             stack: [14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0].to_elements(),
@@ -100,7 +105,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Drop),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false)
+                    .with_location(SourceLocation::new(1, 47)),
                 3,
             )),
             stack: [13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0].to_elements(),
@@ -112,7 +118,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Drop),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 4, "dropw".to_string(), false)
+                    .with_location(SourceLocation::new(1, 47)),
                 4,
             )),
             stack: [12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 0].to_elements(),
@@ -124,7 +131,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Push(Felt::new(17))),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.17".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.17".to_string(), false)
+                    .with_location(SourceLocation::new(1, 53)),
                 1,
             )),
             stack: [17, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0].to_elements(),
@@ -163,7 +171,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Pad),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false)
+                    .with_location(SourceLocation::new(1, 12)),
                 1,
             )),
             stack: [0, 17, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0].to_elements(),
@@ -175,7 +184,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::FmpAdd),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false)
+                    .with_location(SourceLocation::new(1, 12)),
                 2,
             )),
             stack: [2u64.pow(30) + 1, 17, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0]
@@ -188,7 +198,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::MStore),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false)
+                    .with_location(SourceLocation::new(1, 12)),
                 3,
             )),
             stack: [17, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0].to_elements(),
@@ -203,7 +214,8 @@ fn test_exec_iter() {
             ctx: 0,
             op: Some(Operation::Drop),
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 4, "loc_store.0".to_string(), false)
+                    .with_location(SourceLocation::new(1, 12)),
                 4,
             )),
             stack: [12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 0].to_elements(),
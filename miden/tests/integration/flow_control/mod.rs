@@ -1,4 +1,7 @@
-use test_utils::{build_test, AdviceInputs, StackInputs, Test, TestError};
+use test_utils::{
+    build_test, AdviceInputs, DefaultHost, ExecutionError, ExecutionOptions, StackInputs, Test,
+    TestError,
+};
 
 // SIMPLE FLOW CONTROL TESTS
 // ================================================================================================
@@ -183,6 +186,67 @@ fn local_fn_call_with_mem_access() {
     test.prove_and_verify(vec![3, 7], false);
 }
 
+#[test]
+fn nested_fn_call_depth() {
+    let source = "
+        proc.foo
+            push.1
+            drop
+        end
+
+        proc.bar
+            call.foo
+        end
+
+        begin
+            call.bar
+            call.foo
+        end";
+
+    let test = build_test!(source);
+    let trace = test.execute().unwrap();
+    assert_eq!(trace.max_call_depth(), 2);
+
+    // execution should fail when the call depth exceeds the limit
+    let program = test.compile();
+    let options = ExecutionOptions::default().with_max_call_depth(1);
+    let result =
+        processor::execute(&program, StackInputs::default(), DefaultHost::default(), options);
+    assert!(matches!(result, Err(ExecutionError::MaxCallDepthExceeded { depth: 1, .. })));
+}
+
+#[test]
+fn deep_fn_call_depth_limit() {
+    // a chain of 20 procedures, each of which calls the next one
+    let mut source = String::from("proc.p0 push.1 drop end\n");
+    for i in 1..20 {
+        source.push_str(&format!("proc.p{i} call.p{} end\n", i - 1));
+    }
+    source.push_str("begin call.p19 end");
+
+    let test = build_test!(&source);
+    let program = test.compile();
+
+    // the program runs when the limit equals the depth of the chain
+    let options = ExecutionOptions::default().with_max_call_depth(20);
+    let trace =
+        processor::execute(&program, StackInputs::default(), DefaultHost::default(), options)
+            .unwrap();
+    assert_eq!(trace.max_call_depth(), 20);
+
+    // the execution is aborted at the configured depth, before the end of the program
+    let options = ExecutionOptions::default().with_max_call_depth(8);
+    let result =
+        processor::execute(&program, StackInputs::default(), DefaultHost::default(), options);
+    match result {
+        Err(ExecutionError::MaxCallDepthExceeded { clk, depth }) => {
+            assert_eq!(depth, 8);
+            assert!(clk > 0 && (clk as usize) < trace.trace_len_summary().main_trace_len());
+        }
+        _ => panic!("expected the call depth to be exceeded"),
+    }
+}
+
 #[test]
 fn simple_syscall() {
     let kernel_source = "
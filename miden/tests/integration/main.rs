@@ -1,3 +1,4 @@
+use miden::{Assembler, DefaultHost, ExecutionError, ProvingOptions, StackInputs};
 use test_utils::build_test;
 
 mod air;
@@ -19,3 +20,111 @@ fn multi_output_program() {
     let test = build_test!("begin mul movup.2 drop end", &[1, 2, 3]);
     test.prove_and_verify(vec![1, 2, 3], false);
 }
+
+#[test]
+fn proofs_are_deterministic() {
+    let program = Assembler::default()
+        .compile("begin push.3 push.5 add repeat.10 dup mul end end")
+        .unwrap();
+    let stack_inputs = StackInputs::try_from_values([1, 2, 3]).unwrap();
+
+    let prove = || {
+        let (_, proof) = miden::prove(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            ProvingOptions::default(),
+        )
+        .unwrap();
+        proof.to_bytes()
+    };
+
+    assert_eq!(prove(), prove());
+}
+
+#[test]
+fn proof_size_budget() {
+    let program = Assembler::default()
+        .compile("begin push.3 push.5 add repeat.10 dup mul end end")
+        .unwrap();
+    let stack_inputs = StackInputs::try_from_values([1, 2, 3]).unwrap();
+
+    for recursive in [false, true] {
+        let options = ProvingOptions::with_96_bit_security(recursive);
+        let (_, proof) =
+            miden::prove(&program, stack_inputs.clone(), DefaultHost::default(), options.clone())
+                .unwrap();
+        let size = proof.to_bytes().len();
+        let trace_len = proof.clone().into_parts().1.trace_length();
+        let estimate = miden::estimate_proof_size(trace_len, &options);
+        assert!(estimate.abs_diff(size) * 20 < size, "estimate {estimate}, actual {size}");
+
+        // the budget is checked against the estimate before the proof is generated
+        let err = miden::prove_with_size_budget(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            options.clone(),
+            estimate - 1,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            ExecutionError::ProofSizeExceeded {
+                estimated: true,
+                ..
+            }
+        ));
+
+        let (_, budget_proof) = miden::prove_with_size_budget(
+            &program,
+            stack_inputs.clone(),
+            DefaultHost::default(),
+            options,
+            size,
+        )
+        .unwrap();
+        assert_eq!(budget_proof.to_bytes(), proof.to_bytes());
+    }
+}
+
+#[test]
+fn domain_cache_reuses_parameters() {
+    let program = Assembler::default()
+        .compile("begin push.3 push.5 add repeat.10 dup mul end end")
+        .unwrap();
+
+    for recursive in [false, true] {
+        let options = ProvingOptions::with_96_bit_security(recursive);
+        let cache = miden::DomainCache::new();
+        let mut hits = 0;
+        let mut trace_len = 0;
+        for inputs in [[1, 2, 3], [4, 5, 6]] {
+            let stack_inputs = StackInputs::try_from_values(inputs).unwrap();
+            let (_, cached_proof) = miden::prove_with_domain_cache(
+                &program,
+                stack_inputs.clone(),
+                DefaultHost::default(),
+                options.clone(),
+                &cache,
+            )
+            .unwrap();
+
+            // the parameters are computed by the first proof only, and reused afterwards
+            assert_eq!(cache.len(), 1);
+            assert_eq!(cache.misses(), 1);
+            assert!(cache.hits() > hits);
+            hits = cache.hits();
+            trace_len = cached_proof.stark_proof().trace_length();
+
+            let (_, proof) =
+                miden::prove(&program, stack_inputs, DefaultHost::default(), options.clone())
+                    .unwrap();
+            assert_eq!(cached_proof.to_bytes(), proof.to_bytes());
+        }
+
+        // precomputing the parameters of a cached domain does nothing
+        cache.precompute(trace_len, &options);
+        assert_eq!(cache.misses(), 1);
+    }
+}
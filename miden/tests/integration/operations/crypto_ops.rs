@@ -1,8 +1,11 @@
 use test_utils::{
     build_expected_hash, build_expected_perm, build_op_test,
-    crypto::{init_merkle_leaf, init_merkle_store, MerkleTree, NodeIndex},
+    crypto::{
+        init_merkle_leaf, init_merkle_leaves, init_merkle_store, MerkleStore, MerkleTree,
+        NodeIndex, PartialMerkleTree, RpoDigest, SimpleSmt,
+    },
     rand::rand_vector,
-    Felt, StarkField,
+    Felt, StarkField, TestError, Word,
 };
 
 // TESTS
@@ -258,3 +261,111 @@ fn mtree_update() {
     let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone());
     test.expect_stack(&final_stack);
 }
+
+#[test]
+fn mtree_member() {
+    let asm_op = "mtree_member";
+
+    // --- leaves of a full Merkle tree -----------------------------------------------------------
+    let (leaves, store) = init_merkle_store(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+    let stack_inputs = build_member_inputs(tree.root().into(), leaves[5]);
+    let adv_map = [build_leaf_position(leaves[5], tree.depth(), 5)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+    test.expect_stack(&[1]);
+
+    // a value without a position in the advice map is not a member
+    let stack_inputs = build_member_inputs(tree.root().into(), init_merkle_leaf(9));
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone());
+    test.expect_stack(&[0]);
+
+    // a member whose position in the advice map is wrong is not found
+    let stack_inputs = build_member_inputs(tree.root().into(), leaves[5]);
+    let adv_map = [build_leaf_position(leaves[5], tree.depth(), 4)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store, adv_map);
+    test.expect_stack(&[0]);
+
+    // the cost of the check does not depend on the position of the leaf
+    let values = (0..2048).collect::<Vec<u64>>();
+    let (leaves, store) = init_merkle_store(&values);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+    let stack_inputs = build_member_inputs(tree.root().into(), leaves[2000]);
+    let adv_map = [build_leaf_position(leaves[2000], tree.depth(), 2000)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store, adv_map);
+    test.expect_stack(&[1]);
+
+    // --- leaves of a sparse Merkle tree ---------------------------------------------------------
+    let smt_leaves = init_merkle_leaves(&[10, 11]);
+    let smt = SimpleSmt::with_leaves(16, [(3, smt_leaves[0]), (1000, smt_leaves[1])]).unwrap();
+    let store = MerkleStore::from(&smt);
+
+    let stack_inputs = build_member_inputs(smt.root().into(), smt_leaves[1]);
+    let adv_map = [build_leaf_position(smt_leaves[1], smt.depth(), 1000)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+    test.expect_stack(&[1]);
+
+    let stack_inputs = build_member_inputs(smt.root().into(), init_merkle_leaf(12));
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store);
+    test.expect_stack(&[0]);
+
+    // --- leaves of a partial Merkle tree --------------------------------------------------------
+    let (leaves, _) = init_merkle_store(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+    let path = tree.get_path(NodeIndex::new(tree.depth(), 2).unwrap()).unwrap();
+    let ptree = PartialMerkleTree::with_paths([(2, leaves[2].into(), path)]).unwrap();
+    let store = MerkleStore::from(&ptree);
+
+    let stack_inputs = build_member_inputs(ptree.root().into(), leaves[3]);
+    let adv_map = [build_leaf_position(leaves[3], ptree.max_depth(), 3)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+    test.expect_stack(&[1]);
+
+    let stack_inputs = build_member_inputs(ptree.root().into(), leaves[6]);
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store);
+    test.expect_stack(&[0]);
+}
+
+#[test]
+fn mtree_member_invalid_inputs() {
+    let asm_op = "mtree_member";
+
+    let (leaves, store) = init_merkle_store(&[1, 2, 3, 4]);
+    let tree = MerkleTree::new(leaves.clone()).unwrap();
+
+    // the tree must be known to the advice provider
+    let stack_inputs = build_member_inputs(init_merkle_leaf(5), leaves[0]);
+    let adv_map = [build_leaf_position(leaves[0], 2, 0)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+    test.expect_error(TestError::ExecutionError("MerkleStoreLookupFailed"));
+
+    // the position in the advice map must be a valid position of a node of the tree
+    let stack_inputs = build_member_inputs(tree.root().into(), leaves[0]);
+    for depth in [0, 65] {
+        let adv_map = [build_leaf_position(leaves[0], depth, 0)];
+        let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+        test.expect_error(TestError::ExecutionError("InvalidTreeDepth"));
+    }
+
+    let adv_map = [build_leaf_position(leaves[0], 2, 4)];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store.clone(), adv_map);
+    test.expect_error(TestError::ExecutionError("InvalidTreeNodeIndex"));
+
+    let adv_map = [(RpoDigest::from(leaves[0]).as_bytes(), vec![Felt::new(2)])];
+    let test = build_op_test!(asm_op, &stack_inputs, &[], store, adv_map);
+    test.expect_error(TestError::ExecutionError("AdviceMapValueInvalidLength"));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds the stack inputs of `mtree_member` for the specified tree root and leaf value.
+fn build_member_inputs(root: Word, value: Word) -> Vec<u64> {
+    root.iter().chain(value.iter()).map(|v| v.as_int()).collect()
+}
+
+/// Builds the advice map entry holding the position of the specified leaf for `mtree_member`.
+fn build_leaf_position(value: Word, depth: u8, index: u64) -> ([u8; 32], Vec<Felt>) {
+    (RpoDigest::from(value).as_bytes(), vec![Felt::from(depth), Felt::new(index)])
+}
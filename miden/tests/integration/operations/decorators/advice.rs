@@ -273,6 +273,86 @@ fn advice_push_mapval() {
     test.expect_stack(&[15, 14, 13, 12, 11, 5]);
 }
 
+#[test]
+fn advice_iter_map() {
+    // --- test the layout of the entries on the advice stack ---------------------------------
+    let source: &str = "begin
+    # stack: [START, END, ...]
+
+    # load the advice stack with the entries in the range and drop the range
+    adv.iter_map
+    dropw dropw
+
+    # move the number of entries and the two entries to the operand stack
+    adv_push.13
+
+    end";
+
+    // the entries are ordered by the numeric values of the keys, even though the byte encoding
+    // of the key [256, 0, 0, 0] precedes the byte encoding of the key [1, 0, 0, 0]
+    let stack_inputs = [0, 0, 0, 1, 0, 0, 0, 0];
+    let adv_map = [
+        (key_to_bytes([256, 0, 0, 0]), vec![Felt::new(7)]),
+        (key_to_bytes([1, 0, 0, 0]), vec![Felt::new(8)]),
+    ];
+
+    let test = build_test!(source, &stack_inputs, [], MerkleStore::default(), adv_map);
+    test.expect_stack(&[7, 1, 0, 0, 0, 256, 8, 1, 0, 0, 0, 1, 2]);
+
+    // --- test summing all values in a range of the advice map -------------------------------
+    let source: &str = "begin
+    # stack: [START, END, ...]
+
+    adv.iter_map
+    dropw dropw
+
+    # initialize the sum and read the number of entries => [n, sum, ...]
+    push.0 adv_push.1
+
+    dup neq.0
+    while.true
+        # drop the key of the entry and read the number of its values => [len, n, sum, ...]
+        padw adv_loadw dropw
+        adv_push.1
+
+        dup neq.0
+        while.true
+            # add the next value to the sum => [len - 1, n, sum', ...]
+            adv_push.1 movup.3 add movdn.2
+            sub.1 dup neq.0
+        end
+        drop
+
+        sub.1 dup neq.0
+    end
+    drop
+
+    end";
+
+    let adv_map = [
+        (key_to_bytes([1, 0, 0, 0]), vec![Felt::new(1), Felt::new(2), Felt::new(3)]),
+        (key_to_bytes([2, 0, 0, 0]), vec![Felt::new(4), Felt::new(5)]),
+        (key_to_bytes([3, 0, 0, 0]), vec![]),
+        (key_to_bytes([4, 5, 6, 0]), vec![Felt::new(6)]),
+        (key_to_bytes([0, 0, 0, 1]), vec![Felt::new(100)]),
+    ];
+
+    // the range [0, 2^192) excludes the last entry
+    let stack_inputs = [0, 0, 0, 1, 0, 0, 0, 0];
+    let test = build_test!(source, &stack_inputs, [], MerkleStore::default(), adv_map.clone());
+    test.expect_stack(&[21]);
+
+    // the range [0, 2^193) contains all entries
+    let stack_inputs = [0, 0, 0, 2, 0, 0, 0, 0];
+    let test = build_test!(source, &stack_inputs, [], MerkleStore::default(), adv_map.clone());
+    test.expect_stack(&[121]);
+
+    // an empty range contains no entries
+    let stack_inputs = [0, 0, 0, 1, 0, 0, 0, 1];
+    let test = build_test!(source, &stack_inputs, [], MerkleStore::default(), adv_map);
+    test.expect_stack(&[0]);
+}
+
 #[test]
 fn advice_insert_hdword() {
     // --- test hashing without domain ----------------------------------------
@@ -1,6 +1,6 @@
 use processor::{AsmOpInfo, VmStateIterator};
 use test_utils::build_debug_test;
-use vm_core::{AssemblyOp, Felt, Operation};
+use vm_core::{AssemblyOp, Felt, Operation, SourceLocation};
 
 #[test]
 fn asmop_one_span_block_test() {
@@ -21,7 +21,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 7)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -29,7 +30,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 7)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -37,7 +39,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 4,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(1, 14)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -45,7 +48,8 @@ fn asmop_one_span_block_test() {
         VmStatePartial {
             clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(1, 21)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -79,7 +83,8 @@ fn asmop_with_one_procedure() {
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 10)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -87,7 +92,8 @@ fn asmop_with_one_procedure() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(1, 10)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -95,7 +101,8 @@ fn asmop_with_one_procedure() {
         VmStatePartial {
             clk: 4,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(1, 17)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -103,7 +110,8 @@ fn asmop_with_one_procedure() {
         VmStatePartial {
             clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("foo".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("foo".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(1, 24)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -141,7 +149,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 2,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -149,7 +158,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -157,7 +167,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 4,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -165,7 +176,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 5,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -173,7 +185,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 6,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -181,7 +194,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -189,7 +203,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -197,7 +212,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -205,7 +221,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 10,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -213,7 +230,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 11,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(3, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -221,7 +239,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 12,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(3, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -229,7 +248,8 @@ fn asmop_repeat_test() {
         VmStatePartial {
             clk: 13,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(3, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -292,7 +312,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false)
+                    .with_location(SourceLocation::new(2, 13)),
                 1,
             )),
             op: Some(Operation::Eq),
@@ -315,7 +336,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(4, 17)),
                 1,
             )),
             op: Some(Operation::Pad),
@@ -323,7 +345,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 2, "push.1".to_string(), false)
+                    .with_location(SourceLocation::new(4, 17)),
                 2,
             )),
             op: Some(Operation::Incr),
@@ -331,7 +354,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.2".to_string(), false)
+                    .with_location(SourceLocation::new(4, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(2))),
@@ -339,7 +363,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 10,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(4, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -385,7 +410,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 3,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "eq".to_string(), false)
+                    .with_location(SourceLocation::new(2, 13)),
                 1,
             )),
             op: Some(Operation::Eq),
@@ -408,7 +434,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 7,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.3".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.3".to_string(), false)
+                    .with_location(SourceLocation::new(6, 17)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(3))),
@@ -416,7 +443,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 8,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "push.4".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "push.4".to_string(), false)
+                    .with_location(SourceLocation::new(6, 24)),
                 1,
             )),
             op: Some(Operation::Push(Felt::new(4))),
@@ -424,7 +452,8 @@ fn asmop_conditional_execution_test() {
         VmStatePartial {
             clk: 9,
             asmop: Some(AsmOpInfo::new(
-                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false),
+                AssemblyOp::new("#main".to_string(), 1, "add".to_string(), false)
+                    .with_location(SourceLocation::new(6, 31)),
                 1,
             )),
             op: Some(Operation::Add),
@@ -1,4 +1,7 @@
-use test_utils::{build_op_test, build_test, Felt, StarkField, TestError, ToElements};
+use test_utils::{
+    build_op_test, build_test, AdviceInputs, DefaultHost, ExecutionError, ExecutionOptions, Felt,
+    MemAdviceProvider, StackInputs, StarkField, TestError, ToElements,
+};
 use vm_core::chiplets::hasher::apply_permutation;
 
 mod adv_ops;
@@ -74,3 +77,32 @@ fn mem_stream_pipe() {
 
     test.expect_stack(&final_stack);
 }
+
+// DENYING I/O OPERATIONS
+// ================================================================================================
+
+#[test]
+fn denied_memory_and_advice_operations() {
+    let options = ExecutionOptions::default().with_deny_memory(true).with_deny_advice(true);
+    let execute = |source: &str| {
+        let program = build_test!(source).compile();
+        let advice_inputs = AdviceInputs::default().with_stack_values([1, 2, 3, 4]).unwrap();
+        let host = DefaultHost::new(MemAdviceProvider::from(advice_inputs));
+        processor::execute(&program, StackInputs::default(), host, options)
+    };
+
+    // pure computations are allowed
+    assert!(execute("begin push.1 push.2 add end").is_ok());
+
+    // memory accesses are denied
+    let result = execute("begin push.1 mem_store.0 end");
+    assert!(matches!(result, Err(ExecutionError::DeniedOperation("mem_store"))));
+    let result = execute("proc.foo.1 loc_load.0 end begin exec.foo end");
+    assert!(matches!(result, Err(ExecutionError::DeniedOperation("mem_load"))));
+
+    // advice reads are denied
+    let result = execute("begin adv_push.1 end");
+    assert!(matches!(result, Err(ExecutionError::DeniedOperation("adv_push"))));
+    let result = execute("begin adv_loadw end");
+    assert!(matches!(result, Err(ExecutionError::DeniedOperation("adv_loadw"))));
+}
@@ -1,4 +1,8 @@
-use test_utils::{build_op_test, proptest::prelude::*, TestError, STACK_TOP_SIZE, WORD_SIZE};
+use processor::StackError;
+use test_utils::{
+    build_debug_test, build_op_test, proptest::prelude::*, DefaultHost, ExecutionError,
+    ExecutionOptions, StackInputs, TestError, STACK_TOP_SIZE, WORD_SIZE,
+};
 
 // STACK OPERATIONS TESTS
 // ================================================================================================
@@ -284,6 +288,61 @@ fn cdropw() {
     test.expect_stack(&[1, 2, 3, 4, 9, 10, 11, 12, 13, 14, 15, 0, 0, 0, 0, 0]);
 }
 
+// STRICT STACK DEPTH
+// ================================================================================================
+
+#[test]
+fn strict_stack_depth() {
+    let options = ExecutionOptions::default().with_strict_stack_depth(true);
+    let execute = |source: &str, num_inputs: u64| {
+        let program = build_debug_test!(source).compile();
+        let stack_inputs = StackInputs::try_from_values(1..=num_inputs).unwrap();
+        processor::execute(&program, stack_inputs, DefaultHost::default(), options)
+    };
+    let underflow = |source: &str, num_inputs: u64| match execute(source, num_inputs) {
+        Err(ExecutionError::StackError(StackError::Underflow {
+            expected, actual, ..
+        })) => Some((expected, actual)),
+        Err(err) => panic!("unexpected error: {err}"),
+        Ok(_) => None,
+    };
+
+    // operations which read items inserted by the VM fail even if they do not remove them
+    assert_eq!(underflow("begin add end", 2), None);
+    assert_eq!(underflow("begin add end", 1), Some((2, 1)));
+    assert_eq!(underflow("begin dup.5 end", 6), None);
+    assert_eq!(underflow("begin dup.5 end", 3), Some((6, 3)));
+    assert_eq!(underflow("begin swapw end", 7), Some((8, 7)));
+
+    // control flow operations are checked as well
+    let err = execute("begin if.true push.1 else push.2 end end", 0).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Stack underflow at clock cycle 0: 'if.true' requires 1 element but stack has 0"
+    );
+    assert_eq!(underflow("begin push.1 while.true drop end end", 0), Some((1, 0)));
+
+    // other operations are reported by the location of the instruction they are compiled from
+    let err = execute("begin\n    push.1\n    drop\n    add\nend", 1).err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "Stack underflow at line 4: 'add' requires 2 elements but stack has 1"
+    );
+
+    // a called procedure sees only the top 16 items of the stack of its caller
+    let source = "proc.foo repeat.17 drop end end begin call.foo end";
+    assert_eq!(underflow(source, 20), Some((1, 0)));
+    let source = "proc.foo repeat.17 drop end end begin exec.foo end";
+    assert_eq!(underflow(source, 20), None);
+
+    // items of the caller below the items inserted by the VM in a called procedure cannot be
+    // reached after the procedure returns
+    let source = "proc.foo drop end begin call.foo movup.15 end";
+    assert_eq!(underflow(source, 20), Some((16, 15)));
+    let source = "proc.foo swap end begin call.foo movup.15 repeat.5 drop end end";
+    assert_eq!(underflow(source, 20), None);
+}
+
 proptest! {
 
     #[test]
@@ -1,6 +1,9 @@
 use super::{test_inputs_out_of_bounds, test_param_out_of_bounds, test_unchecked_execution};
 use core::cmp::Ordering;
-use test_utils::{build_op_test, proptest::prelude::*, rand::rand_value, TestError, U32_BOUND};
+use test_utils::{
+    build_debug_test, build_op_test, proptest::prelude::*, rand::rand_value, DefaultHost,
+    ExecutionError, ExecutionOptions, StackInputs, TestError, U32_BOUND,
+};
 
 // U32 OPERATIONS TESTS - MANUAL - COMPARISON OPERATIONS
 // ================================================================================================
@@ -333,6 +336,37 @@ fn u32unchecked_max() {
     test_unchecked_execution(asm_op, 2);
 }
 
+// STRICT U32 OVERFLOW
+// ================================================================================================
+
+#[test]
+fn comparisons_in_strict_u32_mode() {
+    let options = ExecutionOptions::default().with_strict_u32_overflow(true);
+    let execute = |source: &str, values: [u64; 2]| {
+        let program = build_debug_test!(source).compile();
+        let stack_inputs = StackInputs::try_from_values(values).unwrap();
+        processor::execute(&program, stack_inputs, DefaultHost::default(), options)
+    };
+
+    // comparisons are compiled to u32 subtractions which underflow for one of the orders of the
+    // operands; these underflows are expected, and thus, are not reported
+    for asm_op in ["u32checked_lt", "u32checked_gt", "u32checked_min", "u32checked_max"] {
+        let source = format!("begin {asm_op} end");
+        for values in [[1, 2], [2, 1]] {
+            let result = execute(&source, values);
+            assert!(result.is_ok(), "{asm_op} failed on {values:?}: {:?}", result.err());
+        }
+    }
+
+    // while an underflow of a wrapping subtraction is
+    let err = execute("begin u32wrapping_sub end", [1, 2]).err().unwrap();
+    assert!(matches!(err, ExecutionError::U32Overflow { .. }), "{err}");
+    assert_eq!(
+        err.to_string(),
+        "Operation u32wrapping_sub overflowed u32 range with operands 1 and 2"
+    );
+}
+
 // U32 OPERATIONS TESTS - RANDOMIZED - COMPARISON OPERATIONS
 // ================================================================================================
 
@@ -0,0 +1,180 @@
+use super::{
+    crypto::MerkleStore, AdviceInputs, BTreeMap, DefaultHost, ExecutionError, ExecutionOptions,
+    Felt, MemAdviceProvider, Program, StackInputs, StackOutputs, Vec,
+};
+
+// ATOMIC EXECUTOR
+// ================================================================================================
+
+/// Executes programs against a snapshot of advice inputs, so that the advice inputs are updated
+/// only if an execution succeeds.
+///
+/// A program may modify the advice provider during execution: values are consumed from the advice
+/// stack, new entries are inserted into the advice map, and new nodes are added to the Merkle
+/// store (e.g., when a Merkle tree is updated). An execution is performed against a copy of the
+/// provided advice inputs; if it fails, all these changes are discarded together with the copy,
+/// and thus, the provided advice inputs remain in their pre-execution state. If it succeeds, the
+/// post-execution state of the advice provider is returned as [UpdatedAdvice].
+#[derive(Debug, Clone, Default)]
+pub struct AtomicExecutor {
+    options: ExecutionOptions,
+}
+
+impl AtomicExecutor {
+    /// Returns a new [AtomicExecutor] which executes programs with the specified options.
+    pub fn new(options: ExecutionOptions) -> Self {
+        Self { options }
+    }
+
+    /// Executes the specified program against the specified stack inputs and a snapshot of the
+    /// specified advice inputs.
+    ///
+    /// Returns the stack outputs of the program together with the state of the advice provider
+    /// at the end of the execution.
+    ///
+    /// # Errors
+    /// Returns an error if the execution fails, in which case none of the changes the program made
+    /// to the advice provider are retained.
+    pub fn execute(
+        &self,
+        program: &Program,
+        stack_inputs: StackInputs,
+        advice: &AdviceInputs,
+    ) -> Result<(StackOutputs, UpdatedAdvice), ExecutionError> {
+        let snapshot = MemAdviceProvider::from(advice.clone());
+        let mut host = DefaultHost::new(snapshot);
+        let stack_outputs = super::execute_outputs(program, stack_inputs, &mut host, self.options)?;
+
+        let (stack, map, store) = host.into_inner().into_parts();
+        Ok((stack_outputs, UpdatedAdvice { stack, map, store }))
+    }
+}
+
+// UPDATED ADVICE
+// ================================================================================================
+
+/// The state of the advice provider after a successful execution of a program.
+#[derive(Debug, Clone)]
+pub struct UpdatedAdvice {
+    /// Values remaining on the advice stack.
+    pub stack: Vec<Felt>,
+    /// Entries of the advice map, including the ones inserted by the program.
+    pub map: BTreeMap<[u8; 32], Vec<Felt>>,
+    /// Nodes of the Merkle store, including the ones added by the program.
+    pub store: MerkleStore,
+}
+
+impl From<UpdatedAdvice> for AdviceInputs {
+    /// Converts the updated advice into advice inputs, so that it can be used as the starting
+    /// state of a subsequent execution.
+    fn from(advice: UpdatedAdvice) -> Self {
+        // values are popped from the front of the advice stack, which is the order in which
+        // they are specified in the advice inputs
+        let mut stack = advice.stack;
+        stack.reverse();
+
+        AdviceInputs::default()
+            .with_stack(stack)
+            .with_map(advice.map)
+            .with_merkle_store(advice.store)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AdviceInputs, AtomicExecutor, Felt, StackInputs, Vec};
+    use crate::{
+        crypto::{MerkleStore, MerkleTree, NodeIndex, RpoDigest},
+        ExecutionError, StarkField,
+    };
+    use miden_assembly::Assembler;
+    use vm_core::utils::IntoBytes;
+
+    /// Returns the leaves of the Merkle tree in the advice inputs.
+    fn leaves() -> Vec<[Felt; 4]> {
+        (1..=4).map(|i| [Felt::new(i); 4]).collect()
+    }
+
+    /// Returns advice inputs with two values on the advice stack and a Merkle tree with four
+    /// leaves in the Merkle store, together with the root of the tree.
+    fn advice_inputs() -> (AdviceInputs, RpoDigest) {
+        let tree = MerkleTree::new(leaves()).unwrap();
+        let store = MerkleStore::from(&tree);
+        let inputs = AdviceInputs::default()
+            .with_stack_values([7, 8])
+            .unwrap()
+            .with_merkle_store(store);
+        (inputs, tree.root())
+    }
+
+    /// Returns the stack inputs for `mtree_set` which set the leaf at index 1 of the Merkle tree
+    /// with the specified root to [9, 9, 9, 9].
+    fn stack_inputs(root: RpoDigest) -> StackInputs {
+        let mut values = vec![9; 4];
+        values.extend(root.iter().map(|e| e.as_int()));
+        values.extend([1, 2]);
+        StackInputs::try_from_values(values).unwrap()
+    }
+
+    #[test]
+    fn atomic_execution_returns_updated_advice() {
+        // consume a value from the advice stack, update the Merkle tree, and store the new root
+        // in the advice map under the key [1, 2, 3, 4]
+        let source = "begin
+            adv_push.1 drop
+            mtree_set dropw
+            mem_storew.100 dropw
+            push.101.100.1.2.3.4 adv.insert_mem dropw drop drop
+        end";
+        let program = Assembler::default().compile(source).unwrap();
+        let (inputs, root) = advice_inputs();
+
+        let executor = AtomicExecutor::default();
+        let (_, updated) = executor.execute(&program, stack_inputs(root), &inputs).unwrap();
+
+        // the program consumed one value from the advice stack
+        assert_eq!(updated.stack, vec![Felt::new(8)]);
+
+        // the new root is in the advice map, and the updated tree is in the Merkle store
+        let mut new_leaves = leaves();
+        new_leaves[1] = [Felt::new(9); 4];
+        let new_root = MerkleTree::new(new_leaves).unwrap().root();
+        let key = [1, 2, 3, 4].map(Felt::new).into_bytes();
+        assert_eq!(updated.map.get(&key).map(Vec::as_slice), Some(new_root.as_elements()));
+        let leaf = updated.store.get_node(new_root, NodeIndex::new(2, 1).unwrap()).unwrap();
+        assert_eq!(leaf, RpoDigest::from([Felt::new(9); 4]));
+
+        // the original advice inputs are not modified
+        assert_eq!(inputs.stack(), &[Felt::new(7), Felt::new(8)]);
+        assert_eq!(inputs.mapped_values(&key), None);
+
+        // the updated advice can be used as the starting state of another execution
+        let next = AdviceInputs::from(updated);
+        assert_eq!(next.stack(), &[Felt::new(8)]);
+        assert!(next.mapped_values(&key).is_some());
+    }
+
+    #[test]
+    fn atomic_execution_discards_changes_on_failure() {
+        // the program updates the Merkle tree and then fails an assertion
+        let source = "begin
+            adv_push.1 drop
+            mtree_set dropw
+            push.1 assertz
+        end";
+        let program = Assembler::default().compile(source).unwrap();
+        let (inputs, root) = advice_inputs();
+
+        let executor = AtomicExecutor::default();
+        let err = executor.execute(&program, stack_inputs(root), &inputs).unwrap_err();
+        assert!(matches!(err, ExecutionError::FailedAssertion(..)));
+
+        // the advice inputs are in their pre-execution state
+        assert_eq!(inputs.stack(), &[Felt::new(7), Felt::new(8)]);
+        let leaf = inputs.merkle_store().get_node(root, NodeIndex::new(2, 1).unwrap()).unwrap();
+        assert_eq!(leaf, RpoDigest::from([Felt::new(2); 4]));
+    }
+}
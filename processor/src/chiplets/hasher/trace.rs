@@ -51,6 +51,10 @@ impl HasherTrace {
     ///
     /// Node index values are provided via `init_index` and `rest_index` parameters. The former is
     /// used for the first row, and the latter for all subsequent rows.
+    ///
+    /// This function is never inlined so that time spent on computing hash permutations can be
+    /// easily identified when profiling the VM.
+    #[inline(never)]
     pub fn append_permutation_with_index(
         &mut self,
         state: &mut HasherState,
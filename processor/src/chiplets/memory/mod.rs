@@ -110,6 +110,14 @@ impl Memory {
         self.get_value(ctx, addr).unwrap_or(INIT_MEM_VALUE)
     }
 
+    /// Returns true if the specified context/address has been written to at least once.
+    ///
+    /// This is derived from the memory access trace, and thus, does not require any bookkeeping
+    /// when memory is written.
+    pub fn is_initialized(&self, ctx: u32, addr: u32) -> bool {
+        self.trace.get(&ctx).is_some_and(|segment| segment.is_written(addr))
+    }
+
     /// Returns the entire memory state for the specified execution context at the specified cycle.
     /// The state is returned as a vector of (address, value) tuples, and includes addresses which
     /// have been accessed at least once.
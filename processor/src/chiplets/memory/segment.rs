@@ -32,6 +32,16 @@ impl MemorySegmentTrace {
         }
     }
 
+    /// Returns true if the specified address has been written to at least once.
+    ///
+    /// Reads of addresses which have not been written to are rejected in strict memory mode, so
+    /// in this mode, the first access to an address is a write and the search ends immediately.
+    pub fn is_written(&self, addr: u32) -> bool {
+        self.0.get(&addr).is_some_and(|addr_trace| {
+            addr_trace.iter().any(|access| access.op == MemoryOperation::Write)
+        })
+    }
+
     /// Returns the entire memory state at the beginning of the specified cycle.
     pub fn get_state_at(&self, clk: u32) -> Vec<(u64, Word)> {
         let mut result: Vec<(u64, Word)> = Vec::new();
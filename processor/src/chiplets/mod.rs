@@ -438,6 +438,11 @@ impl Chiplets {
         self.memory.get_value(ctx, addr)
     }
 
+    /// Returns true if the specified context/address has been written to at least once.
+    pub fn is_mem_initialized(&self, ctx: u32, addr: u32) -> bool {
+        self.memory.is_initialized(ctx, addr)
+    }
+
     /// Returns the entire memory state for the specified execution context at the specified cycle.
     /// The state is returned as a vector of (address, value) tuples, and includes addresses which
     /// have been accessed at least once.
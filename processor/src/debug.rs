@@ -42,6 +42,16 @@ impl fmt::Display for VmState {
     }
 }
 
+/// TrapState holds the state of the process at the clock cycle at which an execution failed.
+///
+/// The stack is the state of the operand stack before the operation which caused the failure was
+/// executed, with the top of the stack first.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrapState {
+    pub clk: u32,
+    pub stack: Vec<Felt>,
+}
+
 /// Iterator that iterates through vm state at each step of the execution.
 /// This allows debugging or replaying ability to view various process state
 /// at each clock cycle.
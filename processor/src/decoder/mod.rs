@@ -59,7 +59,7 @@ where
         // start decoding the JOIN block; this appends a row with JOIN operation to the decoder
         // trace. when JOIN operation is executed, the rest of the VM state does not change
         self.decoder.start_join(child1_hash, child2_hash, addr);
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::Join, Operation::Noop)
     }
 
     ///  Ends decoding of a JOIN block.
@@ -71,7 +71,7 @@ where
         // send the end of control block to the chiplets bus to handle the final hash request.
         self.chiplets.read_hash_result();
 
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::End, Operation::Noop)
     }
 
     // SPLIT BLOCK
@@ -94,7 +94,7 @@ where
         // start decoding the SPLIT block. this appends a row with SPLIT operation to the decoder
         // trace. we also pop the value off the top of the stack and return it.
         self.decoder.start_split(child1_hash, child2_hash, addr, condition);
-        self.execute_op(Operation::Drop)?;
+        self.execute_control_op(Operation::Split, Operation::Drop)?;
         Ok(condition)
     }
 
@@ -107,7 +107,7 @@ where
         // send the end of control block to the chiplets bus to handle the final hash request.
         self.chiplets.read_hash_result();
 
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::End, Operation::Noop)
     }
 
     // LOOP BLOCK
@@ -133,7 +133,7 @@ where
         // basically, if the top of the stack is ZERO, a LOOP operation should be immediately
         // followed by an END operation.
         self.decoder.start_loop(body_hash, addr, condition);
-        self.execute_op(Operation::Drop)?;
+        self.execute_control_op(Operation::Loop, Operation::Drop)?;
         Ok(condition)
     }
 
@@ -159,9 +159,9 @@ where
             #[cfg(debug_assertions)]
             debug_assert_eq!(ZERO, self.stack.peek());
 
-            self.execute_op(Operation::Drop)
+            self.execute_control_op(Operation::End, Operation::Drop)
         } else {
-            self.execute_op(Operation::Noop)
+            self.execute_control_op(Operation::End, Operation::Noop)
         }
     }
 
@@ -195,16 +195,18 @@ where
             next_overflow_addr,
         );
 
-        if block.is_syscall() {
+        let control_op = if block.is_syscall() {
             self.system.start_syscall();
             self.decoder.start_syscall(fn_hash, addr, ctx_info);
+            Operation::SysCall
         } else {
             self.system.start_call(fn_hash);
             self.decoder.start_call(fn_hash, addr, ctx_info);
-        }
+            Operation::Call
+        };
 
         // the rest of the VM state does not change
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(control_op, Operation::Noop)
     }
 
     /// Ends decoding of a CALL or a SYSCALL block.
@@ -238,7 +240,7 @@ where
         );
 
         // the rest of the VM state does not change
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::End, Operation::Noop)
     }
 
     // DYN BLOCK
@@ -255,7 +257,7 @@ where
                 .hash_control_block(EMPTY_WORD, EMPTY_WORD, Dyn::DOMAIN, block.hash());
 
         self.decoder.start_dyn(dyn_hash, addr);
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::Dyn, Operation::Noop)
     }
 
     /// Ends decoding of a DYN block.
@@ -267,7 +269,7 @@ where
         // send the end of control block to the chiplets bus to handle the final hash request.
         self.chiplets.read_hash_result();
 
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::End, Operation::Noop)
     }
 
     // SPAN BLOCK
@@ -287,7 +289,7 @@ where
         // set the value of the group_count register at the beginning of the SPAN.
         let num_op_groups = get_span_op_group_count(op_batches);
         self.decoder.start_span(&op_batches[0], Felt::new(num_op_groups as u64), addr);
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::Span, Operation::Noop)
     }
 
     /// Continues decoding a SPAN block by absorbing the next batch of operations.
@@ -307,7 +309,7 @@ where
         // send the end of control block to the chiplets bus to handle the final hash request.
         self.chiplets.read_hash_result();
 
-        self.execute_op(Operation::Noop)
+        self.execute_control_op(Operation::End, Operation::Noop)
     }
 }
 
@@ -4,7 +4,11 @@ use super::{
     CodeBlock, Digest, Felt, QuadFelt, Word,
 };
 use core::fmt::{Display, Formatter};
-use vm_core::{stack::STACK_TOP_SIZE, utils::to_hex};
+use vm_core::{
+    stack::STACK_TOP_SIZE,
+    utils::{string::String, to_hex},
+    SourceLocation,
+};
 use winter_prover::{math::FieldElement, ProverError};
 
 #[cfg(feature = "std")]
@@ -15,33 +19,71 @@ use std::error::Error;
 
 #[derive(Debug)]
 pub enum ExecutionError {
+    AdviceMapFetchFailed(String),
     AdviceMapKeyNotFound(Word),
+    AdviceMapKeyRangeUnsupported,
     AdviceMapValueInvalidLength(Word, usize, usize),
+    AdviceMapStorageFailed(String),
+    AdviceReadLimitExceeded {
+        clk: u32,
+        num_elements: usize,
+        max_elements: usize,
+    },
     AdviceStackReadFailed(u32),
     CallerNotInSyscall,
     CodeBlockNotFound(Digest),
     DynamicCodeBlockNotFound(Digest),
     CycleLimitExceeded(u32),
+    DeniedOperation(&'static str),
     DivideByZero(u32),
+    ExecutionAborted {
+        clk: u32,
+        reason: String,
+    },
     Ext2InttError(Ext2InttError),
     FailedAssertion(u32, Felt),
     InvalidFmpValue(Felt, Felt),
     InvalidFriDomainSegment(u64),
     InvalidFriLayerFolding(QuadFelt, QuadFelt),
-    InvalidMemoryRange { start_addr: u64, end_addr: u64 },
+    InvalidMemoryRange {
+        start_addr: u64,
+        end_addr: u64,
+    },
     InvalidStackDepthOnReturn(usize),
     InvalidStackWordOffset(usize),
-    InvalidTreeDepth { depth: Felt },
-    InvalidTreeNodeIndex { depth: Felt, value: Felt },
+    InvalidTreeDepth {
+        depth: Felt,
+    },
+    InvalidTreeNodeIndex {
+        depth: Felt,
+        value: Felt,
+    },
+    MaxCallDepthExceeded {
+        clk: u32,
+        depth: usize,
+    },
     MemoryAddressOutOfBounds(u64),
     MerkleStoreMergeFailed(MerkleError),
     MerkleStoreLookupFailed(MerkleError),
     MerkleStoreUpdateFailed(MerkleError),
     NotBinaryValue(Felt),
     NotU32Value(Felt, Felt),
+    ProofSizeExceeded {
+        size: usize,
+        max_size: usize,
+        estimated: bool,
+    },
     ProverError(ProverError),
+    StackError(StackError),
     SyscallTargetNotInKernel(Digest),
+    U32Overflow {
+        operation: String,
+        operands: (u64, u64),
+    },
     UnexecutableCodeBlock(CodeBlock),
+    UninitializedMemoryRead {
+        address: u32,
+    },
     MalformedSignatureKey(&'static str),
     FailedSignatureGeneration(&'static str),
 }
@@ -51,10 +93,16 @@ impl Display for ExecutionError {
         use ExecutionError::*;
 
         match self {
+            AdviceMapFetchFailed(reason) => {
+                write!(f, "Remote advice map access failed: {reason}")
+            }
             AdviceMapKeyNotFound(key) => {
                 let hex = to_hex(Felt::elements_as_bytes(key))?;
                 write!(f, "Value for key {hex} not present in the advice map")
             }
+            AdviceMapKeyRangeUnsupported => {
+                write!(f, "Advice provider does not support listing the keys of the advice map")
+            }
             AdviceMapValueInvalidLength(key, expected, actual) => {
                 let hex = to_hex(Felt::elements_as_bytes(key))?;
                 write!(
@@ -62,6 +110,19 @@ impl Display for ExecutionError {
                     "Expected value for key {hex} to contain {expected} elements, but was {actual}"
                 )
             }
+            AdviceMapStorageFailed(reason) => {
+                write!(f, "Advice map backing storage access failed: {reason}")
+            }
+            AdviceReadLimitExceeded {
+                clk,
+                num_elements,
+                max_elements,
+            } => {
+                write!(
+                    f,
+                    "Advice operation at cycle {clk} reads {num_elements} elements, which exceeds the limit of {max_elements} elements per operation"
+                )
+            }
             AdviceStackReadFailed(step) => write!(f, "Advice stack read failed at step {step}"),
             CallerNotInSyscall => {
                 write!(f, "Instruction `caller` used outside of kernel context")
@@ -83,7 +144,13 @@ impl Display for ExecutionError {
             CycleLimitExceeded(max_cycles) => {
                 write!(f, "Exceeded the allowed number of cycles (max cycles = {max_cycles})")
             }
+            DeniedOperation(instruction) => {
+                write!(f, "Instruction `{instruction}` is denied by the execution options")
+            }
             DivideByZero(clk) => write!(f, "Division by zero at clock cycle {clk}"),
+            ExecutionAborted { clk, reason } => {
+                write!(f, "Execution aborted by the host after {clk} cycles: {reason}")
+            }
             Ext2InttError(err) => write!(f, "Failed to execute Ext2Intt operation: {err}"),
             FailedAssertion(clk, err_code) => {
                 write!(f, "Assertion failed at clock cycle {clk} with error code {err_code}")
@@ -115,6 +182,12 @@ impl Display for ExecutionError {
             InvalidTreeNodeIndex { depth, value } => {
                 write!(f, "The provided index {value} is out of bounds for a node at depth {depth}")
             }
+            MaxCallDepthExceeded { clk, depth } => {
+                write!(
+                    f,
+                    "Exceeded the allowed depth of nested procedure calls at cycle {clk} (max depth = {depth})"
+                )
+            }
             MemoryAddressOutOfBounds(addr) => {
                 write!(f, "Memory address cannot exceed 2^32 but was {addr}")
             }
@@ -136,14 +209,32 @@ impl Display for ExecutionError {
                     "An operation expected a u32 value, but received {v} (error code: {err_code})"
                 )
             }
+            ProofSizeExceeded {
+                size,
+                max_size,
+                estimated,
+            } => {
+                let kind = if *estimated { "Estimated proof" } else { "Proof" };
+                write!(f, "{kind} size {size} bytes exceeds budget {max_size}")
+            }
             ProverError(error) => write!(f, "Proof generation failed: {error}"),
+            StackError(err) => write!(f, "{err}"),
             SyscallTargetNotInKernel(proc) => {
                 let hex = to_hex(&proc.as_bytes())?;
                 write!(f, "Syscall failed: procedure with root {hex} was not found in the kernel")
             }
+            U32Overflow {
+                operation,
+                operands: (a, b),
+            } => {
+                write!(f, "Operation {operation} overflowed u32 range with operands {a} and {b}")
+            }
             UnexecutableCodeBlock(block) => {
                 write!(f, "Execution reached unexecutable code block {block:?}")
             }
+            UninitializedMemoryRead { address } => {
+                write!(f, "Read from memory address {address} which has never been written to")
+            }
             MalformedSignatureKey(signature) => write!(f, "Malformed signature key: {signature}"),
             FailedSignatureGeneration(signature) => {
                 write!(f, "Failed to generate signature: {signature}")
@@ -161,6 +252,63 @@ impl From<Ext2InttError> for ExecutionError {
     }
 }
 
+impl From<StackError> for ExecutionError {
+    fn from(value: StackError) -> Self {
+        Self::StackError(value)
+    }
+}
+
+// STACK ERROR
+// ================================================================================================
+
+#[derive(Debug)]
+pub enum StackError {
+    /// An operation removed more items from the operand stack than were placed onto it.
+    ///
+    /// `expected` is the minimum number of items the stack should have contained for the
+    /// operation to succeed, and `actual` is the number of items it actually contained.
+    /// `instruction` and `source_loc` identify the assembly instruction the operation was compiled
+    /// from, and are taken from the assembly op decorators emitted by the assembler in debug
+    /// mode. If the program was compiled without debug info, the instruction is the name of the
+    /// operation; if the location of the instruction is not known (e.g., because the program was
+    /// compiled without debug info or source locations, or the operation is a control flow
+    /// operation, which is not preceded by an assembly op decorator), the location is `None` and
+    /// the underflow is reported by the clock cycle at which it happened instead.
+    Underflow {
+        expected: usize,
+        actual: usize,
+        instruction: String,
+        source_loc: Option<SourceLocation>,
+        clk: u32,
+    },
+}
+
+impl Display for StackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), core::fmt::Error> {
+        match self {
+            StackError::Underflow {
+                expected,
+                actual,
+                instruction,
+                source_loc,
+                clk,
+            } => {
+                match source_loc {
+                    Some(source_loc) => {
+                        write!(f, "Stack underflow at line {}: ", source_loc.line())?
+                    }
+                    None => write!(f, "Stack underflow at clock cycle {clk}: ")?,
+                }
+                let noun = if *expected == 1 { "element" } else { "elements" };
+                write!(f, "'{instruction}' requires {expected} {noun} but stack has {actual}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for StackError {}
+
 // EXT2INTT ERROR
 // ================================================================================================
 
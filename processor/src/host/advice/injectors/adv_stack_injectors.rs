@@ -1,6 +1,6 @@
 use super::super::{AdviceSource, ExecutionError, Felt, HostResponse, StarkField};
-use crate::{AdviceProvider, Ext2InttError, FieldElement, ProcessState, Vec};
-use vm_core::{QuadExtension, SignatureKind};
+use crate::{AdviceProvider, Ext2InttError, FieldElement, ProcessState, Vec, ONE, ZERO};
+use vm_core::{crypto::hash::RpoDigest, QuadExtension, SignatureKind};
 use winter_prover::math::fft;
 
 // TYPE ALIASES
@@ -56,6 +56,78 @@ pub(crate) fn copy_merkle_node_to_adv_stack<S: ProcessState, A: AdviceProvider>(
     Ok(HostResponse::None)
 }
 
+/// Pushes the position of a leaf of the Merkle tree specified by the values on the top of the
+/// operand stack onto the advice stack, together with the node of the tree at that position.
+///
+/// The position of the leaf is read from the advice map, in which it is expected to be stored
+/// under the value of the leaf as `[depth, index]`.
+///
+/// Inputs:
+///   Operand stack: [VALUE, TREE_ROOT, ...]
+///   Advice stack: [...]
+///   Advice map: {VALUE: [depth, index]}
+///   Merkle store: {TREE_ROOT<-NODE}
+///
+/// Outputs:
+///   Operand stack: [VALUE, TREE_ROOT, ...]
+///   Advice stack: [depth, index, NODE, ...]
+///   Advice map: {VALUE: [depth, index]}
+///   Merkle store: {TREE_ROOT<-NODE}
+///
+/// If the advice map does not contain the position of `VALUE`, the position of the left child of
+/// the root (i.e., depth 1 and index 0) is pushed instead.
+///
+/// # Errors
+/// Returns an error if:
+/// - The value stored in the advice map under `VALUE` does not consist of exactly 2 elements.
+/// - The depth read from the advice map is either zero or greater than 64.
+/// - The index read from the advice map is not smaller than 2^depth.
+/// - The node at the specified position of the Merkle tree with the specified root is not known
+///   to the advice provider.
+pub(crate) fn push_merkle_leaf_position<S: ProcessState, A: AdviceProvider>(
+    advice_provider: &mut A,
+    process: &S,
+) -> Result<HostResponse, ExecutionError> {
+    // read the leaf value and the tree root from the stack
+    let value = [
+        process.get_stack_item(3),
+        process.get_stack_item(2),
+        process.get_stack_item(1),
+        process.get_stack_item(0),
+    ];
+    let root = [
+        process.get_stack_item(7),
+        process.get_stack_item(6),
+        process.get_stack_item(5),
+        process.get_stack_item(4),
+    ];
+
+    // read the position of the leaf from the advice map
+    let (depth, index) = match advice_provider.get_mapped_values(&RpoDigest::from(value).as_bytes())
+    {
+        Some(&[depth, index]) => (depth, index),
+        Some(values) => {
+            return Err(ExecutionError::AdviceMapValueInvalidLength(value, 2, values.len()))
+        }
+        None => (ONE, ZERO),
+    };
+    if !(1..=64).contains(&depth.as_int()) {
+        return Err(ExecutionError::InvalidTreeDepth { depth });
+    }
+    let node = advice_provider.get_tree_node(root, &depth, &index)?;
+
+    // push the node onto the advice stack with the first element pushed last so that it can
+    // be popped first (i.e. stack behavior for word), followed by the position of the node
+    advice_provider.push_stack(AdviceSource::Value(node[3]))?;
+    advice_provider.push_stack(AdviceSource::Value(node[2]))?;
+    advice_provider.push_stack(AdviceSource::Value(node[1]))?;
+    advice_provider.push_stack(AdviceSource::Value(node[0]))?;
+    advice_provider.push_stack(AdviceSource::Value(index))?;
+    advice_provider.push_stack(AdviceSource::Value(depth))?;
+
+    Ok(HostResponse::None)
+}
+
 /// Pushes a list of field elements onto the advice stack. The list is looked up in the advice
 /// map using the specified word from the operand stack as the key. If `include_len` is set to
 /// true, the number of elements in the value is also pushed onto the advice stack.
@@ -101,6 +173,57 @@ pub(crate) fn copy_map_value_to_adv_stack<S: ProcessState, A: AdviceProvider>(
     Ok(HostResponse::None)
 }
 
+/// Pushes all entries of the advice map with keys in the range [START_KEY, END_KEY) onto the
+/// advice stack, preceded by the number of such entries.
+///
+/// Inputs:
+///   Operand stack: [START_KEY, END_KEY, ...]
+///   Advice stack: [...]
+///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+///
+/// Outputs:
+///   Operand stack: [START_KEY, END_KEY, ...]
+///   Advice stack: [n, KEY_1, len_1, values_1, ..., KEY_n, len_n, values_n, ...]
+///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+///
+/// Where KEY_1, ..., KEY_n are the keys of the advice map in the specified range in ascending
+/// order, and len_i is the number of elements in values_i.
+///
+/// This is an unconstrained advice injector: the pushed entries are not checked against the
+/// advice map by the VM.
+pub(crate) fn copy_map_range_to_adv_stack<S: ProcessState, A: AdviceProvider>(
+    advice_provider: &mut A,
+    process: &S,
+) -> Result<HostResponse, ExecutionError> {
+    let start_key = [
+        process.get_stack_item(3),
+        process.get_stack_item(2),
+        process.get_stack_item(1),
+        process.get_stack_item(0),
+    ];
+    let end_key = [
+        process.get_stack_item(7),
+        process.get_stack_item(6),
+        process.get_stack_item(5),
+        process.get_stack_item(4),
+    ];
+
+    let keys = advice_provider.get_map_keys_in_range(start_key, end_key)?;
+
+    // push the entries in reverse order so that the entry with the smallest key ends up at the
+    // top of the advice stack; each entry is pushed as its values, their number, and the key
+    for key in keys.iter().rev() {
+        advice_provider.push_stack(AdviceSource::Map {
+            key: *key,
+            include_len: true,
+        })?;
+        advice_provider.push_stack(AdviceSource::Word(*key))?;
+    }
+    advice_provider.push_stack(AdviceSource::Value(Felt::from(keys.len() as u64)))?;
+
+    Ok(HostResponse::None)
+}
+
 /// Pushes the result of [u64] division (both the quotient and the remainder) onto the advice
 /// stack.
 ///
@@ -250,7 +250,7 @@ fn get_smt_upper_leaf_preimage<A: AdviceProvider>(
 ) -> Result<(Word, Word), ExecutionError> {
     let node_bytes = RpoDigest::from(node).as_bytes();
     let kv = advice_provider
-        .get_mapped_values(&node_bytes)
+        .read_mapped_values(&node_bytes)?
         .ok_or(ExecutionError::AdviceMapKeyNotFound(node))?;
 
     if kv.len() != WORD_SIZE * 2 {
@@ -1,4 +1,6 @@
-use super::{BTreeMap, Felt, InnerNodeInfo, InputError, MerkleStore, Vec};
+use super::{BTreeMap, Felt, InnerNodeInfo, InputError, IntoBytes, MerkleStore, Vec, Word};
+use core::fmt;
+use vm_core::{crypto::hash::Rpo256, utils::to_hex};
 
 // ADVICE INPUTS
 // ================================================================================================
@@ -95,6 +97,43 @@ impl AdviceInputs {
         self.store.extend(iter);
     }
 
+    /// Merges the `other` advice inputs into these advice inputs.
+    ///
+    /// The advice stack of `other` is appended to the advice stack of `self`, and the Merkle store
+    /// of `other` is merged into the Merkle store of `self`. If both advice maps contain the same
+    /// key mapped to different values, an error listing all such keys is returned; identical
+    /// entries are not considered to be conflicts.
+    pub fn merge(self, other: AdviceInputs) -> Result<AdviceInputs, MergeConflict> {
+        let conflicts = other
+            .map
+            .iter()
+            .filter_map(|(key, right)| match self.map.get(key) {
+                Some(left) if left != right => Some((*key, left.clone(), right.clone())),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if !conflicts.is_empty() {
+            return Err(MergeConflict { conflicts });
+        }
+
+        Ok(self.merge_prefer_left(other))
+    }
+
+    /// Merges the `other` advice inputs into these advice inputs.
+    ///
+    /// This is the same as [AdviceInputs::merge()], except that conflicting advice map entries
+    /// are resolved silently by keeping the values from `self`.
+    pub fn merge_prefer_left(mut self, other: AdviceInputs) -> AdviceInputs {
+        let (stack, map, store) = other.into_parts();
+        self.stack.extend(stack);
+        for (key, values) in map {
+            self.map.entry(key).or_insert(values);
+        }
+        self.store.extend(store.inner_nodes());
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -108,6 +147,11 @@ impl AdviceInputs {
         self.map.get(key).map(Vec::as_slice)
     }
 
+    /// Returns an iterator over the keys of the advice map, in ascending order of their bytes.
+    pub fn map_keys(&self) -> impl Iterator<Item = &[u8; 32]> {
+        self.map.keys()
+    }
+
     /// Returns the underlying [MerkleStore].
     pub const fn merkle_store(&self) -> &MerkleStore {
         &self.store
@@ -124,6 +168,68 @@ impl AdviceInputs {
     }
 }
 
+// ADVICE MAP KEY
+// ================================================================================================
+
+/// Helpers for deriving keys of the advice map from structured data.
+///
+/// A key is derived by hashing the provided elements with RPO (i.e., computing
+/// `Rpo256::hash_elements(felts)`), and then encoding the four elements of the resulting digest
+/// into 32 bytes, each element as the little-endian bytes of its canonical integer
+/// representation. This is the same encoding the VM uses when a word on the stack is used as a
+/// key into the advice map, and thus, a program can look up the values under a derived key by
+/// computing the hash of the same elements (e.g., via `hperm` or `hmerge`) in the VM.
+///
+/// To use a word as a key directly, without hashing it, convert it via `word.into_bytes()`.
+pub struct AdviceMapKey;
+
+impl AdviceMapKey {
+    /// Returns the advice map key derived from the RPO hash of the provided elements.
+    pub fn from_felts(felts: &[Felt]) -> [u8; 32] {
+        let digest: Word = Rpo256::hash_elements(felts).into();
+        digest.into_bytes()
+    }
+
+    /// Returns the advice map key derived from the RPO hash of the four elements of the provided
+    /// word.
+    ///
+    /// This is equivalent to calling [AdviceMapKey::from_felts()] with the elements of the word.
+    pub fn from_word(word: Word) -> [u8; 32] {
+        Self::from_felts(&word)
+    }
+}
+
+// MERGE CONFLICT
+// ================================================================================================
+
+/// Error returned when two sets of [AdviceInputs] map the same key to different values.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeConflict {
+    conflicts: Vec<([u8; 32], Vec<Felt>, Vec<Felt>)>,
+}
+
+impl MergeConflict {
+    /// Returns the list of conflicting advice map entries as `(key, left values, right values)`
+    /// tuples, sorted by key.
+    pub fn conflicts(&self) -> &[([u8; 32], Vec<Felt>, Vec<Felt>)] {
+        &self.conflicts
+    }
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "advice map contains {} conflicting key(s):", self.conflicts.len())?;
+        for (key, left, right) in self.conflicts.iter() {
+            let hex = to_hex(key)?;
+            write!(f, "\n  {hex}: {left:?} vs {right:?}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MergeConflict {}
+
 // INTERNALS
 // ================================================================================================
 
@@ -134,3 +240,58 @@ pub struct AdviceInputs {
     pub map: BTreeMap<[u8; 32], Vec<Felt>>,
     pub store: MerkleStore,
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{AdviceInputs, AdviceMapKey, Felt, IntoBytes, Rpo256, Word};
+    use vm_core::{utils::collections::Vec, StarkField};
+
+    #[test]
+    fn advice_map_key() {
+        let felts = [Felt::new(7), Felt::new(42)];
+        let digest: Word = Rpo256::hash_elements(&felts).into();
+        let key = AdviceMapKey::from_felts(&felts);
+        assert_eq!(key, digest.into_bytes());
+        assert_eq!(&key[..8], &digest[0].as_int().to_le_bytes());
+
+        // a word is hashed as a sequence of four elements
+        let word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        assert_eq!(AdviceMapKey::from_word(word), AdviceMapKey::from_felts(&word));
+        assert_ne!(AdviceMapKey::from_word(word), word.into_bytes());
+    }
+
+    #[test]
+    fn merge_advice_inputs() {
+        let left = AdviceInputs::default()
+            .with_stack([Felt::new(1)])
+            .with_map([([1; 32], vec![Felt::new(1)]), ([2; 32], vec![Felt::new(2)])]);
+        let right = AdviceInputs::default()
+            .with_stack([Felt::new(2)])
+            .with_map([([2; 32], vec![Felt::new(2)]), ([3; 32], vec![Felt::new(3)])]);
+
+        let merged = left.merge(right).unwrap();
+        assert_eq!(merged.stack(), &[Felt::new(1), Felt::new(2)]);
+        assert_eq!(merged.mapped_values(&[1; 32]), Some([Felt::new(1)].as_slice()));
+        assert_eq!(merged.mapped_values(&[3; 32]), Some([Felt::new(3)].as_slice()));
+    }
+
+    #[test]
+    fn merge_advice_inputs_conflict() {
+        let left = AdviceInputs::default()
+            .with_map([([1; 32], vec![Felt::new(1)]), ([2; 32], vec![Felt::new(2)])]);
+        let right = AdviceInputs::default()
+            .with_map([([1; 32], vec![Felt::new(5)]), ([2; 32], vec![Felt::new(6)])]);
+
+        let err = left.clone().merge(right.clone()).unwrap_err();
+        let keys = err.conflicts().iter().map(|(key, ..)| *key).collect::<Vec<_>>();
+        assert_eq!(keys, vec![[1; 32], [2; 32]]);
+        assert_eq!(err.conflicts()[0].2, vec![Felt::new(5)]);
+
+        let merged = left.merge_prefer_left(right);
+        assert_eq!(merged.mapped_values(&[1; 32]), Some([Felt::new(1)].as_slice()));
+        assert_eq!(merged.mapped_values(&[2; 32]), Some([Felt::new(2)].as_slice()));
+    }
+}
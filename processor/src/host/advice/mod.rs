@@ -1,6 +1,6 @@
 use super::HostResponse;
 use crate::{ExecutionError, Felt, InputError, ProcessState, StarkField, Word};
-use core::borrow::Borrow;
+use core::{borrow::Borrow, cmp::Ordering};
 use vm_core::{
     crypto::{
         hash::RpoDigest,
@@ -10,19 +10,23 @@ use vm_core::{
         collections::{BTreeMap, KvMap, RecordingMap, Vec},
         IntoBytes,
     },
-    AdviceInjector, SignatureKind,
+    AdviceInjector, SignatureKind, ZERO,
 };
 
 mod extractors;
 pub use extractors::AdviceExtractor;
 
 mod inputs;
-pub use inputs::AdviceInputs;
+pub use inputs::{AdviceInputs, AdviceMapKey, MergeConflict};
 
 mod injectors;
 
 mod providers;
-pub use providers::{MemAdviceProvider, RecAdviceProvider};
+#[cfg(feature = "disk-advice")]
+pub use providers::DiskAdviceProvider;
+pub use providers::{CappedAdviceProvider, MemAdviceProvider, RecAdviceProvider};
+#[cfg(feature = "remote-advice")]
+pub use providers::{RemoteAdviceProvider, REMOTE_ADVICE_KEY_PLACEHOLDER};
 
 mod source;
 pub use source::AdviceSource;
@@ -58,10 +62,12 @@ pub trait AdviceProvider: Sized {
         match advice_injector {
             AdviceInjector::MerkleNodeMerge => self.merge_merkle_nodes(process),
             AdviceInjector::MerkleNodeToStack => self.copy_merkle_node_to_adv_stack(process),
+            AdviceInjector::MerkleLeafToStack => self.push_merkle_leaf_position(process),
             AdviceInjector::MapValueToStack {
                 include_len,
                 key_offset,
             } => self.copy_map_value_to_adv_stack(process, *include_len, *key_offset),
+            AdviceInjector::MapRangeToStack => self.copy_map_range_to_adv_stack(process),
             AdviceInjector::UpdateMerkleNode => self.update_operand_stack_merkle_node(process),
             AdviceInjector::DivU64 => self.push_u64_div_result(process),
             AdviceInjector::Ext2Inv => self.push_ext2_inv_result(process),
@@ -216,6 +222,34 @@ pub trait AdviceProvider: Sized {
         injectors::adv_stack_injectors::copy_merkle_node_to_adv_stack(self, process)
     }
 
+    /// Pushes the position of a leaf of the Merkle tree specified by the values on the top of the
+    /// operand stack onto the advice stack, together with the node of the tree at that position.
+    ///
+    /// Inputs:
+    ///   Operand stack: [VALUE, TREE_ROOT, ...]
+    ///   Advice stack: [...]
+    ///   Advice map: {VALUE: [depth, index]}
+    ///   Merkle store: {TREE_ROOT<-NODE}
+    ///
+    /// Outputs:
+    ///   Operand stack: [VALUE, TREE_ROOT, ...]
+    ///   Advice stack: [depth, index, NODE, ...]
+    ///   Advice map: {VALUE: [depth, index]}
+    ///   Merkle store: {TREE_ROOT<-NODE}
+    ///
+    /// If the advice map does not contain the position of `VALUE`, the position of the left child
+    /// of the root (i.e., depth 1 and index 0) is pushed instead.
+    ///
+    /// # Errors
+    /// Returns an error if the position stored in the advice map is not a valid position of a node
+    /// of the Merkle tree with the specified root which is known to the advice provider.
+    fn push_merkle_leaf_position<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        injectors::adv_stack_injectors::push_merkle_leaf_position(self, process)
+    }
+
     /// Pushes a list of field elements onto the advice stack. The list is looked up in the advice
     /// map using the specified word from the operand stack as the key. If `include_len` is set to
     /// true, the number of elements in the value is also pushed onto the advice stack.
@@ -254,6 +288,32 @@ pub trait AdviceProvider: Sized {
         )
     }
 
+    /// Pushes all entries of the advice map with keys in the range [START_KEY, END_KEY) onto the
+    /// advice stack, preceded by the number of such entries.
+    ///
+    /// Inputs:
+    ///   Operand stack: [START_KEY, END_KEY, ...]
+    ///   Advice stack: [...]
+    ///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+    ///
+    /// Outputs:
+    ///   Operand stack: [START_KEY, END_KEY, ...]
+    ///   Advice stack: [n, KEY_1, len_1, values_1, ..., KEY_n, len_n, values_n, ...]
+    ///   Advice map: {KEY_1: values_1, ..., KEY_n: values_n}
+    ///
+    /// Where KEY_1, ..., KEY_n are the keys of the advice map in the specified range, ordered as
+    /// described in [AdviceProvider::get_map_keys_in_range()], and len_i is the number of elements in
+    /// values_i.
+    ///
+    /// This is an unconstrained advice injector: the pushed entries are not checked against the
+    /// advice map by the VM.
+    fn copy_map_range_to_adv_stack<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        injectors::adv_stack_injectors::copy_map_range_to_adv_stack(self, process)
+    }
+
     /// Pushes the result of [u64] division (both the quotient and the remainder) onto the advice
     /// stack.
     ///
@@ -581,6 +641,19 @@ pub trait AdviceProvider: Sized {
     /// Returns a reference to the value(s) associated with the specified key in the advice map.
     fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]>;
 
+    /// Returns a copy of the value(s) associated with the specified key in the advice map.
+    ///
+    /// Unlike [AdviceProvider::get_mapped_values()], this method does not require the provider to
+    /// keep the values in memory after they are returned, and thus, it is the method used by the
+    /// processor. The default implementation copies the values returned by
+    /// [AdviceProvider::get_mapped_values()].
+    ///
+    /// # Errors
+    /// Returns an error if the values cannot be read from the backing storage of the provider.
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        Ok(self.get_mapped_values(key).map(|values| values.to_vec()))
+    }
+
     /// Inserts the provided value into the advice map under the specified key.
     ///
     /// The values in the advice map can be moved onto the advice stack by invoking
@@ -590,6 +663,31 @@ pub trait AdviceProvider: Sized {
     /// are replaced with the specified values.
     fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError>;
 
+    /// Returns the keys of the advice map in the range [start_key, end_key).
+    ///
+    /// The keys are returned in ascending order, where keys are compared as 256-bit integers with
+    /// the last element of the key being the most significant. If `start_key` is not smaller than
+    /// `end_key`, no keys are returned. Only the keys are returned so that providers do not need to
+    /// keep the values of the whole range in memory; the values can be read via
+    /// [AdviceProvider::read_mapped_values()].
+    ///
+    /// The advice map is keyed by the byte encoding of the keys, which does not preserve their
+    /// numeric order, so the in-memory providers scan all keys of the map and sort the keys in
+    /// the range.
+    ///
+    /// # Errors
+    /// Returns an error if the keys cannot be read from the backing storage of the provider. The
+    /// default implementation returns [ExecutionError::AdviceMapKeyRangeUnsupported], as listing
+    /// the keys of the advice map is not required by any other advice operation.
+    fn get_map_keys_in_range(
+        &self,
+        start_key: Word,
+        end_key: Word,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        let _ = (start_key, end_key);
+        Err(ExecutionError::AdviceMapKeyRangeUnsupported)
+    }
+
     /// Returns a signature on a message using a public key.
     fn get_signature(
         &self,
@@ -740,6 +838,18 @@ where
         T::get_mapped_values(self, key)
     }
 
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        T::read_mapped_values(self, key)
+    }
+
+    fn get_map_keys_in_range(
+        &self,
+        start_key: Word,
+        end_key: Word,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        T::get_map_keys_in_range(self, start_key, end_key)
+    }
+
     fn get_tree_node(
         &self,
         root: Word,
@@ -798,3 +908,23 @@ where
         T::get_store_subset(self, roots)
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Compares two keys of the advice map as 256-bit integers, with the last element of a key being
+/// the most significant.
+fn cmp_map_keys(lhs: &Word, rhs: &Word) -> Ordering {
+    lhs.iter().rev().map(|e| e.as_int()).cmp(rhs.iter().rev().map(|e| e.as_int()))
+}
+
+/// Returns the advice map key encoded by the specified bytes, as produced by
+/// [IntoBytes::into_bytes()].
+fn map_key_from_bytes(bytes: &[u8; 32]) -> Word {
+    let mut key = [ZERO; 4];
+    for (element, chunk) in key.iter_mut().zip(bytes.chunks_exact(8)) {
+        let value = u64::from_le_bytes(chunk.try_into().expect("chunk must be 8 bytes long"));
+        *element = Felt::new(value);
+    }
+    key
+}
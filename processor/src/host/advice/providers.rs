@@ -1,7 +1,7 @@
 use super::{
-    injectors, AdviceInputs, AdviceProvider, AdviceSource, BTreeMap, ExecutionError, Felt,
-    IntoBytes, KvMap, MerklePath, MerkleStore, NodeIndex, RecordingMap, RpoDigest, StarkField,
-    StoreNode, Vec, Word,
+    cmp_map_keys, injectors, map_key_from_bytes, AdviceInputs, AdviceProvider, AdviceSource,
+    BTreeMap, ExecutionError, Felt, IntoBytes, KvMap, MerklePath, MerkleStore, NodeIndex,
+    RecordingMap, RpoDigest, StarkField, StoreNode, Vec, Word,
 };
 use crate::ProcessState;
 use vm_core::SignatureKind;
@@ -135,6 +135,29 @@ where
         Ok(())
     }
 
+    fn get_map_keys_in_range(
+        &self,
+        start_key: Word,
+        end_key: Word,
+    ) -> Result<Vec<Word>, ExecutionError> {
+        if cmp_map_keys(&start_key, &end_key).is_ge() {
+            return Ok(Vec::new());
+        }
+
+        // the byte encoding of the keys does not preserve their numeric order, so the whole map
+        // is scanned and only the keys in the range are sorted
+        let mut keys = self
+            .map
+            .iter()
+            .map(|(key, _)| map_key_from_bytes(key))
+            .filter(|key| {
+                cmp_map_keys(key, &start_key).is_ge() && cmp_map_keys(key, &end_key).is_lt()
+            })
+            .collect::<Vec<_>>();
+        keys.sort_by(cmp_map_keys);
+        Ok(keys)
+    }
+
     // MERKLE STORE
     // --------------------------------------------------------------------------------------------
 
@@ -307,6 +330,10 @@ impl AdviceProvider for MemAdviceProvider {
         self.provider.get_mapped_values(key)
     }
 
+    fn get_map_keys_in_range(&self, start_key: Word, end_key: Word) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_map_keys_in_range(start_key, end_key)
+    }
+
     fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
         self.provider.get_tree_node(root, depth, index)
     }
@@ -432,6 +459,10 @@ impl AdviceProvider for RecAdviceProvider {
         self.provider.get_mapped_values(key)
     }
 
+    fn get_map_keys_in_range(&self, start_key: Word, end_key: Word) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_map_keys_in_range(start_key, end_key)
+    }
+
     fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
         self.provider.get_tree_node(root, depth, index)
     }
@@ -495,3 +526,1080 @@ impl RecAdviceProvider {
         (proof, stack, map, store.into())
     }
 }
+
+// CAPPED ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] which limits the number of elements a single operation can move from the
+/// advice stack onto the operand stack.
+///
+/// All requests are forwarded to the wrapped provider, but a request to pop more than
+/// `max_elements` elements from the advice stack at once (e.g., the 8 elements read by `adv_pipe`
+/// when the limit is 4) fails with [ExecutionError::AdviceReadLimitExceeded], which records the
+/// cycle at which the request was made. This is useful for pinpointing instructions which consume
+/// more advice than intended.
+#[derive(Debug, Clone)]
+pub struct CappedAdviceProvider<A> {
+    provider: A,
+    max_elements: usize,
+}
+
+impl<A: AdviceProvider> CappedAdviceProvider<A> {
+    /// Returns a new [CappedAdviceProvider] which forwards requests to the specified provider and
+    /// allows at most `max_elements` elements to be popped from the advice stack at once.
+    pub fn new(provider: A, max_elements: usize) -> Self {
+        Self {
+            provider,
+            max_elements,
+        }
+    }
+
+    /// Returns the maximum number of elements which can be popped from the advice stack at once.
+    pub fn max_elements(&self) -> usize {
+        self.max_elements
+    }
+
+    /// Consumes the [CappedAdviceProvider] and returns the wrapped provider.
+    pub fn into_inner(self) -> A {
+        self.provider
+    }
+
+    /// Returns an error if popping the specified number of elements exceeds the limit.
+    fn check_read<P: ProcessState>(
+        &self,
+        process: &P,
+        num_elements: usize,
+    ) -> Result<(), ExecutionError> {
+        if num_elements > self.max_elements {
+            return Err(ExecutionError::AdviceReadLimitExceeded {
+                clk: process.clk(),
+                num_elements,
+                max_elements: self.max_elements,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Pass-through implementations of [AdviceProvider] methods, with the reads from the advice stack
+/// checked against the limit.
+#[rustfmt::skip]
+impl<A: AdviceProvider> AdviceProvider for CappedAdviceProvider<A> {
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.check_read(process, 1)?;
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.check_read(process, 4)?;
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(&mut self, process: &S) -> Result<[Word; 2], ExecutionError> {
+        self.check_read(process, 8)?;
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        self.provider.push_stack(source)
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_signature(&self, kind: SignatureKind, pub_key: Word, msg: Word) -> Result<Vec<Felt>, ExecutionError> {
+        self.provider.get_signature(kind, pub_key, msg)
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        self.provider.get_mapped_values(key)
+    }
+
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        self.provider.read_mapped_values(key)
+    }
+
+    fn get_map_keys_in_range(&self, start_key: Word, end_key: Word) -> Result<Vec<Word>, ExecutionError> {
+        self.provider.get_map_keys_in_range(start_key, end_key)
+    }
+
+    fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(&self, root: Word, depth: &Felt, index: &Felt) -> Result<MerklePath, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn get_leaf_depth(&self, root: Word, tree_depth: &Felt, index: &Felt) -> Result<u8, ExecutionError> {
+        self.provider.get_leaf_depth(root, tree_depth, index)
+    }
+
+    fn find_lone_leaf(&self, root: Word, root_index: NodeIndex, tree_depth: u8) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        self.provider.find_lone_leaf(root, root_index, tree_depth)
+    }
+
+    fn update_merkle_node(&mut self, root: Word, depth: &Felt, index: &Felt, value: Word) -> Result<(MerklePath, Word), ExecutionError> {
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+        where
+            I: Iterator<Item = R>,
+            R: core::borrow::Borrow<RpoDigest> {
+        self.provider.get_store_subset(roots)
+    }
+}
+
+// DISK ADVICE PROVIDER
+// ================================================================================================
+
+/// An `[AdviceProvider]` implementation which keeps the advice map on disk.
+///
+/// Values of the advice map are written to a backing file, and only an index of the file, as well
+/// as a bounded cache of recently used values, is kept in memory. The advice stack and the Merkle
+/// store are kept in memory, same as in [MemAdviceProvider].
+///
+/// To keep an advice map which does not fit into memory out of memory entirely, instantiate the
+/// provider from advice inputs without a map, and stream the entries of the map into the provider
+/// via [DiskAdviceProvider::extend_map()].
+///
+/// The processor reads the advice map only via [AdviceProvider::push_stack()] and
+/// [AdviceProvider::read_mapped_values()], which keep at most `cache_capacity` values in memory.
+/// The values returned by [AdviceProvider::get_mapped_values()] are borrowed from the provider,
+/// and thus, are kept in memory until the next mutable access to the provider.
+///
+/// This provider trades execution speed for memory and is intended for programs which require
+/// advice maps too large to fit in memory. Other than that, it behaves identically to
+/// [MemAdviceProvider].
+#[cfg(feature = "disk-advice")]
+pub struct DiskAdviceProvider {
+    provider: BaseAdviceProvider<SimpleAdviceMap, SimpleMerkleMap>,
+    map: DiskAdviceMap,
+}
+
+#[cfg(feature = "disk-advice")]
+impl DiskAdviceProvider {
+    /// Returns a new [DiskAdviceProvider] instantiated from the specified inputs, backing the
+    /// advice map with a file in the temporary directory. The file is removed when the provider
+    /// is dropped.
+    ///
+    /// At most `cache_capacity` advice map values (but at least one) are kept in memory at any
+    /// given time.
+    pub fn new(inputs: AdviceInputs, cache_capacity: usize) -> std::io::Result<Self> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let file_name = format!(
+            "miden-advice-{}-{}.bin",
+            std::process::id(),
+            FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        Self::with_path(inputs, std::env::temp_dir().join(file_name), cache_capacity)
+    }
+
+    /// Returns a new [DiskAdviceProvider] instantiated from the specified inputs, backing the
+    /// advice map with a file at the specified path. If the file exists, it is truncated. The
+    /// file is removed when the provider is dropped.
+    ///
+    /// At most `cache_capacity` advice map values (but at least one) are kept in memory at any
+    /// given time.
+    pub fn with_path(
+        inputs: AdviceInputs,
+        path: std::path::PathBuf,
+        cache_capacity: usize,
+    ) -> std::io::Result<Self> {
+        let (stack, map, store) = inputs.into_parts();
+        let provider = AdviceInputs::default().with_stack(stack).with_merkle_store(store).into();
+        let mut disk_provider = Self {
+            provider,
+            map: DiskAdviceMap::new(path, cache_capacity)?,
+        };
+        disk_provider.extend_map(map)?;
+        Ok(disk_provider)
+    }
+
+    /// Inserts the specified entries into the advice map, writing them to the backing file one
+    /// at a time. Values under keys which are already present in the map are replaced.
+    ///
+    /// Since the entries are consumed one by one, they can be streamed from a source which does
+    /// not fit into memory (e.g., read from a file entry by entry).
+    pub fn extend_map<I>(&mut self, entries: I) -> std::io::Result<()>
+    where
+        I: IntoIterator<Item = ([u8; 32], Vec<Felt>)>,
+    {
+        for (key, values) in entries {
+            self.map.insert(key, values)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the number of advice map values currently kept in memory.
+    pub fn num_resident_values(&self) -> usize {
+        self.map.cache.borrow().len() + self.map.pinned.len()
+    }
+}
+
+/// Pass-through implementations of [AdviceProvider] methods which do not access the advice map.
+#[cfg(feature = "disk-advice")]
+#[rustfmt::skip]
+impl AdviceProvider for DiskAdviceProvider {
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(&mut self, process: &S) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        match source {
+            AdviceSource::Map { key, include_len } => {
+                let values = self
+                    .map
+                    .load(&key.into_bytes())
+                    .map_err(storage_error)?
+                    .ok_or(ExecutionError::AdviceMapKeyNotFound(key))?;
+
+                self.provider.stack.extend(values.iter().rev());
+                if include_len {
+                    self.provider.stack.push(Felt::from(values.len() as u64));
+                }
+                Ok(())
+            }
+            source => self.provider.push_stack(source),
+        }
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.map.insert(key.into_bytes(), values).map_err(storage_error)
+    }
+
+    fn get_signature(&self, kind: SignatureKind, pub_key: Word, msg: Word) -> Result<Vec<Felt>, ExecutionError> {
+        let pk_sk = self
+            .map
+            .read(&pub_key.into_bytes())
+            .map_err(storage_error)?
+            .ok_or(ExecutionError::AdviceMapKeyNotFound(pub_key))?;
+
+        match kind {
+            SignatureKind::RpoFalcon512 => injectors::dsa::falcon_sign(&pk_sk, msg),
+        }
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        self.map.get(key)
+    }
+
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        self.map.read(key).map_err(storage_error)
+    }
+
+    fn get_map_keys_in_range(&self, start_key: Word, end_key: Word) -> Result<Vec<Word>, ExecutionError> {
+        let mut keys = self
+            .map
+            .index
+            .keys()
+            .map(map_key_from_bytes)
+            .filter(|key| cmp_map_keys(key, &start_key).is_ge() && cmp_map_keys(key, &end_key).is_lt())
+            .collect::<Vec<_>>();
+        keys.sort_by(cmp_map_keys);
+        Ok(keys)
+    }
+
+    fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(&self, root: Word, depth: &Felt, index: &Felt) -> Result<MerklePath, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn get_leaf_depth(&self, root: Word, tree_depth: &Felt, index: &Felt) -> Result<u8, ExecutionError> {
+        self.provider.get_leaf_depth(root, tree_depth, index)
+    }
+
+    fn find_lone_leaf(&self, root: Word, root_index: NodeIndex, tree_depth: u8) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        self.provider.find_lone_leaf(root, root_index, tree_depth)
+    }
+
+    fn update_merkle_node(&mut self, root: Word, depth: &Felt, index: &Felt, value: Word) -> Result<(MerklePath, Word), ExecutionError> {
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+        where
+            I: Iterator<Item = R>,
+            R: core::borrow::Borrow<RpoDigest> {
+        self.provider.get_store_subset(roots)
+    }
+}
+
+/// Converts an I/O error of the backing file of the advice map into an execution error.
+#[cfg(feature = "disk-advice")]
+fn storage_error(err: std::io::Error) -> ExecutionError {
+    ExecutionError::AdviceMapStorageFailed(err.to_string())
+}
+
+/// Cache of advice map values, each stored together with the time of its last use.
+#[cfg(feature = "disk-advice")]
+type ValueCache = BTreeMap<[u8; 32], (u64, Vec<Felt>)>;
+
+/// Advice map which keeps its values in a backing file.
+///
+/// Values are appended to the file as sequences of little-endian u64s; the in-memory index maps
+/// each key to the location of its latest value in the file. Recently used values are kept in a
+/// cache bounded by `capacity` and evicted in least-recently-used order.
+///
+/// References to values returned via a shared reference to the map cannot point into the cache,
+/// since the cache may evict them while they are still borrowed. Such values are pinned instead,
+/// and dropped on the next mutable access to the map.
+#[cfg(feature = "disk-advice")]
+struct DiskAdviceMap {
+    path: std::path::PathBuf,
+    file: core::cell::RefCell<std::fs::File>,
+    file_len: u64,
+    index: BTreeMap<[u8; 32], (u64, usize)>,
+    cache: core::cell::RefCell<ValueCache>,
+    pinned: elsa::FrozenVec<Box<([u8; 32], Vec<Felt>)>>,
+    capacity: usize,
+    clock: core::cell::Cell<u64>,
+}
+
+#[cfg(feature = "disk-advice")]
+impl DiskAdviceMap {
+    /// Creates a new empty map backed by a file at the specified path.
+    fn new(path: std::path::PathBuf, capacity: usize) -> std::io::Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+
+        Ok(Self {
+            path,
+            file: core::cell::RefCell::new(file),
+            file_len: 0,
+            index: BTreeMap::new(),
+            cache: core::cell::RefCell::new(BTreeMap::new()),
+            pinned: elsa::FrozenVec::new(),
+            capacity: capacity.max(1),
+            clock: core::cell::Cell::new(0),
+        })
+    }
+
+    /// Returns the values stored under the specified key, pinning them until the next mutable
+    /// access to the map.
+    ///
+    /// Returns None if the values cannot be read from the backing file; use [Self::read()] to
+    /// tell such failures apart from missing keys.
+    fn get(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        if let Some(entry) = self.pinned.iter().find(|(pinned_key, _)| pinned_key == key) {
+            return Some(&entry.1);
+        }
+
+        let values = self.read(key).ok()??;
+        Some(&self.pinned.push_get(Box::new((*key, values))).1)
+    }
+
+    /// Returns a copy of the values stored under the specified key, moving them into the cache.
+    fn read(&self, key: &[u8; 32]) -> std::io::Result<Option<Vec<Felt>>> {
+        let mut cache = self.cache.borrow_mut();
+        if let Some(entry) = cache.get_mut(key) {
+            entry.0 = self.tick();
+            return Ok(Some(entry.1.clone()));
+        }
+
+        let Some(values) = self.read_from_file(key)? else {
+            return Ok(None);
+        };
+        cache.insert(*key, (self.tick(), values.clone()));
+        evict(&mut cache, self.capacity);
+        Ok(Some(values))
+    }
+
+    /// Returns the values stored under the specified key, moving them into the cache.
+    fn load(&mut self, key: &[u8; 32]) -> std::io::Result<Option<&[Felt]>> {
+        self.release_pinned();
+        let clock = self.tick();
+        let cache = self.cache.get_mut();
+        if let Some(entry) = cache.get_mut(key) {
+            entry.0 = clock;
+        } else {
+            let values = match self.index.get(key) {
+                Some(&location) => read_values(self.file.get_mut(), location)?,
+                None => return Ok(None),
+            };
+            cache.insert(*key, (clock, values));
+            // the loaded values are the most recently used ones, and thus, are not evicted
+            evict(cache, self.capacity);
+        }
+
+        Ok(cache.get(key).map(|(_, values)| values.as_slice()))
+    }
+
+    /// Inserts the values under the specified key, appending them to the backing file.
+    fn insert(&mut self, key: [u8; 32], values: Vec<Felt>) -> std::io::Result<()> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        self.release_pinned();
+        let bytes = values.iter().flat_map(|v| v.as_int().to_le_bytes()).collect::<Vec<u8>>();
+        let file = self.file.get_mut();
+        file.seek(SeekFrom::Start(self.file_len))?;
+        file.write_all(&bytes)?;
+
+        self.index.insert(key, (self.file_len, values.len()));
+        self.file_len += bytes.len() as u64;
+
+        let clock = self.tick();
+        let cache = self.cache.get_mut();
+        cache.insert(key, (clock, values));
+        evict(cache, self.capacity);
+        Ok(())
+    }
+
+    /// Reads the values stored under the specified key from the backing file.
+    fn read_from_file(&self, key: &[u8; 32]) -> std::io::Result<Option<Vec<Felt>>> {
+        match self.index.get(key) {
+            Some(&location) => read_values(&mut self.file.borrow_mut(), location).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Drops all pinned values.
+    fn release_pinned(&mut self) {
+        self.pinned.as_mut().clear();
+    }
+
+    /// Advances the clock used to order the values in the cache by the time of their last use,
+    /// and returns the new time.
+    fn tick(&self) -> u64 {
+        let clock = self.clock.get() + 1;
+        self.clock.set(clock);
+        clock
+    }
+}
+
+#[cfg(feature = "disk-advice")]
+impl Drop for DiskAdviceMap {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Reads `len` values starting at the specified offset of the backing file of an advice map.
+#[cfg(feature = "disk-advice")]
+fn read_values(
+    file: &mut std::fs::File,
+    (offset, len): (u64, usize),
+) -> std::io::Result<Vec<Felt>> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut bytes = vec![0u8; len * 8];
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(&mut bytes)?;
+
+    let values = bytes
+        .chunks_exact(8)
+        .map(|chunk| Felt::new(u64::from_le_bytes(chunk.try_into().expect("invalid chunk"))))
+        .collect();
+    Ok(values)
+}
+
+/// Evicts the least recently used values from the cache until it fits into its capacity.
+#[cfg(feature = "disk-advice")]
+fn evict(cache: &mut ValueCache, capacity: usize) {
+    while cache.len() > capacity {
+        let lru_key = cache
+            .iter()
+            .min_by_key(|(_, (last_used, _))| *last_used)
+            .map(|(key, _)| *key)
+            .expect("cache is not empty");
+        cache.remove(&lru_key);
+    }
+}
+
+// REMOTE ADVICE PROVIDER
+// ================================================================================================
+
+/// Placeholder in the URL pattern of a [RemoteAdviceProvider] which is replaced by the key of the
+/// requested advice map entry.
+#[cfg(feature = "remote-advice")]
+pub const REMOTE_ADVICE_KEY_PLACEHOLDER: &str = "{key_hex}";
+
+/// An `[AdviceProvider]` implementation which fetches advice map entries missing from its inputs
+/// over HTTP.
+///
+/// An entry is fetched from the URL obtained by replacing `{key_hex}` in the URL pattern with the
+/// 64-character hex encoding of the key (the same encoding as the keys of the advice map in input
+/// files), e.g., `http://localhost:8080/advice/{key_hex}`. The response body must be a JSON array
+/// of the values of the entry as u64s; a `404 Not Found` response means that the key is not
+/// present in the map.
+///
+/// Entries are fetched lazily, only when the processor reads them, and every fetched entry is
+/// cached for the lifetime of the provider. Requests which fail due to a connection error, a
+/// `429 Too Many Requests` response, or a server error are retried with exponential backoff.
+///
+/// Entries present in the inputs, or inserted into the map during execution, take precedence over
+/// the remote ones. Since the keys of the remote map cannot be listed, operations which read a
+/// range of the advice map are not supported. The advice stack and the Merkle store are kept in
+/// memory, same as in [MemAdviceProvider].
+#[cfg(feature = "remote-advice")]
+pub struct RemoteAdviceProvider {
+    provider: BaseAdviceProvider<SimpleAdviceMap, SimpleMerkleMap>,
+    client: reqwest::blocking::Client,
+    url_pattern: String,
+    max_retries: u32,
+    initial_backoff: std::time::Duration,
+    cache: elsa::FrozenBTreeMap<[u8; 32], Vec<Felt>>,
+}
+
+#[cfg(feature = "remote-advice")]
+impl RemoteAdviceProvider {
+    /// Number of times a failed request is retried by default.
+    pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+    /// Time to wait before the first retry of a failed request by default; the time doubles with
+    /// every subsequent retry.
+    pub const DEFAULT_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+    /// Returns a new [RemoteAdviceProvider] instantiated from the specified inputs, which fetches
+    /// the advice map entries missing from the inputs from URLs built from the specified pattern.
+    ///
+    /// # Errors
+    /// Returns an error if the URL pattern does not start with `http://` or `https://`, if it
+    /// does not contain `{key_hex}`, or if the HTTP client cannot be initialized.
+    pub fn new(inputs: AdviceInputs, url_pattern: &str) -> Result<Self, ExecutionError> {
+        if !url_pattern.starts_with("http://") && !url_pattern.starts_with("https://") {
+            return Err(ExecutionError::AdviceMapFetchFailed(format!(
+                "URL pattern `{url_pattern}` must start with http:// or https://"
+            )));
+        }
+        if !url_pattern.contains(REMOTE_ADVICE_KEY_PLACEHOLDER) {
+            return Err(ExecutionError::AdviceMapFetchFailed(format!(
+                "URL pattern `{url_pattern}` does not contain {REMOTE_ADVICE_KEY_PLACEHOLDER}"
+            )));
+        }
+
+        let client = reqwest::blocking::Client::builder()
+            .build()
+            .map_err(|err| ExecutionError::AdviceMapFetchFailed(err.to_string()))?;
+
+        Ok(Self {
+            provider: inputs.into(),
+            client,
+            url_pattern: url_pattern.to_string(),
+            max_retries: Self::DEFAULT_MAX_RETRIES,
+            initial_backoff: Self::DEFAULT_INITIAL_BACKOFF,
+            cache: elsa::FrozenBTreeMap::new(),
+        })
+    }
+
+    /// Returns this provider with failed requests retried up to `max_retries` times, waiting
+    /// `initial_backoff` before the first retry and doubling the wait time with every retry.
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff: std::time::Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Returns the number of advice map entries fetched so far.
+    pub fn num_fetched_entries(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns the values stored under the specified key, fetching them if they are neither in
+    /// the local map nor in the cache.
+    fn load(&self, key: &[u8; 32]) -> Result<Option<&[Felt]>, ExecutionError> {
+        if let Some(values) = self.provider.get_mapped_values(key) {
+            return Ok(Some(values));
+        }
+        if let Some(values) = self.cache.get(key) {
+            return Ok(Some(values));
+        }
+
+        Ok(self.fetch(key)?.map(|values| self.cache.insert(*key, values)))
+    }
+
+    /// Fetches the values stored under the specified key from the remote map, retrying failed
+    /// requests with exponential backoff.
+    fn fetch(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        use reqwest::StatusCode;
+
+        let key_hex = key.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+        let url = self.url_pattern.replace(REMOTE_ADVICE_KEY_PLACEHOLDER, &key_hex);
+
+        let mut backoff = self.initial_backoff;
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                std::thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+            }
+
+            let response = match self.client.get(&url).send() {
+                Ok(response) => response,
+                Err(err) if attempt < self.max_retries => {
+                    log::debug!("request to {url} failed, retrying: {err}");
+                    continue;
+                }
+                Err(err) => return Err(fetch_error(&url, err)),
+            };
+
+            let status = response.status();
+            if status == StatusCode::NOT_FOUND {
+                return Ok(None);
+            }
+            let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            if retryable && attempt < self.max_retries {
+                log::debug!("request to {url} returned {status}, retrying");
+                continue;
+            }
+            if !status.is_success() {
+                return Err(fetch_error(&url, format!("server returned {status}")));
+            }
+
+            let body = response.text().map_err(|err| fetch_error(&url, err))?;
+            return parse_remote_values(&body).map(Some).map_err(|err| fetch_error(&url, err));
+        }
+        unreachable!("the last attempt always returns")
+    }
+}
+
+/// Pass-through implementations of [AdviceProvider] methods which do not access the advice map.
+#[cfg(feature = "remote-advice")]
+#[rustfmt::skip]
+impl AdviceProvider for RemoteAdviceProvider {
+    fn pop_stack<S: ProcessState>(&mut self, process: &S) -> Result<Felt, ExecutionError> {
+        self.provider.pop_stack(process)
+    }
+
+    fn pop_stack_word<S: ProcessState>(&mut self, process: &S) -> Result<Word, ExecutionError> {
+        self.provider.pop_stack_word(process)
+    }
+
+    fn pop_stack_dword<S: ProcessState>(&mut self, process: &S) -> Result<[Word; 2], ExecutionError> {
+        self.provider.pop_stack_dword(process)
+    }
+
+    fn push_stack(&mut self, source: AdviceSource) -> Result<(), ExecutionError> {
+        match source {
+            AdviceSource::Map { key, include_len } => {
+                let values = self
+                    .load(&key.into_bytes())?
+                    .ok_or(ExecutionError::AdviceMapKeyNotFound(key))?
+                    .to_vec();
+
+                self.provider.stack.extend(values.iter().rev());
+                if include_len {
+                    self.provider.stack.push(Felt::from(values.len() as u64));
+                }
+                Ok(())
+            }
+            source => self.provider.push_stack(source),
+        }
+    }
+
+    fn insert_into_map(&mut self, key: Word, values: Vec<Felt>) -> Result<(), ExecutionError> {
+        self.provider.insert_into_map(key, values)
+    }
+
+    fn get_signature(&self, kind: SignatureKind, pub_key: Word, msg: Word) -> Result<Vec<Felt>, ExecutionError> {
+        let pk_sk = self
+            .load(&pub_key.into_bytes())?
+            .ok_or(ExecutionError::AdviceMapKeyNotFound(pub_key))?;
+
+        match kind {
+            SignatureKind::RpoFalcon512 => injectors::dsa::falcon_sign(pk_sk, msg),
+        }
+    }
+
+    fn get_mapped_values(&self, key: &[u8; 32]) -> Option<&[Felt]> {
+        self.load(key).ok().flatten()
+    }
+
+    fn read_mapped_values(&self, key: &[u8; 32]) -> Result<Option<Vec<Felt>>, ExecutionError> {
+        Ok(self.load(key)?.map(|values| values.to_vec()))
+    }
+
+    fn get_tree_node(&self, root: Word, depth: &Felt, index: &Felt) -> Result<Word, ExecutionError> {
+        self.provider.get_tree_node(root, depth, index)
+    }
+
+    fn get_merkle_path(&self, root: Word, depth: &Felt, index: &Felt) -> Result<MerklePath, ExecutionError> {
+        self.provider.get_merkle_path(root, depth, index)
+    }
+
+    fn get_leaf_depth(&self, root: Word, tree_depth: &Felt, index: &Felt) -> Result<u8, ExecutionError> {
+        self.provider.get_leaf_depth(root, tree_depth, index)
+    }
+
+    fn find_lone_leaf(&self, root: Word, root_index: NodeIndex, tree_depth: u8) -> Result<Option<(NodeIndex, Word)>, ExecutionError> {
+        self.provider.find_lone_leaf(root, root_index, tree_depth)
+    }
+
+    fn update_merkle_node(&mut self, root: Word, depth: &Felt, index: &Felt, value: Word) -> Result<(MerklePath, Word), ExecutionError> {
+        self.provider.update_merkle_node(root, depth, index, value)
+    }
+
+    fn merge_roots(&mut self, lhs: Word, rhs: Word) -> Result<Word, ExecutionError> {
+        self.provider.merge_roots(lhs, rhs)
+    }
+
+    fn get_store_subset<I, R>(&self, roots: I) -> MerkleStore
+        where
+            I: Iterator<Item = R>,
+            R: core::borrow::Borrow<RpoDigest> {
+        self.provider.get_store_subset(roots)
+    }
+}
+
+/// Converts a failure to fetch an advice map entry from the specified URL into an execution error.
+#[cfg(feature = "remote-advice")]
+fn fetch_error(url: &str, err: impl core::fmt::Display) -> ExecutionError {
+    ExecutionError::AdviceMapFetchFailed(format!("{url}: {err}"))
+}
+
+/// Parses the values of a remote advice map entry from a JSON array of u64s.
+#[cfg(feature = "remote-advice")]
+fn parse_remote_values(body: &str) -> Result<Vec<Felt>, String> {
+    let values: Vec<u64> =
+        serde_json::from_str(body).map_err(|err| format!("invalid JSON array of u64s: {err}"))?;
+    values
+        .into_iter()
+        .map(|value| {
+            if value < Felt::MODULUS {
+                Ok(Felt::new(value))
+            } else {
+                Err(format!("value {value} is not a valid field element"))
+            }
+        })
+        .collect()
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "disk-advice")]
+    use super::DiskAdviceProvider;
+    #[cfg(feature = "remote-advice")]
+    use super::RemoteAdviceProvider;
+    use super::{
+        AdviceInputs, AdviceProvider, AdviceSource, CappedAdviceProvider, Felt, IntoBytes,
+        MemAdviceProvider, RecAdviceProvider,
+    };
+    use crate::{DefaultHost, ExecutionError, ExecutionOptions, StackInputs, ZERO};
+    use miden_assembly::Assembler;
+    #[cfg(feature = "remote-advice")]
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn capped_advice_provider_rejects_oversized_reads() {
+        let source = "begin adv_push.2 adv_loadw adv_pipe end";
+        let program = Assembler::default().compile(source).unwrap();
+        let inputs = AdviceInputs::default().with_stack_values(1..=14).unwrap();
+
+        let execute = |max_elements| {
+            let provider =
+                CappedAdviceProvider::new(MemAdviceProvider::from(inputs.clone()), max_elements);
+            crate::execute(
+                &program,
+                StackInputs::default(),
+                DefaultHost::new(provider),
+                ExecutionOptions::default(),
+            )
+        };
+
+        // a limit of 8 elements allows every read of the program
+        assert!(execute(8).is_ok());
+
+        // adv_pipe reads 8 elements at once; it is executed at cycle 4, after the SPAN operation
+        // and the three operations of adv_push.2 and adv_loadw
+        let err = execute(4).err().unwrap();
+        assert!(
+            matches!(
+                err,
+                ExecutionError::AdviceReadLimitExceeded {
+                    clk: 4,
+                    num_elements: 8,
+                    max_elements: 4
+                }
+            ),
+            "{err}"
+        );
+
+        // adv_loadw reads a word at once
+        assert!(matches!(
+            execute(3),
+            Err(ExecutionError::AdviceReadLimitExceeded {
+                num_elements: 4,
+                max_elements: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn map_keys_in_range_are_ordered_numerically() {
+        let key = |value: u64| [Felt::new(value), ZERO, ZERO, ZERO];
+        let entry = |value: u64| (key(value).into_bytes(), vec![Felt::new(value)]);
+
+        // the byte encoding of 256 precedes the byte encoding of 1, but the keys are expected to
+        // be ordered by their numeric values
+        let inputs = AdviceInputs::default().with_map([entry(256), entry(1), entry(7), entry(20)]);
+        let mut provider = RecAdviceProvider::from(inputs);
+
+        let keys = provider.get_map_keys_in_range(key(1), key(256)).unwrap();
+        assert_eq!(keys, [key(1), key(7), key(20)]);
+        assert!(provider.get_map_keys_in_range(key(20), key(7)).unwrap().is_empty());
+
+        // listing the keys does not read the values; only the values pushed onto the advice stack
+        // are recorded as read
+        provider
+            .push_stack(AdviceSource::Map {
+                key: key(7),
+                include_len: false,
+            })
+            .unwrap();
+        let (inputs, ..) = provider.finalize();
+        assert!(inputs.mapped_values(&key(7).into_bytes()).is_some());
+        assert!(inputs.mapped_values(&key(1).into_bytes()).is_none());
+        assert!(inputs.mapped_values(&key(256).into_bytes()).is_none());
+    }
+
+    #[cfg(feature = "disk-advice")]
+    #[test]
+    fn disk_advice_provider_matches_mem_provider() {
+        let source = "begin
+            push.1.2.3.4 adv.push_mapval dropw adv_push.2
+            push.5.6.7.8 adv.push_mapval dropw adv_push.3
+            push.1.2.3.4 adv.push_mapval dropw adv_push.2
+
+            push.9.10.11.12 mem_storew.100 dropw
+            push.101.100.13.14.15.16 adv.insert_mem dropw drop drop
+            push.13.14.15.16 adv.push_mapval dropw adv_push.4
+        end";
+        let program = Assembler::default().compile(source).unwrap();
+
+        let inputs = AdviceInputs::default().with_map([
+            (key_to_bytes([1, 2, 3, 4]), vec![Felt::new(10), Felt::new(11)]),
+            (key_to_bytes([5, 6, 7, 8]), vec![Felt::new(20), Felt::new(21), Felt::new(22)]),
+        ]);
+
+        let mem_host = DefaultHost::new(MemAdviceProvider::from(inputs.clone()));
+        let expected =
+            crate::execute(&program, StackInputs::default(), mem_host, ExecutionOptions::default())
+                .unwrap();
+
+        // a cache capacity of 1 forces values to be evicted and re-read from disk; the entries of
+        // the map are streamed into the provider together with a large number of unused ones
+        let unused_entries =
+            (100..10_000).map(|i| (key_to_bytes([i, 0, 0, 0]), vec![Felt::new(i)]));
+        let mut disk_provider = DiskAdviceProvider::new(AdviceInputs::default(), 1).unwrap();
+        disk_provider.extend_map(unused_entries.chain(inputs.into_parts().1)).unwrap();
+        assert_eq!(disk_provider.num_resident_values(), 1);
+
+        let mut disk_host = DefaultHost::new(disk_provider);
+        let actual = crate::execute(
+            &program,
+            StackInputs::default(),
+            &mut disk_host,
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(expected.stack_outputs(), actual.stack_outputs());
+        assert_eq!(disk_host.into_inner().num_resident_values(), 1);
+    }
+
+    #[cfg(feature = "disk-advice")]
+    #[test]
+    fn disk_advice_provider_reports_storage_errors() {
+        let path = std::env::temp_dir()
+            .join(format!("miden-advice-storage-error-{}.bin", std::process::id()));
+        let inputs = AdviceInputs::default().with_map([
+            (key_to_bytes([1, 2, 3, 4]), vec![Felt::new(10), Felt::new(11)]),
+            (key_to_bytes([5, 6, 7, 8]), vec![Felt::new(20)]),
+        ]);
+        let provider = DiskAdviceProvider::with_path(inputs, path.clone(), 1).unwrap();
+
+        // the first value is no longer cached, and cannot be read from the truncated file
+        std::fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(0).unwrap();
+        let program =
+            Assembler::default().compile("begin push.1.2.3.4 adv.push_mapval end").unwrap();
+        let err = crate::execute(
+            &program,
+            StackInputs::default(),
+            DefaultHost::new(provider),
+            ExecutionOptions::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(err, ExecutionError::AdviceMapStorageFailed(_)), "{err}");
+    }
+
+    #[cfg(feature = "remote-advice")]
+    #[test]
+    fn remote_advice_provider_fetches_and_caches_entries() {
+        let source = "begin
+            push.1.2.3.4 adv.push_mapval dropw adv_push.3
+            push.1.2.3.4 adv.push_mapval dropw adv_push.3
+            push.5.6.7.8 adv.push_mapval dropw adv_push.1
+        end";
+        let program = Assembler::default().compile(source).unwrap();
+
+        // the entry under [1, 2, 3, 4] is served remotely, while [5, 6, 7, 8] is a local input
+        let remote_key = hex_key([1, 2, 3, 4]);
+        let (url, num_requests) = serve(move |path, _| {
+            if path == format!("/advice/{remote_key}") {
+                (200, "[10, 11, 12]".to_string())
+            } else {
+                (404, String::new())
+            }
+        });
+        let inputs =
+            AdviceInputs::default().with_map([(key_to_bytes([5, 6, 7, 8]), vec![Felt::new(20)])]);
+        let provider = RemoteAdviceProvider::new(inputs, &format!("{url}/advice/{{key_hex}}"));
+        let mut host = DefaultHost::new(provider.unwrap());
+
+        let outputs = crate::execute(
+            &program,
+            StackInputs::default(),
+            &mut host,
+            ExecutionOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(outputs.stack_outputs().stack()[..7], [20, 12, 11, 10, 12, 11, 10]);
+
+        // the remote entry was fetched once, and then served from the cache
+        assert_eq!(num_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(host.into_inner().num_fetched_entries(), 1);
+
+        // keys missing from the remote map are reported as such
+        let program =
+            Assembler::default().compile("begin push.9.9.9.9 adv.push_mapval end").unwrap();
+        let provider = RemoteAdviceProvider::new(
+            AdviceInputs::default(),
+            &format!("{url}/advice/{{key_hex}}"),
+        );
+        let err = crate::execute(
+            &program,
+            StackInputs::default(),
+            DefaultHost::new(provider.unwrap()),
+            ExecutionOptions::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(matches!(err, ExecutionError::AdviceMapKeyNotFound(_)), "{err}");
+
+        // URL patterns without the key placeholder or with a scheme other than HTTP(S) are
+        // rejected, while HTTPS URLs are accepted
+        assert!(RemoteAdviceProvider::new(AdviceInputs::default(), &url).is_err());
+        let pattern = "ftp://example.com/{key_hex}";
+        assert!(RemoteAdviceProvider::new(AdviceInputs::default(), pattern).is_err());
+        let pattern = "https://example.com/{key_hex}";
+        assert!(RemoteAdviceProvider::new(AdviceInputs::default(), pattern).is_ok());
+    }
+
+    #[cfg(feature = "remote-advice")]
+    #[test]
+    fn remote_advice_provider_retries_failed_requests() {
+        // the first two requests for every entry fail
+        let (url, num_requests) = serve(|_, request| match request {
+            0 | 1 => (503, String::new()),
+            _ => (200, "[7]".to_string()),
+        });
+        let pattern = format!("{url}/{{key_hex}}");
+        let key = key_to_bytes([1, 2, 3, 4]);
+        let backoff = std::time::Duration::from_millis(1);
+
+        let provider = RemoteAdviceProvider::new(AdviceInputs::default(), &pattern).unwrap();
+        let provider = provider.with_retries(2, backoff);
+        assert_eq!(provider.load(&key).unwrap(), Some(&[Felt::new(7)][..]));
+        assert_eq!(num_requests.load(Ordering::SeqCst), 3);
+
+        // once the retries are exhausted, the last failure is reported
+        let (url, _) = serve(|_, _| (503, String::new()));
+        let pattern = format!("{url}/{{key_hex}}");
+        let provider = RemoteAdviceProvider::new(AdviceInputs::default(), &pattern).unwrap();
+        let err = provider.with_retries(1, backoff).load(&key).unwrap_err();
+        assert!(matches!(err, ExecutionError::AdviceMapFetchFailed(_)), "{err}");
+        assert!(err.to_string().contains("503"), "{err}");
+
+        // values which are not field elements are rejected
+        let (url, _) = serve(|_, _| (200, format!("[{}]", u64::MAX)));
+        let pattern = format!("{url}/{{key_hex}}");
+        let provider = RemoteAdviceProvider::new(AdviceInputs::default(), &pattern).unwrap();
+        let err = provider.load(&key).unwrap_err();
+        assert!(err.to_string().contains("not a valid field element"), "{err}");
+    }
+
+    /// Serves HTTP requests on a local port from a background thread, responding with the status
+    /// and the body returned by the handler for the path and the sequence number of each request.
+    /// Returns the base URL of the server and the number of requests served so far.
+    #[cfg(feature = "remote-advice")]
+    fn serve<F>(handler: F) -> (String, Arc<AtomicUsize>)
+    where
+        F: Fn(&str, usize) -> (u16, String) + Send + 'static,
+    {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let num_requests = Arc::new(AtomicUsize::new(0));
+        let counter = num_requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = handler(path, counter.fetch_add(1, Ordering::SeqCst));
+                let response = format!(
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, num_requests)
+    }
+
+    #[cfg(feature = "remote-advice")]
+    fn hex_key(key: [u64; 4]) -> String {
+        key_to_bytes(key).iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    #[cfg(any(feature = "disk-advice", feature = "remote-advice"))]
+    fn key_to_bytes(key: [u64; 4]) -> [u8; 32] {
+        let mut result = [0; 32];
+        for (chunk, value) in result.chunks_mut(8).zip(key) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+        result
+    }
+}
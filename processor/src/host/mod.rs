@@ -1,12 +1,18 @@
 use super::{ExecutionError, Felt, ProcessState};
 use crate::MemAdviceProvider;
-use vm_core::{crypto::merkle::MerklePath, AdviceInjector, DebugOptions, Word};
+use vm_core::{crypto::merkle::MerklePath, AdviceInjector, DebugOptions, Operation, Word};
 
 pub(super) mod advice;
 use advice::{AdviceExtractor, AdviceProvider};
 
 mod debug;
 
+// CONSTANTS
+// ================================================================================================
+
+/// Minimum number of clock cycles between two consecutive invocations of [Host::on_heartbeat].
+pub const HEARTBEAT_INTERVAL: u32 = 1 << 12;
+
 // HOST TRAIT
 // ================================================================================================
 
@@ -64,6 +70,49 @@ pub trait Host {
         Ok(HostResponse::None)
     }
 
+    /// Handles the trace event with the specified ID emitted by the VM. By default, trace events
+    /// are ignored.
+    fn on_trace<S: ProcessState>(
+        &mut self,
+        process: &S,
+        trace_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        let _ = (process, trace_id);
+        Ok(HostResponse::None)
+    }
+
+    /// Invoked by the VM once at least [HEARTBEAT_INTERVAL] clock cycles have passed since the
+    /// previous invocation, which allows the host to abort long-running executions, e.g., once a
+    /// wall-clock time limit is exceeded, by returning an error. By default, the execution is
+    /// never aborted.
+    ///
+    /// The VM invokes this method only if heartbeats are enabled via
+    /// [ExecutionOptions::with_heartbeat](crate::ExecutionOptions::with_heartbeat).
+    fn on_heartbeat<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        let _ = process;
+        Ok(HostResponse::None)
+    }
+
+    /// Invoked by the VM after every clock cycle with the operation executed in it, which allows
+    /// the host to trace the execution. The process is in the state right after the operation was
+    /// executed; `instruction` is the assembly instruction the operation belongs to, and is
+    /// available only if the program was compiled in debug mode. By default, steps are ignored.
+    ///
+    /// The VM invokes this method only if steps are enabled via
+    /// [ExecutionOptions::with_step_hook](crate::ExecutionOptions::with_step_hook).
+    fn on_step<S: ProcessState>(
+        &mut self,
+        process: &S,
+        op: Operation,
+        instruction: Option<&str>,
+    ) -> Result<HostResponse, ExecutionError> {
+        let _ = (process, op, instruction);
+        Ok(HostResponse::None)
+    }
+
     /// Pops an element from the advice stack and returns it.
     ///
     /// # Errors
@@ -138,6 +187,38 @@ where
     ) -> Result<HostResponse, ExecutionError> {
         H::set_advice(self, process, injector)
     }
+
+    fn on_debug<S: ProcessState>(
+        &mut self,
+        process: &S,
+        options: &DebugOptions,
+    ) -> Result<HostResponse, ExecutionError> {
+        H::on_debug(self, process, options)
+    }
+
+    fn on_trace<S: ProcessState>(
+        &mut self,
+        process: &S,
+        trace_id: u32,
+    ) -> Result<HostResponse, ExecutionError> {
+        H::on_trace(self, process, trace_id)
+    }
+
+    fn on_heartbeat<S: ProcessState>(
+        &mut self,
+        process: &S,
+    ) -> Result<HostResponse, ExecutionError> {
+        H::on_heartbeat(self, process)
+    }
+
+    fn on_step<S: ProcessState>(
+        &mut self,
+        process: &S,
+        op: Operation,
+        instruction: Option<&str>,
+    ) -> Result<HostResponse, ExecutionError> {
+        H::on_step(self, process, op, instruction)
+    }
 }
 
 // HOST RESPONSE
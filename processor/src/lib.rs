@@ -12,15 +12,19 @@ use miden_air::trace::{
 };
 pub use miden_air::{ExecutionOptions, ExecutionOptionsError};
 pub use vm_core::{
-    chiplets::hasher::Digest, errors::InputError, utils::DeserializationError, AdviceInjector,
-    AssemblyOp, Kernel, Operation, Program, ProgramInfo, QuadExtension, StackInputs, StackOutputs,
-    Word, EMPTY_WORD, ONE, ZERO,
+    chiplets::hasher::Digest, code_blocks, errors::InputError, utils::DeserializationError,
+    AdviceInjector, AssemblyOp, DebugOptions, Kernel, Operation, Program, ProgramInfo,
+    QuadExtension, SignatureKind, SourceLocation, StackInputs, StackOutputs, Word, EMPTY_WORD, ONE,
+    ZERO,
 };
 use vm_core::{
     code_blocks::{
         Call, CodeBlock, Dyn, Join, Loop, OpBatch, Span, Split, OP_BATCH_SIZE, OP_GROUP_SIZE,
     },
-    utils::collections::{BTreeMap, Vec},
+    utils::{
+        collections::{BTreeMap, Vec},
+        string::{String, ToString},
+    },
     CodeBlockTable, Decorator, DecoratorIterator, Felt, FieldElement, StackTopState, StarkField,
 };
 
@@ -36,15 +40,23 @@ mod decoder;
 use decoder::Decoder;
 
 mod stack;
+pub use stack::AuxTraceStats;
 use stack::Stack;
 
 mod range;
 use range::RangeChecker;
 
 mod host;
+#[cfg(feature = "disk-advice")]
+pub use host::advice::DiskAdviceProvider;
+#[cfg(feature = "remote-advice")]
+pub use host::advice::{RemoteAdviceProvider, REMOTE_ADVICE_KEY_PLACEHOLDER};
 pub use host::{
-    advice::{AdviceInputs, AdviceProvider, AdviceSource, MemAdviceProvider, RecAdviceProvider},
-    DefaultHost, Host,
+    advice::{
+        AdviceExtractor, AdviceInputs, AdviceMapKey, AdviceProvider, AdviceSource,
+        CappedAdviceProvider, MemAdviceProvider, MergeConflict, RecAdviceProvider,
+    },
+    DefaultHost, Host, HostResponse, HEARTBEAT_INTERVAL,
 };
 
 mod chiplets;
@@ -52,15 +64,20 @@ use chiplets::Chiplets;
 
 mod trace;
 use trace::TraceFragment;
-pub use trace::{ChipletsLengths, ExecutionTrace, TraceLenSummary};
+pub use trace::{
+    ChipletsLengths, ColMatrixExt, ExecutionTrace, MatrixView, TraceColumnRegistry, TraceLenSummary,
+};
 
 mod errors;
-pub use errors::{ExecutionError, Ext2InttError};
+pub use errors::{ExecutionError, Ext2InttError, StackError};
+
+mod atomic;
+pub use atomic::{AtomicExecutor, UpdatedAdvice};
 
 pub mod utils;
 
 mod debug;
-pub use debug::{AsmOpInfo, VmState, VmStateIterator};
+pub use debug::{AsmOpInfo, TrapState, VmState, VmStateIterator};
 
 // RE-EXPORTS
 // ================================================================================================
@@ -129,6 +146,54 @@ where
     Ok(trace)
 }
 
+/// Returns an execution trace resulting from executing the provided program against the provided
+/// inputs.
+///
+/// Unlike [execute()], if the execution fails, the error is returned together with the state of
+/// the process at the clock cycle at which the execution failed.
+pub fn execute_with_trap_state<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ExecutionOptions,
+) -> Result<ExecutionTrace, (ExecutionError, TrapState)>
+where
+    H: Host,
+{
+    let mut process = Process::new(program.kernel().clone(), stack_inputs, host, options);
+    let stack_outputs = match process.execute(program) {
+        Ok(stack_outputs) => stack_outputs,
+        Err(err) => {
+            let state = TrapState {
+                clk: process.clk(),
+                stack: process.get_stack_state(),
+            };
+            return Err((err, state));
+        }
+    };
+    let trace = ExecutionTrace::new(process, stack_outputs);
+    assert_eq!(&program.hash(), trace.program_hash(), "inconsistent program hash");
+    Ok(trace)
+}
+
+/// Returns the stack outputs resulting from executing the provided program against the provided
+/// inputs.
+///
+/// Unlike [execute()], this function does not build an execution trace, and thus is a cheaper
+/// way to compute the outputs of a program when neither the trace nor a proof is needed.
+pub fn execute_outputs<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ExecutionOptions,
+) -> Result<StackOutputs, ExecutionError>
+where
+    H: Host,
+{
+    let mut process = Process::new(program.kernel().clone(), stack_inputs, host, options);
+    process.execute(program)
+}
+
 /// Returns an iterator which allows callers to step through the execution and inspect VM state at
 /// each execution step.
 pub fn execute_iter<H>(program: &Program, stack_inputs: StackInputs, host: H) -> VmStateIterator
@@ -162,6 +227,21 @@ where
     chiplets: Chiplets,
     host: RefCell<H>,
     max_cycles: u32,
+    max_call_depth: usize,
+    call_depth: usize,
+    max_call_depth_reached: usize,
+    strict_stack_depth: bool,
+    strict_u32_overflow: bool,
+    strict_memory: bool,
+    deny_memory: bool,
+    deny_advice: bool,
+    heartbeat: bool,
+    next_heartbeat: u32,
+    step_hook: bool,
+    checked_execution: bool,
+    last_instruction: Option<String>,
+    last_source_loc: Option<SourceLocation>,
+    last_instruction_end: u32,
 }
 
 impl<H> Process<H>
@@ -200,6 +280,25 @@ where
             chiplets: Chiplets::new(kernel),
             host: RefCell::new(host),
             max_cycles: execution_options.max_cycles(),
+            max_call_depth: execution_options.max_call_depth(),
+            call_depth: 0,
+            max_call_depth_reached: 0,
+            strict_stack_depth: execution_options.strict_stack_depth(),
+            strict_u32_overflow: execution_options.strict_u32_overflow(),
+            strict_memory: execution_options.strict_memory(),
+            deny_memory: execution_options.deny_memory(),
+            deny_advice: execution_options.deny_advice(),
+            heartbeat: execution_options.heartbeat(),
+            next_heartbeat: HEARTBEAT_INTERVAL,
+            step_hook: execution_options.step_hook(),
+            checked_execution: execution_options.strict_stack_depth()
+                || execution_options.deny_memory()
+                || execution_options.deny_advice()
+                || execution_options.heartbeat()
+                || execution_options.step_hook(),
+            last_instruction: None,
+            last_source_loc: None,
+            last_instruction_end: 0,
         }
     }
 
@@ -230,8 +329,18 @@ where
             CodeBlock::Join(block) => self.execute_join_block(block, cb_table),
             CodeBlock::Split(block) => self.execute_split_block(block, cb_table),
             CodeBlock::Loop(block) => self.execute_loop_block(block, cb_table),
-            CodeBlock::Call(block) => self.execute_call_block(block, cb_table),
-            CodeBlock::Dyn(block) => self.execute_dyn_block(block, cb_table),
+            CodeBlock::Call(block) => {
+                self.enter_call()?;
+                self.execute_call_block(block, cb_table)?;
+                self.exit_call();
+                Ok(())
+            }
+            CodeBlock::Dyn(block) => {
+                self.enter_call()?;
+                self.execute_dyn_block(block, cb_table)?;
+                self.exit_call();
+                Ok(())
+            }
             CodeBlock::Span(block) => self.execute_span_block(block),
             CodeBlock::Proxy(_) => Err(ExecutionError::UnexecutableCodeBlock(block.clone())),
         }
@@ -261,6 +370,7 @@ where
         cb_table: &CodeBlockTable,
     ) -> Result<(), ExecutionError> {
         // start the SPLIT block; this also pops the stack and returns the popped element
+        self.check_stack_depth(Operation::Split)?;
         let condition = self.start_split_block(block)?;
 
         // execute either the true or the false branch of the split block based on the condition
@@ -283,6 +393,7 @@ where
         cb_table: &CodeBlockTable,
     ) -> Result<(), ExecutionError> {
         // start the LOOP block; this also pops the stack and returns the popped element
+        self.check_stack_depth(Operation::Loop)?;
         let condition = self.start_loop_block(block)?;
 
         // if the top of the stack is ONE, execute the loop body; otherwise skip the loop body
@@ -293,10 +404,12 @@ where
             // keep executing the loop body until the condition on the top of the stack is no
             // longer ONE; each iteration of the loop is preceded by executing REPEAT operation
             // which drops the condition from the stack
+            self.check_stack_depth(Operation::Repeat)?;
             while self.stack.peek() == ONE {
                 self.decoder.repeat();
-                self.execute_op(Operation::Drop)?;
+                self.execute_control_op(Operation::Repeat, Operation::Drop)?;
                 self.execute_code_block(block.body(), cb_table)?;
+                self.check_stack_depth(Operation::Repeat)?;
             }
 
             // end the LOOP block and drop the condition from the stack
@@ -346,6 +459,7 @@ where
         cb_table: &CodeBlockTable,
     ) -> Result<(), ExecutionError> {
         // get target hash from the stack
+        self.check_stack_depth(Operation::Dyn)?;
         let dyn_hash = self.stack.get_word(0);
         self.start_dyn_block(block, dyn_hash)?;
 
@@ -364,11 +478,40 @@ where
     fn execute_span_block(&mut self, block: &Span) -> Result<(), ExecutionError> {
         self.start_span_block(block)?;
 
-        let mut op_offset = 0;
+        // whether the operations need to be checked against the execution options is determined
+        // once per span, so that the default execution does not pay for the checks
         let mut decorators = block.decorator_iter();
+        if self.checked_execution {
+            self.execute_op_batches::<true>(block, &mut decorators)?;
+        } else {
+            self.execute_op_batches::<false>(block, &mut decorators)?;
+        }
+
+        self.end_span_block(block)?;
+
+        // execute any decorators which have not been executed during span ops execution; this
+        // can happen for decorators appearing after all operations in a block. these decorators
+        // are executed after SPAN block is closed to make sure the VM clock cycle advances beyond
+        // the last clock cycle of the SPAN block ops.
+        for decorator in decorators {
+            self.execute_decorator(decorator)?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes all operation batches of the specified [Span] block. If `CHECKED` is set, every
+    /// operation is executed via [Process::execute_checked_op].
+    #[inline(always)]
+    fn execute_op_batches<const CHECKED: bool>(
+        &mut self,
+        block: &Span,
+        decorators: &mut DecoratorIterator,
+    ) -> Result<(), ExecutionError> {
+        let mut op_offset = 0;
 
         // execute the first operation batch
-        self.execute_op_batch(&block.op_batches()[0], &mut decorators, op_offset)?;
+        self.execute_op_batch::<CHECKED>(&block.op_batches()[0], decorators, op_offset)?;
         op_offset += block.op_batches()[0].ops().len();
 
         // if the span contains more operation batches, execute them. each additional batch is
@@ -376,21 +519,11 @@ where
         // of the stack
         for op_batch in block.op_batches().iter().skip(1) {
             self.respan(op_batch);
-            self.execute_op(Operation::Noop)?;
-            self.execute_op_batch(op_batch, &mut decorators, op_offset)?;
+            self.execute_control_op(Operation::Respan, Operation::Noop)?;
+            self.execute_op_batch::<CHECKED>(op_batch, decorators, op_offset)?;
             op_offset += op_batch.ops().len();
         }
 
-        self.end_span_block(block)?;
-
-        // execute any decorators which have not been executed during span ops execution; this
-        // can happen for decorators appearing after all operations in a block. these decorators
-        // are executed after SPAN block is closed to make sure the VM clock cycle advances beyond
-        // the last clock cycle of the SPAN block ops.
-        if let Some(decorator) = decorators.next() {
-            self.execute_decorator(decorator)?;
-        }
-
         Ok(())
     }
 
@@ -401,7 +534,7 @@ where
     /// - If the number of groups in a batch is not a power of 2, NOOPs are executed (one per
     ///   group) to bring it up to the next power of two (e.g., 3 -> 4, 5 -> 8).
     #[inline(always)]
-    fn execute_op_batch(
+    fn execute_op_batch<const CHECKED: bool>(
         &mut self,
         batch: &OpBatch,
         decorators: &mut DecoratorIterator,
@@ -425,7 +558,7 @@ where
 
             // decode and execute the operation
             self.decoder.execute_user_op(op, op_idx);
-            self.execute_op(op)?;
+            self.execute_span_op::<CHECKED>(op)?;
 
             // if the operation carries an immediate value, the value is stored at the next group
             // pointer; so, we advance the pointer to the following group
@@ -445,7 +578,7 @@ where
                     // bug somewhere in the assembler)
                     debug_assert!(op_idx < OP_GROUP_SIZE - 1, "invalid op index");
                     self.decoder.execute_user_op(Operation::Noop, op_idx + 1);
-                    self.execute_op(Operation::Noop)?;
+                    self.execute_span_op::<CHECKED>(Operation::Noop)?;
                 }
 
                 // then, move to the next group and reset operation index
@@ -468,7 +601,7 @@ where
         // the actual number of operation groups was not a power of two
         for group_idx in group_idx..num_batch_groups {
             self.decoder.execute_user_op(Operation::Noop, 0);
-            self.execute_op(Operation::Noop)?;
+            self.execute_span_op::<CHECKED>(Operation::Noop)?;
 
             // if we are not at the last group yet, set up the decoder for decoding the next
             // operation groups. the groups were are processing are just NOOPs - so, the op group
@@ -481,6 +614,20 @@ where
         Ok(())
     }
 
+    /// Executes the specified operation of a [Span] block, checking it against the execution
+    /// options if `CHECKED` is set.
+    #[inline(always)]
+    fn execute_span_op<const CHECKED: bool>(
+        &mut self,
+        op: Operation,
+    ) -> Result<(), ExecutionError> {
+        if CHECKED {
+            self.execute_checked_op(op)
+        } else {
+            self.execute_op(op)
+        }
+    }
+
     /// Executes the specified decorator
     fn execute_decorator(&mut self, decorator: &Decorator) -> Result<(), ExecutionError> {
         match decorator {
@@ -490,7 +637,15 @@ where
             Decorator::Debug(options) => {
                 self.host.borrow_mut().on_debug(self, options)?;
             }
+            Decorator::Trace(trace_id) => {
+                self.host.borrow_mut().on_trace(self, *trace_id)?;
+            }
             Decorator::AsmOp(assembly_op) => {
+                if self.strict_stack_depth || self.strict_u32_overflow || self.step_hook {
+                    self.last_instruction = Some(assembly_op.op().to_string());
+                    self.last_source_loc = assembly_op.location().copied();
+                    self.last_instruction_end = self.system.clk() + assembly_op.num_cycles() as u32;
+                }
                 if self.decoder.in_debug_mode() {
                     self.decoder.append_asmop(self.system.clk(), assembly_op.clone());
                 }
@@ -499,6 +654,27 @@ where
         Ok(())
     }
 
+    /// Increments the depth of nested procedure calls.
+    ///
+    /// # Errors
+    /// Returns an error if the new depth exceeds the maximum allowed call depth.
+    fn enter_call(&mut self) -> Result<(), ExecutionError> {
+        if self.call_depth >= self.max_call_depth {
+            return Err(ExecutionError::MaxCallDepthExceeded {
+                clk: self.system.clk(),
+                depth: self.max_call_depth,
+            });
+        }
+        self.call_depth += 1;
+        self.max_call_depth_reached = self.max_call_depth_reached.max(self.call_depth);
+        Ok(())
+    }
+
+    /// Decrements the depth of nested procedure calls.
+    fn exit_call(&mut self) {
+        self.call_depth -= 1;
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -529,6 +705,9 @@ pub trait ProcessState {
     /// Returns the current execution context ID.
     fn ctx(&self) -> u32;
 
+    /// Returns the current value of the free memory pointer.
+    fn fmp(&self) -> Felt;
+
     /// Returns the value located at the specified position on the stack at the current clock cycle.
     fn get_stack_item(&self, pos: usize) -> Felt;
 
@@ -562,6 +741,10 @@ impl<H: Host> ProcessState for Process<H> {
         self.system.ctx()
     }
 
+    fn fmp(&self) -> Felt {
+        self.system.fmp()
+    }
+
     fn get_stack_item(&self, pos: usize) -> Felt {
         self.stack.get(pos)
     }
@@ -594,4 +777,19 @@ where
     pub chiplets: Chiplets,
     pub host: RefCell<H>,
     pub max_cycles: u32,
+    pub max_call_depth: usize,
+    pub call_depth: usize,
+    pub max_call_depth_reached: usize,
+    pub strict_stack_depth: bool,
+    pub strict_u32_overflow: bool,
+    pub strict_memory: bool,
+    pub deny_memory: bool,
+    pub deny_advice: bool,
+    pub heartbeat: bool,
+    pub next_heartbeat: u32,
+    pub step_hook: bool,
+    pub checked_execution: bool,
+    pub last_instruction: Option<String>,
+    pub last_source_loc: Option<SourceLocation>,
+    pub last_instruction_end: u32,
 }
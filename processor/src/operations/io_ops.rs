@@ -38,6 +38,7 @@ where
         // get the address from the stack and read the word from current memory context
         let ctx = self.system.ctx();
         let addr = Self::get_valid_address(self.stack.get(0))?;
+        self.check_mem_initialized(ctx, addr)?;
         let word = self.chiplets.read_mem(ctx, addr);
 
         // reverse the order of the memory word & update the stack state
@@ -65,6 +66,7 @@ where
         // get the address from the stack and read the word from memory
         let ctx = self.system.ctx();
         let addr = Self::get_valid_address(self.stack.get(0))?;
+        self.check_mem_initialized(ctx, addr)?;
         let mut word = self.chiplets.read_mem(ctx, addr);
         // put the retrieved word into stack order
         word.reverse();
@@ -95,6 +97,8 @@ where
         let addr = Self::get_valid_address(self.stack.get(12))?;
 
         // load two words from memory
+        self.check_mem_initialized(ctx, addr)?;
+        self.check_mem_initialized(ctx, addr + 1)?;
         let words = self.chiplets.read_mem_double(ctx, addr);
 
         // replace the stack elements with the elements from memory (in stack order)
@@ -264,6 +268,19 @@ where
         }
         Ok(addr as u32)
     }
+
+    /// Checks that the specified memory address has been written to if reading from uninitialized
+    /// memory is not allowed.
+    ///
+    /// # Errors
+    /// Returns an error if strict memory mode is enabled and the address in the specified context
+    /// has never been written to.
+    fn check_mem_initialized(&self, ctx: u32, addr: u32) -> Result<(), ExecutionError> {
+        if self.strict_memory && !self.chiplets.is_mem_initialized(ctx, addr) {
+            return Err(ExecutionError::UninitializedMemoryRead { address: addr });
+        }
+        Ok(())
+    }
 }
 
 // TESTS
@@ -275,7 +292,7 @@ mod tests {
         super::{super::AdviceProvider, Operation, STACK_TOP_SIZE},
         Felt, Host, Process,
     };
-    use crate::AdviceSource;
+    use crate::{AdviceSource, ExecutionError};
     use vm_core::{utils::ToElements, Word, ONE, ZERO};
 
     #[test]
@@ -343,6 +360,27 @@ mod tests {
         assert!(process.execute_op(Operation::MLoadW).is_ok());
     }
 
+    #[test]
+    fn op_mload_strict_memory() {
+        let mut process = Process::new_dummy_with_decoder_helpers_and_empty_stack();
+        process.strict_memory = true;
+
+        // save a word at address 1
+        let word = [1, 3, 5, 7].to_elements().try_into().unwrap();
+        store_value(&mut process, 1, word);
+
+        // reading from the initialized address succeeds
+        process.execute_op(Operation::Push(ONE)).unwrap();
+        process.execute_op(Operation::MLoad).unwrap();
+
+        // reading from an address which has never been written to fails
+        process.execute_op(Operation::Push(Felt::new(2))).unwrap();
+        assert!(matches!(
+            process.execute_op(Operation::MLoadW),
+            Err(ExecutionError::UninitializedMemoryRead { address: 2 })
+        ));
+    }
+
     #[test]
     fn op_mload() {
         let mut process = Process::new_dummy_with_decoder_helpers_and_empty_stack();
@@ -1,5 +1,8 @@
-use super::{ExecutionError, Felt, FieldElement, Host, Operation, Process, StarkField};
-use vm_core::stack::STACK_TOP_SIZE;
+use super::{
+    ExecutionError, Felt, FieldElement, Host, Operation, Process, StackError, StarkField,
+    HEARTBEAT_INTERVAL,
+};
+use vm_core::{stack::STACK_TOP_SIZE, utils::string::ToString};
 
 mod crypto_ops;
 mod ext2_ops;
@@ -22,6 +25,9 @@ where
     H: Host,
 {
     /// Executes the specified operation.
+    ///
+    /// This does not validate the operation against the execution options; see
+    /// [Self::execute_checked_op].
     pub(super) fn execute_op(&mut self, op: Operation) -> Result<(), ExecutionError> {
         // make sure there is enough memory allocated to hold the execution trace
         self.ensure_trace_capacity();
@@ -155,6 +161,135 @@ where
         Ok(())
     }
 
+    /// Executes the specified operation after making sure that it is allowed by the execution
+    /// options, and notifies the host about the executed cycle if required.
+    ///
+    /// This is used instead of [Self::execute_op] only when the execution options require any of
+    /// these checks, so that the default execution does not pay for them.
+    pub(super) fn execute_checked_op(&mut self, op: Operation) -> Result<(), ExecutionError> {
+        self.check_op_allowed(op)?;
+        self.check_stack_depth(op)?;
+        self.execute_op(op)?;
+        self.on_cycle_executed(op)
+    }
+
+    /// Executes the specified operation on behalf of the control flow operation decoded in the
+    /// current cycle (e.g., a NOOP for JOIN, or a DROP for SPLIT), and notifies the host about the
+    /// executed cycle if required by the execution options.
+    pub(super) fn execute_control_op(
+        &mut self,
+        control_op: Operation,
+        op: Operation,
+    ) -> Result<(), ExecutionError> {
+        self.execute_op(op)?;
+        if self.checked_execution {
+            self.on_cycle_executed(control_op)?;
+        }
+        Ok(())
+    }
+
+    /// Notifies the host about the progress of the execution as required by the execution options.
+    ///
+    /// If heartbeats are enabled, the host is notified once at least [HEARTBEAT_INTERVAL] cycles
+    /// have passed since the previous notification, so that it can abort the execution. If steps
+    /// are enabled, the host is notified about every executed operation.
+    fn on_cycle_executed(&mut self, op: Operation) -> Result<(), ExecutionError> {
+        let clk = self.system.clk();
+        if self.heartbeat && clk >= self.next_heartbeat {
+            self.next_heartbeat = clk.saturating_add(HEARTBEAT_INTERVAL);
+            self.host.borrow_mut().on_heartbeat(self)?;
+        }
+
+        if self.step_hook {
+            // the operation was executed in the previous cycle, and belongs to the last assembly
+            // instruction only if it was executed within the cycles of that instruction
+            let instruction = match &self.last_instruction {
+                Some(instruction) if clk <= self.last_instruction_end => Some(instruction.as_str()),
+                _ => None,
+            };
+            self.host.borrow_mut().on_step(self, op, instruction)?;
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the specified operation accesses memory or reads from the advice
+    /// provider while such accesses are denied by the execution options.
+    fn check_op_allowed(&self, op: Operation) -> Result<(), ExecutionError> {
+        // this is checked for every operation in checked execution, and thus, bails out right away
+        // if the checked execution was enabled by other options
+        if !self.deny_memory && !self.deny_advice {
+            return Ok(());
+        }
+
+        if self.deny_memory {
+            let instruction = match op {
+                Operation::MLoad => Some("mem_load"),
+                Operation::MLoadW => Some("mem_loadw"),
+                Operation::MStore => Some("mem_store"),
+                Operation::MStoreW => Some("mem_storew"),
+                Operation::MStream => Some("mem_stream"),
+                Operation::Pipe => Some("adv_pipe"),
+                _ => None,
+            };
+            if let Some(instruction) = instruction {
+                return Err(ExecutionError::DeniedOperation(instruction));
+            }
+        }
+
+        if self.deny_advice {
+            let instruction = match op {
+                Operation::AdvPop => Some("adv_push"),
+                Operation::AdvPopW => Some("adv_loadw"),
+                Operation::Pipe => Some("adv_pipe"),
+                Operation::MpVerify => Some("mtree_get"),
+                Operation::MrUpdate => Some("mtree_set"),
+                _ => None,
+            };
+            if let Some(instruction) = instruction {
+                return Err(ExecutionError::DeniedOperation(instruction));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if strict stack depth mode is enabled and the specified operation reads or
+    /// overwrites more items than were placed onto the operand stack, i.e., if it would consume
+    /// the ZERO elements inserted by the VM.
+    pub(super) fn check_stack_depth(&self, op: Operation) -> Result<(), ExecutionError> {
+        // this is checked for every operation in checked execution, and thus, bails out right away
+        // if the checked execution was enabled by other options
+        if !self.strict_stack_depth {
+            return Ok(());
+        }
+
+        let num_inputs = num_stack_inputs(op);
+        let defined_depth = self.stack.defined_depth();
+        if num_inputs <= defined_depth {
+            return Ok(());
+        }
+
+        // control flow operations are not preceded by assembly op decorators, and thus, are
+        // reported by the instructions they are compiled from, without a source location
+        let (instruction, source_loc) = match op {
+            Operation::Split => ("if.true".to_string(), None),
+            Operation::Loop | Operation::Repeat => ("while.true".to_string(), None),
+            Operation::Dyn => ("dynexec".to_string(), None),
+            _ => match &self.last_instruction {
+                Some(instruction) => (instruction.clone(), self.last_source_loc),
+                None => (op.to_string(), None),
+            },
+        };
+        Err(StackError::Underflow {
+            expected: num_inputs,
+            actual: defined_depth,
+            instruction,
+            source_loc,
+            clk: self.system.clk(),
+        }
+        .into())
+    }
+
     /// Increments the clock cycle for all components of the process.
     fn advance_clock(&mut self) -> Result<(), ExecutionError> {
         self.system.advance_clock(self.max_cycles)?;
@@ -170,6 +305,93 @@ where
     }
 }
 
+/// Returns the number of items at the top of the operand stack which the specified operation
+/// reads or overwrites.
+///
+/// For [Operation::Repeat], this is the loop condition which is read before every iteration of a
+/// loop (and dropped either by REPEAT or by the END of the loop).
+fn num_stack_inputs(op: Operation) -> usize {
+    match op {
+        Operation::Noop
+        | Operation::SDepth
+        | Operation::Clk
+        | Operation::Join
+        | Operation::Call
+        | Operation::SysCall
+        | Operation::Span
+        | Operation::Respan
+        | Operation::End
+        | Operation::Halt
+        | Operation::Pad
+        | Operation::Push(_)
+        | Operation::AdvPop => 0,
+
+        Operation::Assert(_)
+        | Operation::FmpAdd
+        | Operation::FmpUpdate
+        | Operation::Split
+        | Operation::Loop
+        | Operation::Repeat
+        | Operation::Neg
+        | Operation::Inv
+        | Operation::Incr
+        | Operation::Not
+        | Operation::Eqz
+        | Operation::U32split
+        | Operation::Drop
+        | Operation::Dup0
+        | Operation::MLoad => 1,
+
+        Operation::Add
+        | Operation::Mul
+        | Operation::And
+        | Operation::Or
+        | Operation::Eq
+        | Operation::U32add
+        | Operation::U32sub
+        | Operation::U32mul
+        | Operation::U32div
+        | Operation::U32and
+        | Operation::U32xor
+        | Operation::U32assert2(_)
+        | Operation::Dup1
+        | Operation::Swap
+        | Operation::MStore => 2,
+
+        Operation::U32add3
+        | Operation::U32madd
+        | Operation::Dup2
+        | Operation::MovUp2
+        | Operation::MovDn2
+        | Operation::CSwap => 3,
+
+        Operation::Caller
+        | Operation::Dyn
+        | Operation::Expacc
+        | Operation::Ext2Mul
+        | Operation::Dup3
+        | Operation::MovUp3
+        | Operation::MovDn3
+        | Operation::AdvPopW => 4,
+
+        Operation::Dup4
+        | Operation::MovUp4
+        | Operation::MovDn4
+        | Operation::MLoadW
+        | Operation::MStoreW => 5,
+
+        Operation::Dup5 | Operation::MovUp5 | Operation::MovDn5 => 6,
+        Operation::Dup6 | Operation::MovUp6 | Operation::MovDn6 => 7,
+        Operation::Dup7 | Operation::MovUp7 | Operation::MovDn7 | Operation::SwapW => 8,
+        Operation::MovUp8 | Operation::MovDn8 | Operation::CSwapW => 9,
+        Operation::Dup9 | Operation::MpVerify => 10,
+        Operation::Dup11 | Operation::SwapW2 | Operation::HPerm => 12,
+        Operation::MStream | Operation::Pipe => 13,
+        Operation::Dup13 | Operation::MrUpdate => 14,
+        Operation::Dup15 | Operation::SwapW3 | Operation::SwapDW | Operation::FriE2F4 => 16,
+    }
+}
+
 #[cfg(test)]
 impl Process<super::DefaultHost<super::MemAdviceProvider>> {
     // TEST METHODS
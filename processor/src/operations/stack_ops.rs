@@ -306,7 +306,7 @@ mod tests {
         super::{Operation, Process},
         STACK_TOP_SIZE,
     };
-    use crate::{Felt, StackInputs, ONE, ZERO};
+    use crate::{ExecutionError, Felt, StackError, StackInputs, ONE, ZERO};
 
     #[test]
     fn op_pad() {
@@ -359,6 +359,33 @@ mod tests {
         assert!(process.execute_op(Operation::Drop).is_ok());
     }
 
+    #[test]
+    fn op_drop_strict_stack_depth() {
+        let stack = StackInputs::try_from_values([1]).unwrap();
+        let mut process = Process::new_dummy(stack);
+        process.strict_stack_depth = true;
+        process.last_instruction = Some("drop".to_string());
+
+        // dropping an item which was placed onto the stack succeeds
+        process.execute_checked_op(Operation::Drop).unwrap();
+
+        // dropping an item which was inserted by the VM fails
+        let err = process.execute_checked_op(Operation::Drop).unwrap_err();
+        assert!(matches!(
+            err,
+            ExecutionError::StackError(StackError::Underflow {
+                expected: 1,
+                actual: 0,
+                source_loc: None,
+                ..
+            })
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Stack underflow at clock cycle 2: 'drop' requires 1 element but stack has 0"
+        );
+    }
+
     #[test]
     fn op_dup() {
         let stack = StackInputs::default();
@@ -3,6 +3,7 @@ use super::{
     ExecutionError, Felt, FieldElement, Host, Operation, Process, StarkField,
 };
 use crate::ZERO;
+use vm_core::utils::string::ToString;
 
 impl<H> Process<H>
 where
@@ -55,6 +56,7 @@ where
         let a = self.stack.get(1);
         let result = a + b;
         let (hi, lo) = split_element(result);
+        self.check_u32_overflow(a.as_int(), b.as_int(), hi != ZERO)?;
 
         self.add_range_checks(Operation::U32add, lo, hi, false);
 
@@ -90,6 +92,7 @@ where
         let result = a.wrapping_sub(b);
         let d = Felt::new(result >> 63);
         let c = Felt::new((result as u32) as u64);
+        self.check_u32_overflow(a, b, d != ZERO)?;
 
         // Force this operation to consume 4 range checks, even though only `lo` is needed.
         // This is required for making the constraints more uniform and grouping the opcodes of
@@ -109,6 +112,7 @@ where
         let a = self.stack.get(1).as_int();
         let result = Felt::new(a * b);
         let (hi, lo) = split_element(result);
+        self.check_u32_overflow(a, b, hi != ZERO)?;
 
         self.add_range_checks(Operation::U32mul, lo, hi, true);
 
@@ -193,6 +197,29 @@ where
         Ok(())
     }
 
+    /// Returns an error if strict u32 overflow detection is enabled, the specified operation was
+    /// compiled from a wrapping u32 addition, subtraction, or multiplication instruction, and the
+    /// result of the operation executed over operands `a` and `b` did not fit into 32 bits.
+    ///
+    /// The instruction is identified by the last assembly op decorator, and thus, operations of
+    /// programs compiled without debug info are never checked. Other instructions, e.g., u32
+    /// comparisons, rely on the carry or borrow of these operations, and are not checked either.
+    fn check_u32_overflow(&self, a: u64, b: u64, overflowed: bool) -> Result<(), ExecutionError> {
+        if !self.strict_u32_overflow || !overflowed {
+            return Ok(());
+        }
+
+        match self.last_instruction.as_deref() {
+            Some(instruction) if is_wrapping_u32_instruction(instruction) => {
+                Err(ExecutionError::U32Overflow {
+                    operation: instruction.to_string(),
+                    operands: (a, b),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Adds 16-bit range checks to the RangeChecker for the high and low 16-bit limbs of two field
     /// elements which are assumed to have 32-bit integer values. This results in 4 range checks.
     ///
@@ -227,6 +254,16 @@ where
     }
 }
 
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns true if the specified assembly instruction is a wrapping u32 addition, subtraction, or
+/// multiplication, with or without an immediate value.
+fn is_wrapping_u32_instruction(instruction: &str) -> bool {
+    let name = instruction.split('.').next().unwrap_or_default();
+    matches!(name, "u32wrapping_add" | "u32wrapping_sub" | "u32wrapping_mul")
+}
+
 // TESTS
 // ================================================================================================
 
@@ -236,7 +273,7 @@ mod tests {
         super::{Felt, Operation},
         split_u32_into_u16, Process,
     };
-    use crate::{StackInputs, ZERO};
+    use crate::{ExecutionError, StackInputs, ZERO};
     use miden_air::trace::{decoder::NUM_USER_OP_HELPERS, stack::STACK_TOP_SIZE};
     use test_utils::rand::rand_value;
 
@@ -381,6 +418,53 @@ mod tests {
         assert_eq!(expected, process.stack.trace_state());
     }
 
+    #[test]
+    fn op_u32_strict_overflow() {
+        let ops = [
+            (Operation::U32add, "u32wrapping_add", 1, u32::MAX),
+            (Operation::U32sub, "u32wrapping_sub.2", 1, 2),
+            (Operation::U32mul, "u32wrapping_mul", 1 << 16, 1 << 16),
+        ];
+
+        for (op, instruction, a, b) in ops {
+            // operations which stay within u32 bounds succeed
+            let stack = StackInputs::try_from_values([1, 1]).unwrap();
+            let mut process = Process::new_dummy_with_decoder_helpers(stack);
+            process.strict_u32_overflow = true;
+            process.last_instruction = Some(instruction.to_string());
+            assert!(process.execute_op(op).is_ok());
+
+            // operations which overflow are rejected only in strict mode
+            let stack = StackInputs::try_from_values([a as u64, b as u64]).unwrap();
+            let mut process = Process::new_dummy_with_decoder_helpers(stack.clone());
+            process.last_instruction = Some(instruction.to_string());
+            assert!(process.execute_op(op).is_ok());
+
+            // overflows are expected by instructions other than the wrapping ones, e.g., by
+            // comparisons, and by operations which cannot be attributed to an instruction
+            for other in [Some("u32checked_lt"), Some("u32overflowing_add"), None] {
+                let mut process = Process::new_dummy_with_decoder_helpers(stack.clone());
+                process.strict_u32_overflow = true;
+                process.last_instruction = other.map(|other| other.to_string());
+                assert!(process.execute_op(op).is_ok());
+            }
+
+            let mut process = Process::new_dummy_with_decoder_helpers(stack);
+            process.strict_u32_overflow = true;
+            process.last_instruction = Some(instruction.to_string());
+            match process.execute_op(op) {
+                Err(ExecutionError::U32Overflow {
+                    operation,
+                    operands,
+                }) => {
+                    assert_eq!(operation, instruction);
+                    assert_eq!(operands, (a as u64, b as u64));
+                }
+                result => panic!("expected u32 overflow error, got {result:?}"),
+            }
+        }
+    }
+
     #[test]
     fn op_u32madd() {
         let (a, b, c, d) = get_rand_values();
@@ -29,9 +29,56 @@ impl AuxTraceBuilder {
         main_trace: &ColMatrix<Felt>,
         rand_elements: &[E],
     ) -> Vec<Vec<E>> {
+        #[cfg(feature = "std")]
+        log::debug!("building stack auxiliary column p1: {:?}", self.stats());
+
         let p1 = self.build_aux_column(main_trace, rand_elements);
         vec![p1]
     }
+
+    /// Returns a summary of the overflow table hints from which the p1 column is built.
+    pub fn stats(&self) -> AuxTraceStats {
+        let mut stats = AuxTraceStats {
+            overflow_table_entries: self.overflow_table_rows.len() as u64,
+            max_overflow_depth: self.num_init_rows,
+            ..Default::default()
+        };
+
+        let mut depth = self.num_init_rows;
+        for (_, hint) in self.get_table_hints() {
+            match hint {
+                OverflowTableUpdate::RowInserted(_) => {
+                    stats.push_events += 1;
+                    depth += 1;
+                    stats.max_overflow_depth = stats.max_overflow_depth.max(depth);
+                }
+                OverflowTableUpdate::RowRemoved(_) => {
+                    stats.pop_events += 1;
+                    depth -= 1;
+                }
+            }
+        }
+
+        stats
+    }
+}
+
+// AUXILIARY TRACE STATS
+// ================================================================================================
+
+/// Summary of the stack overflow table updates recorded during program execution, which can be
+/// used to diagnose unexpected values in the stack auxiliary column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AuxTraceStats {
+    /// The number of rows added to the overflow table, including the rows present in the table
+    /// when execution begins.
+    pub overflow_table_entries: u64,
+    /// The largest number of rows in the overflow table at any point of the execution.
+    pub max_overflow_depth: usize,
+    /// The number of rows added to the overflow table during execution.
+    pub push_events: u64,
+    /// The number of rows removed from the overflow table during execution.
+    pub pop_events: u64,
 }
 
 // OVERFLOW TABLE
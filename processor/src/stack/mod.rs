@@ -13,7 +13,7 @@ use overflow::OverflowTable;
 pub use overflow::{OverflowTableRow, OverflowTableUpdate};
 
 mod aux_trace;
-pub use aux_trace::AuxTraceBuilder;
+pub use aux_trace::{AuxTraceBuilder, AuxTraceStats};
 
 #[cfg(test)]
 mod tests;
@@ -38,6 +38,10 @@ const MAX_TOP_IDX: usize = STACK_TOP_SIZE - 1;
 /// The depth of the stack can never drop below 16. If an item is removed from the stack when the
 /// depth is 16, a ZERO element is inserted into the 16th slot.
 ///
+/// In addition to the actual depth, the stack keeps track of its defined depth, i.e., the number
+/// of items which were explicitly placed onto the stack either as inputs or by the program. This
+/// is used to detect programs which consume the ZERO elements inserted by the VM.
+///
 /// ## Execution trace
 /// The stack execution trace consists of 19 columns as illustrated below:
 ///
@@ -59,6 +63,8 @@ pub struct Stack {
     overflow: OverflowTable,
     active_depth: usize,
     full_depth: usize,
+    defined_depth: usize,
+    ctx_defined_depths: Vec<usize>,
 }
 
 impl Stack {
@@ -93,6 +99,8 @@ impl Stack {
             overflow,
             active_depth: depth,
             full_depth: depth,
+            defined_depth: init_values.len(),
+            ctx_defined_depths: Vec::new(),
         }
     }
 
@@ -104,6 +112,12 @@ impl Stack {
         self.active_depth
     }
 
+    /// Returns the number of items which were explicitly placed onto the stack, either as inputs
+    /// or by the program, and have not been removed from it yet.
+    pub fn defined_depth(&self) -> usize {
+        self.defined_depth
+    }
+
     /// Returns the current clock cycle of the execution trace.
     pub fn current_clk(&self) -> u32 {
         self.clk
@@ -205,6 +219,8 @@ impl Stack {
         debug_assert!(start_pos > 0, "start position must be greater than 0");
         debug_assert!(start_pos <= STACK_TOP_SIZE, "start position cannot exceed stack top size");
 
+        self.defined_depth = self.defined_depth.saturating_sub(1);
+
         match self.active_depth {
             0..=MAX_TOP_IDX => unreachable!("stack underflow"),
             STACK_TOP_SIZE => {
@@ -245,6 +261,7 @@ impl Stack {
         // Stack depth always increases on right shift.
         self.active_depth += 1;
         self.full_depth += 1;
+        self.defined_depth += 1;
     }
 
     // CONTEXT MANAGEMENT
@@ -254,23 +271,35 @@ impl Stack {
     /// stack depth and the address of the overflow table row prior to starting the new context.
     ///
     /// This has the effect of hiding the contents of the overflow table such that it appears as
-    /// if the overflow table in the new context is empty.
+    /// if the overflow table in the new context is empty. Accordingly, only the defined items in
+    /// the top 16 slots of the stack remain defined in the new context.
     pub fn start_context(&mut self) -> (usize, Felt) {
         let current_depth = self.active_depth;
         let current_overflow_addr = self.overflow.last_row_addr();
         self.active_depth = STACK_TOP_SIZE;
         self.overflow.set_last_row_addr(ZERO);
+
+        self.ctx_defined_depths.push(self.defined_depth);
+        self.defined_depth = cmp::min(self.defined_depth, STACK_TOP_SIZE);
         (current_depth, current_overflow_addr)
     }
 
     /// Restores the prior context for this stack.
     ///
-    /// This has the effect bringing back items previously hidden from the overflow table.
+    /// This has the effect bringing back items previously hidden from the overflow table. The
+    /// defined items hidden in the overflow table become defined again only if all of the top 16
+    /// slots of the stack are defined; otherwise, they lie below the ZERO elements inserted by the
+    /// VM, and cannot be reached without consuming these elements.
     pub fn restore_context(&mut self, stack_depth: usize, next_overflow_addr: Felt) {
         debug_assert!(stack_depth <= self.full_depth, "stack depth too big");
         debug_assert_eq!(self.active_depth, STACK_TOP_SIZE, "overflow table not empty");
         self.active_depth = stack_depth;
         self.overflow.set_last_row_addr(next_overflow_addr);
+
+        let prev_defined_depth = self.ctx_defined_depths.pop().expect("no context to restore");
+        if self.defined_depth == STACK_TOP_SIZE {
+            self.defined_depth += prev_defined_depth.saturating_sub(STACK_TOP_SIZE);
+        }
     }
 
     // TRACE GENERATION
@@ -0,0 +1,112 @@
+use crate::{String, ToString, Vec};
+use miden_air::trace::{
+    decoder::{NUM_HASHER_COLUMNS, NUM_OP_BATCH_FLAGS, NUM_OP_BITS, NUM_OP_BITS_EXTRA_COLS},
+    stack::STACK_TOP_SIZE,
+    AUX_TRACE_WIDTH, CHIPLETS_WIDTH, FN_HASH_RANGE, TRACE_WIDTH,
+};
+
+// TRACE COLUMN REGISTRY
+// ================================================================================================
+
+/// Human-readable names of the columns of an execution trace.
+///
+/// Columns are registered in the order in which they appear in the trace, and thus, the name of
+/// a column can be looked up by its index in the main or the auxiliary trace segment. Columns
+/// shared by several components (e.g., the chiplet columns) are named after the segment they
+/// belong to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceColumnRegistry {
+    main: Vec<String>,
+    aux: Vec<String>,
+}
+
+impl TraceColumnRegistry {
+    /// Returns a registry with the names of all columns of Miden VM execution traces.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            main: Vec::with_capacity(TRACE_WIDTH),
+            aux: Vec::with_capacity(AUX_TRACE_WIDTH),
+        };
+
+        // system columns
+        registry.register_main(&["clk", "fmp", "ctx", "in_syscall"]);
+        registry.register_main_group("fn_hash", FN_HASH_RANGE.len());
+
+        // decoder columns
+        registry.register_main(&["addr"]);
+        registry.register_main_group("op_bits", NUM_OP_BITS);
+        registry.register_main_group("hasher_state", NUM_HASHER_COLUMNS);
+        registry.register_main(&["in_span", "group_count", "op_idx"]);
+        registry.register_main_group("op_batch_flags", NUM_OP_BATCH_FLAGS);
+        registry.register_main_group("op_bits_extra", NUM_OP_BITS_EXTRA_COLS);
+
+        // stack columns
+        registry.register_main_group("stack", STACK_TOP_SIZE);
+        registry.register_main(&["stack_depth", "overflow_addr", "stack_h0"]);
+
+        // range checker columns
+        registry.register_main(&["range_m", "range_v"]);
+
+        // chiplets columns
+        registry.register_main_group("chiplets", CHIPLETS_WIDTH);
+
+        // auxiliary columns, in the order in which they are built by the execution trace
+        registry.register_aux(&["p1_aux", "p2_aux", "p3_aux"]);
+        registry.register_aux(&["stack_p1_aux"]);
+        registry.register_aux(&["range_b_aux"]);
+        registry.register_aux(&["hasher_p1_aux", "chiplets_b_aux"]);
+
+        debug_assert_eq!(registry.main.len(), TRACE_WIDTH);
+        debug_assert_eq!(registry.aux.len(), AUX_TRACE_WIDTH);
+        registry
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the name of the main trace column at the specified index, or None if the index is
+    /// out of bounds.
+    pub fn main_column_name(&self, idx: usize) -> Option<&str> {
+        self.main.get(idx).map(String::as_str)
+    }
+
+    /// Returns the name of the auxiliary trace column at the specified index, or None if the
+    /// index is out of bounds.
+    pub fn aux_column_name(&self, idx: usize) -> Option<&str> {
+        self.aux.get(idx).map(String::as_str)
+    }
+
+    /// Returns the names of all main trace columns in the order of the columns.
+    pub fn main_column_names(&self) -> &[String] {
+        &self.main
+    }
+
+    /// Returns the names of all auxiliary trace columns in the order of the columns.
+    pub fn aux_column_names(&self) -> &[String] {
+        &self.aux
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Registers main trace columns with the specified names.
+    fn register_main(&mut self, names: &[&str]) {
+        self.main.extend(names.iter().map(|name| name.to_string()));
+    }
+
+    /// Registers a group of main trace columns named `<prefix>_<i>` for i in `0..count`.
+    fn register_main_group(&mut self, prefix: &str, count: usize) {
+        self.main.extend((0..count).map(|i| format!("{prefix}_{i}")));
+    }
+
+    /// Registers auxiliary trace columns with the specified names.
+    fn register_aux(&mut self, names: &[&str]) {
+        self.aux.extend(names.iter().map(|name| name.to_string()));
+    }
+}
+
+impl Default for TraceColumnRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
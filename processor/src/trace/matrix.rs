@@ -0,0 +1,247 @@
+use super::{ColMatrix, FieldElement};
+use core::ops::{Bound, Index, Range, RangeBounds};
+
+// MATRIX SLICING
+// ================================================================================================
+
+/// Extends [ColMatrix] with methods for selecting sub-matrices without copying the underlying
+/// data.
+pub trait ColMatrixExt<E: FieldElement> {
+    /// Returns a view of the specified range of columns containing all rows of this matrix.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or extends beyond the number of columns.
+    fn column_slice<R: RangeBounds<usize>>(&self, cols: R) -> MatrixView<'_, E>;
+
+    /// Returns a view of the specified range of rows containing all columns of this matrix.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or extends beyond the number of rows.
+    fn row_slice<R: RangeBounds<usize>>(&self, rows: R) -> MatrixView<'_, E>;
+}
+
+impl<E: FieldElement> ColMatrixExt<E> for ColMatrix<E> {
+    fn column_slice<R: RangeBounds<usize>>(&self, cols: R) -> MatrixView<'_, E> {
+        MatrixView::new(self).column_slice(cols)
+    }
+
+    fn row_slice<R: RangeBounds<usize>>(&self, rows: R) -> MatrixView<'_, E> {
+        MatrixView::new(self).row_slice(rows)
+    }
+}
+
+// MATRIX VIEW
+// ================================================================================================
+
+/// A rectangular region of a [ColMatrix] which borrows the data of the matrix.
+///
+/// Columns and rows of the view are indexed relative to the region, so the first column and row
+/// of the view have index 0. Elements can be read by `(col_idx, row_idx)` pairs.
+#[derive(Debug, Clone)]
+pub struct MatrixView<'a, E: FieldElement> {
+    matrix: &'a ColMatrix<E>,
+    cols: Range<usize>,
+    rows: Range<usize>,
+}
+
+impl<'a, E: FieldElement> MatrixView<'a, E> {
+    /// Returns a view of the entire matrix.
+    fn new(matrix: &'a ColMatrix<E>) -> Self {
+        Self {
+            matrix,
+            cols: 0..matrix.num_cols(),
+            rows: 0..matrix.num_rows(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in this view.
+    pub fn num_cols(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the number of rows in this view.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the element at the specified column and row of this view.
+    ///
+    /// # Panics
+    /// Panics if either of the indexes is out of bounds of this view.
+    pub fn get(&self, col_idx: usize, row_idx: usize) -> E {
+        self.get_column(col_idx)[row_idx]
+    }
+
+    /// Returns the rows of the specified column which are covered by this view.
+    ///
+    /// # Panics
+    /// Panics if the column index is out of bounds of this view.
+    pub fn get_column(&self, col_idx: usize) -> &'a [E] {
+        assert!(
+            col_idx < self.num_cols(),
+            "column index {col_idx} is out of bounds for a view with {} columns",
+            self.num_cols()
+        );
+        &self.matrix.get_column(self.cols.start + col_idx)[self.rows.clone()]
+    }
+
+    /// Returns an iterator over the columns of this view.
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = &'a [E]> + '_ {
+        (0..self.num_cols()).map(|col_idx| self.get_column(col_idx))
+    }
+
+    /// Returns an iterator over the elements of the specified row of this view.
+    ///
+    /// # Panics
+    /// Panics if the row index is out of bounds of this view.
+    pub fn row(&self, row_idx: usize) -> impl ExactSizeIterator<Item = E> + '_ {
+        assert!(
+            row_idx < self.num_rows(),
+            "row index {row_idx} is out of bounds for a view with {} rows",
+            self.num_rows()
+        );
+        self.columns().map(move |column| column[row_idx])
+    }
+
+    // SLICING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a view of the specified range of columns of this view.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or extends beyond the number of columns of this view.
+    pub fn column_slice<R: RangeBounds<usize>>(&self, cols: R) -> Self {
+        let cols = resolve_range(cols, self.num_cols(), "column");
+        Self {
+            matrix: self.matrix,
+            cols: self.cols.start + cols.start..self.cols.start + cols.end,
+            rows: self.rows.clone(),
+        }
+    }
+
+    /// Returns a view of the specified range of rows of this view.
+    ///
+    /// # Panics
+    /// Panics if the range is decreasing or extends beyond the number of rows of this view.
+    pub fn row_slice<R: RangeBounds<usize>>(&self, rows: R) -> Self {
+        let rows = resolve_range(rows, self.num_rows(), "row");
+        Self {
+            matrix: self.matrix,
+            cols: self.cols.clone(),
+            rows: self.rows.start + rows.start..self.rows.start + rows.end,
+        }
+    }
+}
+
+impl<'a, E: FieldElement> Index<(usize, usize)> for MatrixView<'a, E> {
+    type Output = E;
+
+    /// Returns a reference to the element at the specified `(col_idx, row_idx)` of this view.
+    fn index(&self, (col_idx, row_idx): (usize, usize)) -> &E {
+        &self.get_column(col_idx)[row_idx]
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts range bounds into a range over `0..len`, panicking if the bounds are invalid.
+fn resolve_range<R: RangeBounds<usize>>(bounds: R, len: usize, kind: &str) -> Range<usize> {
+    let start = match bounds.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match bounds.end_bound() {
+        Bound::Included(&end) => end + 1,
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "{kind} range starts at {start} but ends at {end}");
+    assert!(end <= len, "{kind} range end {end} is out of bounds for a length of {len}");
+    start..end
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{super::Vec, ColMatrix, ColMatrixExt};
+    use vm_core::Felt;
+
+    /// Returns a matrix with 4 columns and 8 rows in which each element encodes its position as
+    /// `10 * col_idx + row_idx`.
+    fn build_matrix() -> ColMatrix<Felt> {
+        let columns = (0..4)
+            .map(|col| (0..8).map(|row| Felt::new(10 * col + row)).collect())
+            .collect::<Vec<_>>();
+        ColMatrix::new(columns)
+    }
+
+    #[test]
+    fn column_slice() {
+        let matrix = build_matrix();
+        let view = matrix.column_slice(1..3);
+        assert_eq!(view.num_cols(), 2);
+        assert_eq!(view.num_rows(), 8);
+        assert_eq!(view.get(0, 0), Felt::new(10));
+        assert_eq!(view[(1, 7)], Felt::new(27));
+        assert_eq!(view.get_column(1), matrix.get_column(2));
+
+        // unbounded and inclusive ranges cover the expected columns
+        assert_eq!(matrix.column_slice(..).num_cols(), 4);
+        assert_eq!(matrix.column_slice(2..).get(0, 0), Felt::new(20));
+        assert_eq!(matrix.column_slice(..=0).num_cols(), 1);
+    }
+
+    #[test]
+    fn row_slice_boundaries() {
+        let matrix = build_matrix();
+
+        // the first and the last rows of the matrix
+        let first = matrix.row_slice(..1);
+        assert_eq!(first.num_rows(), 1);
+        assert_eq!(first.row(0).collect::<Vec<_>>(), [0, 10, 20, 30].map(Felt::new));
+
+        let last = matrix.row_slice(7..);
+        assert_eq!(last.num_rows(), 1);
+        assert_eq!(last.row(0).collect::<Vec<_>>(), [7, 17, 27, 37].map(Felt::new));
+
+        // an empty slice at the end of the matrix
+        let empty = matrix.row_slice(8..8);
+        assert_eq!(empty.num_rows(), 0);
+        assert!(empty.columns().all(|column| column.is_empty()));
+
+        // slicing a view is relative to the view
+        let view = matrix.row_slice(2..6).column_slice(1..).row_slice(1..=3);
+        assert_eq!(view.num_cols(), 3);
+        assert_eq!(view.num_rows(), 3);
+        assert_eq!(view[(0, 0)], Felt::new(13));
+        assert_eq!(view[(2, 2)], Felt::new(35));
+        assert_eq!(view.get_column(0), &[13, 14, 15].map(Felt::new));
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_slice_out_of_bounds() {
+        build_matrix().row_slice(4..9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn column_slice_out_of_bounds() {
+        build_matrix().column_slice(4..=4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn view_index_out_of_bounds() {
+        let matrix = build_matrix();
+        let view = matrix.row_slice(0..7);
+        let _ = view[(0, 7)];
+    }
+}
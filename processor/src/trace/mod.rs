@@ -1,9 +1,10 @@
 use super::{
-    chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder, crypto::RpoRandomCoin,
+    chiplets::AuxTraceBuilder as ChipletsAuxTraceBuilder,
+    crypto::RpoRandomCoin,
     decoder::AuxTraceHints as DecoderAuxTraceHints,
     range::AuxTraceBuilder as RangeCheckerAuxTraceBuilder,
-    stack::AuxTraceBuilder as StackAuxTraceBuilder, ColMatrix, Digest, Felt, FieldElement, Host,
-    Process, StackTopState, Vec,
+    stack::{AuxTraceBuilder as StackAuxTraceBuilder, AuxTraceStats},
+    ColMatrix, Digest, Felt, FieldElement, Host, Process, StackTopState, Vec,
 };
 use miden_air::trace::{
     decoder::{NUM_USER_OP_HELPERS, USER_OP_HELPERS_OFFSET},
@@ -22,6 +23,12 @@ pub use utils::{
     TraceFragment, TraceLenSummary,
 };
 
+mod columns;
+pub use columns::TraceColumnRegistry;
+
+mod matrix;
+pub use matrix::{ColMatrixExt, MatrixView};
+
 mod decoder;
 
 #[cfg(test)]
@@ -45,6 +52,13 @@ pub struct AuxTraceHints {
     pub(crate) chiplets: ChipletsAuxTraceBuilder,
 }
 
+impl AuxTraceHints {
+    /// Returns a summary of the hints used to build the stack auxiliary column.
+    pub fn stats(&self) -> AuxTraceStats {
+        self.stack.stats()
+    }
+}
+
 /// Execution trace which is generated when a program is executed on the VM.
 ///
 /// The trace consists of the following components:
@@ -60,6 +74,7 @@ pub struct ExecutionTrace {
     program_info: ProgramInfo,
     stack_outputs: StackOutputs,
     trace_len_summary: TraceLenSummary,
+    max_call_depth: usize,
 }
 
 impl ExecutionTrace {
@@ -86,6 +101,7 @@ impl ExecutionTrace {
         // create a new program info instance with the underlying kernel
         let kernel = process.kernel().clone();
         let program_info = ProgramInfo::new(program_hash, kernel);
+        let max_call_depth = process.max_call_depth_reached;
         let (main_trace, aux_trace_hints, trace_len_summary) = finalize_trace(process, rng);
 
         Self {
@@ -96,6 +112,7 @@ impl ExecutionTrace {
             program_info,
             stack_outputs,
             trace_len_summary,
+            max_call_depth,
         }
     }
 
@@ -155,6 +172,17 @@ impl ExecutionTrace {
         &self.trace_len_summary
     }
 
+    /// Returns the maximum depth of nested procedure calls reached during program execution.
+    pub fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Returns a summary of the stack overflow table updates from which the stack auxiliary
+    /// column is built.
+    pub fn aux_trace_stats(&self) -> AuxTraceStats {
+        self.aux_trace_hints.stats()
+    }
+
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -247,6 +275,11 @@ impl Trace for ExecutionTrace {
             .chain(range_aux_columns)
             .chain(chiplets)
             .collect::<Vec<_>>();
+        debug_assert_eq!(
+            aux_columns.len(),
+            TraceColumnRegistry::new().aux_column_names().len(),
+            "every auxiliary column must be registered in the trace column registry"
+        );
 
         // inject random values into the last rows of the trace
         let mut rng = RpoRandomCoin::new(self.program_hash().as_elements());
@@ -1,8 +1,13 @@
 use super::{
     super::chiplets::init_state_from_words, ExecutionTrace, Felt, FieldElement, LookupTableRow,
-    Process, Trace, Vec, NUM_RAND_ROWS,
+    Process, Trace, TraceColumnRegistry, Vec, NUM_RAND_ROWS,
 };
 use crate::{AdviceInputs, DefaultHost, ExecutionOptions, MemAdviceProvider, StackInputs};
+use miden_air::trace::{
+    decoder::P1_COL_IDX,
+    stack::{B0_COL_IDX, B1_COL_IDX},
+    AUX_TRACE_WIDTH, CLK_COL_IDX, HASHER_AUX_TRACE_OFFSET, STACK_TRACE_OFFSET, TRACE_WIDTH,
+};
 use test_utils::rand::rand_array;
 use vm_core::{
     code_blocks::CodeBlock, CodeBlockTable, Kernel, Operation, StackOutputs, Word, ONE, ZERO,
@@ -13,6 +18,30 @@ mod hasher;
 mod range;
 mod stack;
 
+// COLUMN NAMES
+// ================================================================================================
+
+#[test]
+fn trace_column_names() {
+    let registry = TraceColumnRegistry::new();
+    assert_eq!(registry.main_column_names().len(), TRACE_WIDTH);
+    assert_eq!(registry.aux_column_names().len(), AUX_TRACE_WIDTH);
+
+    assert_eq!(registry.main_column_name(CLK_COL_IDX), Some("clk"));
+    assert_eq!(registry.main_column_name(STACK_TRACE_OFFSET), Some("stack_0"));
+    assert_eq!(registry.main_column_name(STACK_TRACE_OFFSET + B0_COL_IDX), Some("stack_depth"));
+    assert_eq!(
+        registry.main_column_name(STACK_TRACE_OFFSET + B1_COL_IDX),
+        Some("overflow_addr")
+    );
+    assert_eq!(registry.main_column_name(TRACE_WIDTH - 1), Some("chiplets_16"));
+    assert_eq!(registry.main_column_name(TRACE_WIDTH), None);
+
+    assert_eq!(registry.aux_column_name(P1_COL_IDX), Some("p1_aux"));
+    assert_eq!(registry.aux_column_name(HASHER_AUX_TRACE_OFFSET), Some("hasher_p1_aux"));
+    assert_eq!(registry.aux_column_name(AUX_TRACE_WIDTH), None);
+}
+
 // TEST HELPERS
 // ================================================================================================
 
@@ -2,7 +2,7 @@ use super::{
     build_trace_from_ops, rand_array, Felt, FieldElement, LookupTableRow, Operation, Trace, Vec,
     NUM_RAND_ROWS, ONE, ZERO,
 };
-use crate::stack::OverflowTableRow;
+use crate::{stack::OverflowTableRow, AuxTraceStats};
 use miden_air::trace::{AUX_TRACE_RAND_ELEMENTS, STACK_AUX_TRACE_OFFSET};
 
 // CONSTANTS
@@ -94,3 +94,31 @@ fn p1_trace() {
         assert_eq!(ONE, p1[i]);
     }
 }
+
+#[test]
+fn p1_trace_stats() {
+    let ops = vec![
+        Operation::U32add, // no shift, clk 1
+        Operation::Pad,    // right shift, clk 2
+        Operation::Pad,    // right shift, clk 3
+        Operation::U32add, // no shift, clk 4
+        Operation::Drop,   // left shift, clk 5
+        Operation::Pad,    // right shift, clk 6
+        Operation::Drop,   // left shift, clk 7
+        Operation::Drop,   // left shift, clk 8
+        Operation::Drop,   // left shift, clk 9
+        Operation::Pad,    // right shift, clk 10
+        Operation::Drop,   // left shift, clk 11
+    ];
+    let init_stack = (1..17).collect::<Vec<_>>();
+    let trace = build_trace_from_ops(ops, &init_stack);
+
+    // the DROP at clk 9 does not remove a row since the overflow table is already empty
+    let expected = AuxTraceStats {
+        overflow_table_entries: 4,
+        max_overflow_depth: 2,
+        push_events: 4,
+        pop_events: 4,
+    };
+    assert_eq!(trace.aux_trace_stats(), expected);
+}
@@ -0,0 +1,301 @@
+//! This module contains a cache of domain parameters which allows proofs of execution traces of
+//! the same length to reuse the FFT twiddles and the evaluation offsets of their domains.
+use crate::{ExecutionProver, ProvingOptions, WinterProofOptions};
+use air::{trace::MIN_TRACE_LEN, FieldElement, PublicInputs, StarkField};
+use log::debug;
+use processor::{
+    crypto::{ElementHasher, RandomCoin},
+    math::{fft, Felt},
+    ExecutionTrace,
+};
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use winter_prover::{
+    crypto::MerkleTree,
+    matrix::{build_segments, get_evaluation_offsets},
+    ColMatrix, CompositionPoly, ConstraintCommitment, Prover, RowMatrix, StarkDomain,
+};
+
+#[cfg(feature = "concurrent")]
+use winter_prover::iterators::*;
+
+/// Number of columns evaluated together when polynomials are evaluated over the LDE domain; this
+/// is the same as the segment width used by the Winterfell prover.
+const SEGMENT_WIDTH: usize = 8;
+
+// DOMAIN CACHE
+// ================================================================================================
+
+/// A cache of the parameters of the domains over which execution traces are extended.
+///
+/// Extending an execution trace requires the inverse FFT twiddles of the trace domain, and the
+/// offsets of the rows of the low-degree extension (LDE) domain. Both depend only on the length of
+/// the trace, the blowup factor, and the domain offset, and thus, they can be computed once and
+/// shared by all proofs of traces of the same length (e.g., when the same program is proven
+/// against many inputs). Proofs generated with a cache are identical to the ones generated without
+/// it.
+///
+/// Clones of a cache share its entries, and the cache can be used from multiple threads.
+#[derive(Debug, Clone, Default)]
+pub struct DomainCache {
+    inner: Arc<Mutex<CacheInner>>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    entries: BTreeMap<DomainKey, Arc<DomainParams>>,
+    hits: usize,
+    misses: usize,
+}
+
+/// Length of the trace domain, blowup factor, and domain offset (as an integer).
+type DomainKey = (usize, usize, u64);
+
+impl DomainCache {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new empty [DomainCache].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of domains whose parameters are cached.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns true if no domain parameters are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which were served from the cache.
+    pub fn hits(&self) -> usize {
+        self.lock().hits
+    }
+
+    /// Returns the number of lookups which required the domain parameters to be computed.
+    pub fn misses(&self) -> usize {
+        self.lock().misses
+    }
+
+    // PRECOMPUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes and caches the parameters of the domain used to prove an execution trace of the
+    /// specified length with the specified options, unless they are already cached.
+    ///
+    /// The trace length is padded the same way as execution traces are, so the length reported by
+    /// an execution (e.g., `trace_len_summary().trace_len()`) can be passed as is.
+    pub fn precompute(&self, trace_len: usize, options: &ProvingOptions) {
+        let trace_len = trace_len.max(MIN_TRACE_LEN).next_power_of_two();
+        let blowup_factor = options.proof_options.blowup_factor();
+        self.get(trace_len, blowup_factor, Felt::GENERATOR);
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the parameters of the specified domain, computing them if they are not cached.
+    fn get(
+        &self,
+        trace_len: usize,
+        blowup_factor: usize,
+        domain_offset: Felt,
+    ) -> Arc<DomainParams> {
+        let mut inner = self.lock();
+        let key = (trace_len, blowup_factor, domain_offset.as_int());
+        if let Some(params) = inner.entries.get(&key).cloned() {
+            inner.hits += 1;
+            return params;
+        }
+
+        // the parameters are computed while the lock is held so that concurrent proofs of the
+        // same length do not compute them more than once
+        let now = Instant::now();
+        let params = Arc::new(DomainParams::new(trace_len, blowup_factor, domain_offset));
+        debug!(
+            "Computed parameters of a domain of 2^{} elements ({}x blowup) in {} ms",
+            trace_len.ilog2(),
+            blowup_factor,
+            now.elapsed().as_millis()
+        );
+        inner.entries.insert(key, params.clone());
+        inner.misses += 1;
+        params
+    }
+
+    /// Returns the parameters of the domain over which polynomials of the specified size are
+    /// extended in the specified STARK domain.
+    fn get_for(&self, poly_size: usize, domain: &StarkDomain<Felt>) -> Arc<DomainParams> {
+        self.get(poly_size, domain.trace_to_lde_blowup(), domain.offset())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheInner> {
+        // the cache is never left in an inconsistent state, so a poisoned lock can be reused
+        self.inner.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+// DOMAIN PARAMETERS
+// ================================================================================================
+
+/// Precomputed parameters of a domain: the inverse twiddles used to interpolate trace columns
+/// into polynomials, and the offsets used to evaluate the polynomials over the LDE domain.
+#[derive(Debug)]
+struct DomainParams {
+    inv_twiddles: Vec<Felt>,
+    lde_offsets: Vec<Felt>,
+}
+
+impl DomainParams {
+    fn new(trace_len: usize, blowup_factor: usize, domain_offset: Felt) -> Self {
+        Self {
+            inv_twiddles: fft::get_inv_twiddles(trace_len),
+            lde_offsets: get_evaluation_offsets::<Felt>(trace_len, blowup_factor, domain_offset),
+        }
+    }
+
+    /// Interpolates the columns of the specified matrix into polynomials in coefficient form.
+    fn interpolate_columns<E>(&self, trace: &ColMatrix<E>) -> ColMatrix<E>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        let mut columns = trace.columns().map(|column| column.to_vec()).collect::<Vec<_>>();
+
+        #[cfg(feature = "concurrent")]
+        let iter = columns.par_iter_mut();
+        #[cfg(not(feature = "concurrent"))]
+        let iter = columns.iter_mut();
+        iter.for_each(|column| fft::interpolate_poly(column, &self.inv_twiddles));
+
+        ColMatrix::new(columns)
+    }
+
+    /// Evaluates the polynomials in the columns of the specified matrix over the LDE domain.
+    fn evaluate_columns<E>(&self, polys: &ColMatrix<E>, domain: &StarkDomain<Felt>) -> RowMatrix<E>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        let segments =
+            build_segments::<E, SEGMENT_WIDTH>(polys, domain.trace_twiddles(), &self.lde_offsets);
+        RowMatrix::from_segments(segments, polys.num_base_cols())
+    }
+}
+
+// CACHED PROVER
+// ================================================================================================
+
+/// Wraps an [ExecutionProver] and extends the execution trace and the constraint composition
+/// polynomial with domain parameters taken from a [DomainCache].
+pub(crate) struct CachedExecutionProver<H, R>
+where
+    H: ElementHasher<BaseField = Felt>,
+    R: RandomCoin<BaseField = Felt, Hasher = H>,
+{
+    pub prover: ExecutionProver<H, R>,
+    pub cache: DomainCache,
+}
+
+impl<H, R> Prover for CachedExecutionProver<H, R>
+where
+    H: ElementHasher<BaseField = Felt>,
+    R: RandomCoin<BaseField = Felt, Hasher = H>,
+{
+    type Air = <ExecutionProver<H, R> as Prover>::Air;
+    type BaseField = Felt;
+    type Trace = <ExecutionProver<H, R> as Prover>::Trace;
+    type HashFn = H;
+    type RandomCoin = R;
+
+    fn options(&self) -> &WinterProofOptions {
+        self.prover.options()
+    }
+
+    fn get_pub_inputs(&self, trace: &ExecutionTrace) -> PublicInputs {
+        self.prover.get_pub_inputs(trace)
+    }
+
+    /// Computes a low-degree extension (LDE) of the provided execution trace over the specified
+    /// domain and builds a commitment to the extended trace.
+    ///
+    /// This works the same way as the default implementation, but the inverse twiddles and the
+    /// evaluation offsets of the domain are taken from the cache.
+    fn build_trace_commitment<E>(
+        &self,
+        trace: &ColMatrix<E>,
+        domain: &StarkDomain<Felt>,
+    ) -> (RowMatrix<E>, MerkleTree<Self::HashFn>, ColMatrix<E>)
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        // extend the execution trace
+        let now = Instant::now();
+        let params = self.cache.get_for(trace.num_rows(), domain);
+        let trace_polys = params.interpolate_columns(trace);
+        let trace_lde = params.evaluate_columns(&trace_polys, domain);
+        debug!(
+            "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
+            trace_lde.num_cols(),
+            trace_polys.num_rows().ilog2(),
+            trace_lde.num_rows().ilog2(),
+            domain.trace_to_lde_blowup(),
+            now.elapsed().as_millis()
+        );
+
+        // build trace commitment
+        let now = Instant::now();
+        let trace_tree = trace_lde.commit_to_rows();
+        debug!(
+            "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
+            trace_tree.depth(),
+            now.elapsed().as_millis()
+        );
+
+        (trace_lde, trace_tree, trace_polys)
+    }
+
+    /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
+    /// to these evaluations.
+    ///
+    /// This works the same way as the default implementation, but the evaluation offsets of the
+    /// domain are taken from the cache.
+    fn build_constraint_commitment<E>(
+        &self,
+        composition_poly: &CompositionPoly<E>,
+        domain: &StarkDomain<Felt>,
+    ) -> ConstraintCommitment<E, Self::HashFn>
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        // evaluate composition polynomial columns over the LDE domain
+        let now = Instant::now();
+        let polys = composition_poly.data();
+        let params = self.cache.get_for(polys.num_rows(), domain);
+        let composed_evaluations = params.evaluate_columns(polys, domain);
+        debug!(
+            "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
+            composed_evaluations.num_cols(),
+            composed_evaluations.num_rows().ilog2(),
+            now.elapsed().as_millis()
+        );
+
+        // build constraint evaluation commitment
+        let now = Instant::now();
+        let commitment = composed_evaluations.commit_to_rows();
+        let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
+        debug!(
+            "Computed constraint evaluation commitment (Merkle tree of depth {}) in {} ms",
+            constraint_commitment.tree_depth(),
+            now.elapsed().as_millis()
+        );
+        constraint_commitment
+    }
+}
@@ -1,6 +1,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use air::{ProcessorAir, PublicInputs};
+use air::{
+    trace::{AUX_TRACE_WIDTH, MIN_TRACE_LEN, TRACE_WIDTH},
+    ProcessorAir, PublicInputs,
+};
 use core::marker::PhantomData;
 use processor::{
     crypto::{
@@ -21,6 +24,11 @@ use winter_prover::Trace;
 #[cfg(all(feature = "metal", target_arch = "aarch64", target_os = "macos"))]
 mod gpu;
 
+#[cfg(feature = "std")]
+mod domain_cache;
+#[cfg(feature = "std")]
+use domain_cache::CachedExecutionProver;
+
 // EXPORTS
 // ================================================================================================
 
@@ -31,6 +39,9 @@ pub use processor::{
 };
 pub use winter_prover::StarkProof;
 
+#[cfg(feature = "std")]
+pub use domain_cache::DomainCache;
+
 // PROVER
 // ================================================================================================
 
@@ -55,8 +66,7 @@ where
     // execute the program to create an execution trace
     #[cfg(feature = "std")]
     let now = Instant::now();
-    let trace =
-        processor::execute(program, stack_inputs.clone(), host, *options.execution_options())?;
+    let trace = build_execution_trace(program, stack_inputs.clone(), host, &options)?;
     #[cfg(feature = "std")]
     let padding_percentage = (trace.trace_len_summary().padded_trace_len()
         - trace.trace_len_summary().trace_len())
@@ -72,37 +82,283 @@ where
     );
 
     let stack_outputs = trace.stack_outputs().clone();
-    let hash_fn = options.hash_fn();
 
     // generate STARK proof
+    let proof = prove_execution_trace(trace, options, stack_inputs, stack_outputs.clone())?;
+
+    Ok((stack_outputs, proof))
+}
+
+/// Executes and proves the specified `program` like [prove()], but fails if the proof would be
+/// larger than `max_proof_bytes`.
+///
+/// The size of the proof is estimated via [estimate_proof_size()] right after the program is
+/// executed, so that a program which is too large for the budget is rejected before the expensive
+/// STARK proof generation starts. The size of the generated proof is checked as well, since the
+/// estimate is approximate.
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason, or if
+/// the estimated or the actual size of the proof exceeds `max_proof_bytes`.
+pub fn prove_with_size_budget<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ProvingOptions,
+    max_proof_bytes: usize,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError>
+where
+    H: Host,
+{
+    let trace = build_execution_trace(program, stack_inputs.clone(), host, &options)?;
+
+    let estimate = estimate_proof_size(trace.trace_len_summary().padded_trace_len(), &options);
+    if estimate > max_proof_bytes {
+        return Err(ExecutionError::ProofSizeExceeded {
+            size: estimate,
+            max_size: max_proof_bytes,
+            estimated: true,
+        });
+    }
+
+    let stack_outputs = trace.stack_outputs().clone();
+    let proof = prove_execution_trace(trace, options, stack_inputs, stack_outputs.clone())?;
+
+    let size = proof.to_bytes().len();
+    if size > max_proof_bytes {
+        return Err(ExecutionError::ProofSizeExceeded {
+            size,
+            max_size: max_proof_bytes,
+            estimated: false,
+        });
+    }
+
+    Ok((stack_outputs, proof))
+}
+
+/// Executes and proves the specified `program` like [prove()], but takes the parameters of the
+/// domain over which the execution trace is extended from `domain_cache`.
+///
+/// If the parameters of the domain are not cached yet, they are computed and added to the cache,
+/// so that subsequent proofs of execution traces of the same length reuse them. The generated
+/// proof is identical to the one generated by [prove()].
+///
+/// # Errors
+/// Returns an error if program execution or STARK proof generation fails for any reason.
+#[cfg(feature = "std")]
+pub fn prove_with_domain_cache<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: ProvingOptions,
+    domain_cache: &DomainCache,
+) -> Result<(StackOutputs, ExecutionProof), ExecutionError>
+where
+    H: Host,
+{
+    let trace = build_execution_trace(program, stack_inputs.clone(), host, &options)?;
+    let stack_outputs = trace.stack_outputs().clone();
+
+    #[cfg(feature = "profiling")]
+    puffin::profile_scope!("prove_execution_trace");
+    let hash_fn = options.hash_fn();
+    let cache = domain_cache.clone();
+    let proof = match hash_fn {
+        HashFunction::Blake3_192 => CachedExecutionProver {
+            prover: ExecutionProver::<Blake3_192, WinterRandomCoin<_>>::new(
+                options,
+                stack_inputs,
+                stack_outputs.clone(),
+            ),
+            cache,
+        }
+        .prove(trace),
+        HashFunction::Blake3_256 => CachedExecutionProver {
+            prover: ExecutionProver::<Blake3_256, WinterRandomCoin<_>>::new(
+                options,
+                stack_inputs,
+                stack_outputs.clone(),
+            ),
+            cache,
+        }
+        .prove(trace),
+        HashFunction::Rpo256 => CachedExecutionProver {
+            prover: ExecutionProver::<Rpo256, RpoRandomCoin>::new(
+                options,
+                stack_inputs,
+                stack_outputs.clone(),
+            ),
+            cache,
+        }
+        .prove(trace),
+    }
+    .map_err(ExecutionError::ProverError)?;
+
+    Ok((stack_outputs, ExecutionProof::new(proof, hash_fn)))
+}
+
+// PROOF SIZE ESTIMATION
+// ================================================================================================
+
+/// Number of columns of the constraint composition polynomial; the highest degree of Miden VM
+/// constraints is 9, and thus, the composition polynomial is split into 8 columns.
+const NUM_COMPOSITION_COLUMNS: usize = 8;
+
+/// Returns an estimate of the size in bytes of a proof of an execution trace with the specified
+/// (padded) length generated with the specified options.
+///
+/// The estimate accounts for the trace and constraint queries, the out-of-domain frame, and the
+/// FRI proof. The number of nodes in the Merkle authentication paths depends on the positions of
+/// the queries, which are random, and thus, the expected number of nodes is used; in practice,
+/// the actual size of a proof is within a few percent of the estimate.
+pub fn estimate_proof_size(trace_len: usize, options: &ProvingOptions) -> usize {
+    const ELEMENT_BYTES: usize = 8;
+
+    let proof_options = &options.proof_options;
+    let extension_degree = proof_options.field_extension().degree() as usize;
+    let num_queries = proof_options.num_queries();
+    let digest_bytes = match options.hash_fn() {
+        HashFunction::Blake3_192 => 24,
+        HashFunction::Blake3_256 | HashFunction::Rpo256 => 32,
+    };
+
+    // expected number of distinct nodes hit by the queries at a level of a Merkle tree with the
+    // specified number of nodes
+    let num_hit_nodes = |num_nodes: usize| {
+        let num_nodes = num_nodes as f64;
+        let mut not_hit = 1.0;
+        for _ in 0..num_queries {
+            not_hit *= 1.0 - 1.0 / num_nodes;
+        }
+        num_nodes * (1.0 - not_hit)
+    };
+
+    // expected number of nodes in a batched Merkle proof for the queries into a tree with the
+    // specified number of leaves; a node is needed if its sibling lies on the path of a query but
+    // the node itself does not
+    let num_path_nodes = |num_leaves: usize| {
+        let mut num_nodes = 0.0;
+        let mut level_size = num_leaves;
+        while level_size > 1 {
+            num_nodes += 2.0 * num_hit_nodes(level_size / 2) - num_hit_nodes(level_size);
+            level_size /= 2;
+        }
+        num_nodes as usize
+    };
+
+    let lde_domain_size = trace_len.max(MIN_TRACE_LEN) * proof_options.blowup_factor();
+    let main_width = TRACE_WIDTH;
+    let aux_width = AUX_TRACE_WIDTH * extension_degree;
+    let composition_width = NUM_COMPOSITION_COLUMNS * extension_degree;
+
+    // values of the queried rows of the main, auxiliary, and composition traces together with
+    // their authentication paths
+    let query_values = num_queries * (main_width + aux_width + composition_width) * ELEMENT_BYTES;
+    let query_paths = 3 * num_path_nodes(lde_domain_size) * digest_bytes;
+
+    // evaluations of all trace columns at two out-of-domain points, and of the composition
+    // columns at one point
+    let ood_frame =
+        ((main_width + AUX_TRACE_WIDTH) * extension_degree * 2 + composition_width) * ELEMENT_BYTES;
+
+    // every FRI layer opens `folding_factor` evaluations per query, and the remainder is sent as
+    // a polynomial in coefficient form
+    let fri_options = proof_options.to_fri_options();
+    let folding_factor = fri_options.folding_factor();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+    let fri_layers = (0..num_fri_layers)
+        .map(|layer| {
+            let num_leaves = lde_domain_size / folding_factor.pow(layer as u32 + 1);
+            let num_leaf_values = num_hit_nodes(num_leaves) as usize * folding_factor;
+            num_leaf_values * extension_degree * ELEMENT_BYTES
+                + num_path_nodes(num_leaves) * digest_bytes
+        })
+        .sum::<usize>();
+    let remainder_len =
+        lde_domain_size / folding_factor.pow(num_fri_layers as u32) / proof_options.blowup_factor();
+    let fri_remainder = remainder_len * extension_degree * ELEMENT_BYTES;
+
+    // commitments to the main, auxiliary, and composition traces and to every FRI layer
+    let commitments = (3 + num_fri_layers) * digest_bytes;
+
+    query_values + query_paths + ood_frame + fri_layers + fri_remainder + commitments
+}
+
+// PROFILING HELPERS
+// ================================================================================================
+//
+// The two phases of proof generation are kept in separate functions which are never inlined, so
+// that they appear as distinct frames when the prover is profiled with a sampling profiler. For
+// example, a flamegraph of proving a program can be generated via `cargo flamegraph` by running
+// the following from the `miden` directory:
+//
+//   CARGO_PROFILE_RELEASE_DEBUG=true cargo flamegraph --features executable -- \
+//       prove -a examples/fib/fib.masm
+//
+// In the resulting graph, `build_execution_trace` covers program execution (with hash chiplet
+// permutations showing up as `append_permutation_with_index` frames), while
+// `prove_execution_trace` covers trace low-degree extension, constraint evaluation, and FRI
+// performed by the STARK prover.
+//
+// With the `profiling` feature, both phases are also marked as puffin scopes. The scopes are
+// recorded only if puffin scopes are turned on (via `puffin::set_scopes_on()`); the CLI does this
+// when the `MIDEN_PROFILE` environment variable is set, and writes the recorded profile into a
+// `miden.puffin` file which can be opened in `puffin_viewer`:
+//
+//   cargo build --release --features profiling
+//   MIDEN_PROFILE=1 ./target/release/miden prove -a examples/fib/fib.masm
+
+/// Executes the specified `program` and returns the resulting execution trace.
+#[inline(never)]
+fn build_execution_trace<H>(
+    program: &Program,
+    stack_inputs: StackInputs,
+    host: H,
+    options: &ProvingOptions,
+) -> Result<ExecutionTrace, ExecutionError>
+where
+    H: Host,
+{
+    #[cfg(feature = "profiling")]
+    puffin::profile_function!();
+    processor::execute(program, stack_inputs, host, *options.execution_options())
+}
+
+/// Generates a STARK proof attesting to the correctness of the specified execution trace.
+#[inline(never)]
+fn prove_execution_trace(
+    trace: ExecutionTrace,
+    options: ProvingOptions,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+) -> Result<ExecutionProof, ExecutionError> {
+    #[cfg(feature = "profiling")]
+    puffin::profile_function!();
+    let hash_fn = options.hash_fn();
     let proof = match hash_fn {
         HashFunction::Blake3_192 => ExecutionProver::<Blake3_192, WinterRandomCoin<_>>::new(
             options,
             stack_inputs,
-            stack_outputs.clone(),
+            stack_outputs,
         )
         .prove(trace),
         HashFunction::Blake3_256 => ExecutionProver::<Blake3_256, WinterRandomCoin<_>>::new(
             options,
             stack_inputs,
-            stack_outputs.clone(),
+            stack_outputs,
         )
         .prove(trace),
         HashFunction::Rpo256 => {
-            let prover = ExecutionProver::<Rpo256, RpoRandomCoin>::new(
-                options,
-                stack_inputs,
-                stack_outputs.clone(),
-            );
+            let prover =
+                ExecutionProver::<Rpo256, RpoRandomCoin>::new(options, stack_inputs, stack_outputs);
             #[cfg(all(feature = "metal", target_arch = "aarch64", target_os = "macos"))]
             let prover = gpu::GpuRpoExecutionProver(prover);
             prover.prove(trace)
         }
     }
     .map_err(ExecutionError::ProverError)?;
-    let proof = ExecutionProof::new(proof, hash_fn);
 
-    Ok((stack_outputs, proof))
+    Ok(ExecutionProof::new(proof, hash_fn))
 }
 
 // PROVER
@@ -5,9 +5,26 @@ use assembly::{utils::Deserializable, Library, LibraryNamespace, MaslLibrary, Ve
 // STANDARD LIBRARY
 // ================================================================================================
 
+/// The serialized standard library followed by the checksum marker and the SHA-256 checksum of the
+/// serialized bytes.
+const STDLIB_BYTES: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/assets/std.masl"));
+
 /// TODO: add docs
 pub struct StdLibrary(MaslLibrary);
 
+impl StdLibrary {
+    /// Returns the SHA-256 digest of the serialized standard library.
+    ///
+    /// The digest changes whenever a module of the standard library changes, and thus, it can be
+    /// used to detect that a program is compiled against a different version of the library.
+    pub fn digest(&self) -> [u8; MaslLibrary::CHECKSUM_LEN] {
+        let (_, checksum) = MaslLibrary::split_checksum(STDLIB_BYTES);
+        let mut digest = [0; MaslLibrary::CHECKSUM_LEN];
+        digest.copy_from_slice(checksum.expect("std masl has no checksum!"));
+        digest
+    }
+}
+
 impl From<StdLibrary> for MaslLibrary {
     fn from(value: StdLibrary) -> Self {
         value.0
@@ -16,7 +33,8 @@ impl From<StdLibrary> for MaslLibrary {
 
 impl Default for StdLibrary {
     fn default() -> Self {
-        let bytes = include_bytes!(concat!(env!("OUT_DIR"), "/assets/std.masl"));
+        // the checksum at the end of the file is not needed for the embedded library
+        let (bytes, _) = MaslLibrary::split_checksum(STDLIB_BYTES);
         let contents = MaslLibrary::read_from_bytes(bytes).expect("failed to read std masl!");
         Self(contents)
     }
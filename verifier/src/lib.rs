@@ -1,6 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use air::{HashFunction, ProcessorAir, PublicInputs};
+use air::{ProcessorAir, PublicInputs};
 use core::fmt;
 use vm_core::crypto::{
     hash::{Blake3_192, Blake3_256, Rpo256},
@@ -16,7 +16,7 @@ pub use winter_verifier::VerifierError;
 pub mod math {
     pub use vm_core::{Felt, FieldElement, StarkField};
 }
-pub use air::ExecutionProof;
+pub use air::{ExecutionProof, FieldExtension, HashFunction};
 
 // VERIFIER
 // ================================================================================================
@@ -44,8 +44,31 @@ pub fn verify(
     stack_outputs: StackOutputs,
     proof: ExecutionProof,
 ) -> Result<u32, VerificationError> {
-    // get security level of the proof
-    let security_level = proof.security_level();
+    let witness = verify_with_witness(program_info, stack_inputs, stack_outputs, proof)?;
+    Ok(witness.proof_parameters.security_level)
+}
+
+/// Returns the public inputs against which the proof was verified together with the parameters
+/// of the proof if the specified program was executed correctly against the specified inputs and
+/// outputs.
+///
+/// This works the same way as [verify()], but the returned [PublicWitness] describes exactly what
+/// was verified (e.g., so that it can be logged).
+///
+/// # Errors
+/// Returns an error if the provided proof does not prove a correct execution of the program.
+pub fn verify_with_witness(
+    program_info: ProgramInfo,
+    stack_inputs: StackInputs,
+    stack_outputs: StackOutputs,
+    proof: ExecutionProof,
+) -> Result<PublicWitness, VerificationError> {
+    let witness = PublicWitness {
+        program_hash: *program_info.program_hash(),
+        stack_inputs: stack_inputs.clone(),
+        stack_outputs: stack_outputs.clone(),
+        proof_parameters: ProofParameters::new(&proof),
+    };
 
     // build public inputs and try to verify the proof
     let pub_inputs = PublicInputs::new(program_info, stack_inputs, stack_outputs);
@@ -63,7 +86,60 @@ pub fn verify(
     }
     .map_err(VerificationError::VerifierError)?;
 
-    Ok(security_level)
+    Ok(witness)
+}
+
+// PUBLIC WITNESS
+// ================================================================================================
+
+/// Public inputs against which a proof was successfully verified, and the parameters of the
+/// proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicWitness {
+    /// Hash of the program whose execution was proven.
+    pub program_hash: Digest,
+    /// Stack inputs of the execution.
+    pub stack_inputs: StackInputs,
+    /// Stack outputs of the execution.
+    pub stack_outputs: StackOutputs,
+    /// Parameters of the proof.
+    pub proof_parameters: ProofParameters,
+}
+
+/// Parameters of the STARK protocol with which a proof was generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofParameters {
+    /// Hash function used for the commitments of the proof.
+    pub hash_fn: HashFunction,
+    /// Conjectured security level of the proof in bits.
+    pub security_level: u32,
+    /// Length of the proven execution trace.
+    pub trace_length: usize,
+    /// Number of queries made by the verifier.
+    pub num_queries: usize,
+    /// Factor by which the execution trace was extended.
+    pub blowup_factor: usize,
+    /// Number of bits of the proof-of-work performed by the prover.
+    pub grinding_factor: u32,
+    /// Extension of the base field used by FRI.
+    pub field_extension: FieldExtension,
+}
+
+impl ProofParameters {
+    /// Returns the parameters of the specified proof.
+    pub fn new(proof: &ExecutionProof) -> Self {
+        let stark_proof = proof.stark_proof();
+        let options = stark_proof.options();
+        Self {
+            hash_fn: proof.hash_fn(),
+            security_level: proof.security_level(),
+            trace_length: stark_proof.trace_length(),
+            num_queries: options.num_queries(),
+            blowup_factor: options.blowup_factor(),
+            grinding_factor: options.grinding_factor(),
+            field_extension: options.field_extension(),
+        }
+    }
 }
 
 // ERRORS